@@ -25,6 +25,10 @@ impl<'c, C: Config> AssistantFiles<'c, C> {
     }
 
     /// Create an assistant file by attaching a [File](https://platform.openai.com/docs/api-reference/files) to an [assistant](https://platform.openai.com/docs/api-reference/assistants).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.assistant_files.create", skip_all)
+    )]
     pub async fn create(
         &self,
         request: CreateAssistantFileRequest,
@@ -35,6 +39,10 @@ impl<'c, C: Config> AssistantFiles<'c, C> {
     }
 
     /// Retrieves an AssistantFile.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.assistant_files.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, file_id: &str) -> Result<AssistantFileObject, OpenAIError> {
         self.client
             .get(&format!(
@@ -45,6 +53,10 @@ impl<'c, C: Config> AssistantFiles<'c, C> {
     }
 
     /// Delete an assistant file.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.assistant_files.delete", skip_all)
+    )]
     pub async fn delete(&self, file_id: &str) -> Result<DeleteAssistantFileResponse, OpenAIError> {
         self.client
             .delete(&format!(
@@ -55,6 +67,10 @@ impl<'c, C: Config> AssistantFiles<'c, C> {
     }
 
     /// Returns a list of assistant files.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.assistant_files.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListAssistantFilesResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
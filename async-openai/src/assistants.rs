@@ -1,11 +1,13 @@
+use futures::Stream;
 use serde::Serialize;
 
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::paginate,
     types::{
         AssistantObject, CreateAssistantRequest, DeleteAssistantResponse, ListAssistantsResponse,
-        ModifyAssistantRequest,
+        ModifyAssistantRequest, Pagination,
     },
     AssistantFiles, Client,
 };
@@ -23,11 +25,15 @@ impl<'c, C: Config> Assistants<'c, C> {
     }
 
     /// Assistant [AssistantFiles] API group
-    pub fn files(&self, assistant_id: &str) -> AssistantFiles<C> {
+    pub fn files(&self, assistant_id: &str) -> AssistantFiles<'_, C> {
         AssistantFiles::new(self.client, assistant_id)
     }
 
     /// Create an assistant with a model and instructions.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.assistants.create", skip_all)
+    )]
     pub async fn create(
         &self,
         request: CreateAssistantRequest,
@@ -36,6 +42,10 @@ impl<'c, C: Config> Assistants<'c, C> {
     }
 
     /// Retrieves an assistant.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.assistants.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, assistant_id: &str) -> Result<AssistantObject, OpenAIError> {
         self.client
             .get(&format!("/assistants/{assistant_id}"))
@@ -43,6 +53,10 @@ impl<'c, C: Config> Assistants<'c, C> {
     }
 
     /// Modifies an assistant.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.assistants.update", skip_all)
+    )]
     pub async fn update(
         &self,
         assistant_id: &str,
@@ -54,6 +68,10 @@ impl<'c, C: Config> Assistants<'c, C> {
     }
 
     /// Delete an assistant.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.assistants.delete", skip_all)
+    )]
     pub async fn delete(&self, assistant_id: &str) -> Result<DeleteAssistantResponse, OpenAIError> {
         self.client
             .delete(&format!("/assistants/{assistant_id}"))
@@ -61,10 +79,32 @@ impl<'c, C: Config> Assistants<'c, C> {
     }
 
     /// Returns a list of assistants.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.assistants.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListAssistantsResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
     {
         self.client.get_with_query("/assistants", query).await
     }
+
+    /// Stream all the assistants, fetching the pages of [Assistants::list] as the stream
+    /// is consumed. `query` holds the other parameters of [Assistants::list].
+    pub fn list_all<'a, Q>(
+        &'a self,
+        query: &'a Q,
+        pagination: Pagination,
+    ) -> impl Stream<Item = Result<AssistantObject, OpenAIError>> + 'a
+    where
+        Q: Serialize + ?Sized,
+    {
+        paginate::<_, _, ListAssistantsResponse>(
+            self.client,
+            "/assistants".into(),
+            query,
+            pagination,
+        )
+    }
 }
@@ -1,13 +1,16 @@
 use bytes::Bytes;
+use futures::StreamExt;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use crate::{
     config::Config,
     error::OpenAIError,
     types::{
-        CreateSpeechRequest, CreateSpeechResponse, CreateTranscriptionRequest,
-        CreateTranscriptionResponseJson, CreateTranscriptionResponseVerboseJson,
-        CreateTranslationRequest, CreateTranslationResponseJson,
-        CreateTranslationResponseVerboseJson,
+        AudioResponseFormat, CreateSpeechRequest, CreateSpeechResponse, CreateTranscriptionRequest,
+        CreateTranscriptionResponseDiarizedJson, CreateTranscriptionResponseJson,
+        CreateTranscriptionResponseVerboseJson, CreateTranslationRequest,
+        CreateTranslationResponseJson, CreateTranslationResponseVerboseJson, SpeechByteStream,
+        SpeechResponseStream, SpeechStreamEvent, SpeechStreamFormat,
     },
     Client,
 };
@@ -24,26 +27,76 @@ impl<'c, C: Config> Audio<'c, C> {
     }
 
     /// Transcribes audio into the input language.
+    ///
+    /// The `response_format` of the request must be unset or `json`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.audio.transcribe", skip_all)
+    )]
     pub async fn transcribe(
         &self,
         request: CreateTranscriptionRequest,
     ) -> Result<CreateTranscriptionResponseJson, OpenAIError> {
+        check_json_format(request.response_format, "Audio::transcribe")?;
+
         self.client
             .post_form("/audio/transcriptions", request)
             .await
     }
 
-    /// Transcribes audio into the input language.
+    /// Transcribes audio into the input language, with the language, duration and
+    /// segments or words of the transcript.
+    ///
+    /// The `response_format` of the request is set to `verbose_json`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.audio.transcribe_verbose_json", skip_all)
+    )]
     pub async fn transcribe_verbose_json(
         &self,
-        request: CreateTranscriptionRequest,
+        mut request: CreateTranscriptionRequest,
     ) -> Result<CreateTranscriptionResponseVerboseJson, OpenAIError> {
+        request.response_format = Some(verbose_json_format(
+            request.response_format,
+            "Audio::transcribe",
+        )?);
+
+        self.client
+            .post_form("/audio/transcriptions", request)
+            .await
+    }
+
+    /// Transcribes audio into the input language, in segments labeled by speaker with
+    /// the names of the `known_speakers` of the request. Only with `gpt-4o-transcribe-diarize`.
+    ///
+    /// The `response_format` of the request is set to `diarized_json`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.audio.transcribe_diarized_json", skip_all)
+    )]
+    pub async fn transcribe_diarized_json(
+        &self,
+        mut request: CreateTranscriptionRequest,
+    ) -> Result<CreateTranscriptionResponseDiarizedJson, OpenAIError> {
+        match request.response_format {
+            None | Some(AudioResponseFormat::DiarizedJson) => {
+                request.response_format = Some(AudioResponseFormat::DiarizedJson)
+            }
+            Some(response_format) => {
+                return Err(format_error(response_format, "Audio::transcribe"));
+            }
+        }
+
         self.client
             .post_form("/audio/transcriptions", request)
             .await
     }
 
     /// Transcribes audio into the input language.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.audio.transcribe_raw", skip_all)
+    )]
     pub async fn transcribe_raw(
         &self,
         request: CreateTranscriptionRequest,
@@ -54,22 +107,45 @@ impl<'c, C: Config> Audio<'c, C> {
     }
 
     /// Translates audio into English.
+    ///
+    /// The `response_format` of the request must be unset or `json`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.audio.translate", skip_all)
+    )]
     pub async fn translate(
         &self,
         request: CreateTranslationRequest,
     ) -> Result<CreateTranslationResponseJson, OpenAIError> {
+        check_json_format(request.response_format, "Audio::translate")?;
+
         self.client.post_form("/audio/translations", request).await
     }
 
-    /// Translates audio into English.
+    /// Translates audio into English, with the language, duration and segments of the translation.
+    ///
+    /// The `response_format` of the request is set to `verbose_json`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.audio.translate_verbose_json", skip_all)
+    )]
     pub async fn translate_verbose_json(
         &self,
-        request: CreateTranslationRequest,
+        mut request: CreateTranslationRequest,
     ) -> Result<CreateTranslationResponseVerboseJson, OpenAIError> {
+        request.response_format = Some(verbose_json_format(
+            request.response_format,
+            "Audio::translate",
+        )?);
+
         self.client.post_form("/audio/translations", request).await
     }
 
-    /// Transcribes audio into the input language.
+    /// Translates audio into English, in the `response_format` of the request like `srt` or `vtt`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.audio.translate_raw", skip_all)
+    )]
     pub async fn translate_raw(
         &self,
         request: CreateTranslationRequest,
@@ -80,12 +156,139 @@ impl<'c, C: Config> Audio<'c, C> {
     }
 
     /// Generates audio from the input text.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.audio.speech", skip_all)
+    )]
     pub async fn speech(
         &self,
         request: CreateSpeechRequest,
     ) -> Result<CreateSpeechResponse, OpenAIError> {
+        let response_format = request.response_format.unwrap_or_default();
         let bytes = self.client.post_raw("/audio/speech", request).await?;
 
-        Ok(CreateSpeechResponse { bytes })
+        Ok(CreateSpeechResponse {
+            bytes,
+            response_format,
+        })
     }
+
+    /// Generates audio from the input text, written to `writer` as it is received
+    /// without holding the whole audio in memory. Returns the number of bytes written.
+    ///
+    /// Like [Audio::speech_stream_raw], `stream_format` is set to `audio`, and `sse` is rejected.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.audio.speech_to_writer", skip_all)
+    )]
+    pub async fn speech_to_writer<W: AsyncWrite + Unpin>(
+        &self,
+        request: CreateSpeechRequest,
+        mut writer: W,
+    ) -> Result<u64, OpenAIError> {
+        let mut stream = self.speech_stream_raw(request).await?;
+
+        let mut written = 0;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            writer
+                .write_all(&chunk)
+                .await
+                .map_err(|e| OpenAIError::FileSaveError(e.to_string()))?;
+            written += chunk.len() as u64;
+        }
+
+        writer
+            .flush()
+            .await
+            .map_err(|e| OpenAIError::FileSaveError(e.to_string()))?;
+
+        Ok(written)
+    }
+
+    /// Generates audio from the input text, streamed as [SpeechStreamEvent]s of base64 audio
+    /// chunks while it is generated. The stream ends after [SpeechStreamEvent::Done].
+    ///
+    /// [CreateSpeechRequest] `stream_format` is set to `sse`, not supported by `tts-1` and `tts-1-hd`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.audio.speech_stream", skip_all)
+    )]
+    pub async fn speech_stream(
+        &self,
+        mut request: CreateSpeechRequest,
+    ) -> Result<SpeechResponseStream, OpenAIError> {
+        if request.stream_format == Some(SpeechStreamFormat::Audio) {
+            return Err(OpenAIError::InvalidArgument(
+                "When stream_format is audio, use Audio::speech_stream_raw".into(),
+            ));
+        }
+
+        request.stream_format = Some(SpeechStreamFormat::Sse);
+
+        Ok(self
+            .client
+            .post_stream_until("/audio/speech", request, |event| {
+                matches!(event, SpeechStreamEvent::Done(_))
+            })
+            .await)
+    }
+
+    /// Generates audio from the input text, streamed as chunks of the audio file
+    /// in the `response_format` of the request while it is generated.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.audio.speech_stream_raw", skip_all)
+    )]
+    pub async fn speech_stream_raw(
+        &self,
+        mut request: CreateSpeechRequest,
+    ) -> Result<SpeechByteStream, OpenAIError> {
+        if request.stream_format == Some(SpeechStreamFormat::Sse) {
+            return Err(OpenAIError::InvalidArgument(
+                "When stream_format is sse, use Audio::speech_stream".into(),
+            ));
+        }
+
+        request.stream_format = Some(SpeechStreamFormat::Audio);
+
+        self.client.post_raw_stream("/audio/speech", request).await
+    }
+}
+
+/// Error unless `response_format` is unset or `json`, the format of the response of `method`
+fn check_json_format(
+    response_format: Option<AudioResponseFormat>,
+    method: &str,
+) -> Result<(), OpenAIError> {
+    match response_format {
+        None | Some(AudioResponseFormat::Json) => Ok(()),
+        Some(response_format) => Err(format_error(response_format, method)),
+    }
+}
+
+/// `verbose_json`, the format of the response of `{method}_verbose_json`,
+/// unless `response_format` is another format
+fn verbose_json_format(
+    response_format: Option<AudioResponseFormat>,
+    method: &str,
+) -> Result<AudioResponseFormat, OpenAIError> {
+    match response_format {
+        None | Some(AudioResponseFormat::VerboseJson) => Ok(AudioResponseFormat::VerboseJson),
+        Some(response_format) => Err(format_error(response_format, method)),
+    }
+}
+
+/// Error pointing to the variant of `method` for `response_format`
+fn format_error(response_format: AudioResponseFormat, method: &str) -> OpenAIError {
+    let variant = match response_format {
+        AudioResponseFormat::Json => "",
+        AudioResponseFormat::VerboseJson => "_verbose_json",
+        AudioResponseFormat::DiarizedJson => "_diarized_json",
+        AudioResponseFormat::Text | AudioResponseFormat::Srt | AudioResponseFormat::Vtt => "_raw",
+    };
+
+    OpenAIError::InvalidArgument(format!(
+        "When response_format is {response_format}, use {method}{variant}"
+    ))
 }
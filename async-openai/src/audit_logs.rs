@@ -15,6 +15,10 @@ impl<'c, C: Config> AuditLogs<'c, C> {
     }
 
     /// List user actions and configuration changes within this organization.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.audit_logs.get", skip_all)
+    )]
     pub async fn get<Q>(&self, query: &Q) -> Result<ListAuditLogsResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -1,9 +1,11 @@
+use futures::Stream;
 use serde::Serialize;
 
 use crate::{
     config::Config,
     error::OpenAIError,
-    types::{Batch, BatchRequest, ListBatchesResponse},
+    pagination::paginate,
+    types::{Batch, BatchRequest, ListBatchesResponse, Pagination},
     Client,
 };
 
@@ -20,11 +22,19 @@ impl<'c, C: Config> Batches<'c, C> {
     }
 
     /// Creates and executes a batch from an uploaded file of requests
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.batches.create", skip_all)
+    )]
     pub async fn create(&self, request: BatchRequest) -> Result<Batch, OpenAIError> {
         self.client.post("/batches", request).await
     }
 
     /// List your organization's batches.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.batches.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListBatchesResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -32,12 +42,33 @@ impl<'c, C: Config> Batches<'c, C> {
         self.client.get_with_query("/batches", query).await
     }
 
+    /// Stream all the batches, fetching the pages of [Batches::list] as the stream
+    /// is consumed. `query` holds the other parameters of [Batches::list].
+    pub fn list_all<'a, Q>(
+        &'a self,
+        query: &'a Q,
+        pagination: Pagination,
+    ) -> impl Stream<Item = Result<Batch, OpenAIError>> + 'a
+    where
+        Q: Serialize + ?Sized,
+    {
+        paginate::<_, _, ListBatchesResponse>(self.client, "/batches".into(), query, pagination)
+    }
+
     /// Retrieves a batch.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.batches.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, batch_id: &str) -> Result<Batch, OpenAIError> {
         self.client.get(&format!("/batches/{batch_id}")).await
     }
 
     /// Cancels an in-progress batch. The batch will be in status `cancelling` for up to 10 minutes, before changing to `cancelled`, where it will have partial results (if any) available in the output file.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.batches.cancel", skip_all)
+    )]
     pub async fn cancel(&self, batch_id: &str) -> Result<Batch, OpenAIError> {
         self.client
             .post(
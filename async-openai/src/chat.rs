@@ -1,8 +1,21 @@
+use futures::{
+    stream::{FuturesUnordered, StreamExt},
+    Stream,
+};
+use reqwest::header::HeaderMap;
+use tokio::time::Instant;
+
+#[cfg(feature = "schemars")]
+use crate::types::{ParsedChatCompletion, ResponseFormatJsonSchema};
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::paginate,
     types::{
-        ChatCompletionResponseStream, CreateChatCompletionRequest, CreateChatCompletionResponse,
+        ChatCompletionList, ChatCompletionMessageList, ChatCompletionResponseStream,
+        ChatCompletionStoredMessage, CreateChatCompletionRequest, CreateChatCompletionResponse,
+        ListChatCompletionsQuery, ListChatMessagesQuery, Pagination, RaceEntry, RaceOutcome,
+        RacePolicy,
     },
     Client,
 };
@@ -34,6 +47,10 @@ impl<'c, C: Config> Chat<'c, C> {
     /// of unsupported parameters in reasoning models,
     ///
     /// [refer to the reasoning guide](https://platform.openai.com/docs/guides/reasoning).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.chat.create", skip_all)
+    )]
     pub async fn create(
         &self,
         request: CreateChatCompletionRequest,
@@ -46,11 +63,166 @@ impl<'c, C: Config> Chat<'c, C> {
         self.client.post("/chat/completions", request).await
     }
 
+    /// Same as [Chat::create], also returning the response headers, like
+    /// `openai-processing-ms`, `openai-version` or the `x-request-id`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.chat.create_with_headers", skip_all)
+    )]
+    pub async fn create_with_headers(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> Result<(CreateChatCompletionResponse, HeaderMap), OpenAIError> {
+        if request.stream.is_some() && request.stream.unwrap() {
+            return Err(OpenAIError::InvalidArgument(
+                "When stream is true, use Chat::create_stream".into(),
+            ));
+        }
+        self.client
+            .post_with_headers("/chat/completions", request)
+            .await
+    }
+
+    /// Creates a response in the format of `T` with [Structured Outputs](https://platform.openai.com/docs/guides/structured-outputs),
+    /// and deserializes the content of each choice into `T`.
+    ///
+    /// The strict JSON schema of `T`, see [ResponseFormatJsonSchema::from_type], is set as the `response_format`
+    /// of the request, which must not have one already. Refusals of the model are
+    /// returned as [ParsedMessage::Refusal](crate::types::ParsedMessage::Refusal).
+    ///
+    /// ```no_run
+    /// # async fn example(request: async_openai::types::CreateChatCompletionRequest) -> Result<(), async_openai::error::OpenAIError> {
+    /// use async_openai::Client;
+    /// use schemars::JsonSchema;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, JsonSchema)]
+    /// struct Answer {
+    ///     final_answer: String,
+    ///     steps: Vec<String>,
+    /// }
+    ///
+    /// let completion = Client::new().chat().create_parsed::<Answer>(request).await?;
+    /// if let Some(answer) = completion.parsed() {
+    ///     println!("{}", answer.final_answer);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "schemars")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.chat.create_parsed", skip_all)
+    )]
+    pub async fn create_parsed<T: schemars::JsonSchema + serde::de::DeserializeOwned>(
+        &self,
+        mut request: CreateChatCompletionRequest,
+    ) -> Result<ParsedChatCompletion<T>, OpenAIError> {
+        if request.response_format.is_some() {
+            return Err(OpenAIError::InvalidArgument(
+                "response_format is set by Chat::create_parsed".into(),
+            ));
+        }
+        request.response_format = Some(ResponseFormatJsonSchema::from_type::<T>().into());
+
+        ParsedChatCompletion::from_response(self.create(request).await?)
+    }
+
+    /// Send the same conversation to several models at once, or any set of requests,
+    /// and wait until the [RacePolicy] is satisfied. Requests still in flight are
+    /// then cancelled by dropping them.
+    ///
+    /// Each request goes through the limits of the client, like
+    /// [Client::with_concurrency_limit], so a fan out cannot starve other calls.
+    ///
+    /// ```no_run
+    /// # async fn example(requests: Vec<async_openai::types::CreateChatCompletionRequest>) -> Result<(), async_openai::error::OpenAIError> {
+    /// use async_openai::{types::RacePolicy, Client};
+    ///
+    /// let outcome = Client::new()
+    ///     .chat()
+    ///     .race_models(requests, RacePolicy::FirstSuccess)
+    ///     .await?;
+    ///
+    /// if let Some(winner) = outcome.winner() {
+    ///     println!("{} answered in {:?}", winner.model, winner.latency);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.chat.race_models", skip_all)
+    )]
+    pub async fn race_models(
+        &self,
+        requests: Vec<CreateChatCompletionRequest>,
+        policy: RacePolicy,
+    ) -> Result<RaceOutcome, OpenAIError> {
+        if requests.is_empty() {
+            return Err(OpenAIError::InvalidArgument(
+                "race_models requires at least one request".into(),
+            ));
+        }
+
+        let required = policy.required(requests.len());
+        if required == 0 || required > requests.len() {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "quorum must be between 1 and the {} requests",
+                requests.len()
+            )));
+        }
+
+        let mut pending: Vec<usize> = (0..requests.len()).collect();
+        let mut in_flight: FuturesUnordered<_> = requests
+            .into_iter()
+            .enumerate()
+            .map(|(index, request)| async move {
+                let model = request.model.clone();
+                let started = Instant::now();
+                let result = self.create(request).await;
+
+                RaceEntry {
+                    index,
+                    model,
+                    latency: started.elapsed(),
+                    result,
+                }
+            })
+            .collect();
+
+        let mut completed = Vec::new();
+        let mut successes = 0;
+
+        while let Some(entry) = in_flight.next().await {
+            pending.retain(|index| *index != entry.index);
+            if entry.result.is_ok() {
+                successes += 1;
+            }
+            completed.push(entry);
+
+            if successes >= required {
+                break;
+            }
+        }
+
+        Ok(RaceOutcome {
+            completed,
+            cancelled: pending,
+            satisfied: successes >= required,
+        })
+    }
+
     /// Creates a completion for the chat message
     ///
     /// partial message deltas will be sent, like in ChatGPT. Tokens will be sent as data-only [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events/Using_server-sent_events#Event_stream_format) as they become available, with the stream terminated by a `data: [DONE]` message.
     ///
     /// [ChatCompletionResponseStream] is a parsed SSE stream until a \[DONE\] is received from server.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.chat.create_stream", skip_all)
+    )]
     pub async fn create_stream(
         &self,
         mut request: CreateChatCompletionRequest,
@@ -65,4 +237,67 @@ impl<'c, C: Config> Chat<'c, C> {
 
         Ok(self.client.post_stream("/chat/completions", request).await)
     }
+
+    /// List stored chat completions. Only chat completions that have been stored
+    /// with the `store` parameter set to `true` will be returned.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.chat.list", skip_all)
+    )]
+    pub async fn list(
+        &self,
+        query: &ListChatCompletionsQuery,
+    ) -> Result<ChatCompletionList, OpenAIError> {
+        self.client.get_with_query("/chat/completions", query).await
+    }
+
+    /// Stream all the stored chat completions, fetching the pages of [Chat::list]
+    /// as the stream is consumed.
+    pub fn list_all<'a>(
+        &'a self,
+        query: &'a ListChatCompletionsQuery,
+        pagination: Pagination,
+    ) -> impl Stream<Item = Result<CreateChatCompletionResponse, OpenAIError>> + 'a {
+        paginate::<_, _, ChatCompletionList>(
+            self.client,
+            "/chat/completions".into(),
+            query,
+            pagination,
+        )
+    }
+
+    /// Get the messages in a stored chat completion. Only chat completions that
+    /// have been created with the `store` parameter set to `true` will be returned.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.chat.messages", skip_all)
+    )]
+    pub async fn messages(
+        &self,
+        completion_id: &str,
+        query: &ListChatMessagesQuery,
+    ) -> Result<ChatCompletionMessageList, OpenAIError> {
+        self.client
+            .get_with_query(
+                &format!("/chat/completions/{completion_id}/messages"),
+                query,
+            )
+            .await
+    }
+
+    /// Stream all the messages of a stored chat completion, fetching the pages of
+    /// [Chat::messages] as the stream is consumed.
+    pub fn messages_all<'a>(
+        &'a self,
+        completion_id: &str,
+        query: &'a ListChatMessagesQuery,
+        pagination: Pagination,
+    ) -> impl Stream<Item = Result<ChatCompletionStoredMessage, OpenAIError>> + 'a {
+        paginate::<_, _, ChatCompletionMessageList>(
+            self.client,
+            format!("/chat/completions/{completion_id}/messages"),
+            query,
+            pagination,
+        )
+    }
 }
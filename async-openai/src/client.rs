@@ -1,20 +1,41 @@
+// Request helpers are only used by some of the API groups in minimal builds
+#![cfg_attr(not(feature = "full"), allow(dead_code))]
+use std::collections::HashSet;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 use bytes::Bytes;
-use futures::{stream::StreamExt, Stream};
+use eventsource_stream::Eventsource;
+use futures::{stream::StreamExt, Stream, TryFutureExt};
+#[cfg(feature = "full")]
 use reqwest::multipart::Form;
-use reqwest_eventsource::{Event, EventSource, RequestBuilderExt};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION},
+    Method, StatusCode,
+};
+use reqwest_eventsource::{Event, RequestBuilderExt};
+use secrecy::{ExposeSecret, SecretString};
 use serde::{de::DeserializeOwned, Serialize};
 
+#[cfg(feature = "chat-completion")]
+use crate::Chat;
+#[cfg(feature = "realtime")]
+use crate::Realtime;
+use crate::{
+    config::{Config, OpenAIConfig, FAILOVER_BACKEND_HEADER},
+    deadline::{Budget, DeadlineBackoff},
+    error::{deserialize, map_error_response, OpenAIError, RateLimitCode},
+    limiter::{estimate_tokens, Limiter, RequestPermit},
+    trace::RequestSpan,
+    transport::{HttpTransport, Transport},
+};
+#[cfg(feature = "full")]
 use crate::{
-    config::{Config, OpenAIConfig},
-    error::{map_deserialization_error, OpenAIError, WrappedError},
-    file::Files,
-    image::Images,
-    moderation::Moderations,
-    util::AsyncTryFrom,
-    Assistants, Audio, AuditLogs, Batches, Chat, Completions, Embeddings, FineTuning, Invites,
-    Models, Projects, Threads, Users, VectorStores,
+    file::Files, image::Images, moderation::Moderations, util::AsyncTryFrom, Assistants, Audio,
+    AuditLogs, Batches, Completions, Embeddings, FineTuning, Invites, Models, Projects, Threads,
+    Users, VectorStores,
 };
 
 #[derive(Debug, Clone, Default)]
@@ -24,6 +45,12 @@ pub struct Client<C: Config> {
     http_client: reqwest::Client,
     config: C,
     backoff: backoff::ExponentialBackoff,
+    limiter: Limiter,
+    transport: Option<Transport>,
+    deadline: Option<Duration>,
+    stream_idle_timeout: Option<Duration>,
+    bearer_auth: Option<HeaderValue>,
+    failover: Vec<C>,
 }
 
 impl Client<OpenAIConfig> {
@@ -44,6 +71,12 @@ impl<C: Config> Client<C> {
             http_client,
             config,
             backoff,
+            limiter: Default::default(),
+            transport: None,
+            deadline: None,
+            stream_idle_timeout: None,
+            bearer_auth: None,
+            failover: vec![],
         }
     }
 
@@ -53,6 +86,12 @@ impl<C: Config> Client<C> {
             http_client: reqwest::Client::new(),
             config,
             backoff: Default::default(),
+            limiter: Default::default(),
+            transport: None,
+            deadline: None,
+            stream_idle_timeout: None,
+            bearer_auth: None,
+            failover: vec![],
         }
     }
 
@@ -64,99 +103,223 @@ impl<C: Config> Client<C> {
         self
     }
 
+    /// Send requests with a custom [HttpTransport] instead of the [reqwest::Client],
+    /// for example to return canned responses in tests.
+    ///
+    /// Requests are still built by the [reqwest::Client] set with [Client::with_http_client].
+    pub fn with_transport<T: HttpTransport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Transport(Arc::new(transport)));
+        self
+    }
+
     /// Exponential backoff for retrying [rate limited](https://platform.openai.com/docs/guides/rate-limits) requests.
     pub fn with_backoff(mut self, backoff: backoff::ExponentialBackoff) -> Self {
         self.backoff = backoff;
         self
     }
 
+    /// Limit the number of requests in flight at the same time.
+    ///
+    /// The limit is shared by all clones of this client. Streaming requests
+    /// count towards the limit only until the connection is established.
+    /// ```
+    /// # use async_openai::Client;
+    /// let client = Client::new().with_concurrency_limit(8)?;
+    /// # Ok::<(), async_openai::error::OpenAIError>(())
+    /// ```
+    ///
+    /// Returns [OpenAIError::InvalidArgument] if `limit` is 0, which would block every
+    /// request forever.
+    pub fn with_concurrency_limit(mut self, limit: usize) -> Result<Self, OpenAIError> {
+        self.limiter = self.limiter.with_concurrency_limit(limit)?;
+        Ok(self)
+    }
+
+    /// Limit the number of tokens sent per minute, estimated from the size of
+    /// each request body. Requests whose size is unknown, like file uploads,
+    /// count as a single token.
+    ///
+    /// The budget is shared by all clones of this client.
+    pub fn with_tokens_per_minute(mut self, tokens_per_minute: u64) -> Self {
+        self.limiter = self.limiter.with_tokens_per_minute(tokens_per_minute);
+        self
+    }
+
+    /// Total time budget of each call, covering all attempts and the backoff sleeps
+    /// between them. Once spent, the call fails with [OpenAIError::DeadlineExceeded]
+    /// holding the error of the last attempt.
+    ///
+    /// Retrying stops early when the next backoff sleep would not fit in the budget.
+    /// Streaming calls are not covered.
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// End streams which receive no event for `idle_timeout`, for example when the
+    /// server hangs mid-stream. The stream then yields [OpenAIError::StreamIdleTimeout]
+    /// and terminates. The first event, sent once connected, counts as well.
+    pub fn with_stream_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.stream_idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Send `Authorization: Bearer {api_key}` instead of the header produced by the config,
+    /// for example to make calls on behalf of users who bring their own API key.
+    ///
+    /// Cloning a client is cheap: the clone shares the connection pool, limits and
+    /// transport, so a client with a different key can be created for each call.
+    /// ```
+    /// # use async_openai::Client;
+    /// let client = Client::new();
+    /// let customer_client = client.clone().with_bearer_auth("sk-customer")?;
+    /// # Ok::<(), async_openai::error::OpenAIError>(())
+    /// ```
+    ///
+    /// Returns [OpenAIError::InvalidArgument] if the key is not a valid header value,
+    /// for example when it contains a newline.
+    pub fn with_bearer_auth<S: Into<String>>(mut self, api_key: S) -> Result<Self, OpenAIError> {
+        let api_key = SecretString::from(api_key.into());
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", api_key.expose_secret()))
+            .map_err(|_| {
+                OpenAIError::InvalidArgument("bearer auth key is not a valid header value".into())
+            })?;
+        value.set_sensitive(true);
+        self.bearer_auth = Some(value);
+        Ok(self)
+    }
+
+    /// Configs to fall back on, in order, when a request to the config of the client
+    /// fails with a retryable error: a connection error, a 5xx status, or a 429 status
+    /// once retries are exhausted. The same request is then sent again to the next config.
+    /// Other errors, like a 400 status, are returned without failing over.
+    ///
+    /// Streams fail over only when they fail before their first event. The key of
+    /// [Client::with_bearer_auth] is only sent to the config of the client, fallback
+    /// configs use their own credentials.
+    ///
+    /// Responses get the [crate::config::FAILOVER_BACKEND_HEADER] header with the api base of the config
+    /// which served them, available with methods like [crate::Chat::create_with_headers].
+    /// ```
+    /// # use async_openai::{config::OpenAIConfig, Client};
+    /// let client = Client::with_config(OpenAIConfig::new().with_api_base("https://primary.example.com/v1"))
+    ///     .with_failover([OpenAIConfig::new().with_api_base("https://secondary.example.com/v1")]);
+    /// ```
+    pub fn with_failover<I: IntoIterator<Item = C>>(mut self, configs: I) -> Self {
+        self.failover = configs.into_iter().collect();
+        self
+    }
+
     // API groups
 
     /// To call [Models] group related APIs using this client.
-    pub fn models(&self) -> Models<C> {
+    #[cfg(feature = "full")]
+    pub fn models(&self) -> Models<'_, C> {
         Models::new(self)
     }
 
     /// To call [Completions] group related APIs using this client.
-    pub fn completions(&self) -> Completions<C> {
+    #[cfg(feature = "full")]
+    pub fn completions(&self) -> Completions<'_, C> {
         Completions::new(self)
     }
 
     /// To call [Chat] group related APIs using this client.
-    pub fn chat(&self) -> Chat<C> {
+    #[cfg(feature = "chat-completion")]
+    pub fn chat(&self) -> Chat<'_, C> {
         Chat::new(self)
     }
 
     /// To call [Images] group related APIs using this client.
-    pub fn images(&self) -> Images<C> {
+    #[cfg(feature = "full")]
+    pub fn images(&self) -> Images<'_, C> {
         Images::new(self)
     }
 
     /// To call [Moderations] group related APIs using this client.
-    pub fn moderations(&self) -> Moderations<C> {
+    #[cfg(feature = "full")]
+    pub fn moderations(&self) -> Moderations<'_, C> {
         Moderations::new(self)
     }
 
     /// To call [Files] group related APIs using this client.
-    pub fn files(&self) -> Files<C> {
+    #[cfg(feature = "full")]
+    pub fn files(&self) -> Files<'_, C> {
         Files::new(self)
     }
 
     /// To call [FineTuning] group related APIs using this client.
-    pub fn fine_tuning(&self) -> FineTuning<C> {
+    #[cfg(feature = "full")]
+    pub fn fine_tuning(&self) -> FineTuning<'_, C> {
         FineTuning::new(self)
     }
 
     /// To call [Embeddings] group related APIs using this client.
-    pub fn embeddings(&self) -> Embeddings<C> {
+    #[cfg(feature = "full")]
+    pub fn embeddings(&self) -> Embeddings<'_, C> {
         Embeddings::new(self)
     }
 
     /// To call [Audio] group related APIs using this client.
-    pub fn audio(&self) -> Audio<C> {
+    #[cfg(feature = "full")]
+    pub fn audio(&self) -> Audio<'_, C> {
         Audio::new(self)
     }
 
     /// To call [Assistants] group related APIs using this client.
-    pub fn assistants(&self) -> Assistants<C> {
+    #[cfg(feature = "full")]
+    pub fn assistants(&self) -> Assistants<'_, C> {
         Assistants::new(self)
     }
 
     /// To call [Threads] group related APIs using this client.
-    pub fn threads(&self) -> Threads<C> {
+    #[cfg(feature = "full")]
+    pub fn threads(&self) -> Threads<'_, C> {
         Threads::new(self)
     }
 
     /// To call [VectorStores] group related APIs using this client.
-    pub fn vector_stores(&self) -> VectorStores<C> {
+    #[cfg(feature = "full")]
+    pub fn vector_stores(&self) -> VectorStores<'_, C> {
         VectorStores::new(self)
     }
 
     /// To call [Batches] group related APIs using this client.
-    pub fn batches(&self) -> Batches<C> {
+    #[cfg(feature = "full")]
+    pub fn batches(&self) -> Batches<'_, C> {
         Batches::new(self)
     }
 
     /// To call [AuditLogs] group related APIs using this client.
-    pub fn audit_logs(&self) -> AuditLogs<C> {
+    #[cfg(feature = "full")]
+    pub fn audit_logs(&self) -> AuditLogs<'_, C> {
         AuditLogs::new(self)
     }
 
     /// To call [Invites] group related APIs using this client.
-    pub fn invites(&self) -> Invites<C> {
+    #[cfg(feature = "full")]
+    pub fn invites(&self) -> Invites<'_, C> {
         Invites::new(self)
     }
 
     /// To call [Users] group related APIs using this client.
-    pub fn users(&self) -> Users<C> {
+    #[cfg(feature = "full")]
+    pub fn users(&self) -> Users<'_, C> {
         Users::new(self)
     }
 
     /// To call [Projects] group related APIs using this client.
-    pub fn projects(&self) -> Projects<C> {
+    #[cfg(feature = "full")]
+    pub fn projects(&self) -> Projects<'_, C> {
         Projects::new(self)
     }
 
+    /// To call [Realtime] group related APIs using this client.
+    #[cfg(feature = "realtime")]
+    pub fn realtime(&self) -> Realtime<'_, C> {
+        Realtime::new(self)
+    }
+
     pub fn config(&self) -> &C {
         &self.config
     }
@@ -171,7 +334,7 @@ impl<C: Config> Client<C> {
                 .http_client
                 .get(self.config.url(path))
                 .query(&self.config.query())
-                .headers(self.config.headers())
+                .headers(self.headers()?)
                 .build()?)
         };
 
@@ -185,13 +348,55 @@ impl<C: Config> Client<C> {
         Q: Serialize + ?Sized,
     {
         let request_maker = || async {
-            Ok(self
+            let request = self
                 .http_client
                 .get(self.config.url(path))
-                .query(&self.config.query())
                 .query(query)
-                .headers(self.config.headers())
-                .build()?)
+                .headers(self.headers()?)
+                .build()?;
+
+            Ok(self.merge_config_query(request))
+        };
+
+        self.execute(request_maker).await
+    }
+
+    /// Make a GET request to {path} with given Query, and the `page` query parameters
+    /// replacing the ones of the Query with the same keys
+    pub(crate) async fn get_page<Q, O>(
+        &self,
+        path: &str,
+        query: &Q,
+        page: &[(&str, String)],
+    ) -> Result<O, OpenAIError>
+    where
+        O: DeserializeOwned,
+        Q: Serialize + ?Sized,
+    {
+        let request_maker = || async {
+            let mut request = self
+                .http_client
+                .get(self.config.url(path))
+                .query(query)
+                .headers(self.headers()?)
+                .build()?;
+
+            if !page.is_empty() {
+                let pairs: Vec<(String, String)> = request
+                    .url()
+                    .query_pairs()
+                    .filter(|(key, _)| page.iter().all(|(page_key, _)| page_key != key))
+                    .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                    .collect();
+                request
+                    .url_mut()
+                    .query_pairs_mut()
+                    .clear()
+                    .extend_pairs(pairs)
+                    .extend_pairs(page);
+            }
+
+            Ok(self.merge_config_query(request))
         };
 
         self.execute(request_maker).await
@@ -207,7 +412,7 @@ impl<C: Config> Client<C> {
                 .http_client
                 .delete(self.config.url(path))
                 .query(&self.config.query())
-                .headers(self.config.headers())
+                .headers(self.headers()?)
                 .build()?)
         };
 
@@ -221,13 +426,30 @@ impl<C: Config> Client<C> {
                 .http_client
                 .get(self.config.url(path))
                 .query(&self.config.query())
-                .headers(self.config.headers())
+                .headers(self.headers()?)
                 .build()?)
         };
 
         self.execute_raw(request_maker).await
     }
 
+    /// Make a GET request to the absolute `url`, like the URL of a generated image,
+    /// without the API headers and return the response body
+    #[cfg(feature = "full")]
+    pub(crate) async fn get_url(&self, url: &str) -> Result<Bytes, OpenAIError> {
+        let request = self.http_client.get(url).build()?;
+        let response = self.send(request).await?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = response.bytes().await?;
+        if !status.is_success() {
+            return Err(map_error_response(status, &headers, &bytes));
+        }
+
+        Ok(bytes)
+    }
+
     /// Make a POST request to {path} and return the response body
     pub(crate) async fn post_raw<I>(&self, path: &str, request: I) -> Result<Bytes, OpenAIError>
     where
@@ -238,7 +460,7 @@ impl<C: Config> Client<C> {
                 .http_client
                 .post(self.config.url(path))
                 .query(&self.config.query())
-                .headers(self.config.headers())
+                .headers(self.headers()?)
                 .json(&request)
                 .build()?)
         };
@@ -257,7 +479,7 @@ impl<C: Config> Client<C> {
                 .http_client
                 .post(self.config.url(path))
                 .query(&self.config.query())
-                .headers(self.config.headers())
+                .headers(self.headers()?)
                 .json(&request)
                 .build()?)
         };
@@ -265,7 +487,31 @@ impl<C: Config> Client<C> {
         self.execute(request_maker).await
     }
 
+    /// Make a POST request to {path} and deserialize the response body, along with the response headers
+    pub(crate) async fn post_with_headers<I, O>(
+        &self,
+        path: &str,
+        request: I,
+    ) -> Result<(O, HeaderMap), OpenAIError>
+    where
+        I: Serialize,
+        O: DeserializeOwned,
+    {
+        let request_maker = || async {
+            Ok(self
+                .http_client
+                .post(self.config.url(path))
+                .query(&self.config.query())
+                .headers(self.headers()?)
+                .json(&request)
+                .build()?)
+        };
+
+        self.execute_with_headers(request_maker).await
+    }
+
     /// POST a form at {path} and return the response body
+    #[cfg(feature = "full")]
     pub(crate) async fn post_form_raw<F>(&self, path: &str, form: F) -> Result<Bytes, OpenAIError>
     where
         Form: AsyncTryFrom<F, Error = OpenAIError>,
@@ -276,7 +522,7 @@ impl<C: Config> Client<C> {
                 .http_client
                 .post(self.config.url(path))
                 .query(&self.config.query())
-                .headers(self.config.headers())
+                .headers(self.headers()?)
                 .multipart(<Form as AsyncTryFrom<F>>::try_from(form.clone()).await?)
                 .build()?)
         };
@@ -285,6 +531,7 @@ impl<C: Config> Client<C> {
     }
 
     /// POST a form at {path} and deserialize the response body
+    #[cfg(feature = "full")]
     pub(crate) async fn post_form<O, F>(&self, path: &str, form: F) -> Result<O, OpenAIError>
     where
         O: DeserializeOwned,
@@ -296,7 +543,7 @@ impl<C: Config> Client<C> {
                 .http_client
                 .post(self.config.url(path))
                 .query(&self.config.query())
-                .headers(self.config.headers())
+                .headers(self.headers()?)
                 .multipart(<Form as AsyncTryFrom<F>>::try_from(form.clone()).await?)
                 .build()?)
         };
@@ -304,6 +551,82 @@ impl<C: Config> Client<C> {
         self.execute(request_maker).await
     }
 
+    /// Headers of the config, with the Authorization set by [Client::with_bearer_auth] if any
+    fn headers(&self) -> Result<HeaderMap, OpenAIError> {
+        let mut headers = self.config.try_headers()?;
+
+        if let Some(value) = &self.bearer_auth {
+            headers.insert(AUTHORIZATION, value.clone());
+        }
+
+        Ok(headers)
+    }
+
+    /// Add the query parameters of the config to the request url,
+    /// except for keys which are already set on the request
+    fn merge_config_query(&self, request: reqwest::Request) -> reqwest::Request {
+        merge_query(&self.config, request)
+    }
+
+    /// Send a request built for the config of the client to `config` instead:
+    /// replace its base url, config query parameters and config headers. The
+    /// Authorization of [Client::with_bearer_auth] is replaced by the credentials of `config`.
+    fn retarget(
+        &self,
+        mut request: reqwest::Request,
+        config: &C,
+    ) -> Result<reqwest::Request, OpenAIError> {
+        let mut url = request.url().clone();
+        url.set_query(None);
+        let path = url
+            .as_str()
+            .strip_prefix(&self.config.url(""))
+            .ok_or_else(|| {
+                OpenAIError::InvalidArgument(format!("cannot fail over request to {url}"))
+            })?;
+        let mut url = reqwest::Url::parse(&config.url(path))
+            .map_err(|e| OpenAIError::InvalidArgument(e.to_string()))?;
+
+        let primary_query = self.config.query();
+        let query: Vec<(String, String)> = request
+            .url()
+            .query_pairs()
+            .filter(|(key, value)| !primary_query.contains(&(key.as_ref(), value.as_ref())))
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        *request.url_mut() = url;
+
+        let headers = request.headers_mut();
+        for name in self.headers()?.keys() {
+            headers.remove(name);
+        }
+        headers.extend(config.try_headers()?);
+
+        Ok(merge_query(config, request))
+    }
+
+    /// Same as [Self::retarget] for requests which are not built yet
+    fn retarget_builder(
+        &self,
+        request_builder: &reqwest::RequestBuilder,
+        config: &C,
+    ) -> Result<reqwest::RequestBuilder, OpenAIError> {
+        let request = request_builder
+            .try_clone()
+            .ok_or_else(|| {
+                OpenAIError::InvalidArgument("cannot fail over a streamed request body".into())
+            })?
+            .build()?;
+
+        Ok(reqwest::RequestBuilder::from_parts(
+            self.http_client.clone(),
+            self.retarget(request, config)?,
+        ))
+    }
+
     /// Execute a HTTP request and retry on rate limit
     ///
     /// request_maker serves one purpose: to be able to create request again
@@ -314,50 +637,152 @@ impl<C: Config> Client<C> {
         M: Fn() -> Fut,
         Fut: core::future::Future<Output = Result<reqwest::Request, OpenAIError>>,
     {
-        let client = self.http_client.clone();
+        let (bytes, _headers) = self.execute_raw_with_headers(request_maker).await?;
+        Ok(bytes)
+    }
 
-        backoff::future::retry(self.backoff.clone(), || async {
-            let request = request_maker().await.map_err(backoff::Error::Permanent)?;
-            let response = client
-                .execute(request)
+    /// Same as [Self::execute_raw], also returning the headers of the successful response
+    async fn execute_raw_with_headers<M, Fut>(
+        &self,
+        request_maker: M,
+    ) -> Result<(Bytes, HeaderMap), OpenAIError>
+    where
+        M: Fn() -> Fut,
+        Fut: core::future::Future<Output = Result<reqwest::Request, OpenAIError>>,
+    {
+        let budget = Budget::new(self.deadline);
+        let mut fallbacks = self.failover.iter();
+        let mut config = None;
+
+        loop {
+            let fail_over = AtomicBool::new(false);
+
+            match self
+                .execute_attempts(&request_maker, config, &budget, &fail_over)
                 .await
-                .map_err(OpenAIError::Reqwest)
-                .map_err(backoff::Error::Permanent)?;
+            {
+                Ok((bytes, mut headers)) => {
+                    if !self.failover.is_empty() {
+                        let api_base = config.unwrap_or(&self.config).api_base();
+                        if let Ok(value) = HeaderValue::from_str(api_base) {
+                            headers.insert(FAILOVER_BACKEND_HEADER, value);
+                        }
+                    }
+                    return Ok((bytes, headers));
+                }
+                Err(err @ OpenAIError::DeadlineExceeded { .. }) => return Err(err),
+                Err(err) if fail_over.load(Ordering::Relaxed) => match fallbacks.next() {
+                    Some(fallback) => {
+                        tracing::warn!("failing over to {}: {}", fallback.api_base(), err);
+                        budget.record_error(err);
+                        config = Some(fallback);
+                    }
+                    None => return Err(err),
+                },
+                Err(err) => return Err(err),
+            }
+        }
+    }
 
-            let status = response.status();
-            let bytes = response
-                .bytes()
+    /// Send the request to `config`, or the config of the client, until it succeeds or
+    /// retries are exhausted. `fail_over` is set when the last attempt failed with an
+    /// error worth sending the request to another config.
+    async fn execute_attempts<M, Fut>(
+        &self,
+        request_maker: &M,
+        config: Option<&C>,
+        budget: &Budget,
+        fail_over: &AtomicBool,
+    ) -> Result<(Bytes, HeaderMap), OpenAIError>
+    where
+        M: Fn() -> Fut,
+        Fut: core::future::Future<Output = Result<reqwest::Request, OpenAIError>>,
+    {
+        let backoff = DeadlineBackoff {
+            inner: self.backoff.clone(),
+            budget,
+        };
+
+        let operation = || async {
+            budget.start_attempt();
+            fail_over.store(false, Ordering::Relaxed);
+
+            let attempt = async {
+                let request = match request_maker().await {
+                    Ok(request) => request,
+                    // An input read from a reader can't be sent again: the error of
+                    // the attempt which read it is returned instead
+                    Err(err @ OpenAIError::FileReadError(_)) => {
+                        return Err(budget.take_last_error().unwrap_or(err))
+                    }
+                    Err(err) => return Err(err),
+                };
+                let request = match config {
+                    Some(config) => self.retarget(request, config)?,
+                    None => request,
+                };
+                let _permit = self.acquire_permit(&request).await;
+
+                let span = RequestSpan::http(&request);
+                span.instrument(async {
+                    let response = self.send(request).await?;
+
+                    let status = response.status();
+                    let headers = response.headers().clone();
+                    span.record_response(status, &headers);
+                    let bytes = response.bytes().await.map_err(OpenAIError::Reqwest)?;
+
+                    Ok::<_, OpenAIError>((status, headers, bytes))
+                })
                 .await
-                .map_err(OpenAIError::Reqwest)
-                .map_err(backoff::Error::Permanent)?;
+                .map_err(|err| {
+                    if fails_over(&err) {
+                        fail_over.store(true, Ordering::Relaxed);
+                    }
+                    err
+                })
+            };
+
+            let (status, headers, bytes) = match budget.deadline() {
+                Some(deadline) => tokio::time::timeout_at(deadline, attempt)
+                    .await
+                    .map_err(|_| backoff::Error::Permanent(budget.exceeded(None)))?,
+                None => attempt.await,
+            }
+            .map_err(backoff::Error::Permanent)?;
 
             // Deserialize response body from either error object or actual response object
             if !status.is_success() {
-                let wrapped_error: WrappedError = serde_json::from_slice(bytes.as_ref())
-                    .map_err(|e| map_deserialization_error(e, bytes.as_ref()))
-                    .map_err(backoff::Error::Permanent)?;
+                if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+                    fail_over.store(true, Ordering::Relaxed);
+                }
 
-                if status.as_u16() == 429
+                return match map_error_response(status, &headers, &bytes) {
                     // API returns 429 also when:
                     // "You exceeded your current quota, please check your plan and billing details."
-                    && wrapped_error.error.r#type != Some("insufficient_quota".to_string())
-                {
-                    // Rate limited retry...
-                    tracing::warn!("Rate limited: {}", wrapped_error.error.message);
-                    return Err(backoff::Error::Transient {
-                        err: OpenAIError::ApiError(wrapped_error.error),
-                        retry_after: None,
-                    });
-                } else {
-                    return Err(backoff::Error::Permanent(OpenAIError::ApiError(
-                        wrapped_error.error,
-                    )));
-                }
+                    err @ OpenAIError::RateLimited {
+                        code: RateLimitCode::InsufficientQuota,
+                        ..
+                    } => Err(backoff::Error::Permanent(err)),
+                    err @ OpenAIError::RateLimited { retry_after, .. } => {
+                        if retry_after.is_some_and(|retry_after| budget.would_exceed(retry_after)) {
+                            return Err(backoff::Error::Permanent(budget.exceeded(Some(err))));
+                        }
+
+                        // Rate limited retry...
+                        tracing::warn!("{}", err);
+                        Err(backoff::Error::Transient { err, retry_after })
+                    }
+                    err => Err(backoff::Error::Permanent(err)),
+                };
             }
 
-            Ok(bytes)
-        })
-        .await
+            Ok((bytes, headers))
+        };
+
+        backoff::future::retry_notify(backoff, operation, |err, _| budget.record_error(err))
+            .await
+            .map_err(|err| budget.finish(err))
     }
 
     /// Execute a HTTP request and retry on rate limit
@@ -373,12 +798,160 @@ impl<C: Config> Client<C> {
     {
         let bytes = self.execute_raw(request_maker).await?;
 
-        let response: O = serde_json::from_slice(bytes.as_ref())
-            .map_err(|e| map_deserialization_error(e, bytes.as_ref()))?;
+        let response: O = deserialize(bytes.as_ref())?;
 
         Ok(response)
     }
 
+    /// Same as [Self::execute], also returning the headers of the successful response
+    async fn execute_with_headers<O, M, Fut>(
+        &self,
+        request_maker: M,
+    ) -> Result<(O, HeaderMap), OpenAIError>
+    where
+        O: DeserializeOwned,
+        M: Fn() -> Fut,
+        Fut: core::future::Future<Output = Result<reqwest::Request, OpenAIError>>,
+    {
+        let (bytes, headers) = self.execute_raw_with_headers(request_maker).await?;
+
+        let response: O = deserialize(bytes.as_ref())?;
+
+        Ok((response, headers))
+    }
+
+    /// Send the request with the custom transport if any, otherwise with the HTTP client
+    async fn send(&self, request: reqwest::Request) -> Result<reqwest::Response, OpenAIError> {
+        match &self.transport {
+            Some(transport) => transport.0.execute(request).await,
+            None => self
+                .http_client
+                .execute(request)
+                .await
+                .map_err(OpenAIError::Reqwest),
+        }
+    }
+
+    /// Server-sent events of the request, received with the custom transport if any.
+    /// Fails over to the fallback configs, if any, when the stream fails before its first event.
+    fn event_stream(
+        &self,
+        request_builder: Result<reqwest::RequestBuilder, OpenAIError>,
+    ) -> EventStream {
+        let fallbacks: Vec<_> = match &request_builder {
+            Ok(request_builder) => self
+                .failover
+                .iter()
+                .map(|config| self.retarget_builder(request_builder, config))
+                .collect(),
+            Err(_) => vec![],
+        };
+        let mut request_builders = std::iter::once(request_builder).chain(fallbacks).peekable();
+
+        let event_stream: EventStream = match &self.transport {
+            Some(transport) => {
+                let transport = transport.clone();
+                Box::pin(
+                    async move {
+                        let bytes = loop {
+                            let request = request_builders.next().unwrap()?.build()?;
+                            match transport.0.execute_stream(request).await {
+                                Err(e) if fails_over(&e) && request_builders.peek().is_some() => {
+                                    tracing::warn!("failing over stream: {}", e);
+                                }
+                                bytes => break bytes?,
+                            }
+                        };
+                        Ok(bytes.eventsource().map(|event| {
+                            event
+                                .map(Event::Message)
+                                .map_err(|e| OpenAIError::StreamError(e.to_string()))
+                        }))
+                    }
+                    .try_flatten_stream(),
+                )
+            }
+            None => Box::pin(
+                async move {
+                    loop {
+                        let mut event_source =
+                            request_builders.next().unwrap()?.eventsource().unwrap();
+                        match event_source.next().await {
+                            Some(Err(e))
+                                if stream_fails_over(&e) && request_builders.peek().is_some() =>
+                            {
+                                tracing::warn!("failing over stream: {}", e);
+                                event_source.close();
+                            }
+                            Some(Err(reqwest_eventsource::Error::InvalidStatusCode(
+                                status,
+                                response,
+                            ))) => {
+                                event_source.close();
+                                let headers = response.headers().clone();
+                                let bytes = response.bytes().await?;
+                                break Err(map_error_response(status, &headers, &bytes));
+                            }
+                            first => {
+                                break Ok::<_, OpenAIError>(
+                                    futures::stream::iter(first)
+                                        .chain(event_source)
+                                        .map(|event| {
+                                            event.map_err(|e| {
+                                                OpenAIError::StreamError(e.to_string())
+                                            })
+                                        }),
+                                )
+                            }
+                        }
+                    }
+                }
+                .try_flatten_stream(),
+            ),
+        };
+
+        match self.stream_idle_timeout {
+            Some(idle_timeout) => Box::pin(futures::stream::unfold(
+                Some(event_stream),
+                move |event_stream| async move {
+                    // The stream ends after reporting the timeout
+                    let mut event_stream = event_stream?;
+                    match tokio::time::timeout(idle_timeout, event_stream.next()).await {
+                        Ok(event) => Some((event?, Some(event_stream))),
+                        Err(_) => Some((Err(OpenAIError::StreamIdleTimeout(idle_timeout)), None)),
+                    }
+                },
+            )),
+            None => event_stream,
+        }
+    }
+
+    /// Wait for the client side limits, if any, before sending the request
+    async fn acquire_permit(&self, request: &reqwest::Request) -> RequestPermit {
+        let estimated_tokens = if self.limiter.has_token_budget() {
+            request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(estimate_tokens)
+        } else {
+            None
+        };
+
+        self.limiter.acquire(estimated_tokens).await
+    }
+
+    /// Same as [Self::acquire_permit] for requests which are not built yet
+    async fn acquire_stream_permit<I: Serialize>(&self, request: Option<&I>) -> RequestPermit {
+        let estimated_tokens = match request {
+            Some(request) if self.limiter.has_token_budget() => serde_json::to_vec(request)
+                .ok()
+                .map(|body| estimate_tokens(&body)),
+            _ => None,
+        };
+
+        self.limiter.acquire(estimated_tokens).await
+    }
+
     /// Make HTTP POST request to receive SSE
     pub(crate) async fn post_stream<I, O>(
         &self,
@@ -389,16 +962,76 @@ impl<C: Config> Client<C> {
         I: Serialize,
         O: DeserializeOwned + std::marker::Send + 'static,
     {
-        let event_source = self
+        let permit = self.acquire_stream_permit(Some(&request)).await;
+        let span = RequestSpan::stream(Method::POST, path, Some(&request));
+
+        let event_source = self.event_stream(self.headers().map(|headers| {
+            self.http_client
+                .post(self.config.url(path))
+                .query(&self.config.query())
+                .headers(headers)
+                .json(&request)
+        }));
+
+        stream(event_source, permit, span).await
+    }
+
+    /// Same as [Self::post_stream] for streams which end with the event for which
+    /// `is_done` is true instead of `[DONE]`
+    pub(crate) async fn post_stream_until<I, O>(
+        &self,
+        path: &str,
+        request: I,
+        is_done: fn(&O) -> bool,
+    ) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
+    where
+        I: Serialize,
+        O: DeserializeOwned + std::marker::Send + 'static,
+    {
+        let permit = self.acquire_stream_permit(Some(&request)).await;
+        let span = RequestSpan::stream(Method::POST, path, Some(&request));
+
+        let event_source = self.event_stream(self.headers().map(|headers| {
+            self.http_client
+                .post(self.config.url(path))
+                .query(&self.config.query())
+                .headers(headers)
+                .json(&request)
+        }));
+
+        stream_until(event_source, permit, span, is_done).await
+    }
+
+    /// Make HTTP POST request to receive the response body in chunks, as they arrive
+    pub(crate) async fn post_raw_stream<I>(
+        &self,
+        path: &str,
+        request: I,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Bytes, OpenAIError>> + Send>>, OpenAIError>
+    where
+        I: Serialize,
+    {
+        let _permit = self.acquire_stream_permit(Some(&request)).await;
+        let span = RequestSpan::stream(Method::POST, path, Some(&request));
+
+        let request = self
             .http_client
             .post(self.config.url(path))
             .query(&self.config.query())
-            .headers(self.config.headers())
+            .headers(self.headers()?)
             .json(&request)
-            .eventsource()
-            .unwrap();
+            .build()?;
+
+        let bytes = span
+            .instrument(async {
+                match &self.transport {
+                    Some(transport) => transport.0.execute_stream(request).await,
+                    None => self.http_client.execute_stream(request).await,
+                }
+            })
+            .await?;
 
-        stream(event_source).await
+        Ok(Box::pin(bytes))
     }
 
     pub(crate) async fn post_stream_mapped_raw_events<I, O>(
@@ -411,16 +1044,18 @@ impl<C: Config> Client<C> {
         I: Serialize,
         O: DeserializeOwned + std::marker::Send + 'static,
     {
-        let event_source = self
-            .http_client
-            .post(self.config.url(path))
-            .query(&self.config.query())
-            .headers(self.config.headers())
-            .json(&request)
-            .eventsource()
-            .unwrap();
+        let permit = self.acquire_stream_permit(Some(&request)).await;
+        let span = RequestSpan::stream(Method::POST, path, Some(&request));
 
-        stream_mapped_raw_events(event_source, event_mapper).await
+        let event_source = self.event_stream(self.headers().map(|headers| {
+            self.http_client
+                .post(self.config.url(path))
+                .query(&self.config.query())
+                .headers(headers)
+                .json(&request)
+        }));
+
+        stream_mapped_raw_events(event_source, permit, span, event_mapper).await
     }
 
     /// Make HTTP GET request to receive SSE
@@ -433,67 +1068,143 @@ impl<C: Config> Client<C> {
         Q: Serialize + ?Sized,
         O: DeserializeOwned + std::marker::Send + 'static,
     {
-        let event_source = self
-            .http_client
-            .get(self.config.url(path))
-            .query(query)
-            .query(&self.config.query())
-            .headers(self.config.headers())
-            .eventsource()
-            .unwrap();
+        let permit = self.acquire_stream_permit::<()>(None).await;
+        let span = RequestSpan::stream::<()>(Method::GET, path, None);
 
-        stream(event_source).await
+        let event_source = self.event_stream(self.headers().map(|headers| {
+            self.http_client
+                .get(self.config.url(path))
+                .query(query)
+                .query(&self.config.query())
+                .headers(headers)
+        }));
+
+        stream(event_source, permit, span).await
+    }
+}
+
+/// Add the query parameters of `config` to the request url,
+/// except for keys which are already set on the request
+fn merge_query<C: Config>(config: &C, mut request: reqwest::Request) -> reqwest::Request {
+    let existing: HashSet<String> = request
+        .url()
+        .query_pairs()
+        .map(|(key, _)| key.into_owned())
+        .collect();
+
+    let config_query: Vec<_> = config
+        .query()
+        .into_iter()
+        .filter(|(key, _)| !existing.contains(*key))
+        .collect();
+
+    if !config_query.is_empty() {
+        request
+            .url_mut()
+            .query_pairs_mut()
+            .extend_pairs(config_query);
     }
+
+    request
 }
 
+/// Whether a failed request is worth sending to a fallback config, see [Client::with_failover]
+fn fails_over(error: &OpenAIError) -> bool {
+    match error {
+        OpenAIError::Reqwest(e) => e.is_connect() || e.is_timeout(),
+        error => error.status().is_some_and(|status| {
+            status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+        }),
+    }
+}
+
+/// Same as [fails_over] for errors of the first event of a stream
+fn stream_fails_over(error: &reqwest_eventsource::Error) -> bool {
+    match error {
+        reqwest_eventsource::Error::Transport(e) => e.is_connect() || e.is_timeout(),
+        reqwest_eventsource::Error::InvalidStatusCode(status, _) => {
+            status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+        }
+        _ => false,
+    }
+}
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<Event, OpenAIError>> + Send>>;
+
 /// Request which responds with SSE.
 /// [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events/Using_server-sent_events#event_stream_format)
 pub(crate) async fn stream<O>(
-    mut event_source: EventSource,
+    event_source: EventStream,
+    permit: RequestPermit,
+    span: RequestSpan,
+) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
+where
+    O: DeserializeOwned + std::marker::Send + 'static,
+{
+    stream_until(event_source, permit, span, |_| false).await
+}
+
+/// Same as [stream], also ending after the event for which `is_done` is true
+pub(crate) async fn stream_until<O>(
+    mut event_source: EventStream,
+    permit: RequestPermit,
+    span: RequestSpan,
+    is_done: fn(&O) -> bool,
 ) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
 where
     O: DeserializeOwned + std::marker::Send + 'static,
 {
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
 
-    tokio::spawn(async move {
+    tokio::spawn(span.clone().instrument(async move {
+        // Connection is established on first poll, release the permit after that
+        let mut permit = Some(permit);
+        let mut events = 0;
+
         while let Some(ev) = event_source.next().await {
+            permit.take();
+
             match ev {
                 Err(e) => {
-                    if let Err(_e) = tx.send(Err(OpenAIError::StreamError(e.to_string()))) {
+                    if let Err(_e) = tx.send(Err(e)) {
                         // rx dropped
                         break;
                     }
                 }
                 Ok(event) => match event {
                     Event::Message(message) => {
+                        events += 1;
                         if message.data == "[DONE]" {
                             break;
                         }
 
-                        let response = match serde_json::from_str::<O>(&message.data) {
-                            Err(e) => Err(map_deserialization_error(e, message.data.as_bytes())),
-                            Ok(output) => Ok(output),
-                        };
+                        let response = deserialize::<O>(message.data.as_bytes());
+                        let done = matches!(&response, Ok(response) if is_done(response));
 
                         if let Err(_e) = tx.send(response) {
                             // rx dropped
                             break;
                         }
+
+                        if done {
+                            break;
+                        }
                     }
                     Event::Open => continue,
                 },
             }
         }
 
-        event_source.close();
-    });
+        span.record_events(events);
+    }));
 
     Box::pin(tokio_stream::wrappers::UnboundedReceiverStream::new(rx))
 }
 
 pub(crate) async fn stream_mapped_raw_events<O>(
-    mut event_source: EventSource,
+    mut event_source: EventStream,
+    permit: RequestPermit,
+    span: RequestSpan,
     event_mapper: impl Fn(eventsource_stream::Event) -> Result<O, OpenAIError> + Send + 'static,
 ) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
 where
@@ -501,17 +1212,24 @@ where
 {
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
 
-    tokio::spawn(async move {
+    tokio::spawn(span.clone().instrument(async move {
+        // Connection is established on first poll, release the permit after that
+        let mut permit = Some(permit);
+        let mut events = 0;
+
         while let Some(ev) = event_source.next().await {
+            permit.take();
+
             match ev {
                 Err(e) => {
-                    if let Err(_e) = tx.send(Err(OpenAIError::StreamError(e.to_string()))) {
+                    if let Err(_e) = tx.send(Err(e)) {
                         // rx dropped
                         break;
                     }
                 }
                 Ok(event) => match event {
                     Event::Message(message) => {
+                        events += 1;
                         let mut done = false;
 
                         if message.data == "[DONE]" {
@@ -534,8 +1252,8 @@ where
             }
         }
 
-        event_source.close();
-    });
+        span.record_events(events);
+    }));
 
     Box::pin(tokio_stream::wrappers::UnboundedReceiverStream::new(rx))
 }
@@ -21,6 +21,10 @@ impl<'c, C: Config> Completions<'c, C> {
     }
 
     /// Creates a completion for the provided prompt and parameters
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.completions.create", skip_all)
+    )]
     pub async fn create(
         &self,
         request: CreateCompletionRequest,
@@ -40,6 +44,10 @@ impl<'c, C: Config> Completions<'c, C> {
     /// as they become available, with the stream terminated by a data: \[DONE\] message.
     ///
     /// [CompletionResponseStream] is a parsed SSE stream until a \[DONE\] is received from server.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.completions.create_stream", skip_all)
+    )]
     pub async fn create_stream(
         &self,
         mut request: CreateCompletionRequest,
@@ -1,8 +1,12 @@
 //! Client configurations: [OpenAIConfig] for OpenAI, [AzureConfig] for Azure OpenAI Service.
-use reqwest::header::{HeaderMap, AUTHORIZATION};
+use std::sync::Arc;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
 use secrecy::{ExposeSecret, SecretString};
 use serde::Deserialize;
 
+use crate::error::OpenAIError;
+
 /// Default v1 API base url
 pub const OPENAI_API_BASE: &str = "https://api.openai.com/v1";
 /// Organization header
@@ -13,45 +17,239 @@ pub const OPENAI_PROJECT_HEADER: &str = "OpenAI-Project";
 /// Calls to the Assistants API require that you pass a Beta header
 pub const OPENAI_BETA_HEADER: &str = "OpenAI-Beta";
 
+/// Response header holding the api base of the config which served a response,
+/// added when the client has fallback configs, see [crate::Client::with_failover]
+pub const FAILOVER_BACKEND_HEADER: &str = "x-async-openai-backend";
+
+/// Default base url of a local Ollama server
+pub const OLLAMA_API_BASE: &str = "http://localhost:11434/v1";
+/// Base url of the Gemini OpenAI compatible API
+pub const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta/openai";
+/// Base url of the Groq OpenAI compatible API
+pub const GROQ_API_BASE: &str = "https://api.groq.com/openai/v1";
+/// Base url of the OpenRouter API
+pub const OPENROUTER_API_BASE: &str = "https://openrouter.ai/api/v1";
+/// Default base url of a local vLLM OpenAI compatible server
+pub const VLLM_API_BASE: &str = "http://localhost:8000/v1";
+
 /// [crate::Client] relies on this for every API call on OpenAI
 /// or Azure OpenAI service
 pub trait Config: Clone {
     fn headers(&self) -> HeaderMap;
+
+    /// Same as [Config::headers], failing with [OpenAIError::InvalidArgument] instead of
+    /// leaving out a header which is not valid, like the key of an [ApiKeyProvider]
+    /// containing a newline. Used by [crate::Client] for every request.
+    fn try_headers(&self) -> Result<HeaderMap, OpenAIError> {
+        Ok(self.headers())
+    }
+
     fn url(&self, path: &str) -> String;
     fn query(&self) -> Vec<(&str, &str)>;
 
     fn api_base(&self) -> &str;
 
+    /// The static API key of the config. A key provider set with
+    /// [OpenAIConfig::with_api_key_provider] is not called, only [Config::headers]
+    /// and [Config::try_headers] resolve it.
     fn api_key(&self) -> &SecretString;
 }
 
+/// Resolves the API key at request time, see [OpenAIConfig::with_api_key_provider]
+#[derive(Clone)]
+pub struct ApiKeyProvider(Arc<dyn Fn() -> SecretString + Send + Sync>);
+
+impl ApiKeyProvider {
+    pub fn new<F>(provider: F) -> Self
+    where
+        F: Fn() -> SecretString + Send + Sync + 'static,
+    {
+        Self(Arc::new(provider))
+    }
+
+    /// Invoke the provider to get the current API key
+    pub fn api_key(&self) -> SecretString {
+        (self.0)()
+    }
+}
+
+impl std::fmt::Debug for ApiKeyProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ApiKeyProvider")
+    }
+}
+
 /// Configuration for OpenAI API
 #[derive(Clone, Debug, Deserialize)]
 #[serde(default)]
 pub struct OpenAIConfig {
     api_base: String,
     api_key: SecretString,
+    #[serde(skip)]
+    api_key_provider: Option<ApiKeyProvider>,
     org_id: String,
     project_id: String,
+    #[serde(skip)]
+    custom_headers: HeaderMap,
+    query_params: Vec<(String, String)>,
+}
+
+/// OpenAI compatible providers for [OpenAIConfig::preset].
+///
+/// Providers implement only a subset of the OpenAI API,
+/// the API groups known to work are listed on each variant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Provider {
+    /// Local [Ollama](https://ollama.com) server at [OLLAMA_API_BASE].
+    /// Ollama ignores the API key but requires one to be present.
+    ///
+    /// Known to work: chat, completions, embeddings, models.
+    Ollama,
+    /// [Gemini](https://ai.google.dev/gemini-api/docs/openai) at [GEMINI_API_BASE]
+    /// with API key from GEMINI_API_KEY env var.
+    ///
+    /// Known to work: chat, embeddings, images, models.
+    Gemini,
+    /// [Groq](https://console.groq.com/docs/openai) at [GROQ_API_BASE]
+    /// with API key from GROQ_API_KEY env var.
+    ///
+    /// Known to work: chat, audio transcriptions and translations, models.
+    Groq,
+    /// [OpenRouter](https://openrouter.ai/docs) at [OPENROUTER_API_BASE]
+    /// with API key from OPENROUTER_API_KEY env var.
+    ///
+    /// `site_url` and `site_name` are sent as `HTTP-Referer` and `X-Title`
+    /// headers for app attribution on openrouter.ai.
+    ///
+    /// Known to work: chat, completions, models.
+    OpenRouter {
+        site_url: Option<String>,
+        site_name: Option<String>,
+    },
+    /// Local [vLLM](https://docs.vllm.ai) server at [VLLM_API_BASE]
+    /// with API key from VLLM_API_KEY env var, if the server was started with `--api-key`.
+    ///
+    /// Known to work: chat, completions, embeddings, models.
+    Vllm,
 }
 
 impl Default for OpenAIConfig {
+    /// Same as [OpenAIConfig::from_env], falling back to [OPENAI_API_BASE]
+    /// when OPENAI_BASE_URL is not a valid url.
     fn default() -> Self {
+        Self::from_env().unwrap_or_else(|e| {
+            tracing::warn!("{e}, using {OPENAI_API_BASE}");
+            Self::base().with_env_credentials()
+        })
+    }
+}
+
+/// Trimmed value of an env var, `None` when unset or blank
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+impl OpenAIConfig {
+    /// Create client with default [OPENAI_API_BASE] url and default API key from OPENAI_API_KEY env var,
+    /// organization, project and base url are also read from env vars, see [OpenAIConfig::from_env]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Config from the OPENAI_API_KEY, OPENAI_ORG_ID, OPENAI_PROJECT_ID and
+    /// OPENAI_BASE_URL env vars, like the official SDKs. Values are trimmed and
+    /// unset or blank vars keep their default.
+    ///
+    /// A missing API key is not an error here, API calls fail with an
+    /// authentication error instead. Fails if OPENAI_BASE_URL is not a valid url.
+    pub fn from_env() -> Result<Self, OpenAIError> {
+        let mut config = Self::base().with_env_credentials();
+
+        if let Some(api_base) = env_var("OPENAI_BASE_URL") {
+            reqwest::Url::parse(&api_base).map_err(|e| {
+                OpenAIError::InvalidArgument(format!("invalid OPENAI_BASE_URL {api_base}: {e}"))
+            })?;
+            config.api_base = api_base.trim_end_matches('/').to_string();
+        }
+
+        Ok(config)
+    }
+
+    /// Config with default values only, without reading env vars
+    fn base() -> Self {
         Self {
             api_base: OPENAI_API_BASE.to_string(),
-            api_key: std::env::var("OPENAI_API_KEY")
-                .unwrap_or_else(|_| "".to_string())
-                .into(),
+            api_key: "".into(),
+            api_key_provider: None,
             org_id: Default::default(),
             project_id: Default::default(),
+            custom_headers: Default::default(),
+            query_params: Default::default(),
         }
     }
-}
 
-impl OpenAIConfig {
-    /// Create client with default [OPENAI_API_BASE] url and default API key from OPENAI_API_KEY env var
-    pub fn new() -> Self {
-        Default::default()
+    /// Set API key, organization and project from env vars
+    fn with_env_credentials(mut self) -> Self {
+        if let Some(api_key) = env_var("OPENAI_API_KEY") {
+            self = self.with_api_key(api_key);
+        }
+        if let Some(org_id) = env_var("OPENAI_ORG_ID") {
+            self = self.with_org_id(org_id);
+        }
+        if let Some(project_id) = env_var("OPENAI_PROJECT_ID") {
+            self = self.with_project_id(project_id);
+        }
+        self
+    }
+
+    /// Config for an OpenAI compatible [Provider] with its base url, API key and headers.
+    ///
+    /// Values can be overridden with the other `with_*` methods:
+    /// ```
+    /// use async_openai::config::{OpenAIConfig, Provider};
+    ///
+    /// let config = OpenAIConfig::preset(Provider::Ollama).with_api_base("http://gpu-box:11434/v1");
+    /// ```
+    pub fn preset(provider: Provider) -> Self {
+        let env_api_key = |name: &str| std::env::var(name).unwrap_or_default();
+
+        match provider {
+            Provider::Ollama => Self::base()
+                .with_api_base(OLLAMA_API_BASE)
+                .with_api_key("ollama"),
+            Provider::Gemini => Self::base()
+                .with_api_base(GEMINI_API_BASE)
+                .with_api_key(env_api_key("GEMINI_API_KEY")),
+            Provider::Groq => Self::base()
+                .with_api_base(GROQ_API_BASE)
+                .with_api_key(env_api_key("GROQ_API_KEY")),
+            Provider::OpenRouter {
+                site_url,
+                site_name,
+            } => {
+                let mut config = Self::base()
+                    .with_api_base(OPENROUTER_API_BASE)
+                    .with_api_key(env_api_key("OPENROUTER_API_KEY"));
+                let headers = [("HTTP-Referer", site_url), ("X-Title", site_name)];
+                for (name, value) in headers {
+                    let Some(value) = value else { continue };
+                    if let Err(e) = config.insert_header(name, &value) {
+                        tracing::warn!("OpenRouter {name} header is not sent: {e}");
+                    }
+                }
+                config
+            }
+            Provider::Vllm => {
+                // vLLM accepts any key unless started with --api-key
+                let api_key = std::env::var("VLLM_API_KEY").unwrap_or_else(|_| "EMPTY".into());
+                Self::base()
+                    .with_api_base(VLLM_API_BASE)
+                    .with_api_key(api_key)
+            }
+        }
     }
 
     /// To use a different organization id other than default
@@ -72,19 +270,90 @@ impl OpenAIConfig {
         self
     }
 
+    /// To resolve the API key on every request instead of using a static key,
+    /// for example when keys are rotated by a secrets manager.
+    ///
+    /// The provider is called each time request headers are built, including
+    /// retries, streaming and multipart requests. It takes precedence over the
+    /// key set by [OpenAIConfig::with_api_key] or the OPENAI_API_KEY env var,
+    /// which [Config::api_key] keeps returning.
+    ///
+    /// Requests fail with [OpenAIError::InvalidArgument] when the provided key is not a
+    /// valid header value, for example when it contains a newline.
+    pub fn with_api_key_provider<F>(mut self, provider: F) -> Self
+    where
+        F: Fn() -> SecretString + Send + Sync + 'static,
+    {
+        self.api_key_provider = Some(ApiKeyProvider::new(provider));
+        self
+    }
+
     /// To use a API base url different from default [OPENAI_API_BASE]
     pub fn with_api_base<S: Into<String>>(mut self, api_base: S) -> Self {
         self.api_base = api_base.into();
         self
     }
 
+    /// Additional header sent with every request.
+    ///
+    /// Fails with [OpenAIError::InvalidArgument] if name or value is not a valid
+    /// header name or value.
+    pub fn with_header<K: AsRef<str>, V: AsRef<str>>(
+        mut self,
+        name: K,
+        value: V,
+    ) -> Result<Self, OpenAIError> {
+        self.insert_header(name.as_ref(), value.as_ref())?;
+        Ok(self)
+    }
+
+    fn insert_header(&mut self, name: &str, value: &str) -> Result<(), OpenAIError> {
+        let header_name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| OpenAIError::InvalidArgument(format!("header name {name:?}: {e}")))?;
+        let header_value = HeaderValue::from_str(value)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("value of header {name}: {e}")))?;
+        self.custom_headers.insert(header_name, header_value);
+        Ok(())
+    }
+
+    /// Additional query parameter added to every request url, for example
+    /// for providers or proxies which require a constant parameter.
+    ///
+    /// Setting the same key again replaces its value. A value passed to a
+    /// list call for the same key takes precedence over the configured one.
+    pub fn with_query_param<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        let key = key.into();
+        let value = value.into();
+        match self.query_params.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => self.query_params.push((key, value)),
+        }
+        self
+    }
+
     pub fn org_id(&self) -> &str {
         &self.org_id
     }
 }
 
-impl Config for OpenAIConfig {
-    fn headers(&self) -> HeaderMap {
+impl OpenAIConfig {
+    /// Value of the Authorization header, with the key of the provider if any
+    fn authorization(&self) -> Result<HeaderValue, OpenAIError> {
+        let api_key = match &self.api_key_provider {
+            Some(provider) => provider.api_key(),
+            None => self.api_key.clone(),
+        };
+
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", api_key.expose_secret()))
+            .map_err(|_| {
+                OpenAIError::InvalidArgument("API key is not a valid header value".into())
+            })?;
+        value.set_sensitive(true);
+        Ok(value)
+    }
+
+    /// Headers of the config with the given Authorization, if any
+    fn headers_with(&self, authorization: Option<HeaderValue>) -> HeaderMap {
         let mut headers = HeaderMap::new();
         if !self.org_id.is_empty() {
             headers.insert(
@@ -100,20 +369,39 @@ impl Config for OpenAIConfig {
             );
         }
 
-        headers.insert(
-            AUTHORIZATION,
-            format!("Bearer {}", self.api_key.expose_secret())
-                .as_str()
-                .parse()
-                .unwrap(),
-        );
+        if let Some(authorization) = authorization {
+            headers.insert(AUTHORIZATION, authorization);
+        }
 
         // hack for Assistants APIs
         // Calls to the Assistants API require that you pass a Beta header
         headers.insert(OPENAI_BETA_HEADER, "assistants=v2".parse().unwrap());
 
+        for (name, value) in self.custom_headers.iter() {
+            headers.insert(name, value.clone());
+        }
+
         headers
     }
+}
+
+impl Config for OpenAIConfig {
+    /// Headers of the config. An API key which is not a valid header value is left out,
+    /// see [Config::try_headers].
+    fn headers(&self) -> HeaderMap {
+        let authorization = match self.authorization() {
+            Ok(authorization) => Some(authorization),
+            Err(e) => {
+                tracing::warn!("no Authorization header: {e}");
+                None
+            }
+        };
+        self.headers_with(authorization)
+    }
+
+    fn try_headers(&self) -> Result<HeaderMap, OpenAIError> {
+        Ok(self.headers_with(Some(self.authorization()?)))
+    }
 
     fn url(&self, path: &str) -> String {
         format!("{}{}", self.api_base, path)
@@ -128,7 +416,10 @@ impl Config for OpenAIConfig {
     }
 
     fn query(&self) -> Vec<(&str, &str)> {
-        vec![]
+        self.query_params
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect()
     }
 }
 
@@ -211,3 +502,298 @@ impl Config for AzureConfig {
         vec![("api-version", &self.api_version)]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn authorization(config: &impl Config) -> String {
+        config.headers()[AUTHORIZATION]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_static_api_key() {
+        let config = OpenAIConfig::new().with_api_key("sk-static");
+
+        assert_eq!(authorization(&config), "Bearer sk-static");
+        assert_eq!(authorization(&config), "Bearer sk-static");
+    }
+
+    #[test]
+    fn test_api_key_provider_resolved_per_request() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let provider_counter = counter.clone();
+
+        let config = OpenAIConfig::new()
+            .with_api_key("sk-static")
+            .with_api_key_provider(move || {
+                let n = provider_counter.fetch_add(1, Ordering::SeqCst);
+                SecretString::from(format!("sk-rotated-{n}"))
+            });
+
+        assert_eq!(authorization(&config), "Bearer sk-rotated-0");
+        // Clones share the same provider
+        assert_eq!(authorization(&config.clone()), "Bearer sk-rotated-1");
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_invalid_api_key_from_provider() {
+        let config =
+            OpenAIConfig::new().with_api_key_provider(|| SecretString::from("sk-rotated\n"));
+
+        assert!(matches!(
+            config.try_headers(),
+            Err(OpenAIError::InvalidArgument(_))
+        ));
+        let headers = config.headers();
+        assert!(!headers.contains_key(AUTHORIZATION));
+        assert_eq!(headers[OPENAI_BETA_HEADER], "assistants=v2");
+    }
+
+    #[test]
+    fn test_query_params() {
+        let config = OpenAIConfig::new()
+            .with_query_param("api-version", "2024-10-21")
+            .with_query_param("tenant", "a")
+            .with_query_param("tenant", "b");
+
+        assert_eq!(
+            config.query(),
+            vec![("api-version", "2024-10-21"), ("tenant", "b")]
+        );
+        assert!(OpenAIConfig::new().query().is_empty());
+    }
+
+    /// Env vars are process wide, tests changing them must not run concurrently
+    static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    const ENV_VARS: [&str; 4] = [
+        "OPENAI_API_KEY",
+        "OPENAI_ORG_ID",
+        "OPENAI_PROJECT_ID",
+        "OPENAI_BASE_URL",
+    ];
+
+    /// Run `f` with only the given OpenAI env vars set
+    fn with_env<T>(vars: &[(&str, &str)], f: impl FnOnce() -> T) -> T {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let saved: Vec<_> = ENV_VARS
+            .iter()
+            .map(|name| (*name, std::env::var(name).ok()))
+            .collect();
+
+        for name in ENV_VARS {
+            std::env::remove_var(name);
+        }
+        for (name, value) in vars {
+            std::env::set_var(name, value);
+        }
+
+        let result = f();
+
+        for (name, value) in saved {
+            match value {
+                Some(value) => std::env::set_var(name, value),
+                None => std::env::remove_var(name),
+            }
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_from_env_all_vars() {
+        let config = with_env(
+            &[
+                ("OPENAI_API_KEY", " sk-env \n"),
+                ("OPENAI_ORG_ID", "org-123"),
+                ("OPENAI_PROJECT_ID", "proj_123"),
+                ("OPENAI_BASE_URL", "https://proxy.example.com/v1/"),
+            ],
+            OpenAIConfig::from_env,
+        )
+        .unwrap();
+        let headers = config.headers();
+
+        assert_eq!(authorization(&config), "Bearer sk-env");
+        assert_eq!(headers[OPENAI_ORGANIZATION_HEADER], "org-123");
+        assert_eq!(headers[OPENAI_PROJECT_HEADER], "proj_123");
+        assert_eq!(
+            config.url("/chat/completions"),
+            "https://proxy.example.com/v1/chat/completions"
+        );
+    }
+
+    #[test]
+    fn test_from_env_missing_vars() {
+        let config = with_env(&[], OpenAIConfig::from_env).unwrap();
+        let headers = config.headers();
+
+        assert_eq!(authorization(&config), "Bearer ");
+        assert!(!headers.contains_key(OPENAI_ORGANIZATION_HEADER));
+        assert!(!headers.contains_key(OPENAI_PROJECT_HEADER));
+        assert_eq!(config.api_base(), OPENAI_API_BASE);
+
+        // Blank values are ignored
+        let config = with_env(
+            &[("OPENAI_ORG_ID", "  "), ("OPENAI_BASE_URL", "")],
+            OpenAIConfig::from_env,
+        )
+        .unwrap();
+        assert!(!config.headers().contains_key(OPENAI_ORGANIZATION_HEADER));
+        assert_eq!(config.api_base(), OPENAI_API_BASE);
+    }
+
+    #[test]
+    fn test_from_env_partial_vars() {
+        let config = with_env(
+            &[
+                ("OPENAI_API_KEY", "sk-env"),
+                ("OPENAI_PROJECT_ID", "proj_123"),
+            ],
+            OpenAIConfig::from_env,
+        )
+        .unwrap();
+        let headers = config.headers();
+
+        assert_eq!(authorization(&config), "Bearer sk-env");
+        assert!(!headers.contains_key(OPENAI_ORGANIZATION_HEADER));
+        assert_eq!(headers[OPENAI_PROJECT_HEADER], "proj_123");
+        assert_eq!(config.api_base(), OPENAI_API_BASE);
+    }
+
+    #[test]
+    fn test_from_env_invalid_base_url() {
+        let vars = [
+            ("OPENAI_API_KEY", "sk-env"),
+            ("OPENAI_BASE_URL", "not a url"),
+        ];
+
+        let result = with_env(&vars, OpenAIConfig::from_env);
+        assert!(matches!(result, Err(OpenAIError::InvalidArgument(_))));
+
+        // Default falls back to the OpenAI base url and keeps the other vars
+        let config = with_env(&vars, OpenAIConfig::new);
+        assert_eq!(config.api_base(), OPENAI_API_BASE);
+        assert_eq!(authorization(&config), "Bearer sk-env");
+    }
+
+    #[test]
+    fn test_new_reads_env() {
+        let config = with_env(
+            &[
+                ("OPENAI_ORG_ID", "org-123"),
+                ("OPENAI_BASE_URL", "http://localhost:8080/v1"),
+            ],
+            OpenAIConfig::new,
+        );
+
+        assert_eq!(config.org_id(), "org-123");
+        assert_eq!(config.api_base(), "http://localhost:8080/v1");
+    }
+
+    #[test]
+    fn test_presets_ignore_openai_env() {
+        let config = with_env(
+            &[
+                ("OPENAI_ORG_ID", "org-123"),
+                ("OPENAI_BASE_URL", "http://localhost:8080/v1"),
+            ],
+            || OpenAIConfig::preset(Provider::Groq),
+        );
+
+        assert_eq!(config.org_id(), "");
+        assert_eq!(config.api_base(), GROQ_API_BASE);
+    }
+
+    #[test]
+    fn test_provider_presets() {
+        let config = OpenAIConfig::preset(Provider::Ollama);
+        assert_eq!(
+            config.url("/chat/completions"),
+            "http://localhost:11434/v1/chat/completions"
+        );
+        assert_eq!(authorization(&config), "Bearer ollama");
+
+        let config = OpenAIConfig::preset(Provider::Gemini);
+        assert_eq!(
+            config.url("/chat/completions"),
+            "https://generativelanguage.googleapis.com/v1beta/openai/chat/completions"
+        );
+
+        let config = OpenAIConfig::preset(Provider::Groq);
+        assert_eq!(
+            config.url("/chat/completions"),
+            "https://api.groq.com/openai/v1/chat/completions"
+        );
+
+        let config = OpenAIConfig::preset(Provider::Vllm);
+        assert_eq!(
+            config.url("/chat/completions"),
+            "http://localhost:8000/v1/chat/completions"
+        );
+    }
+
+    #[test]
+    fn test_openrouter_preset_headers() {
+        let config = OpenAIConfig::preset(Provider::OpenRouter {
+            site_url: Some("https://example.com".into()),
+            site_name: Some("Example".into()),
+        });
+        let headers = config.headers();
+
+        assert_eq!(
+            config.url("/chat/completions"),
+            "https://openrouter.ai/api/v1/chat/completions"
+        );
+        assert_eq!(headers["HTTP-Referer"], "https://example.com");
+        assert_eq!(headers["X-Title"], "Example");
+
+        let config = OpenAIConfig::preset(Provider::OpenRouter {
+            site_url: None,
+            site_name: None,
+        });
+        let headers = config.headers();
+
+        assert!(!headers.contains_key("HTTP-Referer"));
+        assert!(!headers.contains_key("X-Title"));
+
+        // Invalid values are left out
+        let config = OpenAIConfig::preset(Provider::OpenRouter {
+            site_url: Some("https://example.com\n".into()),
+            site_name: Some("Example".into()),
+        });
+        let headers = config.headers();
+        assert!(!headers.contains_key("HTTP-Referer"));
+        assert_eq!(headers["X-Title"], "Example");
+    }
+
+    #[test]
+    fn test_with_header() {
+        let config = OpenAIConfig::new().with_header("X-Team", "search").unwrap();
+        assert_eq!(config.headers()["X-Team"], "search");
+
+        let error = OpenAIConfig::new()
+            .with_header("X-Team", "line\nbreak")
+            .unwrap_err();
+        assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+        assert!(OpenAIConfig::new().with_header("X Team", "search").is_err());
+    }
+
+    #[test]
+    fn test_api_key_ignores_provider() {
+        let config = OpenAIConfig::new()
+            .with_api_key("sk-static")
+            .with_api_key_provider(|| SecretString::from("sk-rotated"));
+
+        assert_eq!(config.api_key().expose_secret(), "sk-static");
+        assert_eq!(authorization(&config), "Bearer sk-rotated");
+    }
+}
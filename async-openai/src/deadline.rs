@@ -0,0 +1,97 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use backoff::backoff::Backoff;
+use tokio::time::Instant;
+
+use crate::error::OpenAIError;
+
+/// Wall-clock budget of a single call, shared by all of its attempts and backoff sleeps.
+/// Unbounded when no deadline is configured.
+#[derive(Debug)]
+pub(crate) struct Budget {
+    deadline: Option<Instant>,
+    attempts: AtomicU32,
+    exceeded: AtomicBool,
+    last_error: Mutex<Option<OpenAIError>>,
+}
+
+impl Budget {
+    pub(crate) fn new(deadline: Option<Duration>) -> Self {
+        Self {
+            deadline: deadline.map(|deadline| Instant::now() + deadline),
+            attempts: AtomicU32::new(0),
+            exceeded: AtomicBool::new(false),
+            last_error: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Count a new attempt
+    pub(crate) fn start_attempt(&self) {
+        self.attempts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Whether waiting for `duration` from now would spend the whole budget
+    pub(crate) fn would_exceed(&self, duration: Duration) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() + duration >= deadline)
+    }
+
+    /// Keep the error of a failed attempt which is going to be retried
+    pub(crate) fn record_error(&self, error: OpenAIError) {
+        *self.last_error.lock().unwrap() = Some(error);
+    }
+
+    /// Error of the previous attempt, if any
+    pub(crate) fn take_last_error(&self) -> Option<OpenAIError> {
+        self.last_error.lock().unwrap().take()
+    }
+
+    /// Error returned when the budget is spent, `error` defaults to the one of the previous attempt
+    pub(crate) fn exceeded(&self, error: Option<OpenAIError>) -> OpenAIError {
+        self.exceeded.store(true, Ordering::Relaxed);
+        let last_error = error.or_else(|| self.take_last_error());
+
+        OpenAIError::DeadlineExceeded {
+            attempts: self.attempts.load(Ordering::Relaxed),
+            last_error: last_error.map(Box::new),
+        }
+    }
+
+    /// Final error of the retry loop, annotated when it gave up because of the deadline
+    pub(crate) fn finish(&self, error: OpenAIError) -> OpenAIError {
+        match error {
+            OpenAIError::DeadlineExceeded { .. } => error,
+            error if self.exceeded.load(Ordering::Relaxed) => self.exceeded(Some(error)),
+            error => error,
+        }
+    }
+}
+
+/// Stops retrying when the next backoff sleep would not fit in the [Budget]
+pub(crate) struct DeadlineBackoff<'a, B> {
+    pub(crate) inner: B,
+    pub(crate) budget: &'a Budget,
+}
+
+impl<B: Backoff> Backoff for DeadlineBackoff<'_, B> {
+    fn reset(&mut self) {
+        self.inner.reset()
+    }
+
+    fn next_backoff(&mut self) -> Option<Duration> {
+        let duration = self.inner.next_backoff()?;
+
+        if self.budget.would_exceed(duration) {
+            self.budget.exceeded.store(true, Ordering::Relaxed);
+            return None;
+        }
+
+        Some(duration)
+    }
+}
@@ -56,6 +56,12 @@ pub(crate) async fn download_url<P: AsRef<Path>>(
     Ok(file_path)
 }
 
+pub(crate) async fn save_bytes(bytes: &[u8], path: &Path) -> Result<(), OpenAIError> {
+    tokio::fs::write(path, bytes)
+        .await
+        .map_err(|e| OpenAIError::FileSaveError(format!("{}, path: {}", e, path.display())))
+}
+
 pub(crate) async fn save_b64<P: AsRef<Path>>(b64: &str, dir: P) -> Result<PathBuf, OpenAIError> {
     let filename: String = rand::thread_rng()
         .sample_iter(&Alphanumeric)
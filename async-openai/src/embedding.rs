@@ -1,13 +1,24 @@
+use std::ops::Range;
+
+use futures::StreamExt;
+use reqwest::header::HeaderMap;
+
 use crate::{
     config::Config,
     error::OpenAIError,
+    limiter::estimate_tokens,
     types::{
-        CreateBase64EmbeddingResponse, CreateEmbeddingRequest, CreateEmbeddingResponse,
-        EncodingFormat,
+        BatchOptions, BulkOutcome, CreateBase64EmbeddingResponse, CreateEmbeddingRequest,
+        CreateEmbeddingResponse, EmbeddingChunk, EmbeddingInput, EncodingFormat,
+        FailedEmbeddingChunk, MAX_EMBEDDING_INPUTS,
     },
     Client,
 };
 
+/// Estimated tokens of the inputs of a request of [Embeddings::create_batched],
+/// under the limit of the API of 300k tokens per request
+const MAX_BATCH_TOKENS: u64 = 250_000;
+
 /// Get a vector representation of a given input that can be easily
 /// consumed by machine learning models and algorithms.
 ///
@@ -22,6 +33,10 @@ impl<'c, C: Config> Embeddings<'c, C> {
     }
 
     /// Creates an embedding vector representing the input text.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.embeddings.create", skip_all)
+    )]
     pub async fn create(
         &self,
         request: CreateEmbeddingRequest,
@@ -34,9 +49,31 @@ impl<'c, C: Config> Embeddings<'c, C> {
         self.client.post("/embeddings", request).await
     }
 
+    /// Same as [Embeddings::create], also returning the response headers, like
+    /// `openai-processing-ms`, `openai-version` or the `x-request-id`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.embeddings.create_with_headers", skip_all)
+    )]
+    pub async fn create_with_headers(
+        &self,
+        request: CreateEmbeddingRequest,
+    ) -> Result<(CreateEmbeddingResponse, HeaderMap), OpenAIError> {
+        if matches!(request.encoding_format, Some(EncodingFormat::Base64)) {
+            return Err(OpenAIError::InvalidArgument(
+                "When encoding_format is base64, use Embeddings::create_base64".into(),
+            ));
+        }
+        self.client.post_with_headers("/embeddings", request).await
+    }
+
     /// Creates an embedding vector representing the input text.
     ///
     /// The response will contain the embedding in base64 format.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.embeddings.create_base64", skip_all)
+    )]
     pub async fn create_base64(
         &self,
         request: CreateEmbeddingRequest,
@@ -49,6 +86,135 @@ impl<'c, C: Config> Embeddings<'c, C> {
 
         self.client.post("/embeddings", request).await
     }
+
+    /// Embeddings of any number of `inputs`, by request.
+    ///
+    /// The inputs are split into requests of at most [BatchOptions::max_items_per_request]
+    /// inputs and about 250k estimated tokens, sent up to [BatchOptions::max_concurrency] at
+    /// once. Each request is retried like any other request of the client, and its result
+    /// is in the outcome at the index of the request, with the range of its inputs.
+    ///
+    /// A failed request doesn't stop the others: the embeddings of the requests which
+    /// succeeded are kept. A response without exactly one embedding per input is a
+    /// failure with [OpenAIError::InvalidArgument].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.embeddings.create_batched", skip_all)
+    )]
+    pub async fn create_batched(
+        &self,
+        inputs: Vec<String>,
+        model: impl Into<String>,
+        options: BatchOptions,
+    ) -> Result<BulkOutcome<EmbeddingChunk, FailedEmbeddingChunk>, OpenAIError> {
+        if !(1..=MAX_EMBEDDING_INPUTS).contains(&options.max_items_per_request) {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "max_items_per_request must be between 1 and {MAX_EMBEDDING_INPUTS}, got {}",
+                options.max_items_per_request
+            )));
+        }
+        if options.max_concurrency == 0 {
+            return Err(OpenAIError::InvalidArgument(
+                "max_concurrency must be at least 1".into(),
+            ));
+        }
+
+        let model = model.into();
+        let ranges = batch_ranges(&inputs, options.max_items_per_request);
+        let mut inputs = inputs.into_iter();
+        let requests: Vec<_> = ranges
+            .into_iter()
+            .map(|range| {
+                let request = CreateEmbeddingRequest {
+                    model: model.clone(),
+                    input: EmbeddingInput::StringArray(inputs.by_ref().take(range.len()).collect()),
+                    ..Default::default()
+                };
+                (range, request)
+            })
+            .collect();
+
+        let mut responses = futures::stream::iter(requests.into_iter().enumerate())
+            .map(|(index, (range, request))| async move {
+                let result = match self.create(request).await {
+                    Ok(response) => chunk(range.clone(), response),
+                    Err(e) => Err(e),
+                };
+                let result = result.map_err(|error| FailedEmbeddingChunk {
+                    inputs: range,
+                    error,
+                });
+                (index, result)
+            })
+            .buffer_unordered(options.max_concurrency);
+
+        let mut outcome = BulkOutcome::default();
+        while let Some((index, result)) = responses.next().await {
+            outcome.push(index, result);
+        }
+
+        Ok(outcome)
+    }
+}
+
+/// Embeddings of the `inputs` of a request in their order, if the response has
+/// exactly one embedding per input
+fn chunk(
+    inputs: Range<usize>,
+    response: CreateEmbeddingResponse,
+) -> Result<EmbeddingChunk, OpenAIError> {
+    if response.data.len() != inputs.len() {
+        return Err(OpenAIError::InvalidArgument(format!(
+            "response has {} embeddings for {} inputs",
+            response.data.len(),
+            inputs.len()
+        )));
+    }
+
+    let mut embeddings = vec![None; inputs.len()];
+    for embedding in response.data {
+        let index = embedding.index as usize;
+        match embeddings.get_mut(index) {
+            Some(slot @ None) => *slot = Some(embedding.embedding),
+            _ => {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "response has an unexpected embedding at index {index}"
+                )))
+            }
+        }
+    }
+
+    Ok(EmbeddingChunk {
+        inputs,
+        // Every slot is filled: as many distinct indices as inputs, all in range
+        embeddings: embeddings.into_iter().flatten().collect(),
+        usage: response.usage,
+    })
+}
+
+/// Split `inputs` into consecutive ranges of at most `max_items` inputs and
+/// [MAX_BATCH_TOKENS] estimated tokens, an input over the limit being sent alone
+fn batch_ranges(inputs: &[String], max_items: usize) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let mut start = 0;
+    let mut tokens = 0;
+
+    for (index, input) in inputs.iter().enumerate() {
+        let input_tokens = estimate_tokens(input.as_bytes());
+        if index > start && (index - start == max_items || tokens + input_tokens > MAX_BATCH_TOKENS)
+        {
+            ranges.push(start..index);
+            start = index;
+            tokens = 0;
+        }
+        tokens += input_tokens;
+    }
+
+    if start < inputs.len() {
+        ranges.push(start..inputs.len());
+    }
+
+    ranges
 }
 
 #[cfg(test)]
@@ -57,6 +223,19 @@ mod tests {
     use crate::types::{CreateEmbeddingResponse, Embedding, EncodingFormat};
     use crate::{types::CreateEmbeddingRequestArgs, Client};
 
+    #[test]
+    fn test_batch_ranges() {
+        let inputs: Vec<String> = (0..7).map(|i| i.to_string()).collect();
+        assert_eq!(super::batch_ranges(&inputs, 3), [0..3, 3..6, 6..7]);
+        assert_eq!(super::batch_ranges(&inputs, 7), vec![(0..7)]);
+        assert!(super::batch_ranges(&[], 3).is_empty());
+
+        // ~150k estimated tokens each
+        let long = "a".repeat(600_000);
+        let inputs = vec![long.clone(), long.clone(), "short".into(), long];
+        assert_eq!(super::batch_ranges(&inputs, 2048), [0..1, 1..3, 3..4]);
+    }
+
     #[tokio::test]
     async fn test_embedding_string() {
         let client = Client::new();
@@ -1,5 +1,10 @@
 //! Errors originating from API calls, parsing responses, and reading-or-writing to the file system.
-use serde::Deserialize;
+use std::time::Duration;
+
+use reqwest::{header::HeaderMap, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::types::{ContentFilterResult, InnerError};
 
 #[derive(Debug, thiserror::Error)]
 pub enum OpenAIError {
@@ -9,9 +14,33 @@ pub enum OpenAIError {
     /// OpenAI returns error object with details of API call failure
     #[error("{0}")]
     ApiError(ApiError),
+    /// API call failed with a body which is not an error object,
+    /// like the HTML error page of a proxy
+    #[error("http status {status}: {body}")]
+    HttpError {
+        status: StatusCode,
+        /// Response body, truncated to its first 4 KiB
+        body: String,
+        headers: HeaderMap,
+    },
+    /// API call was rejected with status 429
+    #[error("rate limited: {message}{}", fmt_retry_after(retry_after))]
+    RateLimited {
+        /// Wait hint from the `retry-after-ms` or `retry-after` response header
+        retry_after: Option<Duration>,
+        code: RateLimitCode,
+        message: String,
+    },
     /// Error when a response cannot be deserialized into a Rust type
-    #[error("failed to deserialize api response: {0}")]
-    JSONDeserialize(serde_json::Error),
+    #[error("failed to deserialize api response at {path}: {source}")]
+    JSONDeserialize {
+        source: serde_json::Error,
+        /// Path of the value which failed to deserialize, like `choices[0].message`
+        path: String,
+        /// Response body truncated to its first 2 KiB, left out of the error message,
+        /// see [OpenAIError::body_snippet]
+        body_snippet: String,
+    },
     /// Error on the client side when saving file to file system
     #[error("failed to save file: {0}")]
     FileSaveError(String),
@@ -21,19 +50,233 @@ pub enum OpenAIError {
     /// Error on SSE streaming
     #[error("stream failed: {0}")]
     StreamError(String),
+    /// No event was received on a stream within the timeout set with
+    /// [crate::Client::with_stream_idle_timeout]
+    #[error("stream idle for {0:?}")]
+    StreamIdleTimeout(Duration),
+    /// The deadline set with [crate::Client::with_deadline] was spent before an attempt succeeded
+    #[error(
+        "deadline exceeded after {attempts} attempt(s){}",
+        fmt_last_error(last_error)
+    )]
+    DeadlineExceeded {
+        attempts: u32,
+        /// Error of the last attempt, if any attempt completed
+        last_error: Option<Box<OpenAIError>>,
+    },
+    /// Error of a WebSocket connection, like the one of a `RealtimeSession` of the
+    /// `realtime-ws` feature. Always present, so matching on it needs no feature gate.
+    #[error("websocket error: {0}")]
+    WebSocket(String),
     /// Error from client side validation
     /// or when builder fails to build request before making API call
     #[error("invalid args: {0}")]
     InvalidArgument(String),
 }
 
+impl OpenAIError {
+    /// HTTP status of the response, for errors of API calls which got a response
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::ApiError(error) => error.status,
+            Self::HttpError { status, .. } => Some(*status),
+            Self::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            _ => None,
+        }
+    }
+
+    /// Whether the same call may succeed when retried: connection errors, timeouts and
+    /// WebSocket errors, statuses 408, 429, 500, 502, 503 and 504, and the `server_error`
+    /// and `rate_limit_exceeded` API codes. Validation errors, authentication failures,
+    /// exhausted quotas and context length errors are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Reqwest(e) => e.is_connect() || e.is_timeout(),
+            Self::RateLimited { code, .. } => *code != RateLimitCode::InsufficientQuota,
+            Self::ApiError(error) => match error.code_enum() {
+                Some(ApiErrorCode::ServerError | ApiErrorCode::RateLimitExceeded) => true,
+                Some(
+                    ApiErrorCode::InsufficientQuota
+                    | ApiErrorCode::ContextLengthExceeded
+                    | ApiErrorCode::InvalidApiKey,
+                ) => false,
+                _ => error.status.is_some_and(is_retryable_status),
+            },
+            Self::HttpError { status, .. } => is_retryable_status(*status),
+            Self::StreamIdleTimeout(_) => true,
+            Self::WebSocket(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Wait hint of the server before retrying, from the `retry-after-ms`
+    /// or `retry-after` header of the response
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after, .. } => *retry_after,
+            Self::HttpError { headers, .. } => parse_retry_after(headers),
+            _ => None,
+        }
+    }
+
+    /// Start of the response body which failed to deserialize
+    pub fn body_snippet(&self) -> Option<&str> {
+        match self {
+            Self::JSONDeserialize { body_snippet, .. } => Some(body_snippet),
+            _ => None,
+        }
+    }
+}
+
 /// OpenAI API returns error object on failure
 #[derive(Debug, Deserialize, Clone)]
 pub struct ApiError {
     pub message: String,
     pub r#type: Option<String>,
     pub param: Option<String>,
+    /// Numeric codes, returned by some Azure endpoints, are converted to strings
+    #[serde(default, deserialize_with = "deserialize_code")]
     pub code: Option<String>,
+    /// Details of Azure OpenAI errors, like the content filtering result
+    pub innererror: Option<Box<InnerError>>,
+    /// HTTP status of the response, not part of the error object
+    #[serde(skip)]
+    pub status: Option<StatusCode>,
+}
+
+impl ApiError {
+    /// The `code` of the error, if any
+    pub fn code_enum(&self) -> Option<ApiErrorCode> {
+        self.code.as_deref().map(ApiErrorCode::from)
+    }
+
+    /// Categories which caused Azure OpenAI to filter the prompt or the completion
+    pub fn content_filter_result(&self) -> Option<&ContentFilterResult> {
+        self.innererror.as_ref()?.content_filter_result.as_ref()
+    }
+
+    /// The prompt and output are too long for the context window of the model
+    pub fn is_context_length_exceeded(&self) -> bool {
+        self.code_enum() == Some(ApiErrorCode::ContextLengthExceeded)
+    }
+
+    /// The API key is wrong, revoked or missing
+    pub fn is_invalid_api_key(&self) -> bool {
+        self.code_enum() == Some(ApiErrorCode::InvalidApiKey)
+    }
+
+    /// The plan or billing quota is exhausted
+    pub fn is_insufficient_quota(&self) -> bool {
+        self.code_enum() == Some(ApiErrorCode::InsufficientQuota)
+    }
+
+    /// The request was rejected by the safety system, or by the content filter on Azure
+    pub fn is_content_policy_violation(&self) -> bool {
+        matches!(
+            self.code_enum(),
+            Some(ApiErrorCode::ContentPolicyViolation | ApiErrorCode::ContentFilter)
+        )
+    }
+}
+
+fn deserialize_code<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<ApiErrorCode>::deserialize(deserializer)?.map(|code| code.as_str().to_string()))
+}
+
+/// Error codes documented by OpenAI and Azure OpenAI, see [ApiError::code_enum].
+///
+/// Deserialized from a string or a number, unknown codes are kept as [ApiErrorCode::Other].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ApiErrorCode {
+    /// `invalid_api_key`
+    InvalidApiKey,
+    /// `context_length_exceeded`
+    ContextLengthExceeded,
+    /// `insufficient_quota`
+    InsufficientQuota,
+    /// `rate_limit_exceeded`
+    RateLimitExceeded,
+    /// `content_policy_violation`
+    ContentPolicyViolation,
+    /// `content_filter`, Azure OpenAI content filtering
+    ContentFilter,
+    /// `model_not_found`
+    ModelNotFound,
+    /// `string_above_max_length`
+    StringAboveMaxLength,
+    /// `unsupported_country_region_territory`
+    UnsupportedCountryRegionTerritory,
+    /// `server_error`
+    ServerError,
+    /// Any other code, numeric codes included
+    Other(String),
+}
+
+impl ApiErrorCode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::InvalidApiKey => "invalid_api_key",
+            Self::ContextLengthExceeded => "context_length_exceeded",
+            Self::InsufficientQuota => "insufficient_quota",
+            Self::RateLimitExceeded => "rate_limit_exceeded",
+            Self::ContentPolicyViolation => "content_policy_violation",
+            Self::ContentFilter => "content_filter",
+            Self::ModelNotFound => "model_not_found",
+            Self::StringAboveMaxLength => "string_above_max_length",
+            Self::UnsupportedCountryRegionTerritory => "unsupported_country_region_territory",
+            Self::ServerError => "server_error",
+            Self::Other(code) => code,
+        }
+    }
+}
+
+impl From<&str> for ApiErrorCode {
+    fn from(code: &str) -> Self {
+        match code {
+            "invalid_api_key" => Self::InvalidApiKey,
+            "context_length_exceeded" => Self::ContextLengthExceeded,
+            "insufficient_quota" => Self::InsufficientQuota,
+            "rate_limit_exceeded" => Self::RateLimitExceeded,
+            "content_policy_violation" => Self::ContentPolicyViolation,
+            "content_filter" => Self::ContentFilter,
+            "model_not_found" => Self::ModelNotFound,
+            "string_above_max_length" => Self::StringAboveMaxLength,
+            "unsupported_country_region_territory" => Self::UnsupportedCountryRegionTerritory,
+            "server_error" => Self::ServerError,
+            code => Self::Other(code.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ApiErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ApiErrorCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ApiErrorCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Code {
+            String(String),
+            Number(serde_json::Number),
+        }
+
+        Ok(match Code::deserialize(deserializer)? {
+            Code::String(code) => Self::from(code.as_str()),
+            Code::Number(code) => Self::Other(code.to_string()),
+        })
+    }
 }
 
 impl std::fmt::Display for ApiError {
@@ -61,16 +304,362 @@ impl std::fmt::Display for ApiError {
     }
 }
 
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Reason of a rate limited API call, from the error object `code` (or `type`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RateLimitCode {
+    /// `rate_limit_exceeded`: too many requests or tokens, the request can be retried later
+    RateLimitExceeded,
+    /// `insufficient_quota`: the plan or billing quota is exhausted, retrying won't help
+    InsufficientQuota,
+    /// Any other code, or none
+    Other(Option<String>),
+}
+
+impl RateLimitCode {
+    fn from_api_error(error: &ApiError) -> Self {
+        let is = |value: &str| {
+            error.code.as_deref() == Some(value) || error.r#type.as_deref() == Some(value)
+        };
+
+        if is("insufficient_quota") {
+            Self::InsufficientQuota
+        } else if is("rate_limit_exceeded") {
+            Self::RateLimitExceeded
+        } else {
+            Self::Other(error.code.clone())
+        }
+    }
+}
+
+fn fmt_retry_after(retry_after: &Option<Duration>) -> String {
+    match retry_after {
+        Some(duration) => format!(" (retry after {:.3}s)", duration.as_secs_f64()),
+        None => String::new(),
+    }
+}
+
+fn fmt_last_error(last_error: &Option<Box<OpenAIError>>) -> String {
+    match last_error {
+        Some(error) => format!(": {error}"),
+        None => String::new(),
+    }
+}
+
+/// Wait hint of a response: `retry-after-ms` (milliseconds) takes precedence over
+/// `retry-after` (seconds). HTTP-date values of `retry-after` are not supported.
+pub(crate) fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .filter(|value| value.is_finite() && *value >= 0.0)
+    };
+
+    header("retry-after-ms")
+        .map(|ms| Duration::from_secs_f64(ms / 1000.0))
+        .or_else(|| header("retry-after").map(Duration::from_secs_f64))
+}
+
+/// Error of an API call which responded with a non-success status
+pub(crate) fn map_api_error(
+    status: StatusCode,
+    headers: &HeaderMap,
+    mut error: ApiError,
+) -> OpenAIError {
+    error.status = Some(status);
+
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        OpenAIError::RateLimited {
+            retry_after: parse_retry_after(headers),
+            code: RateLimitCode::from_api_error(&error),
+            message: error.message,
+        }
+    } else {
+        OpenAIError::ApiError(error)
+    }
+}
+
+/// Error of an API call which responded with a non-success status and `body`,
+/// which is either an error object or the unexpected body of a proxy or server
+pub(crate) fn map_error_response(
+    status: StatusCode,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> OpenAIError {
+    match serde_json::from_slice::<WrappedError>(body) {
+        Ok(wrapped_error) => map_api_error(status, headers, wrapped_error.error),
+        Err(_) => OpenAIError::HttpError {
+            status,
+            body: truncate(body, MAX_ERROR_BODY_LEN),
+            headers: headers.clone(),
+        },
+    }
+}
+
+/// Length of the response body kept in [OpenAIError::HttpError]
+const MAX_ERROR_BODY_LEN: usize = 4096;
+
+/// Body as a string of at most `max_len` bytes
+fn truncate(body: &[u8], max_len: usize) -> String {
+    let mut body = String::from_utf8_lossy(body).into_owned();
+    if body.len() > max_len {
+        let mut end = max_len;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        body.truncate(end);
+    }
+    body
+}
+
 /// Wrapper to deserialize the error object nested in "error" JSON key
 #[derive(Debug, Deserialize)]
 pub(crate) struct WrappedError {
     pub(crate) error: ApiError,
 }
 
-pub(crate) fn map_deserialization_error(e: serde_json::Error, bytes: &[u8]) -> OpenAIError {
-    tracing::error!(
-        "failed deserialization of: {}",
-        String::from_utf8_lossy(bytes)
-    );
-    OpenAIError::JSONDeserialize(e)
+/// Length of the response body kept in [OpenAIError::JSONDeserialize]
+const MAX_BODY_SNIPPET_LEN: usize = 2048;
+
+/// Deserialize a response body, or a server-sent event, keeping the path of the
+/// value which failed along with the start of the body
+pub(crate) fn deserialize<O: DeserializeOwned>(bytes: &[u8]) -> Result<O, OpenAIError> {
+    serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_slice(bytes)).map_err(
+        |e| {
+            let path = e.path().to_string();
+            let body_snippet = truncate(bytes, MAX_BODY_SNIPPET_LEN);
+            // The body may hold user content or base64 audio and images, only the
+            // path is logged by default
+            tracing::error!("failed deserialization at {path}");
+            tracing::trace!("body of the failed deserialization: {body_snippet}");
+
+            OpenAIError::JSONDeserialize {
+                source: e.into_inner(),
+                path,
+                body_snippet,
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_code_round_trip() {
+        for code in [
+            ApiErrorCode::ContextLengthExceeded,
+            ApiErrorCode::ContentFilter,
+            ApiErrorCode::Other("some_new_code".into()),
+        ] {
+            let json = serde_json::to_string(&code).unwrap();
+            assert_eq!(serde_json::from_str::<ApiErrorCode>(&json).unwrap(), code);
+        }
+
+        assert_eq!(
+            serde_json::to_string(&ApiErrorCode::InvalidApiKey).unwrap(),
+            r#""invalid_api_key""#
+        );
+    }
+
+    #[test]
+    fn test_api_error_code_from_number() {
+        assert_eq!(
+            serde_json::from_str::<ApiErrorCode>("429").unwrap(),
+            ApiErrorCode::Other("429".into())
+        );
+    }
+
+    #[test]
+    fn test_api_error_codes() {
+        // OpenAI
+        let error: WrappedError = serde_json::from_str(
+            r#"{"error": {"message": "This model's maximum context length is 8192 tokens", "type": "invalid_request_error", "param": "messages", "code": "context_length_exceeded"}}"#,
+        )
+        .unwrap();
+        assert!(error.error.is_context_length_exceeded());
+        assert!(!error.error.is_invalid_api_key());
+
+        // Azure OpenAI
+        let error: WrappedError = serde_json::from_str(
+            r#"{"error": {"message": "Rate limit is exceeded.", "type": null, "param": null, "code": 429}}"#,
+        )
+        .unwrap();
+        assert_eq!(error.error.code.as_deref(), Some("429"));
+        assert_eq!(
+            error.error.code_enum(),
+            Some(ApiErrorCode::Other("429".into()))
+        );
+
+        let error: WrappedError = serde_json::from_str(
+            r#"{"error": {"message": "The response was filtered", "code": "content_filter", "param": "prompt", "status": 400}}"#,
+        )
+        .unwrap();
+        assert!(error.error.is_content_policy_violation());
+
+        let error: WrappedError =
+            serde_json::from_str(r#"{"error": {"message": "Internal error"}}"#).unwrap();
+        assert_eq!(error.error.code_enum(), None);
+    }
+
+    #[test]
+    fn test_deserialize_error_path_and_snippet() {
+        #[derive(Debug, Deserialize)]
+        struct Response {
+            #[allow(dead_code)]
+            choices: Vec<Choice>,
+        }
+        #[derive(Debug, Deserialize)]
+        struct Choice {
+            #[allow(dead_code)]
+            index: u32,
+        }
+
+        let body = format!(
+            r#"{{"choices": [{{"index": 0}}, {{"index": "one"}}], "padding": "{}"}}"#,
+            "x".repeat(4096)
+        );
+        let error = deserialize::<Response>(body.as_bytes()).unwrap_err();
+
+        assert!(
+            matches!(error, OpenAIError::JSONDeserialize { ref path, .. } if path == "choices[1].index")
+        );
+        let snippet = error.body_snippet().unwrap();
+        assert!(snippet.starts_with(r#"{"choices": [{"index": 0}"#));
+        assert_eq!(snippet.len(), MAX_BODY_SNIPPET_LEN);
+        assert!(!error.to_string().contains("padding"));
+    }
+
+    fn api_error(status: u16, code: Option<&str>) -> OpenAIError {
+        OpenAIError::ApiError(ApiError {
+            message: "message".into(),
+            r#type: None,
+            param: None,
+            code: code.map(Into::into),
+            innererror: None,
+            status: Some(StatusCode::from_u16(status).unwrap()),
+        })
+    }
+
+    fn http_error(status: u16, headers: &[(&'static str, &str)]) -> OpenAIError {
+        OpenAIError::HttpError {
+            status: StatusCode::from_u16(status).unwrap(),
+            body: "<html></html>".into(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.parse().unwrap(), value.parse().unwrap()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        let builder_error = reqwest::Client::new().get("not a url").build().unwrap_err();
+
+        let cases = [
+            (api_error(500, Some("server_error")), true),
+            (api_error(502, None), true),
+            (api_error(503, None), true),
+            (api_error(504, None), true),
+            (api_error(408, None), true),
+            (api_error(200, Some("server_error")), true),
+            (api_error(400, Some("rate_limit_exceeded")), true),
+            (api_error(400, None), false),
+            (api_error(400, Some("context_length_exceeded")), false),
+            (api_error(401, Some("invalid_api_key")), false),
+            (api_error(404, Some("model_not_found")), false),
+            (api_error(429, Some("insufficient_quota")), false),
+            (http_error(502, &[]), true),
+            (http_error(404, &[]), false),
+            (
+                OpenAIError::RateLimited {
+                    retry_after: None,
+                    code: RateLimitCode::RateLimitExceeded,
+                    message: "slow down".into(),
+                },
+                true,
+            ),
+            (
+                OpenAIError::RateLimited {
+                    retry_after: None,
+                    code: RateLimitCode::InsufficientQuota,
+                    message: "quota".into(),
+                },
+                false,
+            ),
+            (
+                OpenAIError::StreamIdleTimeout(Duration::from_secs(30)),
+                true,
+            ),
+            (OpenAIError::WebSocket("connection reset".into()), true),
+            (OpenAIError::Reqwest(builder_error), false),
+            (
+                OpenAIError::InvalidArgument("n must be positive".into()),
+                false,
+            ),
+            (OpenAIError::FileReadError("not found".into()), false),
+            (
+                OpenAIError::DeadlineExceeded {
+                    attempts: 3,
+                    last_error: None,
+                },
+                false,
+            ),
+        ];
+
+        for (error, retryable) in cases {
+            assert_eq!(error.is_retryable(), retryable, "{error:?}");
+        }
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let error = OpenAIError::RateLimited {
+            retry_after: Some(Duration::from_millis(1500)),
+            code: RateLimitCode::RateLimitExceeded,
+            message: "slow down".into(),
+        };
+        assert_eq!(error.retry_after(), Some(Duration::from_millis(1500)));
+
+        let error = http_error(503, &[("retry-after", "2")]);
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(2)));
+
+        assert_eq!(api_error(500, None).retry_after(), None);
+    }
+
+    #[test]
+    fn test_map_error_response() {
+        let error = map_error_response(
+            StatusCode::UNAUTHORIZED,
+            &HeaderMap::new(),
+            br#"{"error": {"message": "Incorrect API key provided", "type": "invalid_request_error", "param": null, "code": "invalid_api_key"}}"#,
+        );
+        assert_eq!(error.status(), Some(StatusCode::UNAUTHORIZED));
+        assert!(matches!(error, OpenAIError::ApiError(ref e) if e.is_invalid_api_key()));
+
+        let page = format!("<html><body>{}</body></html>", "é".repeat(4096));
+        let error = map_error_response(StatusCode::BAD_GATEWAY, &HeaderMap::new(), page.as_bytes());
+        assert_eq!(error.status(), Some(StatusCode::BAD_GATEWAY));
+        match error {
+            OpenAIError::HttpError { body, .. } => {
+                assert!(body.starts_with("<html><body>é"));
+                assert!(body.len() <= MAX_ERROR_BODY_LEN);
+            }
+            error => panic!("unexpected error {error:?}"),
+        }
+    }
 }
@@ -1,13 +1,27 @@
+use std::path::Path;
+
 use bytes::Bytes;
+use futures::Stream;
 use serde::Serialize;
 
 use crate::{
     config::Config,
     error::OpenAIError,
-    types::{CreateFileRequest, DeleteFileResponse, ListFilesResponse, OpenAIFile},
+    pagination::paginate,
+    types::{
+        CreateFileRequest, DeleteFileResponse, FileInput, FilePurpose, ListFilesResponse,
+        OpenAIFile, Pagination,
+    },
     Client,
 };
 
+/// Image types accepted as model inputs
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp", "gif"];
+/// Largest image accepted as a model input
+const MAX_IMAGE_BYTES: u64 = 20 * 1024 * 1024;
+/// Largest PDF accepted as a model input
+const MAX_PDF_BYTES: u64 = 32 * 1024 * 1024;
+
 /// Files are used to upload documents that can be used with features like Assistants and Fine-tuning.
 pub struct Files<'c, C: Config> {
     client: &'c Client<C>,
@@ -27,11 +41,101 @@ impl<'c, C: Config> Files<'c, C> {
     ///The Batch API only supports `.jsonl` files up to 100 MB in size. The input also has a specific required [format](https://platform.openai.com/docs/api-reference/batch/request-input).
     ///
     /// Please [contact us](https://help.openai.com/) if you need to increase these storage limits.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.files.create", skip_all)
+    )]
     pub async fn create(&self, request: CreateFileRequest) -> Result<OpenAIFile, OpenAIError> {
         self.client.post_form("/files", request).await
     }
 
+    /// Upload a local image with purpose `vision` and return its file id,
+    /// to reference the image by id instead of inlining it as a base64 data URL.
+    ///
+    /// Base64 inflates the image by a third and counts towards the request size
+    /// limit, so uploading is preferable for images over a few MB, and required
+    /// when the same image is sent in several requests.
+    ///
+    /// Accepts `png`, `jpg`, `jpeg`, `webp` and non-animated `gif` files up to 20 MB.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.files.upload_image", skip_all)
+    )]
+    pub async fn upload_image<P: AsRef<Path>>(&self, path: P) -> Result<String, OpenAIError> {
+        self.upload_input(
+            path.as_ref(),
+            IMAGE_EXTENSIONS,
+            MAX_IMAGE_BYTES,
+            FilePurpose::Vision,
+        )
+        .await
+    }
+
+    /// Upload a local PDF with purpose `user_data` and return its file id,
+    /// to reference the document by id instead of inlining it as base64 file data.
+    ///
+    /// Accepts `pdf` files up to 32 MB.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.files.upload_pdf", skip_all)
+    )]
+    pub async fn upload_pdf<P: AsRef<Path>>(&self, path: P) -> Result<String, OpenAIError> {
+        self.upload_input(
+            path.as_ref(),
+            &["pdf"],
+            MAX_PDF_BYTES,
+            FilePurpose::UserData,
+        )
+        .await
+    }
+
+    /// Validate the extension and size of a model input file before uploading it
+    async fn upload_input(
+        &self,
+        path: &Path,
+        extensions: &[&str],
+        max_bytes: u64,
+        purpose: FilePurpose,
+    ) -> Result<String, OpenAIError> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_lowercase())
+            .unwrap_or_default();
+
+        if !extensions.contains(&extension.as_str()) {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "unsupported file type of {}, expected one of: {}",
+                path.display(),
+                extensions.join(", ")
+            )));
+        }
+
+        let size = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| OpenAIError::FileReadError(format!("{}: {e}", path.display())))?
+            .len();
+
+        if size > max_bytes {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "{} is {size} bytes, larger than the limit of {max_bytes} bytes",
+                path.display()
+            )));
+        }
+
+        let request = CreateFileRequest {
+            file: FileInput::from(path),
+            purpose,
+        };
+
+        Ok(self.create(request).await?.id)
+    }
+
     /// Returns a list of files that belong to the user's organization.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.files.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListFilesResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -39,12 +143,33 @@ impl<'c, C: Config> Files<'c, C> {
         self.client.get_with_query("/files", query).await
     }
 
+    /// Stream all the files, fetching the pages of [Files::list] as the stream
+    /// is consumed. `query` holds the other parameters of [Files::list].
+    pub fn list_all<'a, Q>(
+        &'a self,
+        query: &'a Q,
+        pagination: Pagination,
+    ) -> impl Stream<Item = Result<OpenAIFile, OpenAIError>> + 'a
+    where
+        Q: Serialize + ?Sized,
+    {
+        paginate::<_, _, ListFilesResponse>(self.client, "/files".into(), query, pagination)
+    }
+
     /// Returns information about a specific file.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.files.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, file_id: &str) -> Result<OpenAIFile, OpenAIError> {
         self.client.get(format!("/files/{file_id}").as_str()).await
     }
 
     /// Delete a file.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.files.delete", skip_all)
+    )]
     pub async fn delete(&self, file_id: &str) -> Result<DeleteFileResponse, OpenAIError> {
         self.client
             .delete(format!("/files/{file_id}").as_str())
@@ -52,6 +177,10 @@ impl<'c, C: Config> Files<'c, C> {
     }
 
     /// Returns the contents of the specified file
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.files.content", skip_all)
+    )]
     pub async fn content(&self, file_id: &str) -> Result<Bytes, OpenAIError> {
         self.client
             .get_raw(format!("/files/{file_id}/content").as_str())
@@ -1,11 +1,13 @@
+use futures::Stream;
 use serde::Serialize;
 
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::paginate,
     types::{
         CreateFineTuningJobRequest, FineTuningJob, ListFineTuningJobCheckpointsResponse,
-        ListFineTuningJobEventsResponse, ListPaginatedFineTuningJobsResponse,
+        ListFineTuningJobEventsResponse, ListPaginatedFineTuningJobsResponse, Pagination,
     },
     Client,
 };
@@ -27,6 +29,10 @@ impl<'c, C: Config> FineTuning<'c, C> {
     /// Response includes details of the enqueued job including job status and the name of the fine-tuned models once complete.
     ///
     /// [Learn more about Fine-tuning](https://platform.openai.com/docs/guides/fine-tuning)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.fine_tuning.create", skip_all)
+    )]
     pub async fn create(
         &self,
         request: CreateFineTuningJobRequest,
@@ -35,6 +41,10 @@ impl<'c, C: Config> FineTuning<'c, C> {
     }
 
     /// List your organization's fine-tuning jobs
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.fine_tuning.list_paginated", skip_all)
+    )]
     pub async fn list_paginated<Q>(
         &self,
         query: &Q,
@@ -45,9 +55,31 @@ impl<'c, C: Config> FineTuning<'c, C> {
         self.client.get_with_query("/fine_tuning/jobs", query).await
     }
 
+    /// Stream all the fine-tuning jobs, fetching the pages of [FineTuning::list_paginated] as the stream
+    /// is consumed. `query` holds the other parameters of [FineTuning::list_paginated].
+    pub fn list_all<'a, Q>(
+        &'a self,
+        query: &'a Q,
+        pagination: Pagination,
+    ) -> impl Stream<Item = Result<FineTuningJob, OpenAIError>> + 'a
+    where
+        Q: Serialize + ?Sized,
+    {
+        paginate::<_, _, ListPaginatedFineTuningJobsResponse>(
+            self.client,
+            "/fine_tuning/jobs".into(),
+            query,
+            pagination,
+        )
+    }
+
     /// Gets info about the fine-tune job.
     ///
     /// [Learn more about Fine-tuning](https://platform.openai.com/docs/guides/fine-tuning)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.fine_tuning.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, fine_tuning_job_id: &str) -> Result<FineTuningJob, OpenAIError> {
         self.client
             .get(format!("/fine_tuning/jobs/{fine_tuning_job_id}").as_str())
@@ -55,6 +87,10 @@ impl<'c, C: Config> FineTuning<'c, C> {
     }
 
     /// Immediately cancel a fine-tune job.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.fine_tuning.cancel", skip_all)
+    )]
     pub async fn cancel(&self, fine_tuning_job_id: &str) -> Result<FineTuningJob, OpenAIError> {
         self.client
             .post(
@@ -65,6 +101,10 @@ impl<'c, C: Config> FineTuning<'c, C> {
     }
 
     /// Get fine-grained status updates for a fine-tune job.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.fine_tuning.list_events", skip_all)
+    )]
     pub async fn list_events<Q>(
         &self,
         fine_tuning_job_id: &str,
@@ -81,6 +121,10 @@ impl<'c, C: Config> FineTuning<'c, C> {
             .await
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.fine_tuning.list_checkpoints", skip_all)
+    )]
     pub async fn list_checkpoints<Q>(
         &self,
         fine_tuning_job_id: &str,
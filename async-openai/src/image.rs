@@ -20,11 +20,19 @@ impl<'c, C: Config> Images<'c, C> {
     }
 
     /// Creates an image given a prompt.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.images.create", skip_all)
+    )]
     pub async fn create(&self, request: CreateImageRequest) -> Result<ImagesResponse, OpenAIError> {
         self.client.post("/images/generations", request).await
     }
 
     /// Creates an edited or extended image given an original image and a prompt.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.images.create_edit", skip_all)
+    )]
     pub async fn create_edit(
         &self,
         request: CreateImageEditRequest,
@@ -33,6 +41,10 @@ impl<'c, C: Config> Images<'c, C> {
     }
 
     /// Creates a variation of a given image.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.images.create_variation", skip_all)
+    )]
     pub async fn create_variation(
         &self,
         request: CreateImageVariationRequest,
@@ -18,6 +18,10 @@ impl<'c, C: Config> Invites<'c, C> {
     }
 
     /// Returns a list of invites in the organization.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.invites.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<InviteListResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -28,6 +32,10 @@ impl<'c, C: Config> Invites<'c, C> {
     }
 
     /// Retrieves an invite.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.invites.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, invite_id: &str) -> Result<Invite, OpenAIError> {
         self.client
             .get(format!("/organization/invites/{invite_id}").as_str())
@@ -35,11 +43,19 @@ impl<'c, C: Config> Invites<'c, C> {
     }
 
     /// Create an invite for a user to the organization. The invite must be accepted by the user before they have access to the organization.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.invites.create", skip_all)
+    )]
     pub async fn create(&self, request: InviteRequest) -> Result<Invite, OpenAIError> {
         self.client.post("/organization/invites", request).await
     }
 
     /// Delete an invite. If the invite has already been accepted, it cannot be deleted.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.invites.delete", skip_all)
+    )]
     pub async fn delete(&self, invite_id: &str) -> Result<InviteDeleteResponse, OpenAIError> {
         self.client
             .delete(format!("/organization/invites/{invite_id}").as_str())
@@ -46,6 +46,7 @@
 //! ## Making requests
 //!
 //!```
+//!# #[cfg(feature = "full")]
 //!# tokio_test::block_on(async {
 //!
 //! use async_openai::{Client, types::{CreateCompletionRequestArgs}};
@@ -77,67 +78,169 @@
 //! For full working examples for all supported features see [examples](https://github.com/64bit/async-openai/tree/main/examples) directory in the repository.
 //!
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#[cfg(feature = "full")]
 mod assistant_files;
+#[cfg(feature = "full")]
 mod assistants;
+#[cfg(feature = "full")]
 mod audio;
+#[cfg(feature = "full")]
 mod audit_logs;
+#[cfg(feature = "full")]
 mod batches;
+#[cfg(feature = "chat-completion")]
 mod chat;
 mod client;
+#[cfg(feature = "full")]
 mod completion;
 pub mod config;
+mod deadline;
+#[cfg(feature = "full")]
 mod download;
+#[cfg(feature = "full")]
 mod embedding;
 pub mod error;
+#[cfg(feature = "full")]
 mod file;
+#[cfg(feature = "full")]
 mod fine_tuning;
+#[cfg(feature = "full")]
 mod image;
+#[cfg(feature = "full")]
 mod invites;
+mod limiter;
+#[cfg(feature = "full")]
 mod message_files;
+#[cfg(feature = "full")]
 mod messages;
+#[cfg(feature = "full")]
 mod model;
+#[cfg(feature = "full")]
 mod moderation;
+#[cfg(feature = "chat-completion")]
+mod pagination;
+#[cfg(feature = "full")]
 mod project_api_keys;
+#[cfg(feature = "full")]
 mod project_service_accounts;
+#[cfg(feature = "full")]
 mod project_users;
+#[cfg(feature = "full")]
 mod projects;
+#[cfg(feature = "realtime")]
+mod realtime;
+#[cfg(feature = "realtime-ws")]
+mod realtime_audio;
+#[cfg(feature = "realtime")]
+mod realtime_calls;
+#[cfg(feature = "realtime-ws")]
+mod realtime_client;
+#[cfg(feature = "realtime")]
+mod realtime_client_secrets;
+#[cfg(feature = "realtime-ws")]
+mod realtime_reconnect;
+#[cfg(feature = "realtime")]
+mod realtime_tool_router;
+#[cfg(feature = "realtime")]
+mod realtime_usage;
+#[cfg(feature = "full")]
 mod runs;
+#[cfg(feature = "full")]
 mod steps;
+#[cfg(feature = "full")]
 mod threads;
+#[cfg(any(feature = "tool-runner", feature = "realtime"))]
+mod tool_registry;
+#[cfg(feature = "tool-runner")]
+mod tool_runner;
+mod trace;
+pub mod transport;
 pub mod types;
+#[cfg(feature = "full")]
 mod uploads;
+#[cfg(feature = "full")]
 mod users;
+#[cfg(feature = "full")]
 mod util;
+#[cfg(feature = "full")]
 mod vector_store_file_batches;
+#[cfg(feature = "full")]
 mod vector_store_files;
+#[cfg(feature = "full")]
 mod vector_stores;
 
+#[cfg(feature = "full")]
 pub use assistant_files::AssistantFiles;
+#[cfg(feature = "full")]
 pub use assistants::Assistants;
+#[cfg(feature = "full")]
 pub use audio::Audio;
+#[cfg(feature = "full")]
 pub use audit_logs::AuditLogs;
+#[cfg(feature = "full")]
 pub use batches::Batches;
+#[cfg(feature = "chat-completion")]
 pub use chat::Chat;
 pub use client::Client;
+#[cfg(feature = "full")]
 pub use completion::Completions;
+#[cfg(feature = "full")]
 pub use embedding::Embeddings;
+#[cfg(feature = "full")]
 pub use file::Files;
+#[cfg(feature = "full")]
 pub use fine_tuning::FineTuning;
+#[cfg(feature = "full")]
 pub use image::Images;
+#[cfg(feature = "full")]
 pub use invites::Invites;
+#[cfg(feature = "full")]
 pub use message_files::MessageFiles;
+#[cfg(feature = "full")]
 pub use messages::Messages;
+#[cfg(feature = "full")]
 pub use model::Models;
+#[cfg(feature = "full")]
 pub use moderation::Moderations;
+#[cfg(feature = "full")]
 pub use project_api_keys::ProjectAPIKeys;
+#[cfg(feature = "full")]
 pub use project_service_accounts::ProjectServiceAccounts;
+#[cfg(feature = "full")]
 pub use project_users::ProjectUsers;
+#[cfg(feature = "full")]
 pub use projects::Projects;
+#[cfg(feature = "realtime")]
+pub use realtime::Realtime;
+#[cfg(feature = "realtime-ws")]
+pub use realtime_audio::AudioAppender;
+#[cfg(feature = "realtime")]
+pub use realtime_calls::Calls;
+#[cfg(feature = "realtime-ws")]
+pub use realtime_client::{RealtimeClient, RealtimeEvents, RealtimeSender, RealtimeSession};
+#[cfg(feature = "realtime")]
+pub use realtime_client_secrets::ClientSecrets;
+#[cfg(feature = "realtime-ws")]
+pub use realtime_reconnect::{RealtimeSessionEvent, ReconnectPolicy, ReconnectingSession};
+#[cfg(feature = "realtime")]
+pub use realtime_tool_router::RealtimeToolRouter;
+#[cfg(feature = "realtime")]
+pub use realtime_usage::{RealtimePricing, RealtimeTokenUsage, RealtimeUsageTracker};
+#[cfg(feature = "full")]
 pub use runs::Runs;
+#[cfg(feature = "full")]
 pub use steps::Steps;
+#[cfg(feature = "full")]
 pub use threads::Threads;
+#[cfg(feature = "tool-runner")]
+pub use tool_runner::{ChatToolRun, ChatToolRunner, ToolCallRecord};
+#[cfg(feature = "full")]
 pub use uploads::Uploads;
+#[cfg(feature = "full")]
 pub use users::Users;
+#[cfg(feature = "full")]
 pub use vector_store_file_batches::VectorStoreFileBatches;
+#[cfg(feature = "full")]
 pub use vector_store_files::VectorStoreFiles;
+#[cfg(feature = "full")]
 pub use vector_stores::VectorStores;
@@ -0,0 +1,191 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+use crate::error::OpenAIError;
+
+/// Client side limits shared by all clones of a [crate::Client].
+/// No-op when neither limit is configured.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Limiter {
+    semaphore: Option<Arc<Semaphore>>,
+    token_bucket: Option<Arc<TokenBucket>>,
+}
+
+/// Held for as long as a request counts towards the concurrency limit.
+#[derive(Debug)]
+pub(crate) struct RequestPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Limiter {
+    pub(crate) fn with_concurrency_limit(mut self, limit: usize) -> Result<Self, OpenAIError> {
+        if limit == 0 {
+            return Err(OpenAIError::InvalidArgument(
+                "concurrency limit must be at least 1".into(),
+            ));
+        }
+        self.semaphore = Some(Arc::new(Semaphore::new(limit)));
+        Ok(self)
+    }
+
+    pub(crate) fn with_tokens_per_minute(mut self, tokens_per_minute: u64) -> Self {
+        self.token_bucket = Some(Arc::new(TokenBucket::new(tokens_per_minute)));
+        self
+    }
+
+    /// Whether a token budget is configured, so that callers can skip estimating tokens.
+    pub(crate) fn has_token_budget(&self) -> bool {
+        self.token_bucket.is_some()
+    }
+
+    /// Wait until a request with `estimated_tokens` is allowed to be sent.
+    /// When the estimate is unknown the request counts as a single token.
+    pub(crate) async fn acquire(&self, estimated_tokens: Option<u64>) -> RequestPermit {
+        let permit = match &self.semaphore {
+            Some(semaphore) => Some(
+                semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed"),
+            ),
+            None => None,
+        };
+
+        if let Some(token_bucket) = &self.token_bucket {
+            token_bucket.take(estimated_tokens.unwrap_or(1)).await;
+        }
+
+        RequestPermit { _permit: permit }
+    }
+}
+
+/// Rough estimate of the tokens in a request body, assuming ~4 bytes per token.
+pub(crate) fn estimate_tokens(body: &[u8]) -> u64 {
+    (body.len() as u64 / 4).max(1)
+}
+
+/// Token bucket refilled continuously up to one minute worth of tokens.
+#[derive(Debug)]
+struct TokenBucket {
+    tokens_per_minute: u64,
+    state: Mutex<TokenBucketState>,
+}
+
+#[derive(Debug)]
+struct TokenBucketState {
+    available: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(tokens_per_minute: u64) -> Self {
+        Self {
+            tokens_per_minute,
+            state: Mutex::new(TokenBucketState {
+                available: tokens_per_minute as f64,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    async fn take(&self, tokens: u64) {
+        let capacity = self.tokens_per_minute as f64;
+        let per_second = capacity / 60.0;
+        // A request larger than the whole budget waits for a full bucket instead of forever
+        let tokens = (tokens as f64).min(capacity);
+
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                let now = Instant::now();
+                let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+                state.available = (state.available + elapsed * per_second).min(capacity);
+                state.last_refill = now;
+
+                if state.available >= tokens {
+                    state.available -= tokens;
+                    return;
+                }
+
+                Duration::from_secs_f64((tokens - state.available) / per_second)
+            };
+
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unconfigured_limiter_is_noop() {
+        let limiter = Limiter::default();
+        let _permits: Vec<_> =
+            futures::future::join_all((0..100).map(|_| limiter.acquire(None))).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_concurrency_limit_shared_across_clones() {
+        let limiter = Limiter::default().with_concurrency_limit(2).unwrap();
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let tasks = (0..8).map(|_| {
+            let limiter = limiter.clone();
+            let in_flight = in_flight.clone();
+            let max_in_flight = max_in_flight.clone();
+            tokio::spawn(async move {
+                let _permit = limiter.acquire(None).await;
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+            })
+        });
+
+        for task in futures::future::join_all(tasks).await {
+            task.unwrap();
+        }
+
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_zero_concurrency_limit() {
+        let error = Limiter::default().with_concurrency_limit(0).unwrap_err();
+        assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tokens_per_minute() {
+        let limiter = Limiter::default().with_tokens_per_minute(600);
+        let start = Instant::now();
+
+        // Full bucket is available right away
+        limiter.acquire(Some(600)).await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        // Refills at 10 tokens per second
+        limiter.acquire(Some(100)).await;
+        assert!(start.elapsed() >= Duration::from_secs(10));
+        assert!(start.elapsed() < Duration::from_secs(11));
+
+        // Unknown estimate counts as a single request
+        limiter.acquire(None).await;
+        assert!(start.elapsed() < Duration::from_millis(10_200));
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(b""), 1);
+        assert_eq!(estimate_tokens(&[b'a'; 400]), 100);
+    }
+}
@@ -24,6 +24,10 @@ impl<'c, C: Config> MessageFiles<'c, C> {
     }
 
     /// Retrieves a message file.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.message_files.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, file_id: &str) -> Result<MessageFileObject, OpenAIError> {
         self.client
             .get(&format!(
@@ -34,6 +38,10 @@ impl<'c, C: Config> MessageFiles<'c, C> {
     }
 
     /// Returns a list of message files.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.message_files.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListMessageFilesResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -1,11 +1,13 @@
+use futures::Stream;
 use serde::Serialize;
 
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::paginate,
     types::{
         CreateMessageRequest, DeleteMessageResponse, ListMessagesResponse, MessageObject,
-        ModifyMessageRequest,
+        ModifyMessageRequest, Pagination,
     },
     Client, MessageFiles,
 };
@@ -26,11 +28,15 @@ impl<'c, C: Config> Messages<'c, C> {
     }
 
     /// Call [MessageFiles] API group
-    pub fn files(&self, message_id: &str) -> MessageFiles<C> {
+    pub fn files(&self, message_id: &str) -> MessageFiles<'_, C> {
         MessageFiles::new(self.client, &self.thread_id, message_id)
     }
 
     /// Create a message.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.messages.create", skip_all)
+    )]
     pub async fn create(
         &self,
         request: CreateMessageRequest,
@@ -41,6 +47,10 @@ impl<'c, C: Config> Messages<'c, C> {
     }
 
     /// Retrieve a message.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.messages.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, message_id: &str) -> Result<MessageObject, OpenAIError> {
         self.client
             .get(&format!(
@@ -51,6 +61,10 @@ impl<'c, C: Config> Messages<'c, C> {
     }
 
     /// Modifies a message.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.messages.update", skip_all)
+    )]
     pub async fn update(
         &self,
         message_id: &str,
@@ -65,6 +79,10 @@ impl<'c, C: Config> Messages<'c, C> {
     }
 
     /// Returns a list of messages for a given thread.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.messages.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListMessagesResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -74,6 +92,28 @@ impl<'c, C: Config> Messages<'c, C> {
             .await
     }
 
+    /// Stream all the messages of the thread, fetching the pages of [Messages::list] as the stream
+    /// is consumed. `query` holds the other parameters of [Messages::list].
+    pub fn list_all<'a, Q>(
+        &'a self,
+        query: &'a Q,
+        pagination: Pagination,
+    ) -> impl Stream<Item = Result<MessageObject, OpenAIError>> + 'a
+    where
+        Q: Serialize + ?Sized,
+    {
+        paginate::<_, _, ListMessagesResponse>(
+            self.client,
+            format!("/threads/{}/messages", self.thread_id),
+            query,
+            pagination,
+        )
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.messages.delete", skip_all)
+    )]
     pub async fn delete(&self, message_id: &str) -> Result<DeleteMessageResponse, OpenAIError> {
         self.client
             .delete(&format!(
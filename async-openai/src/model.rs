@@ -19,17 +19,29 @@ impl<'c, C: Config> Models<'c, C> {
 
     /// Lists the currently available models, and provides basic information
     /// about each one such as the owner and availability.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.models.list", skip_all)
+    )]
     pub async fn list(&self) -> Result<ListModelResponse, OpenAIError> {
         self.client.get("/models").await
     }
 
     /// Retrieves a model instance, providing basic information about the model
     /// such as the owner and permissioning.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.models.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, id: &str) -> Result<Model, OpenAIError> {
         self.client.get(format!("/models/{id}").as_str()).await
     }
 
     /// Delete a fine-tuned model. You must have the Owner role in your organization.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.models.delete", skip_all)
+    )]
     pub async fn delete(&self, model: &str) -> Result<DeleteModelResponse, OpenAIError> {
         self.client
             .delete(format!("/models/{model}").as_str())
@@ -19,6 +19,10 @@ impl<'c, C: Config> Moderations<'c, C> {
 
     /// Classifies if text and/or image inputs are potentially harmful. Learn
     /// more in the [moderation guide](https://platform.openai.com/docs/guides/moderation).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.moderations.create", skip_all)
+    )]
     pub async fn create(
         &self,
         request: CreateModerationRequest,
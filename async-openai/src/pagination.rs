@@ -0,0 +1,64 @@
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    types::{Paginated, Pagination},
+    Client,
+};
+
+/// Items of every page of the list at {path}, each page is fetched once the
+/// items of the previous one are consumed. The stream ends after the first error.
+pub(crate) fn paginate<'a, C, Q, P>(
+    client: &'a Client<C>,
+    path: String,
+    query: &'a Q,
+    pagination: Pagination,
+) -> impl Stream<Item = Result<P::Item, OpenAIError>> + 'a
+where
+    C: Config,
+    Q: Serialize + ?Sized,
+    P: Paginated + DeserializeOwned + 'a,
+{
+    let max_items = pagination.max_items.unwrap_or(usize::MAX);
+
+    // State is the cursor of the next page and the number of items fetched so far,
+    // None after the last page
+    stream::try_unfold(Some((None, 0)), move |state| {
+        let path = path.clone();
+        async move {
+            let Some((after, fetched)) = state else {
+                return Ok(None);
+            };
+            if fetched >= max_items {
+                return Ok(None);
+            }
+
+            let mut page_query = vec![];
+            if let Some(page_size) = pagination.page_size {
+                let limit = (page_size as usize).min(max_items - fetched);
+                page_query.push(("limit", limit.to_string()));
+            }
+            if let Some(after) = after {
+                page_query.push(("after", after));
+            }
+
+            let page: P = client.get_page(&path, query, &page_query).await?;
+
+            let next = match page.next_cursor() {
+                Some(cursor) if page.has_more() && !page.items().is_empty() => {
+                    Some((Some(cursor.to_string()), fetched + page.items().len()))
+                }
+                _ => None,
+            };
+
+            Ok::<_, OpenAIError>(Some((
+                stream::iter(page.into_items().into_iter().map(Ok)),
+                next,
+            )))
+        }
+    })
+    .try_flatten()
+    .take(max_items)
+}
@@ -23,6 +23,10 @@ impl<'c, C: Config> ProjectAPIKeys<'c, C> {
     }
 
     /// Returns a list of API keys in the project.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.project_api_keys.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ProjectApiKeyListResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -36,6 +40,10 @@ impl<'c, C: Config> ProjectAPIKeys<'c, C> {
     }
 
     /// Retrieves an API key in the project.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.project_api_keys.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, api_key: &str) -> Result<ProjectApiKey, OpenAIError> {
         self.client
             .get(
@@ -49,6 +57,10 @@ impl<'c, C: Config> ProjectAPIKeys<'c, C> {
     }
 
     /// Deletes an API key from the project.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.project_api_keys.delete", skip_all)
+    )]
     pub async fn delete(&self, api_key: &str) -> Result<ProjectApiKeyDeleteResponse, OpenAIError> {
         self.client
             .delete(
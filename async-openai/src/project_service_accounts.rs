@@ -29,6 +29,10 @@ impl<'c, C: Config> ProjectServiceAccounts<'c, C> {
     }
 
     /// Returns a list of service accounts in the project.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.project_service_accounts.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ProjectServiceAccountListResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -46,6 +50,10 @@ impl<'c, C: Config> ProjectServiceAccounts<'c, C> {
     }
 
     /// Creates a new service account in the project. This also returns an unredacted API key for the service account.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.project_service_accounts.create", skip_all)
+    )]
     pub async fn create(
         &self,
         request: ProjectServiceAccountCreateRequest,
@@ -63,6 +71,10 @@ impl<'c, C: Config> ProjectServiceAccounts<'c, C> {
     }
 
     /// Retrieves a service account in the project.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.project_service_accounts.retrieve", skip_all)
+    )]
     pub async fn retrieve(
         &self,
         service_account_id: &str,
@@ -79,6 +91,10 @@ impl<'c, C: Config> ProjectServiceAccounts<'c, C> {
     }
 
     /// Deletes a service account from the project.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.project_service_accounts.delete", skip_all)
+    )]
     pub async fn delete(
         &self,
         service_account_id: &str,
@@ -26,6 +26,10 @@ impl<'c, C: Config> ProjectUsers<'c, C> {
     }
 
     /// Returns a list of users in the project.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.project_users.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ProjectUserListResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -39,6 +43,10 @@ impl<'c, C: Config> ProjectUsers<'c, C> {
     }
 
     /// Adds a user to the project. Users must already be members of the organization to be added to a project.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.project_users.create", skip_all)
+    )]
     pub async fn create(
         &self,
         request: ProjectUserCreateRequest,
@@ -52,6 +60,10 @@ impl<'c, C: Config> ProjectUsers<'c, C> {
     }
 
     /// Retrieves a user in the project.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.project_users.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, user_id: &str) -> Result<ProjectUser, OpenAIError> {
         self.client
             .get(format!("/organization/projects/{}/users/{user_id}", self.project_id).as_str())
@@ -59,6 +71,10 @@ impl<'c, C: Config> ProjectUsers<'c, C> {
     }
 
     /// Modifies a user's role in the project.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.project_users.modify", skip_all)
+    )]
     pub async fn modify(
         &self,
         user_id: &str,
@@ -73,6 +89,10 @@ impl<'c, C: Config> ProjectUsers<'c, C> {
     }
 
     /// Deletes a user from the project.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.project_users.delete", skip_all)
+    )]
     pub async fn delete(&self, user_id: &str) -> Result<ProjectUserDeleteResponse, OpenAIError> {
         self.client
             .delete(format!("/organization/projects/{}/users/{user_id}", self.project_id).as_str())
@@ -20,21 +20,25 @@ impl<'c, C: Config> Projects<'c, C> {
     }
 
     // call [ProjectUsers] group APIs
-    pub fn users(&self, project_id: &str) -> ProjectUsers<C> {
+    pub fn users(&self, project_id: &str) -> ProjectUsers<'_, C> {
         ProjectUsers::new(self.client, project_id)
     }
 
     // call [ProjectServiceAccounts] group APIs
-    pub fn service_accounts(&self, project_id: &str) -> ProjectServiceAccounts<C> {
+    pub fn service_accounts(&self, project_id: &str) -> ProjectServiceAccounts<'_, C> {
         ProjectServiceAccounts::new(self.client, project_id)
     }
 
     // call [ProjectAPIKeys] group APIs
-    pub fn api_keys(&self, project_id: &str) -> ProjectAPIKeys<C> {
+    pub fn api_keys(&self, project_id: &str) -> ProjectAPIKeys<'_, C> {
         ProjectAPIKeys::new(self.client, project_id)
     }
 
     /// Returns a list of projects.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.projects.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ProjectListResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -45,11 +49,19 @@ impl<'c, C: Config> Projects<'c, C> {
     }
 
     /// Create a new project in the organization. Projects can be created and archived, but cannot be deleted.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.projects.create", skip_all)
+    )]
     pub async fn create(&self, request: ProjectCreateRequest) -> Result<Project, OpenAIError> {
         self.client.post("/organization/projects", request).await
     }
 
     /// Retrieves a project.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.projects.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, project_id: String) -> Result<Project, OpenAIError> {
         self.client
             .get(format!("/organization/projects/{project_id}").as_str())
@@ -57,6 +69,10 @@ impl<'c, C: Config> Projects<'c, C> {
     }
 
     /// Modifies a project in the organization.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.projects.modify", skip_all)
+    )]
     pub async fn modify(
         &self,
         project_id: String,
@@ -71,6 +87,10 @@ impl<'c, C: Config> Projects<'c, C> {
     }
 
     /// Archives a project in the organization. Archived projects cannot be used or updated.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.projects.archive", skip_all)
+    )]
     pub async fn archive(&self, project_id: String) -> Result<Project, OpenAIError> {
         self.client
             .post(
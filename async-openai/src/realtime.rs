@@ -0,0 +1,27 @@
+use crate::{
+    config::Config, realtime_calls::Calls, realtime_client_secrets::ClientSecrets, Client,
+};
+
+/// Realtime API group of HTTP calls. Sessions over WebSocket are started with
+/// `RealtimeClient`, with feature `realtime-ws`.
+///
+/// Related guide: [Realtime](https://platform.openai.com/docs/guides/realtime)
+pub struct Realtime<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Realtime<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// [Calls] API group
+    pub fn calls(&self) -> Calls<'c, C> {
+        Calls::new(self.client)
+    }
+
+    /// [ClientSecrets] API group
+    pub fn client_secrets(&self) -> ClientSecrets<'c, C> {
+        ClientSecrets::new(self.client)
+    }
+}
@@ -0,0 +1,238 @@
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
+
+use crate::{
+    error::OpenAIError,
+    types::realtime::{AudioFormat, InputAudioBufferClearEvent},
+    RealtimeSender,
+};
+
+/// Streams input audio to a realtime session, in `input_audio_buffer.append` events
+/// of [AudioAppender::with_chunk_duration], 100ms by default.
+///
+/// Audio is in the `input_audio_format` of the session: 24kHz mono PCM16 little-endian,
+/// or 8kHz G.711.
+///
+/// ```no_run
+/// # async fn run(sender: async_openai::RealtimeSender, samples: Vec<i16>) -> Result<(), async_openai::error::OpenAIError> {
+/// use async_openai::{types::realtime::AudioFormat, AudioAppender};
+///
+/// let mut appender = AudioAppender::new(sender, AudioFormat::PCM16);
+/// appender.push_samples(&samples).await?;
+/// appender.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AudioAppender<S = tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>> {
+    sender: RealtimeSender<S>,
+    format: AudioFormat,
+    chunk_len: usize,
+    buffer: Vec<u8>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AudioAppender<S> {
+    pub fn new(sender: RealtimeSender<S>, format: AudioFormat) -> Self {
+        let mut appender = Self {
+            sender,
+            format,
+            chunk_len: 0,
+            buffer: vec![],
+        };
+        appender.set_chunk_duration(Duration::from_millis(100));
+        appender
+    }
+
+    /// Duration of the audio of each append event
+    pub fn with_chunk_duration(mut self, duration: Duration) -> Self {
+        self.set_chunk_duration(duration);
+        self
+    }
+
+    fn set_chunk_duration(&mut self, duration: Duration) {
+        let samples =
+            (self.format.sample_rate() as u128 * duration.as_micros() / 1_000_000).max(1) as usize;
+        self.chunk_len = samples * self.format.sample_len();
+    }
+
+    /// Number of bytes sent in each append event
+    pub fn chunk_len(&self) -> usize {
+        self.chunk_len
+    }
+
+    /// Append PCM16 samples, only for the [AudioFormat::PCM16] format
+    pub async fn push_samples(&mut self, samples: &[i16]) -> Result<(), OpenAIError> {
+        if !matches!(self.format, AudioFormat::PCM16) {
+            return Err(OpenAIError::InvalidArgument(
+                "samples can only be pushed for the pcm16 format".into(),
+            ));
+        }
+
+        let bytes: Vec<u8> = samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        self.push_bytes(&bytes).await
+    }
+
+    /// Append audio bytes, an append event is sent for each complete chunk
+    pub async fn push_bytes(&mut self, bytes: &[u8]) -> Result<(), OpenAIError> {
+        self.buffer.extend_from_slice(bytes);
+
+        let full_len = self.buffer.len() - self.buffer.len() % self.chunk_len;
+        let full: Vec<u8> = self.buffer.drain(..full_len).collect();
+        for chunk in full.chunks(self.chunk_len) {
+            self.sender.append_audio(chunk).await?;
+        }
+        Ok(())
+    }
+
+    /// Append all the audio of `reader`, like a raw audio file or the data of a WAV
+    /// file after its header, returning the number of bytes read. The audio is sent
+    /// as fast as it is read, without pacing.
+    pub async fn push_async_read<R: AsyncRead + Unpin>(
+        &mut self,
+        mut reader: R,
+    ) -> Result<u64, OpenAIError> {
+        let mut read = 0;
+        let mut buffer = vec![0; self.chunk_len.max(4096)];
+        loop {
+            let n = reader
+                .read(&mut buffer)
+                .await
+                .map_err(|e| OpenAIError::FileReadError(e.to_string()))?;
+            if n == 0 {
+                return Ok(read);
+            }
+            read += n as u64;
+            self.push_bytes(&buffer[..n]).await?;
+        }
+    }
+
+    /// Send the buffered audio of an incomplete chunk
+    pub async fn flush(&mut self) -> Result<(), OpenAIError> {
+        if !self.buffer.is_empty() {
+            let rest = std::mem::take(&mut self.buffer);
+            self.sender.append_audio(rest).await?;
+        }
+        Ok(())
+    }
+
+    /// Send the buffered audio and commit the input audio buffer to a user message
+    pub async fn commit(&mut self) -> Result<(), OpenAIError> {
+        self.flush().await?;
+        self.sender.commit_audio().await
+    }
+
+    /// Drop the buffered audio and clear the input audio buffer of the session
+    pub async fn clear(&mut self) -> Result<(), OpenAIError> {
+        self.buffer.clear();
+        self.sender
+            .send(InputAudioBufferClearEvent::default())
+            .await
+    }
+
+    pub fn sender(&mut self) -> &mut RealtimeSender<S> {
+        &mut self.sender
+    }
+
+    /// The sender, dropping the buffered audio
+    pub fn into_sender(self) -> RealtimeSender<S> {
+        self.sender
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use futures::StreamExt;
+    use serde_json::Value;
+    use tokio::io::DuplexStream;
+    use tokio_tungstenite::{
+        tungstenite::{protocol::Role, Message},
+        WebSocketStream,
+    };
+
+    use super::*;
+    use crate::RealtimeSession;
+
+    async fn appender(
+        format: AudioFormat,
+    ) -> (AudioAppender<DuplexStream>, WebSocketStream<DuplexStream>) {
+        let (client, server) = tokio::io::duplex(1 << 20);
+        let client = WebSocketStream::from_raw_socket(client, Role::Client, None).await;
+        let server = WebSocketStream::from_raw_socket(server, Role::Server, None).await;
+        let (sender, _) = RealtimeSession::new(client).split();
+        (AudioAppender::new(sender, format), server)
+    }
+
+    /// Client events received by the server, until the client is dropped
+    async fn received(server: WebSocketStream<DuplexStream>) -> Vec<Value> {
+        server
+            .filter_map(|message| async move {
+                match message {
+                    Ok(Message::Text(text)) => Some(serde_json::from_str(&text).unwrap()),
+                    _ => None,
+                }
+            })
+            .collect()
+            .await
+    }
+
+    fn audio(event: &Value) -> Vec<u8> {
+        assert_eq!(event["type"], "input_audio_buffer.append");
+        STANDARD.decode(event["audio"].as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_chunks_of_100ms() {
+        let (mut appender, server) = appender(AudioFormat::PCM16).await;
+        assert_eq!(appender.chunk_len(), 4800);
+
+        let samples: Vec<i16> = (0..6000).map(|i| (i as i16).wrapping_mul(7)).collect();
+        // Split at an odd place, not on a chunk boundary
+        appender.push_samples(&samples[..1001]).await.unwrap();
+        appender.push_samples(&samples[1001..]).await.unwrap();
+        appender.commit().await.unwrap();
+        drop(appender);
+
+        let events = received(server).await;
+        assert_eq!(events.len(), 4);
+        let chunks: Vec<_> = events[..3].iter().map(audio).collect();
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+            [4800, 4800, 2400]
+        );
+        let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        assert_eq!(chunks.concat(), bytes);
+        assert_eq!(events[3]["type"], "input_audio_buffer.commit");
+    }
+
+    #[tokio::test]
+    async fn test_async_read_and_clear() {
+        let (appender, server) = appender(AudioFormat::G711ULAW).await;
+        let mut appender = appender.with_chunk_duration(Duration::from_millis(20));
+        assert_eq!(appender.chunk_len(), 160);
+
+        let bytes: Vec<u8> = (0..=255).cycle().take(500).collect();
+        let read = appender.push_async_read(&bytes[..]).await.unwrap();
+        assert_eq!(read, 500);
+        appender.clear().await.unwrap();
+        // Samples are 16 bit PCM only
+        assert!(matches!(
+            appender.push_samples(&[0]).await,
+            Err(OpenAIError::InvalidArgument(_))
+        ));
+        drop(appender);
+
+        let events = received(server).await;
+        assert_eq!(events.len(), 4);
+        assert_eq!(
+            events[..3].iter().map(audio).collect::<Vec<_>>().concat(),
+            bytes[..480]
+        );
+        // The last 20 bytes are dropped by clear
+        assert_eq!(events[3]["type"], "input_audio_buffer.clear");
+    }
+}
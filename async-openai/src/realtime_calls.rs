@@ -0,0 +1,80 @@
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    types::realtime::{RealtimeSessionConfig, ReferCallRequest, RejectCallRequest},
+    Client,
+};
+
+/// Control of incoming SIP calls, announced by the `realtime.call.incoming` webhook
+/// with their `call_id`.
+///
+/// Related guide: [Realtime API with SIP](https://platform.openai.com/docs/guides/realtime-sip)
+pub struct Calls<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Calls<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Accept an incoming call with the configuration of its session. The
+    /// session can then be joined over WebSocket with its `call_id`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.realtime.calls.accept", skip_all)
+    )]
+    pub async fn accept(
+        &self,
+        call_id: &str,
+        session: RealtimeSessionConfig,
+    ) -> Result<(), OpenAIError> {
+        self.client
+            .post_raw(&format!("/realtime/calls/{call_id}/accept"), session)
+            .await?;
+        Ok(())
+    }
+
+    /// Decline an incoming call.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.realtime.calls.reject", skip_all)
+    )]
+    pub async fn reject(
+        &self,
+        call_id: &str,
+        request: RejectCallRequest,
+    ) -> Result<(), OpenAIError> {
+        self.client
+            .post_raw(&format!("/realtime/calls/{call_id}/reject"), request)
+            .await?;
+        Ok(())
+    }
+
+    /// Transfer an active call to another SIP or phone number destination.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.realtime.calls.refer", skip_all)
+    )]
+    pub async fn refer(&self, call_id: &str, request: ReferCallRequest) -> Result<(), OpenAIError> {
+        self.client
+            .post_raw(&format!("/realtime/calls/{call_id}/refer"), request)
+            .await?;
+        Ok(())
+    }
+
+    /// End an active call.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.realtime.calls.hangup", skip_all)
+    )]
+    pub async fn hangup(&self, call_id: &str) -> Result<(), OpenAIError> {
+        self.client
+            .post_raw(
+                &format!("/realtime/calls/{call_id}/hangup"),
+                serde_json::json!({}),
+            )
+            .await?;
+        Ok(())
+    }
+}
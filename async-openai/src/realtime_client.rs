@@ -0,0 +1,485 @@
+use std::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use futures::{
+    stream::{SplitSink, SplitStream},
+    SinkExt, Stream, StreamExt,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpStream,
+};
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{self, client::IntoClientRequest, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+
+use crate::{
+    config::{Config, OpenAIConfig, OPENAI_BETA_HEADER},
+    error::{deserialize, map_error_response, OpenAIError},
+    types::realtime::{
+        ClientEvent, ConversationItemCreateEvent, InputAudioBufferAppendEvent,
+        InputAudioBufferCommitEvent, Item, ItemContent, ItemContentType, ItemRole, ItemType,
+        ResponseCreateEvent, ResponseCreateOptions, ServerEvent,
+    },
+};
+
+/// Connects to the [Realtime API](https://platform.openai.com/docs/guides/realtime)
+/// over WebSocket, with the url, API key and headers of a [Config].
+///
+/// ```no_run
+/// # async fn run() -> Result<(), async_openai::error::OpenAIError> {
+/// use async_openai::{types::realtime::ServerEvent, RealtimeClient};
+/// use futures::StreamExt;
+///
+/// let mut session = RealtimeClient::new()
+///     .connect("gpt-4o-realtime-preview-2024-12-17")
+///     .await?;
+///
+/// session.send_text("Say hello").await?;
+/// session.create_response(None).await?;
+///
+/// while let Some(event) = session.events().next().await {
+///     match event? {
+///         ServerEvent::ResponseTextDelta(event) => print!("{}", event.delta),
+///         ServerEvent::ResponseDone(_) => break,
+///         _ => {}
+///     }
+/// }
+/// session.close().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RealtimeClient<C: Config> {
+    config: C,
+}
+
+impl RealtimeClient<OpenAIConfig> {
+    /// Client with the default [OpenAIConfig]
+    pub fn new() -> Self {
+        Self::with_config(OpenAIConfig::default())
+    }
+}
+
+impl Default for RealtimeClient<OpenAIConfig> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Config> RealtimeClient<C> {
+    pub fn with_config(config: C) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &C {
+        &self.config
+    }
+
+    /// Start a session with `model`
+    pub async fn connect(&self, model: &str) -> Result<RealtimeSession, OpenAIError> {
+        self.connect_with(("model", model)).await
+    }
+
+    /// Join the session of the call `call_id`, like a SIP call accepted by a webhook
+    pub async fn connect_call(&self, call_id: &str) -> Result<RealtimeSession, OpenAIError> {
+        self.connect_with(("call_id", call_id)).await
+    }
+
+    async fn connect_with(&self, param: (&str, &str)) -> Result<RealtimeSession, OpenAIError> {
+        let mut url = reqwest::Url::parse(&self.config.url("/realtime"))
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid realtime url: {e}")))?;
+        let scheme = match url.scheme() {
+            "https" | "wss" => "wss",
+            _ => "ws",
+        };
+        // Both are special schemes, so this cannot fail
+        let _ = url.set_scheme(scheme);
+        url.query_pairs_mut()
+            .extend_pairs(self.config.query())
+            .append_pair(param.0, param.1);
+
+        let mut request = url.as_str().into_client_request().map_err(map_error)?;
+        request.headers_mut().extend(self.config.try_headers()?);
+        request
+            .headers_mut()
+            .insert(OPENAI_BETA_HEADER, "realtime=v1".parse().unwrap());
+
+        let (socket, _) = connect_async(request).await.map_err(map_error)?;
+        Ok(RealtimeSession::new(socket))
+    }
+}
+
+/// A connection to the Realtime API, sending [ClientEvent]s and receiving [ServerEvent]s.
+///
+/// Pings of the server are answered while events are read. Use [RealtimeSession::split]
+/// to send events from one task while another reads them.
+#[derive(Debug)]
+pub struct RealtimeSession<S = MaybeTlsStream<TcpStream>> {
+    sender: RealtimeSender<S>,
+    events: RealtimeEvents<S>,
+}
+
+/// Sending half of a [RealtimeSession]
+#[derive(Debug)]
+pub struct RealtimeSender<S = MaybeTlsStream<TcpStream>> {
+    sink: SplitSink<WebSocketStream<S>, Message>,
+}
+
+/// Receiving half of a [RealtimeSession], a stream of the events of the server
+/// which ends when the connection is closed.
+#[derive(Debug)]
+pub struct RealtimeEvents<S = MaybeTlsStream<TcpStream>> {
+    stream: SplitStream<WebSocketStream<S>>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> RealtimeSession<S> {
+    /// Session over an established WebSocket connection
+    pub fn new(socket: WebSocketStream<S>) -> Self {
+        let (sink, stream) = socket.split();
+        Self {
+            sender: RealtimeSender { sink },
+            events: RealtimeEvents { stream },
+        }
+    }
+
+    pub async fn send(&mut self, event: impl Into<ClientEvent>) -> Result<(), OpenAIError> {
+        self.sender.send(event).await
+    }
+
+    /// See [RealtimeSender::send_text]
+    pub async fn send_text(&mut self, text: impl Into<String>) -> Result<(), OpenAIError> {
+        self.sender.send_text(text).await
+    }
+
+    /// See [RealtimeSender::append_audio]
+    pub async fn append_audio(&mut self, audio: impl AsRef<[u8]>) -> Result<(), OpenAIError> {
+        self.sender.append_audio(audio).await
+    }
+
+    /// See [RealtimeSender::commit_audio]
+    pub async fn commit_audio(&mut self) -> Result<(), OpenAIError> {
+        self.sender.commit_audio().await
+    }
+
+    /// See [RealtimeSender::create_response]
+    pub async fn create_response(
+        &mut self,
+        response: Option<ResponseCreateOptions>,
+    ) -> Result<(), OpenAIError> {
+        self.sender.create_response(response).await
+    }
+
+    /// The events sent by the server
+    pub fn events(&mut self) -> &mut RealtimeEvents<S> {
+        &mut self.events
+    }
+
+    pub fn split(self) -> (RealtimeSender<S>, RealtimeEvents<S>) {
+        (self.sender, self.events)
+    }
+
+    /// Send a close frame and wait for the server to close the connection,
+    /// dropping the events received in between.
+    pub async fn close(mut self) -> Result<(), OpenAIError> {
+        self.sender.close().await?;
+        while let Some(event) = self.events.next().await {
+            if let Err(OpenAIError::WebSocket(e)) = event {
+                return Err(OpenAIError::WebSocket(e));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> RealtimeSender<S> {
+    pub async fn send(&mut self, event: impl Into<ClientEvent>) -> Result<(), OpenAIError> {
+        self.sink
+            .send(Message::from(event.into()))
+            .await
+            .map_err(map_error)
+    }
+
+    /// Add a user message with `text` to the conversation. The model answers
+    /// after [RealtimeSender::create_response].
+    pub async fn send_text(&mut self, text: impl Into<String>) -> Result<(), OpenAIError> {
+        self.send(user_text(text.into())).await
+    }
+
+    /// Append audio to the input audio buffer, in the `input_audio_format` of the session
+    pub async fn append_audio(&mut self, audio: impl AsRef<[u8]>) -> Result<(), OpenAIError> {
+        self.send(InputAudioBufferAppendEvent {
+            event_id: None,
+            audio: STANDARD.encode(audio),
+        })
+        .await
+    }
+
+    /// Commit the input audio buffer to a user message, needed when server
+    /// VAD is disabled.
+    pub async fn commit_audio(&mut self) -> Result<(), OpenAIError> {
+        self.send(InputAudioBufferCommitEvent::default()).await
+    }
+
+    /// Ask the model for a response, configured by `response` instead of the
+    /// session when set.
+    pub async fn create_response(
+        &mut self,
+        response: Option<ResponseCreateOptions>,
+    ) -> Result<(), OpenAIError> {
+        self.send(ResponseCreateEvent {
+            event_id: None,
+            response,
+        })
+        .await
+    }
+
+    /// Send a close frame, the server then closes the connection
+    pub async fn close(&mut self) -> Result<(), OpenAIError> {
+        match self.sink.close().await {
+            Ok(()) | Err(tungstenite::Error::ConnectionClosed) => Ok(()),
+            Err(e) => Err(map_error(e)),
+        }
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> Stream for RealtimeEvents<S> {
+    type Item = Result<ServerEvent, OpenAIError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let message = match ready!(self.stream.poll_next_unpin(cx)) {
+                None
+                | Some(Err(tungstenite::Error::ConnectionClosed))
+                | Some(Err(tungstenite::Error::AlreadyClosed)) => return Poll::Ready(None),
+                Some(Err(e)) => return Poll::Ready(Some(Err(map_error(e)))),
+                Some(Ok(message)) => message,
+            };
+
+            match message {
+                Message::Text(text) => return Poll::Ready(Some(deserialize(text.as_bytes()))),
+                Message::Binary(data) => return Poll::Ready(Some(deserialize(&data))),
+                // The close frame is answered by tungstenite
+                Message::Close(_) => return Poll::Ready(None),
+                // Pongs are sent by tungstenite on the next read or write
+                Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => continue,
+            }
+        }
+    }
+}
+
+/// `conversation.item.create` of a user message with `text`
+pub(crate) fn user_text(text: String) -> ConversationItemCreateEvent {
+    ConversationItemCreateEvent::from(Item {
+        r#type: Some(ItemType::Message),
+        role: Some(ItemRole::User),
+        content: Some(vec![ItemContent {
+            r#type: ItemContentType::InputText,
+            text: Some(text),
+            audio: None,
+            transcript: None,
+        }]),
+        ..Default::default()
+    })
+}
+
+fn map_error(error: tungstenite::Error) -> OpenAIError {
+    match error {
+        // The handshake was rejected, like for an invalid API key
+        tungstenite::Error::Http(response) => map_error_response(
+            response.status(),
+            response.headers(),
+            response.body().as_deref().unwrap_or_default(),
+        ),
+        error => OpenAIError::WebSocket(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+// The handshake callbacks of tungstenite return its large error response
+#[allow(clippy::result_large_err)]
+mod tests {
+    use serde_json::{json, Value};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::{
+        accept_hdr_async,
+        tungstenite::handshake::server::{Request, Response},
+    };
+
+    use super::*;
+
+    /// Client of a local server, which sends back the path and headers of the
+    /// handshake as a `session.created` like event, then runs `server`
+    async fn connect<F, Fut>(server: F) -> RealtimeSession
+    where
+        F: FnOnce(WebSocketStream<TcpStream>) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut handshake = Value::Null;
+            let mut socket = accept_hdr_async(stream, |request: &Request, response: Response| {
+                handshake = json!({
+                    "type": "test.handshake",
+                    "uri": request.uri().to_string(),
+                    "authorization": request.headers()["authorization"].to_str().unwrap(),
+                    "beta": request.headers()["openai-beta"].to_str().unwrap(),
+                });
+                Ok(response)
+            })
+            .await
+            .unwrap();
+            socket
+                .send(Message::text(handshake.to_string()))
+                .await
+                .unwrap();
+            server(socket).await;
+        });
+
+        let config = OpenAIConfig::new()
+            .with_api_base(format!("http://{address}/v1"))
+            .with_api_key("sk-test");
+        RealtimeClient::with_config(config)
+            .connect("gpt-4o-realtime-preview")
+            .await
+            .unwrap()
+    }
+
+    async fn next_event(session: &mut RealtimeSession) -> Value {
+        let event = session.events().next().await.unwrap().unwrap();
+        serde_json::to_value(event).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_handshake() {
+        let mut session = connect(|_| async {}).await;
+
+        assert_eq!(
+            next_event(&mut session).await,
+            json!({
+                "type": "test.handshake",
+                "uri": "/v1/realtime?model=gpt-4o-realtime-preview",
+                "authorization": "Bearer sk-test",
+                "beta": "realtime=v1",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_and_receive_events() {
+        let mut session = connect(|mut socket| async move {
+            // Answer each client event with its type, after a ping
+            while let Some(Ok(message)) = socket.next().await {
+                let Message::Text(text) = message else {
+                    continue;
+                };
+                let event: Value = serde_json::from_str(&text).unwrap();
+                socket.send(Message::Ping("ping".into())).await.unwrap();
+                socket
+                    .send(Message::text(
+                        json!({
+                            "type": "test.echo",
+                            "event": event,
+                        })
+                        .to_string(),
+                    ))
+                    .await
+                    .unwrap();
+            }
+        })
+        .await;
+        next_event(&mut session).await;
+
+        session.send_text("Hello").await.unwrap();
+        assert_eq!(
+            next_event(&mut session).await["event"],
+            json!({
+                "type": "conversation.item.create",
+                "item": {
+                    "type": "message",
+                    "role": "user",
+                    "content": [{"type": "input_text", "text": "Hello"}]
+                }
+            })
+        );
+
+        session.append_audio(b"RIFF").await.unwrap();
+        assert_eq!(
+            next_event(&mut session).await["event"],
+            json!({"type": "input_audio_buffer.append", "audio": "UklGRg=="})
+        );
+
+        session.commit_audio().await.unwrap();
+        assert_eq!(
+            next_event(&mut session).await["event"],
+            json!({"type": "input_audio_buffer.commit"})
+        );
+
+        session.create_response(None).await.unwrap();
+        assert_eq!(
+            next_event(&mut session).await["event"],
+            json!({"type": "response.create"})
+        );
+
+        session.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_pings_are_answered() {
+        let (pong_tx, pong_rx) = tokio::sync::oneshot::channel();
+        let mut session = connect(|mut socket| async move {
+            socket.send(Message::Ping("ping".into())).await.unwrap();
+            while let Some(Ok(message)) = socket.next().await {
+                if let Message::Pong(data) = message {
+                    pong_tx.send(data).unwrap();
+                    break;
+                }
+            }
+            socket.close(None).await.unwrap();
+        })
+        .await;
+
+        next_event(&mut session).await;
+        // The ping is skipped and the stream ends with the close of the server
+        assert!(session.events().next().await.is_none());
+        assert_eq!(pong_rx.await.unwrap().as_ref(), b"ping");
+    }
+
+    #[tokio::test]
+    async fn test_rejected_handshake() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let _ = accept_hdr_async(stream, |_: &Request, _: Response| {
+                Err(Response::builder()
+                    .status(401)
+                    .body(Some(
+                        json!({"error": {"message": "Incorrect API key provided", "type": "invalid_request_error", "param": null, "code": "invalid_api_key"}})
+                            .to_string(),
+                    ))
+                    .unwrap())
+            })
+            .await;
+        });
+
+        let config = OpenAIConfig::new().with_api_base(format!("http://{address}/v1"));
+        let error = RealtimeClient::with_config(config)
+            .connect("gpt-4o-realtime-preview")
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(error, OpenAIError::ApiError(ref e) if e.message == "Incorrect API key provided")
+        );
+        assert_eq!(error.status(), Some(reqwest::StatusCode::UNAUTHORIZED));
+    }
+}
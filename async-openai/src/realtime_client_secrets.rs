@@ -0,0 +1,32 @@
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    types::realtime::{CreateRealtimeClientSecretRequest, RealtimeClientSecret},
+    Client,
+};
+
+/// Ephemeral keys for browser and mobile clients of the Realtime API, created
+/// by a server holding the API key.
+///
+/// Related guide: [Realtime API with WebRTC](https://platform.openai.com/docs/guides/realtime-webrtc)
+pub struct ClientSecrets<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> ClientSecrets<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Create a Realtime client secret with an associated session configuration.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.realtime.client_secrets.create", skip_all)
+    )]
+    pub async fn create(
+        &self,
+        request: CreateRealtimeClientSecretRequest,
+    ) -> Result<RealtimeClientSecret, OpenAIError> {
+        self.client.post("/realtime/client_secrets", request).await
+    }
+}
@@ -0,0 +1,528 @@
+use std::time::{Duration, Instant};
+
+use backoff::{backoff::Backoff, ExponentialBackoff};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use futures::StreamExt;
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    realtime_client::user_text,
+    types::realtime::{
+        ClientEvent, ConversationItemCreateEvent, InputAudioBufferAppendEvent,
+        InputAudioBufferCommitEvent, Item, ItemContentType, ItemType, ResponseCreateEvent,
+        ResponseCreateOptions, ServerEvent, SessionUpdateEvent,
+    },
+    RealtimeClient, RealtimeSession,
+};
+
+/// When and how a [ReconnectingSession] reconnects after its connection drops.
+///
+/// By default reconnection is enabled, with at most 5 attempts and the default
+/// [ExponentialBackoff] between attempts.
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    enabled: bool,
+    max_attempts: u32,
+    backoff: ExponentialBackoff,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_attempts: 5,
+            backoff: ExponentialBackoff::default(),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Never reconnect: the session ends with its first connection
+    pub fn disabled() -> Self {
+        Self::default().with_enabled(false)
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Maximum number of connection attempts for each drop of the connection
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Backoff between connection attempts, reset for each drop of the connection
+    pub fn with_backoff(mut self, backoff: ExponentialBackoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+}
+
+/// Event of a [ReconnectingSession]
+#[derive(Debug, Clone)]
+// Nearly all events are server events, not worth a box each
+#[allow(clippy::large_enum_variant)]
+pub enum RealtimeSessionEvent {
+    /// An event of the server
+    Server(ServerEvent),
+    /// The connection dropped and a new session was started with the replayed state.
+    /// Audio items and in-progress responses of the old session are lost.
+    Reconnected {
+        /// Time between the drop of the connection and the end of the replay
+        dropped_duration: Duration,
+    },
+}
+
+impl<C: Config + Clone> RealtimeClient<C> {
+    /// Start a session with `model` which reconnects according to `policy` when its
+    /// connection drops, like at the end of the maximum duration of a session.
+    pub async fn connect_reconnecting(
+        &self,
+        model: &str,
+        policy: ReconnectPolicy,
+    ) -> Result<ReconnectingSession<C>, OpenAIError> {
+        let session = self.connect(model).await?;
+        Ok(ReconnectingSession {
+            client: self.clone(),
+            model: model.to_string(),
+            policy,
+            session,
+            session_update: None,
+            items: vec![],
+            disconnected_at: None,
+            reconnected: None,
+            closed: false,
+        })
+    }
+}
+
+/// A realtime session which survives drops of its connection.
+///
+/// On a drop, a new connection is made, the last `session.update` sent is replayed,
+/// and the text items of the conversation are created again with their IDs: messages
+/// with text content only, function calls and function call outputs. Audio can't be
+/// replayed. [RealtimeSessionEvent::Reconnected] is then returned by
+/// [ReconnectingSession::next_event], before the events of the new connection.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), async_openai::error::OpenAIError> {
+/// use async_openai::{RealtimeClient, RealtimeSessionEvent, ReconnectPolicy};
+///
+/// let mut session = RealtimeClient::new()
+///     .connect_reconnecting("gpt-4o-realtime-preview-2024-12-17", ReconnectPolicy::new())
+///     .await?;
+///
+/// while let Some(event) = session.next_event().await {
+///     match event? {
+///         RealtimeSessionEvent::Server(event) => println!("{event:?}"),
+///         RealtimeSessionEvent::Reconnected { dropped_duration } => {
+///             println!("reconnected after {dropped_duration:?}")
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ReconnectingSession<C: Config> {
+    client: RealtimeClient<C>,
+    model: String,
+    policy: ReconnectPolicy,
+    session: RealtimeSession,
+    session_update: Option<SessionUpdateEvent>,
+    items: Vec<Item>,
+    disconnected_at: Option<Instant>,
+    reconnected: Option<Duration>,
+    closed: bool,
+}
+
+impl<C: Config + Clone> ReconnectingSession<C> {
+    /// Send `event`, reconnecting first when the connection dropped
+    pub async fn send(&mut self, event: impl Into<ClientEvent>) -> Result<(), OpenAIError> {
+        let event = event.into();
+        if let ClientEvent::SessionUpdate(update) = &event {
+            self.session_update = Some(update.clone());
+        }
+
+        if self.disconnected_at.is_none() {
+            match self.session.send(event.clone()).await {
+                Err(OpenAIError::WebSocket(_)) if self.policy.enabled && !self.closed => {
+                    self.disconnected_at = Some(Instant::now());
+                }
+                result => return result,
+            }
+        }
+        self.reconnect().await?;
+        self.session.send(event).await
+    }
+
+    /// See [crate::RealtimeSender::send_text]
+    pub async fn send_text(&mut self, text: impl Into<String>) -> Result<(), OpenAIError> {
+        self.send(user_text(text.into())).await
+    }
+
+    /// See [crate::RealtimeSender::append_audio]
+    pub async fn append_audio(&mut self, audio: impl AsRef<[u8]>) -> Result<(), OpenAIError> {
+        self.send(InputAudioBufferAppendEvent {
+            event_id: None,
+            audio: STANDARD.encode(audio),
+        })
+        .await
+    }
+
+    /// See [crate::RealtimeSender::commit_audio]
+    pub async fn commit_audio(&mut self) -> Result<(), OpenAIError> {
+        self.send(InputAudioBufferCommitEvent::default()).await
+    }
+
+    /// See [crate::RealtimeSender::create_response]
+    pub async fn create_response(
+        &mut self,
+        response: Option<ResponseCreateOptions>,
+    ) -> Result<(), OpenAIError> {
+        self.send(ResponseCreateEvent {
+            event_id: None,
+            response,
+        })
+        .await
+    }
+
+    /// The next event, `None` once the session is closed or could not reconnect after
+    /// returning the error of the last attempt.
+    ///
+    /// This method is cancel safe: a reconnection interrupted by a drop of the future
+    /// is started again by the next call.
+    pub async fn next_event(&mut self) -> Option<Result<RealtimeSessionEvent, OpenAIError>> {
+        loop {
+            if let Some(dropped_duration) = self.reconnected.take() {
+                return Some(Ok(RealtimeSessionEvent::Reconnected { dropped_duration }));
+            }
+            if self.closed {
+                return None;
+            }
+            if self.disconnected_at.is_some() {
+                if let Err(e) = self.reconnect().await {
+                    return Some(Err(e));
+                }
+                continue;
+            }
+
+            match self.session.events().next().await {
+                Some(Ok(event)) => {
+                    self.track(&event);
+                    return Some(Ok(RealtimeSessionEvent::Server(event)));
+                }
+                None | Some(Err(OpenAIError::WebSocket(_))) if self.policy.enabled => {
+                    self.disconnected_at = Some(Instant::now());
+                }
+                None => {
+                    self.closed = true;
+                    return None;
+                }
+                Some(Err(e)) => return Some(Err(e)),
+            }
+        }
+    }
+
+    /// See [RealtimeSession::close]
+    pub async fn close(self) -> Result<(), OpenAIError> {
+        self.session.close().await
+    }
+
+    /// Items created again after a reconnection
+    pub fn replayed_items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// Keep the replayable items of the conversation up to date
+    fn track(&mut self, event: &ServerEvent) {
+        let item = match event {
+            ServerEvent::ConversationItemCreated(event) => &event.item,
+            ServerEvent::ResponseOutputItemDone(event) => &event.item,
+            ServerEvent::ConversationItemDeleted(event) => {
+                self.items
+                    .retain(|item| item.id.as_deref() != Some(&event.item_id));
+                return;
+            }
+            _ => return,
+        };
+        if item.id.is_none() || !is_replayable(item) {
+            return;
+        }
+
+        match self.items.iter_mut().find(|tracked| tracked.id == item.id) {
+            Some(tracked) => *tracked = item.clone(),
+            None => self.items.push(item.clone()),
+        }
+    }
+
+    async fn reconnect(&mut self) -> Result<(), OpenAIError> {
+        let disconnected_at = *self.disconnected_at.get_or_insert_with(Instant::now);
+        let mut backoff = self.policy.backoff.clone();
+        backoff.reset();
+
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let error = match self.client.connect(&self.model).await {
+                Ok(session) => {
+                    self.session = session;
+                    match self.replay().await {
+                        Ok(()) => break,
+                        Err(e) => e,
+                    }
+                }
+                Err(e) => e,
+            };
+
+            let retry = match backoff.next_backoff() {
+                Some(duration) if attempts < self.policy.max_attempts && error.is_retryable() => {
+                    duration
+                }
+                _ => {
+                    self.closed = true;
+                    self.disconnected_at = None;
+                    return Err(error);
+                }
+            };
+            tokio::time::sleep(retry).await;
+        }
+
+        self.disconnected_at = None;
+        self.reconnected = Some(disconnected_at.elapsed());
+        Ok(())
+    }
+
+    async fn replay(&mut self) -> Result<(), OpenAIError> {
+        if let Some(update) = &self.session_update {
+            self.session.send(update.clone()).await?;
+        }
+        // The server echoes the items in `conversation.item.created` events,
+        // which replace the tracked items of the same IDs
+        for item in &self.items {
+            let item = Item {
+                status: None,
+                ..item.clone()
+            };
+            self.session
+                .send(ConversationItemCreateEvent::from(item))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+fn is_replayable(item: &Item) -> bool {
+    match item.r#type {
+        Some(ItemType::FunctionCall | ItemType::FunctionCallOutput) => true,
+        Some(ItemType::Message) => item.content.as_ref().is_some_and(|content| {
+            !content.is_empty()
+                && content.iter().all(|part| {
+                    matches!(
+                        part.r#type,
+                        ItemContentType::InputText | ItemContentType::Text
+                    )
+                })
+        }),
+        // The tools of MCP servers are listed again by the new session
+        Some(
+            ItemType::McpListTools
+            | ItemType::McpCall
+            | ItemType::McpApprovalRequest
+            | ItemType::McpApprovalResponse,
+        )
+        | None => false,
+    }
+}
+
+#[cfg(test)]
+// The handshake callbacks of tungstenite return its large error response
+#[allow(clippy::result_large_err)]
+mod tests {
+    use futures::SinkExt;
+    use serde_json::{json, Value};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_tungstenite::{accept_async, tungstenite::Message, WebSocketStream};
+
+    use super::*;
+    use crate::{config::OpenAIConfig, types::realtime::RealtimeSessionUpdateArgs};
+
+    fn client(address: std::net::SocketAddr) -> RealtimeClient<OpenAIConfig> {
+        RealtimeClient::with_config(
+            OpenAIConfig::new()
+                .with_api_base(format!("http://{address}/v1"))
+                .with_api_key("sk-test"),
+        )
+    }
+
+    fn policy() -> ReconnectPolicy {
+        ReconnectPolicy::new()
+            .with_max_attempts(3)
+            .with_backoff(ExponentialBackoff {
+                initial_interval: Duration::from_millis(1),
+                max_interval: Duration::from_millis(1),
+                ..Default::default()
+            })
+    }
+
+    async fn accept(listener: &TcpListener) -> WebSocketStream<TcpStream> {
+        let (stream, _) = listener.accept().await.unwrap();
+        accept_async(stream).await.unwrap()
+    }
+
+    async fn receive(socket: &mut WebSocketStream<TcpStream>) -> Value {
+        loop {
+            if let Message::Text(text) = socket.next().await.unwrap().unwrap() {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    async fn send(socket: &mut WebSocketStream<TcpStream>, event: Value) {
+        socket.send(Message::text(event.to_string())).await.unwrap();
+    }
+
+    fn item_created(item: Value) -> Value {
+        json!({
+            "type": "conversation.item.created",
+            "event_id": "event_1",
+            "previous_item_id": null,
+            "item": item,
+        })
+    }
+
+    async fn server_event(session: &mut ReconnectingSession<OpenAIConfig>) -> Value {
+        match session.next_event().await.unwrap().unwrap() {
+            RealtimeSessionEvent::Server(event) => serde_json::to_value(event).unwrap(),
+            event => panic!("expected a server event, got {event:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_replays_session() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (replayed_tx, replayed_rx) = tokio::sync::oneshot::channel();
+
+        tokio::spawn(async move {
+            let mut socket = accept(&listener).await;
+            assert_eq!(receive(&mut socket).await["type"], "session.update");
+            let mut text_item = receive(&mut socket).await["item"].clone();
+            text_item["id"] = json!("item_1");
+            text_item["status"] = json!("completed");
+            send(&mut socket, item_created(text_item)).await;
+            // User audio can't be replayed
+            send(
+                &mut socket,
+                item_created(json!({
+                    "id": "item_2",
+                    "type": "message",
+                    "role": "user",
+                    "content": [{"type": "input_audio", "transcript": "Hi"}]
+                })),
+            )
+            .await;
+            // A drop of the connection in the middle of the session
+            drop(socket);
+
+            let mut socket = accept(&listener).await;
+            let replayed = [receive(&mut socket).await, receive(&mut socket).await];
+            replayed_tx.send(replayed).unwrap();
+            send(&mut socket, json!({"type": "test.after_reconnect"})).await;
+            socket.close(None).await.unwrap();
+        });
+
+        let mut session = client(address)
+            .connect_reconnecting("gpt-4o-realtime-preview", policy())
+            .await
+            .unwrap();
+        session
+            .send(
+                RealtimeSessionUpdateArgs::default()
+                    .instructions("Be brief.")
+                    .build()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        session.send_text("Hello").await.unwrap();
+
+        assert_eq!(server_event(&mut session).await["item"]["id"], "item_1");
+        assert_eq!(server_event(&mut session).await["item"]["id"], "item_2");
+        assert!(matches!(
+            session.next_event().await,
+            Some(Ok(RealtimeSessionEvent::Reconnected { .. }))
+        ));
+        assert_eq!(
+            server_event(&mut session).await["type"],
+            "test.after_reconnect"
+        );
+
+        assert_eq!(
+            replayed_rx.await.unwrap(),
+            [
+                json!({"type": "session.update", "session": {"instructions": "Be brief."}}),
+                json!({
+                    "type": "conversation.item.create",
+                    "item": {
+                        "id": "item_1",
+                        "type": "message",
+                        "role": "user",
+                        "content": [{"type": "input_text", "text": "Hello"}]
+                    }
+                }),
+            ]
+        );
+        assert_eq!(session.replayed_items().len(), 1);
+        session.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_disabled() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut socket = accept(&listener).await;
+            send(&mut socket, json!({"type": "test.event"})).await;
+            socket.close(None).await.unwrap();
+        });
+
+        let mut session = client(address)
+            .connect_reconnecting("gpt-4o-realtime-preview", ReconnectPolicy::disabled())
+            .await
+            .unwrap();
+        assert_eq!(server_event(&mut session).await["type"], "test.event");
+        assert!(session.next_event().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_max_attempts() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            drop(accept(&listener).await);
+            // The next connections fail without a WebSocket handshake
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                drop(stream);
+            }
+        });
+
+        let mut session = client(address)
+            .connect_reconnecting("gpt-4o-realtime-preview", policy())
+            .await
+            .unwrap();
+        assert!(matches!(
+            session.next_event().await,
+            Some(Err(OpenAIError::WebSocket(_)))
+        ));
+        assert!(session.next_event().await.is_none());
+    }
+}
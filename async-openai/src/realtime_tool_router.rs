@@ -0,0 +1,366 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use serde_json::{json, Value};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    tool_registry::{output_text, ToolRegistry},
+    types::realtime::{
+        ClientEvent, ConversationItemCreateEvent, Item, ItemType, ResponseCreateEvent, ServerEvent,
+        ToolDefinition,
+    },
+};
+
+/// Runs the functions registered with [RealtimeToolRouter::tool] for the function
+/// calls of a realtime session, and replies with their outputs.
+///
+/// Give every server event to [RealtimeToolRouter::handle]. When the arguments of a
+/// call are done, its function runs in a task, which then sends a `function_call_output`
+/// item on the `replies` channel. Once all the calls of a response have an output and
+/// the response is done, a `response.create` event is sent for the model to continue.
+///
+/// A failing function, an unknown function or invalid arguments get
+/// `{"error": "..."}` as output.
+///
+/// ```no_run
+/// # async fn run(
+/// #     mut events: impl futures::Stream<Item = Result<async_openai::types::realtime::ServerEvent, async_openai::error::OpenAIError>> + Unpin,
+/// # ) -> Result<(), async_openai::error::OpenAIError> {
+/// use async_openai::{types::realtime::ToolDefinition, RealtimeToolRouter};
+/// use futures::StreamExt;
+/// use serde_json::{json, Value};
+///
+/// let mut router = RealtimeToolRouter::new().tool(
+///     ToolDefinition::Function {
+///         name: "get_weather".into(),
+///         description: "Weather of a location".into(),
+///         parameters: json!({
+///             "type": "object",
+///             "properties": {"location": {"type": "string"}},
+///         }),
+///     },
+///     |args: Value| async move {
+///         Ok::<_, String>(json!({"location": args["location"], "temperature": 22}))
+///     },
+/// );
+///
+/// // Replies are received here, to send to the session
+/// let (replies, mut replies_rx) = tokio::sync::mpsc::unbounded_channel();
+///
+/// while let Some(event) = events.next().await {
+///     router.handle(&event?, &replies);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct RealtimeToolRouter {
+    tools: ToolRegistry<ToolDefinition>,
+    /// Name and arguments of the calls in progress, by call id
+    calls: HashMap<String, PendingCall>,
+    responses: Arc<Mutex<HashMap<String, ResponseCalls>>>,
+}
+
+#[derive(Default)]
+struct PendingCall {
+    name: Option<String>,
+    arguments: String,
+}
+
+/// Function calls of a response
+#[derive(Default)]
+struct ResponseCalls {
+    running: usize,
+    done: bool,
+}
+
+impl std::fmt::Debug for RealtimeToolRouter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RealtimeToolRouter")
+            .field("tools", &self.tools.names())
+            .finish()
+    }
+}
+
+impl RealtimeToolRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the function `definition`, called with the parsed arguments of the
+    /// model. Its result is sent back as the function output, and its error as
+    /// `{"error": "<error>"}`. A function of the same name is replaced.
+    ///
+    /// Only function definitions are routed. A [ToolDefinition::Mcp] is ignored, as
+    /// the server calls the tools of MCP servers itself.
+    pub fn tool<F, Fut, E>(mut self, definition: ToolDefinition, handler: F) -> Self
+    where
+        F: Fn(Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Value, E>> + Send + 'static,
+        E: Display,
+    {
+        if let ToolDefinition::Function { name, .. } = &definition {
+            self.tools.insert(name.clone(), definition, handler);
+        }
+        self
+    }
+
+    /// Definitions of the registered functions, for the `tools` of the session
+    pub fn tools(&self) -> Vec<ToolDefinition> {
+        self.tools.definitions()
+    }
+
+    /// Follow the function calls of the session with its `event`, running the
+    /// function of a call when its arguments are done. Replies are sent on `replies`.
+    ///
+    /// Returns whether the event is about function calls.
+    pub fn handle(&mut self, event: &ServerEvent, replies: &UnboundedSender<ClientEvent>) -> bool {
+        match event {
+            ServerEvent::ResponseOutputItemAdded(event) => {
+                let (Some(ItemType::FunctionCall), Some(call_id)) =
+                    (&event.item.r#type, &event.item.call_id)
+                else {
+                    return false;
+                };
+                self.calls.entry(call_id.clone()).or_default().name = event.item.name.clone();
+            }
+            ServerEvent::ResponseFunctionCallArgumentsDelta(event) => {
+                self.calls
+                    .entry(event.call_id.clone())
+                    .or_default()
+                    .arguments
+                    .push_str(&event.delta);
+            }
+            ServerEvent::ResponseFunctionCallArgumentsDone(event) => {
+                let pending = self.calls.remove(&event.call_id).unwrap_or_default();
+                let name = event.name.clone().or(pending.name).unwrap_or_default();
+                let arguments = if event.arguments.is_empty() {
+                    pending.arguments
+                } else {
+                    event.arguments.clone()
+                };
+
+                self.responses
+                    .lock()
+                    .unwrap()
+                    .entry(event.response_id.clone())
+                    .or_default()
+                    .running += 1;
+
+                let call = self.tools.call(&name, &arguments);
+                let (call_id, response_id) = (event.call_id.clone(), event.response_id.clone());
+                let (responses, replies) = (self.responses.clone(), replies.clone());
+                tokio::spawn(async move {
+                    let output = call.await.unwrap_or_else(|error| json!({ "error": error }));
+                    let _ = replies.send(output_event(call_id, &output));
+
+                    let mut responses = responses.lock().unwrap();
+                    if let Some(calls) = responses.get_mut(&response_id) {
+                        calls.running -= 1;
+                        if calls.running == 0 && calls.done {
+                            responses.remove(&response_id);
+                            let _ = replies.send(ResponseCreateEvent::default().into());
+                        }
+                    }
+                });
+            }
+            ServerEvent::ResponseDone(event) => {
+                let mut responses = self.responses.lock().unwrap();
+                let Some(calls) = responses.get_mut(&event.response.id) else {
+                    return false;
+                };
+                calls.done = true;
+                if calls.running == 0 {
+                    responses.remove(&event.response.id);
+                    let _ = replies.send(ResponseCreateEvent::default().into());
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+fn output_event(call_id: String, output: &Value) -> ClientEvent {
+    ConversationItemCreateEvent::from(Item {
+        r#type: Some(ItemType::FunctionCallOutput),
+        call_id: Some(call_id),
+        output: Some(output_text(output)),
+        ..Default::default()
+    })
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use super::*;
+    use crate::types::realtime::McpTool;
+
+    fn router() -> RealtimeToolRouter {
+        RealtimeToolRouter::new()
+            .tool(
+                ToolDefinition::Function {
+                    name: "get_weather".into(),
+                    description: "Weather of a location".into(),
+                    parameters: json!({"type": "object"}),
+                },
+                |args: Value| async move {
+                    match args["location"].as_str() {
+                        Some("Atlantis") => Err("no weather service there"),
+                        location => {
+                            // Finishes after the other calls
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            Ok(json!({"location": location, "temperature": 22}))
+                        }
+                    }
+                },
+            )
+            .tool(
+                ToolDefinition::Function {
+                    name: "get_time".into(),
+                    description: "Current time".into(),
+                    parameters: json!({"type": "object"}),
+                },
+                |_| async { Ok::<_, String>(json!("12:00")) },
+            )
+            // Not routed, calls of this name stay unknown
+            .tool(
+                McpTool::new("get_news", "https://example.com/mcp").into(),
+                |_| async { Ok::<_, String>(json!("news")) },
+            )
+    }
+
+    fn event(value: Value) -> ServerEvent {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn function_call(call_id: &str, name: &str, deltas: &[&str]) -> Vec<ServerEvent> {
+        let mut events = vec![event(json!({
+            "type": "response.output_item.added",
+            "event_id": "event_1",
+            "response_id": "resp_1",
+            "output_index": 0,
+            "item": {"id": format!("item_{call_id}"), "type": "function_call", "call_id": call_id, "name": name}
+        }))];
+        for delta in deltas {
+            events.push(event(json!({
+                "type": "response.function_call_arguments.delta",
+                "event_id": "event_2",
+                "response_id": "resp_1",
+                "item_id": format!("item_{call_id}"),
+                "output_index": 0,
+                "call_id": call_id,
+                "delta": delta
+            })));
+        }
+        events.push(event(json!({
+            "type": "response.function_call_arguments.done",
+            "event_id": "event_3",
+            "response_id": "resp_1",
+            "item_id": format!("item_{call_id}"),
+            "output_index": 0,
+            "call_id": call_id,
+            "arguments": ""
+        })));
+        events
+    }
+
+    fn response_done(response_id: &str) -> ServerEvent {
+        event(json!({
+            "type": "response.done",
+            "event_id": "event_4",
+            "response": {
+                "id": response_id,
+                "object": "realtime.response",
+                "status": "completed",
+                "status_details": null,
+                "output": [],
+                "usage": null
+            }
+        }))
+    }
+
+    /// Outputs by call id, and whether a response was created after all of them
+    fn outputs(replies: Vec<ClientEvent>) -> (HashMap<String, String>, bool) {
+        let (last, outputs) = replies.split_last().unwrap();
+        let outputs = outputs
+            .iter()
+            .map(|reply| match reply {
+                ClientEvent::ConversationItemCreate(event) => (
+                    event.item.call_id.clone().unwrap(),
+                    event.item.output.clone().unwrap(),
+                ),
+                reply => panic!("unexpected {reply:?}"),
+            })
+            .collect();
+        (outputs, matches!(last, ClientEvent::ResponseCreate(_)))
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_calls() {
+        let mut router = router();
+        assert_eq!(router.tools().len(), 2);
+        let (replies, mut replies_rx) = unbounded_channel();
+
+        let mut events = function_call("call_1", "get_weather", &["{\"loca", "tion\": \"Paris\"}"]);
+        events.extend(function_call(
+            "call_2",
+            "get_weather",
+            &["{\"location\": \"Atlantis\"}"],
+        ));
+        events.extend(function_call("call_3", "get_time", &[]));
+        events.extend(function_call("call_4", "get_news", &["{}"]));
+        events.extend(function_call("call_5", "get_time", &["{"]));
+        events.push(response_done("resp_1"));
+        for event in &events {
+            assert!(router.handle(event, &replies));
+        }
+        assert!(!router.handle(
+            &event(json!({"type": "input_audio_buffer.cleared", "event_id": "event_5"})),
+            &replies
+        ));
+        drop(replies);
+
+        let mut received = vec![];
+        while let Some(reply) = replies_rx.recv().await {
+            received.push(reply);
+        }
+        let (outputs, response_created) = outputs(received);
+
+        assert!(response_created);
+        assert_eq!(outputs.len(), 5);
+        assert_eq!(
+            serde_json::from_str::<Value>(&outputs["call_1"]).unwrap(),
+            json!({"location": "Paris", "temperature": 22})
+        );
+        assert_eq!(
+            outputs["call_2"],
+            json!({"error": "no weather service there"}).to_string()
+        );
+        assert_eq!(outputs["call_3"], "12:00");
+        assert_eq!(
+            outputs["call_4"],
+            json!({"error": "unknown tool get_news"}).to_string()
+        );
+        assert!(outputs["call_5"].contains("invalid JSON arguments"));
+    }
+
+    #[tokio::test]
+    async fn test_response_without_calls() {
+        let mut router = router();
+        let (replies, mut replies_rx) = unbounded_channel();
+
+        assert!(!router.handle(&response_done("resp_2"), &replies));
+        drop(replies);
+        assert!(replies_rx.recv().await.is_none());
+    }
+}
@@ -0,0 +1,299 @@
+use crate::types::realtime::{RateLimit, ServerEvent, Usage};
+
+/// Cumulative token counts of the responses of a realtime session, by modality.
+///
+/// Text and audio input tokens include the cached tokens.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RealtimeTokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub total_tokens: u64,
+    pub input_text_tokens: u64,
+    pub input_audio_tokens: u64,
+    pub cached_text_tokens: u64,
+    pub cached_audio_tokens: u64,
+    pub output_text_tokens: u64,
+    pub output_audio_tokens: u64,
+}
+
+impl RealtimeTokenUsage {
+    /// Add the usage of a response
+    pub fn add(&mut self, usage: &Usage) {
+        self.input_tokens += usage.input_tokens as u64;
+        self.output_tokens += usage.output_tokens as u64;
+        self.total_tokens += usage.total_tokens as u64;
+
+        if let Some(input) = &usage.input_token_details {
+            self.input_text_tokens += input.text_tokens as u64;
+            self.input_audio_tokens += input.audio_tokens as u64;
+            match &input.cached_tokens_details {
+                Some(cached) => {
+                    self.cached_text_tokens += cached.text_tokens as u64;
+                    self.cached_audio_tokens += cached.audio_tokens as u64;
+                }
+                // Without a breakdown, cached tokens are counted as text
+                None => self.cached_text_tokens += input.cached_tokens as u64,
+            }
+        }
+        if let Some(output) = &usage.output_token_details {
+            self.output_text_tokens += output.text_tokens as u64;
+            self.output_audio_tokens += output.audio_tokens as u64;
+        }
+    }
+}
+
+/// Prices of a realtime model, in USD per million tokens.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RealtimePricing {
+    pub text_input: f64,
+    pub cached_text_input: f64,
+    pub text_output: f64,
+    pub audio_input: f64,
+    pub cached_audio_input: f64,
+    pub audio_output: f64,
+}
+
+impl RealtimePricing {
+    /// Cost of `usage` in USD
+    pub fn cost(&self, usage: &RealtimeTokenUsage) -> f64 {
+        let tokens = [
+            (
+                usage
+                    .input_text_tokens
+                    .saturating_sub(usage.cached_text_tokens),
+                self.text_input,
+            ),
+            (usage.cached_text_tokens, self.cached_text_input),
+            (
+                usage
+                    .input_audio_tokens
+                    .saturating_sub(usage.cached_audio_tokens),
+                self.audio_input,
+            ),
+            (usage.cached_audio_tokens, self.cached_audio_input),
+            (usage.output_text_tokens, self.text_output),
+            (usage.output_audio_tokens, self.audio_output),
+        ];
+        tokens
+            .iter()
+            .map(|(tokens, price)| *tokens as f64 * price / 1_000_000.0)
+            .sum()
+    }
+}
+
+/// Accounts the token usage of a realtime session from its `response.done` events,
+/// and keeps the rate limits of its last `rate_limits.updated` event.
+///
+/// ```
+/// use async_openai::{types::realtime::ServerEvent, RealtimePricing, RealtimeUsageTracker};
+///
+/// let mut tracker = RealtimeUsageTracker::new();
+/// # let events: Vec<ServerEvent> = vec![];
+/// for event in &events {
+///     tracker.handle(event);
+/// }
+///
+/// let pricing = RealtimePricing {
+///     text_input: 4.0,
+///     cached_text_input: 0.4,
+///     text_output: 16.0,
+///     audio_input: 32.0,
+///     cached_audio_input: 0.4,
+///     audio_output: 64.0,
+/// };
+/// if tracker.estimate_cost(&pricing) > 5.0 {
+///     // End the session before it goes over budget
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RealtimeUsageTracker {
+    usage: RealtimeTokenUsage,
+    responses: u64,
+    rate_limits: Vec<RateLimit>,
+}
+
+impl RealtimeUsageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Account `event` of the session. Returns whether the event is about usage
+    /// or rate limits.
+    pub fn handle(&mut self, event: &ServerEvent) -> bool {
+        match event {
+            ServerEvent::ResponseDone(event) => {
+                self.responses += 1;
+                if let Some(usage) = &event.response.usage {
+                    self.usage.add(usage);
+                }
+                true
+            }
+            ServerEvent::RateLimitsUpdated(event) => {
+                self.rate_limits = event.rate_limits.clone();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Cumulative usage of the done responses
+    pub fn usage(&self) -> &RealtimeTokenUsage {
+        &self.usage
+    }
+
+    /// Number of done responses
+    pub fn responses(&self) -> u64 {
+        self.responses
+    }
+
+    /// Rate limits of the last `rate_limits.updated` event
+    pub fn rate_limits(&self) -> &[RateLimit] {
+        &self.rate_limits
+    }
+
+    /// Rate limit `name` of the last `rate_limits.updated` event, like "requests" or "tokens"
+    pub fn rate_limit(&self, name: &str) -> Option<&RateLimit> {
+        self.rate_limits.iter().find(|limit| limit.name == name)
+    }
+
+    /// Cost in USD of the cumulative usage
+    pub fn estimate_cost(&self, pricing: &RealtimePricing) -> f64 {
+        pricing.cost(&self.usage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn response_done(id: &str, usage: Value) -> ServerEvent {
+        serde_json::from_value(json!({
+            "type": "response.done",
+            "event_id": format!("event_{id}"),
+            "response": {
+                "id": id,
+                "object": "realtime.response",
+                "status": "completed",
+                "status_details": null,
+                "output": [],
+                "usage": usage
+            }
+        }))
+        .unwrap()
+    }
+
+    fn rate_limits(remaining: u32) -> ServerEvent {
+        serde_json::from_value(json!({
+            "type": "rate_limits.updated",
+            "event_id": "event_limits",
+            "rate_limits": [
+                {"name": "requests", "limit": 1000, "remaining": 999, "reset_seconds": 0.06},
+                {"name": "tokens", "limit": 50000, "remaining": remaining, "reset_seconds": 1.5}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_session_totals() {
+        let events = [
+            serde_json::from_value(
+                json!({"type": "response.created", "event_id": "event_0", "response": {
+                    "id": "resp_1", "object": "realtime.response", "status": "in_progress",
+                    "status_details": null, "output": [], "usage": null
+                }}),
+            )
+            .unwrap(),
+            response_done(
+                "resp_1",
+                json!({
+                    "total_tokens": 253,
+                    "input_tokens": 132,
+                    "output_tokens": 121,
+                    "input_token_details": {
+                        "text_tokens": 119,
+                        "audio_tokens": 13,
+                        "cached_tokens": 64,
+                        "cached_tokens_details": {"text_tokens": 64, "audio_tokens": 0}
+                    },
+                    "output_token_details": {"text_tokens": 30, "audio_tokens": 91}
+                }),
+            ),
+            rate_limits(49747),
+            response_done(
+                "resp_2",
+                json!({
+                    "total_tokens": 500,
+                    "input_tokens": 300,
+                    "output_tokens": 200,
+                    "input_token_details": {
+                        "text_tokens": 150,
+                        "audio_tokens": 150,
+                        "cached_tokens": 128,
+                        "cached_tokens_details": {"text_tokens": 64, "audio_tokens": 64}
+                    },
+                    "output_token_details": {"text_tokens": 50, "audio_tokens": 150}
+                }),
+            ),
+            // A cancelled response without usage
+            response_done("resp_3", Value::Null),
+            rate_limits(49247),
+        ];
+
+        let mut tracker = RealtimeUsageTracker::new();
+        let handled = events.iter().filter(|event| tracker.handle(event)).count();
+        assert_eq!(handled, 5);
+
+        assert_eq!(tracker.responses(), 3);
+        assert_eq!(
+            tracker.usage(),
+            &RealtimeTokenUsage {
+                input_tokens: 432,
+                output_tokens: 321,
+                total_tokens: 753,
+                input_text_tokens: 269,
+                input_audio_tokens: 163,
+                cached_text_tokens: 128,
+                cached_audio_tokens: 64,
+                output_text_tokens: 80,
+                output_audio_tokens: 241,
+            }
+        );
+        assert_eq!(tracker.rate_limits().len(), 2);
+        assert_eq!(tracker.rate_limit("tokens").unwrap().remaining, 49247);
+    }
+
+    #[test]
+    fn test_estimate_cost() {
+        let mut tracker = RealtimeUsageTracker::new();
+        tracker.handle(&response_done(
+            "resp_1",
+            json!({
+                "total_tokens": 3_000_000,
+                "input_tokens": 2_000_000,
+                "output_tokens": 1_000_000,
+                "input_token_details": {
+                    "text_tokens": 1_000_000,
+                    "audio_tokens": 1_000_000,
+                    "cached_tokens": 500_000
+                },
+                "output_token_details": {"text_tokens": 500_000, "audio_tokens": 500_000}
+            }),
+        ));
+        // Without a breakdown, the cached tokens are text tokens
+        assert_eq!(tracker.usage().cached_text_tokens, 500_000);
+
+        let pricing = RealtimePricing {
+            text_input: 4.0,
+            cached_text_input: 0.5,
+            text_output: 16.0,
+            audio_input: 32.0,
+            cached_audio_input: 0.5,
+            audio_output: 64.0,
+        };
+        // 0.5 * 4 + 0.5 * 0.5 + 1 * 32 + 0.5 * 16 + 0.5 * 64
+        assert_eq!(tracker.estimate_cost(&pricing), 74.25);
+    }
+}
@@ -1,12 +1,14 @@
+use futures::Stream;
 use serde::Serialize;
 
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::paginate,
     steps::Steps,
     types::{
         AssistantEventStream, AssistantStreamEvent, CreateRunRequest, ListRunsResponse,
-        ModifyRunRequest, RunObject, SubmitToolOutputsRunRequest,
+        ModifyRunRequest, Pagination, RunObject, SubmitToolOutputsRunRequest,
     },
     Client,
 };
@@ -28,11 +30,15 @@ impl<'c, C: Config> Runs<'c, C> {
     }
 
     ///  [Steps] API group
-    pub fn steps(&self, run_id: &str) -> Steps<C> {
+    pub fn steps(&self, run_id: &str) -> Steps<'_, C> {
         Steps::new(self.client, &self.thread_id, run_id)
     }
 
     /// Create a run.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.runs.create", skip_all)
+    )]
     pub async fn create(&self, request: CreateRunRequest) -> Result<RunObject, OpenAIError> {
         self.client
             .post(&format!("/threads/{}/runs", self.thread_id), request)
@@ -40,6 +46,10 @@ impl<'c, C: Config> Runs<'c, C> {
     }
 
     /// Create a run.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.runs.create_stream", skip_all)
+    )]
     pub async fn create_stream(
         &self,
         mut request: CreateRunRequest,
@@ -63,6 +73,10 @@ impl<'c, C: Config> Runs<'c, C> {
     }
 
     /// Retrieves a run.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.runs.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, run_id: &str) -> Result<RunObject, OpenAIError> {
         self.client
             .get(&format!("/threads/{}/runs/{run_id}", self.thread_id))
@@ -70,6 +84,10 @@ impl<'c, C: Config> Runs<'c, C> {
     }
 
     /// Modifies a run.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.runs.update", skip_all)
+    )]
     pub async fn update(
         &self,
         run_id: &str,
@@ -84,6 +102,10 @@ impl<'c, C: Config> Runs<'c, C> {
     }
 
     /// Returns a list of runs belonging to a thread.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.runs.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListRunsResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -93,7 +115,29 @@ impl<'c, C: Config> Runs<'c, C> {
             .await
     }
 
+    /// Stream all the runs of the thread, fetching the pages of [Runs::list] as the stream
+    /// is consumed. `query` holds the other parameters of [Runs::list].
+    pub fn list_all<'a, Q>(
+        &'a self,
+        query: &'a Q,
+        pagination: Pagination,
+    ) -> impl Stream<Item = Result<RunObject, OpenAIError>> + 'a
+    where
+        Q: Serialize + ?Sized,
+    {
+        paginate::<_, _, ListRunsResponse>(
+            self.client,
+            format!("/threads/{}/runs", self.thread_id),
+            query,
+            pagination,
+        )
+    }
+
     /// When a run has the status: "requires_action" and required_action.type is submit_tool_outputs, this endpoint can be used to submit the outputs from the tool calls once they're all completed. All outputs must be submitted in a single request.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.runs.submit_tool_outputs", skip_all)
+    )]
     pub async fn submit_tool_outputs(
         &self,
         run_id: &str,
@@ -110,6 +154,10 @@ impl<'c, C: Config> Runs<'c, C> {
             .await
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.runs.submit_tool_outputs_stream", skip_all)
+    )]
     pub async fn submit_tool_outputs_stream(
         &self,
         run_id: &str,
@@ -137,6 +185,10 @@ impl<'c, C: Config> Runs<'c, C> {
     }
 
     /// Cancels a run that is `in_progress`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.runs.cancel", skip_all)
+    )]
     pub async fn cancel(&self, run_id: &str) -> Result<RunObject, OpenAIError> {
         self.client
             .post(
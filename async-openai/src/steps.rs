@@ -1,9 +1,11 @@
+use futures::Stream;
 use serde::Serialize;
 
 use crate::{
     config::Config,
     error::OpenAIError,
-    types::{ListRunStepsResponse, RunStepObject},
+    pagination::paginate,
+    types::{ListRunStepsResponse, Pagination, RunStepObject},
     Client,
 };
 
@@ -24,6 +26,10 @@ impl<'c, C: Config> Steps<'c, C> {
     }
 
     /// Retrieves a run step.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.steps.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, step_id: &str) -> Result<RunStepObject, OpenAIError> {
         self.client
             .get(&format!(
@@ -34,6 +40,10 @@ impl<'c, C: Config> Steps<'c, C> {
     }
 
     /// Returns a list of run steps belonging to a run.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.steps.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListRunStepsResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -45,4 +55,22 @@ impl<'c, C: Config> Steps<'c, C> {
             )
             .await
     }
+
+    /// Stream all the steps of the run, fetching the pages of [Steps::list] as the stream
+    /// is consumed. `query` holds the other parameters of [Steps::list].
+    pub fn list_all<'a, Q>(
+        &'a self,
+        query: &'a Q,
+        pagination: Pagination,
+    ) -> impl Stream<Item = Result<RunStepObject, OpenAIError>> + 'a
+    where
+        Q: Serialize + ?Sized,
+    {
+        paginate::<_, _, ListRunStepsResponse>(
+            self.client,
+            format!("/threads/{}/runs/{}/steps", self.thread_id, self.run_id),
+            query,
+            pagination,
+        )
+    }
 }
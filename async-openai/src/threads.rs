@@ -21,16 +21,20 @@ impl<'c, C: Config> Threads<'c, C> {
     }
 
     /// Call [Messages] group API to manage message in [thread_id] thread.
-    pub fn messages(&self, thread_id: &str) -> Messages<C> {
+    pub fn messages(&self, thread_id: &str) -> Messages<'_, C> {
         Messages::new(self.client, thread_id)
     }
 
     /// Call [Runs] group API to manage runs in [thread_id] thread.
-    pub fn runs(&self, thread_id: &str) -> Runs<C> {
+    pub fn runs(&self, thread_id: &str) -> Runs<'_, C> {
         Runs::new(self.client, thread_id)
     }
 
     /// Create a thread and run it in one request.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.threads.create_and_run", skip_all)
+    )]
     pub async fn create_and_run(
         &self,
         request: CreateThreadAndRunRequest,
@@ -39,6 +43,10 @@ impl<'c, C: Config> Threads<'c, C> {
     }
 
     /// Create a thread and run it in one request (streaming).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.threads.create_and_run_stream", skip_all)
+    )]
     pub async fn create_and_run_stream(
         &self,
         mut request: CreateThreadAndRunRequest,
@@ -58,16 +66,28 @@ impl<'c, C: Config> Threads<'c, C> {
     }
 
     /// Create a thread.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.threads.create", skip_all)
+    )]
     pub async fn create(&self, request: CreateThreadRequest) -> Result<ThreadObject, OpenAIError> {
         self.client.post("/threads", request).await
     }
 
     /// Retrieves a thread.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.threads.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, thread_id: &str) -> Result<ThreadObject, OpenAIError> {
         self.client.get(&format!("/threads/{thread_id}")).await
     }
 
     /// Modifies a thread.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.threads.update", skip_all)
+    )]
     pub async fn update(
         &self,
         thread_id: &str,
@@ -79,6 +99,10 @@ impl<'c, C: Config> Threads<'c, C> {
     }
 
     /// Delete a thread.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.threads.delete", skip_all)
+    )]
     pub async fn delete(&self, thread_id: &str) -> Result<DeleteThreadResponse, OpenAIError> {
         self.client.delete(&format!("/threads/{thread_id}")).await
     }
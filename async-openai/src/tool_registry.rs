@@ -0,0 +1,93 @@
+use std::{fmt::Display, future::Future, pin::Pin, sync::Arc};
+
+use serde_json::Value;
+
+pub(crate) type ToolFuture = Pin<Box<dyn Future<Output = Result<Value, String>> + Send>>;
+type ToolHandler = Arc<dyn Fn(Value) -> ToolFuture + Send + Sync>;
+
+/// Functions called by name for the tool calls of the model, each with the definition
+/// `T` of the tool sent to the model. Shared by [crate::ChatToolRunner] and
+/// [crate::RealtimeToolRouter].
+pub(crate) struct ToolRegistry<T> {
+    tools: Vec<(String, T, ToolHandler)>,
+}
+
+impl<T> Default for ToolRegistry<T> {
+    fn default() -> Self {
+        Self { tools: vec![] }
+    }
+}
+
+impl<T: Clone> Clone for ToolRegistry<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tools: self.tools.clone(),
+        }
+    }
+}
+
+impl<T: Clone> ToolRegistry<T> {
+    /// Register the function `name`, replacing a function of the same name. Errors of
+    /// `handler` are turned into strings.
+    pub(crate) fn insert<F, Fut, E>(&mut self, name: String, definition: T, handler: F)
+    where
+        F: Fn(Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Value, E>> + Send + 'static,
+        E: Display,
+    {
+        let handler: ToolHandler = Arc::new(move |arguments| {
+            let future = handler(arguments);
+            Box::pin(async move { future.await.map_err(|e| e.to_string()) })
+        });
+
+        self.tools.retain(|(registered, ..)| *registered != name);
+        self.tools.push((name, definition, handler));
+    }
+
+    /// Definitions of the registered functions, in the order of registration
+    pub(crate) fn definitions(&self) -> Vec<T> {
+        self.tools
+            .iter()
+            .map(|(_, definition, _)| definition.clone())
+            .collect()
+    }
+
+    pub(crate) fn names(&self) -> Vec<&str> {
+        self.tools.iter().map(|(name, ..)| name.as_str()).collect()
+    }
+
+    /// Call the function `name` with the JSON `arguments` of the model. Unknown
+    /// functions and invalid arguments fail without calling any function.
+    pub(crate) fn call(&self, name: &str, arguments: &str) -> ToolFuture {
+        let Some((.., handler)) = self
+            .tools
+            .iter()
+            .find(|(registered, ..)| registered == name)
+        else {
+            let error = format!("unknown tool {name}");
+            return Box::pin(async move { Err(error) });
+        };
+
+        // Functions without parameters may be called with empty arguments
+        let arguments = match arguments.trim() {
+            "" => Value::Object(Default::default()),
+            arguments => match serde_json::from_str(arguments) {
+                Ok(arguments) => arguments,
+                Err(e) => {
+                    let error = format!("invalid JSON arguments: {e}");
+                    return Box::pin(async move { Err(error) });
+                }
+            },
+        };
+
+        handler(arguments)
+    }
+}
+
+/// Text of a tool output sent back to the model: strings as is, other values as JSON
+pub(crate) fn output_text(output: &Value) -> String {
+    match output {
+        Value::String(text) => text.clone(),
+        output => output.to_string(),
+    }
+}
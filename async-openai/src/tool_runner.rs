@@ -0,0 +1,221 @@
+use std::{fmt::Display, future::Future};
+
+use futures::{future::join_all, StreamExt};
+use serde_json::{json, Value};
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    tool_registry::{output_text, ToolRegistry},
+    types::{
+        ChatCompletionMessageToolCall, ChatCompletionRequestMessage,
+        ChatCompletionRequestToolMessage, ChatCompletionStreamAccumulator, ChatCompletionTool,
+        ChatCompletionToolType, CreateChatCompletionRequest, CreateChatCompletionResponse,
+        FunctionObject,
+    },
+    Chat,
+};
+
+/// Calls the functions registered with [ChatToolRunner::tool] when the model asks for
+/// them, and sends their results back until the model answers without tool calls.
+///
+/// The tool calls of a turn run concurrently. A failing tool, an unknown tool or
+/// invalid arguments don't stop the run: the model gets `{"error": "..."}` as output.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), async_openai::error::OpenAIError> {
+/// use async_openai::{
+///     types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs, FunctionObjectArgs},
+///     ChatToolRunner, Client,
+/// };
+/// use serde_json::{json, Value};
+///
+/// let runner = ChatToolRunner::new().tool(
+///     FunctionObjectArgs::default()
+///         .name("get_current_weather")
+///         .parameters(json!({
+///             "type": "object",
+///             "properties": {"location": {"type": "string"}},
+///             "required": ["location"],
+///         }))
+///         .build()?,
+///     |args: Value| async move {
+///         Ok::<_, String>(json!({"location": args["location"], "temperature": 22}))
+///     },
+/// );
+///
+/// let request = CreateChatCompletionRequestArgs::default()
+///     .model("gpt-4o")
+///     .messages([ChatCompletionRequestUserMessageArgs::default()
+///         .content("What's the weather like in Boston and Atlanta?")
+///         .build()?
+///         .into()])
+///     .build()?;
+///
+/// let client = Client::new();
+/// let run = runner.run(&client.chat(), request).await?;
+/// println!("{:?}", run.response.choices[0].message.content);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ChatToolRunner {
+    tools: ToolRegistry<ChatCompletionTool>,
+    max_iterations: usize,
+}
+
+/// A tool call of the model and the output sent back for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCallRecord {
+    pub call: ChatCompletionMessageToolCall,
+    pub output: Value,
+    /// The output is an `{"error": "..."}` object, not a result of the tool
+    pub is_error: bool,
+}
+
+/// Result of [ChatToolRunner::run]
+#[derive(Debug, Clone)]
+pub struct ChatToolRun {
+    /// The last response, the answer of the model unless `max_iterations_reached`
+    pub response: CreateChatCompletionResponse,
+    /// The conversation, with the tool calls and outputs, and the message of `response`
+    pub messages: Vec<ChatCompletionRequestMessage>,
+    /// Tool calls in the order of the conversation
+    pub calls: Vec<ToolCallRecord>,
+    /// The model still asked for tools in the last response allowed by
+    /// [ChatToolRunner::with_max_iterations], these calls were not run.
+    pub max_iterations_reached: bool,
+}
+
+impl Default for ChatToolRunner {
+    fn default() -> Self {
+        Self {
+            tools: Default::default(),
+            max_iterations: 10,
+        }
+    }
+}
+
+impl std::fmt::Debug for ChatToolRunner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChatToolRunner")
+            .field("tools", &self.tools.names())
+            .field("max_iterations", &self.max_iterations)
+            .finish()
+    }
+}
+
+impl ChatToolRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum number of requests of a run, 10 by default
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations.max(1);
+        self
+    }
+
+    /// Register the function `definition`, called with the parsed arguments of the
+    /// model. Its result is sent back as the tool output, and its error as
+    /// `{"error": "<error>"}`. A function of the same name is replaced.
+    pub fn tool<F, Fut, E>(mut self, definition: FunctionObject, handler: F) -> Self
+    where
+        F: Fn(Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Value, E>> + Send + 'static,
+        E: Display,
+    {
+        let name = definition.name.clone();
+        let tool = ChatCompletionTool {
+            r#type: ChatCompletionToolType::Function,
+            function: definition,
+        };
+
+        self.tools.insert(name, tool, handler);
+        self
+    }
+
+    /// Definitions of the registered functions, sent as `tools` of the requests
+    pub fn tools(&self) -> Vec<ChatCompletionTool> {
+        self.tools.definitions()
+    }
+
+    /// Send `request` and run the tools called by the model, until it answers
+    /// without tool calls or [ChatToolRunner::with_max_iterations] requests are sent.
+    ///
+    /// The `tools` of the request are the registered tools when not set. With
+    /// `stream: true`, each response is streamed and reassembled with
+    /// [ChatCompletionStreamAccumulator]. Only the first choice is used.
+    pub async fn run<C: Config>(
+        &self,
+        chat: &Chat<'_, C>,
+        mut request: CreateChatCompletionRequest,
+    ) -> Result<ChatToolRun, OpenAIError> {
+        if request.tools.is_none() {
+            request.tools = Some(self.tools());
+        }
+
+        let mut calls = vec![];
+        let mut iteration = 0;
+        loop {
+            iteration += 1;
+            let response = if request.stream == Some(true) {
+                let mut stream = chat.create_stream(request.clone()).await?;
+                let mut accumulator = ChatCompletionStreamAccumulator::new();
+                while let Some(chunk) = stream.next().await {
+                    accumulator.push(chunk?);
+                }
+                accumulator.finish()
+            } else {
+                chat.create(request.clone()).await?
+            };
+
+            let Some(message) = response
+                .choices
+                .first()
+                .map(|choice| choice.message.clone())
+            else {
+                return Ok(ChatToolRun {
+                    response,
+                    messages: request.messages,
+                    calls,
+                    max_iterations_reached: false,
+                });
+            };
+            let tool_calls = message.tool_calls.clone().unwrap_or_default();
+            request.messages.push(message.into());
+
+            if tool_calls.is_empty() || iteration >= self.max_iterations {
+                return Ok(ChatToolRun {
+                    response,
+                    messages: request.messages,
+                    calls,
+                    max_iterations_reached: !tool_calls.is_empty(),
+                });
+            }
+
+            let outputs = join_all(tool_calls.iter().map(|call| {
+                self.tools
+                    .call(&call.function.name, &call.function.arguments)
+            }))
+            .await;
+            for (call, output) in tool_calls.into_iter().zip(outputs) {
+                let is_error = output.is_err();
+                let output = output.unwrap_or_else(|error| json!({ "error": error }));
+
+                request.messages.push(
+                    ChatCompletionRequestToolMessage {
+                        content: output_text(&output).into(),
+                        tool_call_id: call.id.clone(),
+                    }
+                    .into(),
+                );
+                calls.push(ToolCallRecord {
+                    call,
+                    output,
+                    is_error,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Spans of HTTP requests, enabled with the `tracing` feature.
+//!
+//! API group methods open a span named after the group and operation, like
+//! `openai.chat.create`. Each HTTP attempt gets an `openai.http` child span, and each
+//! stream an `openai.stream` child span which stays open until the stream ends.
+//! Request and response bodies and API keys are never recorded.
+//!
+//! Without the feature [RequestSpan] is zero sized and all its methods are no-ops.
+use std::future::Future;
+
+use reqwest::{header::HeaderMap, Method, StatusCode};
+use serde::Serialize;
+
+#[cfg(feature = "tracing")]
+use tokio::time::Instant;
+#[cfg(feature = "tracing")]
+use tracing::{field::Empty, Instrument};
+
+/// Request id header set by OpenAI on every response
+#[cfg(feature = "tracing")]
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+#[derive(Debug, Clone)]
+pub(crate) struct RequestSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    started: Instant,
+}
+
+#[cfg(feature = "tracing")]
+#[derive(serde::Deserialize)]
+struct Model {
+    model: Option<String>,
+}
+
+#[cfg(feature = "tracing")]
+fn model_of(body: &[u8]) -> Option<String> {
+    serde_json::from_slice::<Model>(body).ok()?.model
+}
+
+impl RequestSpan {
+    /// Span of a single HTTP attempt
+    #[allow(unused_variables)]
+    pub(crate) fn http(request: &reqwest::Request) -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "openai.http",
+                http.request.method = %request.method(),
+                url.path = request.url().path(),
+                model = request
+                    .body()
+                    .and_then(|body| body.as_bytes())
+                    .and_then(model_of),
+                http.status_code = Empty,
+                latency_ms = Empty,
+                openai.request_id = Empty,
+            ),
+            #[cfg(feature = "tracing")]
+            started: Instant::now(),
+        }
+    }
+
+    /// Span of a server-sent events stream
+    #[allow(unused_variables)]
+    pub(crate) fn stream<I: Serialize>(method: Method, path: &str, request: Option<&I>) -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "openai.stream",
+                http.request.method = %method,
+                url.path = path,
+                model = request
+                    .and_then(|request| serde_json::to_vec(request).ok())
+                    .and_then(|body| model_of(&body)),
+                latency_ms = Empty,
+                openai.events = Empty,
+            ),
+            #[cfg(feature = "tracing")]
+            started: Instant::now(),
+        }
+    }
+
+    /// Record the status, latency and request id of the response
+    #[allow(unused_variables)]
+    pub(crate) fn record_response(&self, status: StatusCode, headers: &HeaderMap) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("http.status_code", status.as_u16());
+            self.span
+                .record("latency_ms", self.started.elapsed().as_millis() as u64);
+            if let Some(request_id) = headers
+                .get(REQUEST_ID_HEADER)
+                .and_then(|value| value.to_str().ok())
+            {
+                self.span.record("openai.request_id", request_id);
+            }
+        }
+    }
+
+    /// Record the number of events received once a stream ends
+    #[allow(unused_variables)]
+    pub(crate) fn record_events(&self, events: usize) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("openai.events", events);
+            self.span
+                .record("latency_ms", self.started.elapsed().as_millis() as u64);
+        }
+    }
+
+    /// Run `future` in the span
+    #[cfg(feature = "tracing")]
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> tracing::instrument::Instrumented<F> {
+        future.instrument(self.span.clone())
+    }
+
+    /// Run `future` in the span
+    #[cfg(not(feature = "tracing"))]
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> F {
+        future
+    }
+}
@@ -0,0 +1,71 @@
+//! Pluggable HTTP transport, see [crate::Client::with_transport].
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::{future::BoxFuture, stream::BoxStream, StreamExt};
+
+use crate::error::{map_error_response, OpenAIError};
+
+/// Sends HTTP requests built by [crate::Client].
+///
+/// By default requests are sent with the [reqwest::Client] of the client. A custom
+/// transport can be used to serve canned responses in tests without an HTTP server;
+/// a [reqwest::Response] can be created from an `http::Response`.
+pub trait HttpTransport: Send + Sync {
+    /// Send the request and return the response, whatever its status code.
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, Result<reqwest::Response, OpenAIError>>;
+
+    /// Send the request and return the body as a stream of bytes,
+    /// used for server-sent events.
+    ///
+    /// Default implementation calls [HttpTransport::execute] and returns
+    /// [OpenAIError::ApiError] (or [OpenAIError::RateLimited] for status 429, or
+    /// [OpenAIError::HttpError] when the body is not an error object)
+    /// when the response status is not a success.
+    fn execute_stream(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, Result<BoxStream<'static, Result<Bytes, OpenAIError>>, OpenAIError>> {
+        Box::pin(async move {
+            let response = self.execute(request).await?;
+            let status = response.status();
+
+            if !status.is_success() {
+                let headers = response.headers().clone();
+                let bytes = response.bytes().await?;
+                return Err(map_error_response(status, &headers, &bytes));
+            }
+
+            Ok(response
+                .bytes_stream()
+                .map(|chunk| chunk.map_err(OpenAIError::Reqwest))
+                .boxed())
+        })
+    }
+}
+
+impl HttpTransport for reqwest::Client {
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, Result<reqwest::Response, OpenAIError>> {
+        Box::pin(async move {
+            reqwest::Client::execute(self, request)
+                .await
+                .map_err(OpenAIError::Reqwest)
+        })
+    }
+}
+
+/// Cloneable handle to a transport, shared by clones of a client
+#[derive(Clone)]
+pub(crate) struct Transport(pub(crate) Arc<dyn HttpTransport>);
+
+impl std::fmt::Debug for Transport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Transport")
+    }
+}
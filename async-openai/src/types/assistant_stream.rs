@@ -3,7 +3,7 @@ use std::pin::Pin;
 use futures::Stream;
 use serde::Deserialize;
 
-use crate::error::{map_deserialization_error, ApiError, OpenAIError};
+use crate::error::{deserialize, ApiError, OpenAIError};
 
 use super::{
     MessageDeltaObject, MessageObject, RunObject, RunStepDeltaObject, RunStepObject, ThreadObject,
@@ -108,6 +108,14 @@ pub enum AssistantStreamEvent {
     /// Occurs when a stream ends.
     #[serde(rename = "done")]
     Done(String),
+    /// An event unknown to this crate, like an event added to the API after its release.
+    /// `data` is a string when it is not JSON.
+    #[serde(untagged)]
+    Other {
+        #[serde(rename = "event")]
+        event_type: String,
+        data: serde_json::Value,
+    },
 }
 
 pub type AssistantEventStream =
@@ -117,99 +125,62 @@ impl TryFrom<eventsource_stream::Event> for AssistantStreamEvent {
     type Error = OpenAIError;
     fn try_from(value: eventsource_stream::Event) -> Result<Self, Self::Error> {
         match value.event.as_str() {
-            "thread.created" => serde_json::from_str::<ThreadObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.created" => deserialize::<ThreadObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::TreadCreated),
-            "thread.run.created" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.run.created" => deserialize::<RunObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadRunCreated),
-            "thread.run.queued" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.run.queued" => deserialize::<RunObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadRunQueued),
-            "thread.run.in_progress" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.run.in_progress" => deserialize::<RunObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadRunInProgress),
-            "thread.run.requires_action" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.run.requires_action" => deserialize::<RunObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadRunRequiresAction),
-            "thread.run.completed" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.run.completed" => deserialize::<RunObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadRunCompleted),
-            "thread.run.incomplete" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.run.incomplete" => deserialize::<RunObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadRunIncomplete),
-            "thread.run.failed" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.run.failed" => deserialize::<RunObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadRunFailed),
-            "thread.run.cancelling" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.run.cancelling" => deserialize::<RunObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadRunCancelling),
-            "thread.run.cancelled" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.run.cancelled" => deserialize::<RunObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadRunCancelled),
-            "thread.run.expired" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.run.expired" => deserialize::<RunObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadRunExpired),
-            "thread.run.step.created" => serde_json::from_str::<RunStepObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.run.step.created" => deserialize::<RunStepObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadRunStepCreated),
-            "thread.run.step.in_progress" => {
-                serde_json::from_str::<RunStepObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                    .map(AssistantStreamEvent::ThreadRunStepInProgress)
-            }
-            "thread.run.step.delta" => {
-                serde_json::from_str::<RunStepDeltaObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                    .map(AssistantStreamEvent::ThreadRunStepDelta)
-            }
-            "thread.run.step.completed" => {
-                serde_json::from_str::<RunStepObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                    .map(AssistantStreamEvent::ThreadRunStepCompleted)
-            }
-            "thread.run.step.failed" => serde_json::from_str::<RunStepObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.run.step.in_progress" => deserialize::<RunStepObject>(value.data.as_bytes())
+                .map(AssistantStreamEvent::ThreadRunStepInProgress),
+            "thread.run.step.delta" => deserialize::<RunStepDeltaObject>(value.data.as_bytes())
+                .map(AssistantStreamEvent::ThreadRunStepDelta),
+            "thread.run.step.completed" => deserialize::<RunStepObject>(value.data.as_bytes())
+                .map(AssistantStreamEvent::ThreadRunStepCompleted),
+            "thread.run.step.failed" => deserialize::<RunStepObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadRunStepFailed),
-            "thread.run.step.cancelled" => {
-                serde_json::from_str::<RunStepObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                    .map(AssistantStreamEvent::ThreadRunStepCancelled)
-            }
-            "thread.run.step.expired" => serde_json::from_str::<RunStepObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.run.step.cancelled" => deserialize::<RunStepObject>(value.data.as_bytes())
+                .map(AssistantStreamEvent::ThreadRunStepCancelled),
+            "thread.run.step.expired" => deserialize::<RunStepObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadRunStepExpired),
-            "thread.message.created" => serde_json::from_str::<MessageObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "thread.message.created" => deserialize::<MessageObject>(value.data.as_bytes())
                 .map(AssistantStreamEvent::ThreadMessageCreated),
-            "thread.message.in_progress" => {
-                serde_json::from_str::<MessageObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                    .map(AssistantStreamEvent::ThreadMessageInProgress)
-            }
-            "thread.message.delta" => {
-                serde_json::from_str::<MessageDeltaObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                    .map(AssistantStreamEvent::ThreadMessageDelta)
-            }
-            "thread.message.completed" => {
-                serde_json::from_str::<MessageObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                    .map(AssistantStreamEvent::ThreadMessageCompleted)
-            }
-            "thread.message.incomplete" => {
-                serde_json::from_str::<MessageObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                    .map(AssistantStreamEvent::ThreadMessageIncomplete)
+            "thread.message.in_progress" => deserialize::<MessageObject>(value.data.as_bytes())
+                .map(AssistantStreamEvent::ThreadMessageInProgress),
+            "thread.message.delta" => deserialize::<MessageDeltaObject>(value.data.as_bytes())
+                .map(AssistantStreamEvent::ThreadMessageDelta),
+            "thread.message.completed" => deserialize::<MessageObject>(value.data.as_bytes())
+                .map(AssistantStreamEvent::ThreadMessageCompleted),
+            "thread.message.incomplete" => deserialize::<MessageObject>(value.data.as_bytes())
+                .map(AssistantStreamEvent::ThreadMessageIncomplete),
+            "error" => {
+                deserialize::<ApiError>(value.data.as_bytes()).map(AssistantStreamEvent::ErrorEvent)
             }
-            "error" => serde_json::from_str::<ApiError>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ErrorEvent),
             "done" => Ok(AssistantStreamEvent::Done(value.data)),
 
-            _ => Err(OpenAIError::StreamError(
-                "Unrecognized event: {value:?#}".into(),
-            )),
+            _ => Ok(AssistantStreamEvent::Other {
+                data: serde_json::from_str(&value.data)
+                    .unwrap_or(serde_json::Value::String(value.data)),
+                event_type: value.event,
+            }),
         }
     }
 }
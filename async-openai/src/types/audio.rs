@@ -1,5 +1,9 @@
+use std::pin::Pin;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
 use bytes::Bytes;
 use derive_builder::Builder;
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
 use super::InputSource;
@@ -19,6 +23,8 @@ pub enum AudioResponseFormat {
     Srt,
     VerboseJson,
     Vtt,
+    /// Segments of the transcript labeled by speaker, only with `gpt-4o-transcribe-diarize`
+    DiarizedJson,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
@@ -33,17 +39,35 @@ pub enum SpeechResponseFormat {
     Wav,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SpeechStreamFormat {
+    /// Server-sent events of base64 audio chunks, not supported by `tts-1` and `tts-1-hd`
+    Sse,
+    /// The audio file, sent in chunks as it is generated
+    Audio,
+}
+
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum Voice {
     #[default]
     Alloy,
+    Ash,
+    Ballad,
+    Coral,
     Echo,
     Fable,
     Onyx,
     Nova,
+    Sage,
     Shimmer,
+    Verse,
+    Marin,
+    Cedar,
+    #[serde(untagged)]
+    Other(String),
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
@@ -53,6 +77,8 @@ pub enum SpeechModel {
     Tts1,
     #[serde(rename = "tts-1-hd")]
     Tts1Hd,
+    #[serde(rename = "gpt-4o-mini-tts")]
+    Gpt4oMiniTts,
     #[serde(untagged)]
     Other(String),
 }
@@ -65,12 +91,40 @@ pub enum TimestampGranularity {
     Segment,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptionInclude {
+    /// The log probabilities of the tokens of the transcript, only with the `json` response format
+    /// and the `gpt-4o-transcribe` and `gpt-4o-mini-transcribe` models.
+    Logprobs,
+}
+
+/// A speaker of the audio of a diarized transcription, labeling its segments with `name`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct KnownSpeaker {
+    /// The label of the segments of the speaker, like `agent` or `customer`.
+    pub name: String,
+
+    /// A sample of 2 to 10 seconds of the voice of the speaker, in one of the formats of
+    /// [CreateTranscriptionRequest::file].
+    pub reference_audio: AudioInput,
+}
+
+impl KnownSpeaker {
+    pub fn new(name: impl Into<String>, reference_audio: impl Into<AudioInput>) -> Self {
+        Self {
+            name: name.into(),
+            reference_audio: reference_audio.into(),
+        }
+    }
+}
+
 #[derive(Clone, Default, Debug, Builder, PartialEq)]
 #[builder(name = "CreateTranscriptionRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateTranscriptionRequest {
     /// The audio file to transcribe, in one of these formats: mp3, mp4, mpeg, mpga, m4a, wav, or webm.
     pub file: AudioInput,
@@ -92,6 +146,38 @@ pub struct CreateTranscriptionRequest {
 
     /// The timestamp granularities to populate for this transcription. `response_format` must be set `verbose_json` to use timestamp granularities. Either or both of these options are supported: `word`, or `segment`. Note: There is no additional latency for segment timestamps, but generating word timestamps incurs additional latency.
     pub timestamp_granularities: Option<Vec<TimestampGranularity>>,
+
+    /// Additional information to include in the response. `logprobs` returns the log
+    /// probabilities of the tokens of the transcript, in [CreateTranscriptionResponseJson::logprobs].
+    pub include: Option<Vec<TranscriptionInclude>>,
+
+    /// Up to 4 speakers of the audio, labeling the segments of their voice in
+    /// [CreateTranscriptionResponseDiarizedJson]. Only with `gpt-4o-transcribe-diarize`.
+    pub known_speakers: Option<Vec<KnownSpeaker>>,
+}
+
+/// Maximum number of [CreateTranscriptionRequest::known_speakers]
+pub const MAX_KNOWN_SPEAKERS: usize = 4;
+
+impl CreateTranscriptionRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(known_speakers)) = &self.known_speakers {
+            if known_speakers.len() > MAX_KNOWN_SPEAKERS {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "at most {MAX_KNOWN_SPEAKERS} known speakers are supported, got {}",
+                    known_speakers.len()
+                )));
+            }
+
+            if known_speakers.iter().any(|speaker| speaker.name.is_empty()) {
+                return Err(OpenAIError::InvalidArgument(
+                    "known speakers must have a name".into(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Represents a transcription response returned by model, based on the provided
@@ -100,6 +186,63 @@ pub struct CreateTranscriptionRequest {
 pub struct CreateTranscriptionResponseJson {
     /// The transcribed text.
     pub text: String,
+
+    /// The language of the input audio, only returned by some models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// The duration of the input audio in seconds, only returned by some models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f32>,
+
+    /// The log probabilities of the tokens of the transcript, when the request
+    /// includes [TranscriptionInclude::Logprobs].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<Vec<TranscriptionLogprob>>,
+}
+
+/// Transcription response with the `diarized_json` response format.
+#[derive(Debug, Deserialize, Clone, Serialize)]
+pub struct CreateTranscriptionResponseDiarizedJson {
+    /// The duration of the input audio, in seconds.
+    pub duration: f32,
+
+    /// The transcribed text.
+    pub text: String,
+
+    /// Segments of the transcript, by speaker.
+    pub segments: Vec<TranscriptionDiarizedSegment>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct TranscriptionDiarizedSegment {
+    /// Unique identifier of the segment.
+    pub id: String,
+
+    /// Start time of the segment in seconds.
+    pub start: f32,
+
+    /// End time of the segment in seconds.
+    pub end: f32,
+
+    /// Text content of the segment.
+    pub text: String,
+
+    /// The [KnownSpeaker::name] of the speaker of the segment, or a label like `A`
+    /// for other speakers.
+    pub speaker: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct TranscriptionLogprob {
+    /// The token of the transcript.
+    pub token: String,
+
+    /// The log probability of the token.
+    pub logprob: f32,
+
+    /// The bytes of the token.
+    pub bytes: Vec<u8>,
 }
 
 /// Represents a verbose json transcription response returned by model, based on
@@ -177,15 +320,15 @@ pub struct TranscriptionSegment {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateSpeechRequest {
     /// The text to generate audio for. The maximum length is 4096 characters.
     pub input: String,
 
-    /// One of the available [TTS models](https://platform.openai.com/docs/models/tts): `tts-1` or `tts-1-hd`
+    /// One of the available [TTS models](https://platform.openai.com/docs/models/tts): `tts-1`, `tts-1-hd` or `gpt-4o-mini-tts`.
     pub model: SpeechModel,
 
-    /// The voice to use when generating the audio. Supported voices are `alloy`, `echo`, `fable`, `onyx`, `nova`, and `shimmer`. Previews of the voices are available in the [Text to speech guide](https://platform.openai.com/docs/guides/text-to-speech/voice-options).
+    /// The voice to use when generating the audio. Supported voices are `alloy`, `ash`, `ballad`, `coral`, `echo`, `fable`, `onyx`, `nova`, `sage`, `shimmer`, `verse`, `marin` and `cedar`; `ballad`, `verse`, `marin` and `cedar` are not supported by `tts-1` and `tts-1-hd`. Previews of the voices are available in the [Text to speech guide](https://platform.openai.com/docs/guides/text-to-speech/voice-options).
     pub voice: Voice,
 
     /// The format to audio in. Supported formats are `mp3`, `opus`, `aac`, `flac`, `wav`, and `pcm`.
@@ -195,6 +338,64 @@ pub struct CreateSpeechRequest {
     /// The speed of the generated audio. Select a value from 0.25 to 4.0. 1.0 is the default.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub speed: Option<f32>, // default: 1.0
+
+    /// Control the voice of your generated audio with additional instructions. Does not work with `tts-1` or `tts-1-hd`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// The format to stream the audio in, set by [crate::Audio::speech_stream] and
+    /// [crate::Audio::speech_stream_raw].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_format: Option<SpeechStreamFormat>,
+}
+
+impl CreateSpeechRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(speed)) = self.speed {
+            if !(0.25..=4.0).contains(&speed) {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "speed must be between 0.25 and 4.0, got {speed}"
+                )));
+            }
+
+            if let Some(SpeechModel::Gpt4oMiniTts) = &self.model {
+                return Err(OpenAIError::InvalidArgument(
+                    "speed is not supported by gpt-4o-mini-tts, use instructions to control the pace".into(),
+                ));
+            }
+        }
+
+        if let (
+            Some(SpeechModel::Tts1 | SpeechModel::Tts1Hd),
+            Some(voice @ (Voice::Ballad | Voice::Verse | Voice::Marin | Voice::Cedar)),
+        ) = (&self.model, &self.voice)
+        {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "voice {} is not supported by tts-1 and tts-1-hd",
+                serde_json::to_string(voice).unwrap_or_default()
+            )));
+        }
+
+        if let (Some(SpeechModel::Tts1 | SpeechModel::Tts1Hd), Some(Some(_))) =
+            (&self.model, &self.instructions)
+        {
+            return Err(OpenAIError::InvalidArgument(
+                "instructions are not supported by tts-1 and tts-1-hd".into(),
+            ));
+        }
+
+        if let (
+            Some(SpeechModel::Tts1 | SpeechModel::Tts1Hd),
+            Some(Some(SpeechStreamFormat::Sse)),
+        ) = (&self.model, &self.stream_format)
+        {
+            return Err(OpenAIError::InvalidArgument(
+                "the sse stream format is not supported by tts-1 and tts-1-hd".into(),
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Clone, Default, Debug, Builder, PartialEq)]
@@ -230,8 +431,8 @@ pub struct CreateTranslationResponseJson {
 pub struct CreateTranslationResponseVerboseJson {
     /// The language of the output translation (always `english`).
     pub language: String,
-    /// The duration of the input audio.
-    pub duration: String,
+    /// The duration of the input audio, in seconds.
+    pub duration: f32,
     /// The translated text.
     pub text: String,
     /// Segments of the translated text and their corresponding details.
@@ -242,4 +443,56 @@ pub struct CreateTranslationResponseVerboseJson {
 #[derive(Debug, Clone)]
 pub struct CreateSpeechResponse {
     pub bytes: Bytes,
+
+    /// The format of the audio, the `response_format` of the request.
+    pub response_format: SpeechResponseFormat,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct SpeechAudioDeltaEvent {
+    /// A chunk of the audio, base64 encoded.
+    pub audio: String,
+}
+
+impl SpeechAudioDeltaEvent {
+    /// The decoded chunk of audio
+    pub fn bytes(&self) -> Result<Bytes, OpenAIError> {
+        STANDARD
+            .decode(&self.audio)
+            .map(Bytes::from)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid base64 audio: {e}")))
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct SpeechUsage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub total_tokens: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct SpeechAudioDoneEvent {
+    /// Token usage of the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<SpeechUsage>,
 }
+
+/// Event of a speech streamed with the `sse` stream format.
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum SpeechStreamEvent {
+    /// A chunk of audio.
+    #[serde(rename = "speech.audio.delta")]
+    Audio(SpeechAudioDeltaEvent),
+    /// The audio is complete, the last event of the stream.
+    #[serde(rename = "speech.audio.done")]
+    Done(SpeechAudioDoneEvent),
+}
+
+/// Parsed server side events stream until the [SpeechStreamEvent::Done] event.
+pub type SpeechResponseStream =
+    Pin<Box<dyn Stream<Item = Result<SpeechStreamEvent, OpenAIError>> + Send>>;
+
+/// Chunks of a speech audio file, as they are received.
+pub type SpeechByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, OpenAIError>> + Send>>;
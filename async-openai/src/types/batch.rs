@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
+use super::Metadata;
+
 #[derive(Debug, Serialize, Default, Clone, Builder, PartialEq, Deserialize)]
 #[builder(name = "BatchRequestArgs")]
 #[builder(pattern = "mutable")]
@@ -26,7 +28,8 @@ pub struct BatchRequest {
     pub completion_window: BatchCompletionWindow,
 
     /// Optional custom metadata for the batch.
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    #[builder(try_setter)]
+    pub metadata: Option<Metadata>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
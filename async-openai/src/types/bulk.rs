@@ -0,0 +1,147 @@
+use std::future::Future;
+
+use crate::error::OpenAIError;
+
+/// Outcome of a helper which performs many independent operations, like saving
+/// every image of a response. Each item is identified by its index in the input.
+#[derive(Debug)]
+pub struct BulkOutcome<T, E = OpenAIError> {
+    /// Successful items sorted by index
+    pub successes: Vec<(usize, T)>,
+    /// Failed items sorted by index
+    pub failures: Vec<(usize, E)>,
+}
+
+impl<T, E> Default for BulkOutcome<T, E> {
+    fn default() -> Self {
+        Self {
+            successes: vec![],
+            failures: vec![],
+        }
+    }
+}
+
+impl<T, E> FromIterator<Result<T, E>> for BulkOutcome<T, E> {
+    /// Index of each item is its position in the iterator
+    fn from_iter<I: IntoIterator<Item = Result<T, E>>>(iter: I) -> Self {
+        let mut outcome = Self::default();
+        for (index, result) in iter.into_iter().enumerate() {
+            outcome.push(index, result);
+        }
+        outcome
+    }
+}
+
+impl<T, E> BulkOutcome<T, E> {
+    /// Record the result of the item at `index`
+    pub fn push(&mut self, index: usize, result: Result<T, E>) {
+        match result {
+            Ok(value) => {
+                let position = self.successes.partition_point(|(i, _)| *i < index);
+                self.successes.insert(position, (index, value));
+            }
+            Err(e) => {
+                let position = self.failures.partition_point(|(i, _)| *i < index);
+                self.failures.insert(position, (index, e));
+            }
+        }
+    }
+
+    /// True when no item failed
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Successful values in index order, or all failures if any item failed
+    pub fn into_result(self) -> Result<Vec<T>, Vec<(usize, E)>> {
+        if self.is_complete() {
+            Ok(self.successes.into_iter().map(|(_, value)| value).collect())
+        } else {
+            Err(self.failures)
+        }
+    }
+
+    /// Run `f` again for the index of every failed item, successes are kept as is.
+    pub async fn retry_failed<F, Fut>(self, mut f: F) -> Self
+    where
+        F: FnMut(usize) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut outcome = Self {
+            successes: self.successes,
+            failures: vec![],
+        };
+
+        for (index, _) in self.failures {
+            let result = f(index).await;
+            outcome.push(index, result);
+        }
+
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome() -> BulkOutcome<u32, String> {
+        vec![
+            Ok(0),
+            Err("one".to_string()),
+            Ok(2),
+            Err("three".to_string()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_collect_keeps_indices() {
+        let outcome = outcome();
+
+        assert_eq!(outcome.successes, vec![(0, 0), (2, 2)]);
+        assert_eq!(
+            outcome.failures,
+            vec![(1, "one".to_string()), (3, "three".to_string())]
+        );
+        assert!(!outcome.is_complete());
+    }
+
+    #[test]
+    fn test_into_result() {
+        assert_eq!(
+            outcome().into_result().unwrap_err(),
+            vec![(1, "one".to_string()), (3, "three".to_string())]
+        );
+
+        let complete: BulkOutcome<u32, String> = vec![Ok(1), Ok(2)].into_iter().collect();
+        assert!(complete.is_complete());
+        assert_eq!(complete.into_result().unwrap(), vec![1, 2]);
+
+        let empty = BulkOutcome::<u32, String>::default();
+        assert_eq!(empty.into_result().unwrap(), Vec::<u32>::new());
+    }
+
+    #[tokio::test]
+    async fn test_retry_failed() {
+        let mut attempts = vec![];
+
+        let outcome = outcome()
+            .retry_failed(|index| {
+                attempts.push(index);
+                async move {
+                    if index == 1 {
+                        Ok(1)
+                    } else {
+                        Err(format!("still failing {index}"))
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(attempts, vec![1, 3]);
+        assert_eq!(outcome.successes, vec![(0, 0), (1, 1), (2, 2)]);
+        assert_eq!(outcome.failures, vec![(3, "still failing 3".to_string())]);
+    }
+}
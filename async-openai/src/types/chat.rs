@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
+use super::{ContentFilterResult, Metadata, PromptFilterResult};
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum Prompt {
@@ -99,18 +101,20 @@ pub struct CompletionUsage {
     pub completion_tokens_details: Option<CompletionTokensDetails>,
 }
 
-/// Breakdown of tokens used in a completion.
+/// Breakdown of tokens used in the prompt.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct PromptTokensDetails {
     /// Audio input tokens present in the prompt.
     pub audio_tokens: Option<u32>,
-    /// Cached tokens present in the prompt.
+    /// Cached tokens present in the prompt, read from the [prompt cache](https://platform.openai.com/docs/guides/prompt-caching).
     pub cached_tokens: Option<u32>,
 }
 
 /// Breakdown of tokens used in a completion.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct CompletionTokensDetails {
+    /// When using Predicted Outputs, the number of tokens in the
+    /// prediction that appeared in the completion.
     pub accepted_prediction_tokens: Option<u32>,
     /// Audio input tokens generated by the model.
     pub audio_tokens: Option<u32>,
@@ -190,14 +194,31 @@ pub enum ImageDetail {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct ImageUrl {
-    /// Either a URL of the image or the base64 encoded image data.
+    /// Either a URL of the image or the base64 encoded image data, as a data URL
+    /// like `data:image/jpeg;base64,...`, see [ImageUrl::from_base64].
     pub url: String,
     /// Specifies the detail level of the image. Learn more in the [Vision guide](https://platform.openai.com/docs/guides/vision/low-or-high-fidelity-image-understanding).
     pub detail: Option<ImageDetail>,
 }
 
+impl ImageUrlArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(url) = &self.url {
+            if reqwest::Url::parse(url).is_err() {
+                return Err(OpenAIError::InvalidArgument(
+                    "url must be a URL or a data URL like data:image/png;base64,..., \
+                     use ImageUrl::from_base64 for base64 image data"
+                        .into(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
 #[builder(name = "ChatCompletionRequestMessageContentPartImageArgs")]
 #[builder(pattern = "mutable")]
@@ -430,6 +451,11 @@ pub struct ChatCompletionResponseMessage {
 
     /// If the audio output modality is requested, this object contains data about the audio response from the model. [Learn more](https://platform.openai.com/docs/guides/audio).
     pub audio: Option<ChatCompletionResponseMessageAudio>,
+
+    /// Annotations for the message, when applicable, as when using the
+    /// [web search tool](https://platform.openai.com/docs/guides/tools-web-search?api-mode=chat).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Vec<ChatCompletionResponseMessageAnnotation>>,
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
@@ -554,23 +580,37 @@ pub enum ChatCompletionToolChoiceOption {
     Named(ChatCompletionNamedToolChoice),
 }
 
+/// Processing tier of a request, see [service_tier](CreateChatCompletionRequest::service_tier).
+///
+/// Tiers unknown to this crate, requested or returned by the API, are kept as [ServiceTier::Other].
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum ServiceTier {
     Auto,
     Default,
+    Flex,
+    Scale,
+    Priority,
+    #[serde(untagged)]
+    Other(String),
 }
 
+#[deprecated(note = "use ServiceTier, returned by responses too")]
+pub type ServiceTierResponse = ServiceTier;
+
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
-pub enum ServiceTierResponse {
-    Scale,
-    Default,
+pub enum ReasoningEffort {
+    Minimal,
+    Low,
+    Medium,
+    High,
 }
 
+/// Constrains the verbosity of the model's response.
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
-pub enum ReasoningEffort {
+pub enum Verbosity {
     Low,
     Medium,
     High,
@@ -589,6 +629,87 @@ pub enum ChatCompletionModalities {
     Audio,
 }
 
+/// Amount of context window space to use for the search.
+#[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchContextSize {
+    Low,
+    Medium,
+    High,
+}
+
+/// Approximate location parameters for the search.
+#[derive(Clone, Serialize, Default, Debug, Builder, Deserialize, PartialEq)]
+#[builder(name = "WebSearchLocationArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct WebSearchLocation {
+    /// Free text input for the city of the user, e.g. `San Francisco`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// The two-letter [ISO country code](https://en.wikipedia.org/wiki/ISO_3166-1) of the user, e.g. `US`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// Free text input for the region of the user, e.g. `California`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// The [IANA timezone](https://timeapi.io/documentation/iana-timezones) of the user, e.g. `America/Los_Angeles`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+/// Approximate location of the user for the search.
+#[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum WebSearchUserLocation {
+    Approximate { approximate: WebSearchLocation },
+}
+
+impl From<WebSearchLocation> for WebSearchUserLocation {
+    fn from(approximate: WebSearchLocation) -> Self {
+        Self::Approximate { approximate }
+    }
+}
+
+/// Options for the [web search](https://platform.openai.com/docs/guides/tools-web-search?api-mode=chat)
+/// of the search models, like `gpt-4o-search-preview`.
+#[derive(Clone, Serialize, Default, Debug, Builder, Deserialize, PartialEq)]
+#[builder(name = "WebSearchOptionsArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct WebSearchOptions {
+    /// High level guidance for the amount of context window space to use for the search, `medium` by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_context_size: Option<SearchContextSize>,
+    /// Approximate location parameters for the search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_location: Option<WebSearchUserLocation>,
+}
+
+/// A URL citation when using web search.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct UrlCitation {
+    /// The index of the last character of the URL citation in the message.
+    pub end_index: u32,
+    /// The index of the first character of the URL citation in the message.
+    pub start_index: u32,
+    /// The title of the web resource.
+    pub title: String,
+    /// The URL of the web resource.
+    pub url: String,
+}
+
+/// An annotation of a message, like a URL citation of the web search.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChatCompletionResponseMessageAnnotation {
+    UrlCitation { url_citation: UrlCitation },
+}
+
 /// The content that should be matched when generating a model response. If generated tokens would match this content, the entire model response can be returned much more quickly.
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
 #[serde(untagged)]
@@ -596,7 +717,14 @@ pub enum PredictionContentContent {
     /// The content used for a Predicted Output. This is often the text of a file you are regenerating with minor changes.
     Text(String),
     /// An array of content parts with a defined type. Supported options differ based on the [model](https://platform.openai.com/docs/models) being used to generate the response. Can contain text inputs.
-    Array(Vec<ChatCompletionRequestMessageContentPartText>),
+    Array(Vec<PredictionContentPart>),
+}
+
+#[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+#[serde(rename_all = "snake_case")]
+pub enum PredictionContentPart {
+    Text(ChatCompletionRequestMessageContentPartText),
 }
 
 /// Static predicted output content, such as the content of a text file that is being regenerated.
@@ -644,7 +772,7 @@ pub struct ChatCompletionAudio {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateChatCompletionRequest {
     /// A list of messages comprising the conversation so far. Depending on the [model](https://platform.openai.com/docs/models) you use, different message types (modalities) are supported, like [text](https://platform.openai.com/docs/guides/text-generation), [images](https://platform.openai.com/docs/guides/vision), and [audio](https://platform.openai.com/docs/guides/audio).
     pub messages: Vec<ChatCompletionRequestMessage>, // min: 1
@@ -659,21 +787,28 @@ pub struct CreateChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub store: Option<bool>, // nullable: true, default: false
 
-    /// **o1 models only**
+    /// **o-series and gpt-5 models only**
     ///
     /// Constrains effort on reasoning for
     /// [reasoning models](https://platform.openai.com/docs/guides/reasoning).
     ///
-    /// Currently supported values are `low`, `medium`, and `high`. Reducing
+    /// Currently supported values are `minimal`, `low`, `medium`, and `high`. Reducing
     ///
     /// reasoning effort can result in faster responses and fewer tokens
     /// used on reasoning in a response.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reasoning_effort: Option<ReasoningEffort>,
 
+    /// Constrains the verbosity of the model's response. Lower values will result in
+    /// more concise responses, while higher values will result in more verbose responses.
+    /// Currently supported values are `low`, `medium`, and `high`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<Verbosity>,
+
     ///  Developer-defined tags and values used for filtering completions in the [dashboard](https://platform.openai.com/chat-completions).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<serde_json::Value>, // nullable: true
+    #[builder(try_setter)]
+    pub metadata: Option<Metadata>, // nullable: true
 
     /// Number between -2.0 and 2.0. Positive values penalize new tokens based on their existing frequency in the text so far, decreasing the model's likelihood to repeat the same line verbatim.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -745,9 +880,10 @@ pub struct CreateChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub seed: Option<i64>,
 
-    /// Specifies the latency tier to use for processing the request. This parameter is relevant for customers subscribed to the scale tier service:
-    /// - If set to 'auto', the system will utilize scale tier credits until they are exhausted.
-    /// - If set to 'default', the request will be processed using the default service tier with a lower uptime SLA and no latency guarentee.
+    /// Specifies the processing type used for serving the request.
+    /// - If set to 'auto', the request will be processed with the service tier configured in the Project settings.
+    /// - If set to 'default', the request will be processed with the standard pricing and performance for the selected model.
+    /// - If set to '[flex](https://platform.openai.com/docs/guides/flex-processing)' or 'priority', the request will be processed with the corresponding service tier.
     /// - When not set, the default behavior is 'auto'.
     ///
     /// When this parameter is set, the response body will include the `service_tier` utilized.
@@ -794,10 +930,28 @@ pub struct CreateChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parallel_tool_calls: Option<bool>,
 
+    /// This tool searches the web for relevant results to use in a response.
+    /// Learn more about the [web search tool](https://platform.openai.com/docs/guides/tools-web-search?api-mode=chat).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web_search_options: Option<WebSearchOptions>,
+
+    /// Deprecated in favor of `safety_identifier` and `prompt_cache_key`, still sent when set.
+    ///
     /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/guides/safety-best-practices#end-user-ids).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
 
+    /// A stable identifier used to help detect users of your application that may be violating OpenAI's usage policies.
+    /// The IDs should be a string that uniquely identifies each user. We recommend hashing their username or email address,
+    /// in order to avoid sending us any identifying information. [Learn more](https://platform.openai.com/docs/guides/safety-best-practices#safety-identifiers).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safety_identifier: Option<String>,
+
+    /// Used by OpenAI to cache responses for similar requests to optimize your cache hit rates.
+    /// [Learn more](https://platform.openai.com/docs/guides/prompt-caching).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_cache_key: Option<String>,
+
     /// Deprecated in favor of `tool_choice`.
     ///
     /// Controls which (if any) function is called by the model.
@@ -818,6 +972,19 @@ pub struct CreateChatCompletionRequest {
     pub functions: Option<Vec<ChatCompletionFunctions>>,
 }
 
+impl CreateChatCompletionRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let (Some(Some(_)), Some(Some(_))) = (&self.max_tokens, &self.max_completion_tokens) {
+            return Err(OpenAIError::InvalidArgument(
+                "max_tokens and max_completion_tokens cannot both be set, use max_completion_tokens"
+                    .into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 /// Options for streaming response. Only set this when you set `stream: true`.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub struct ChatCompletionStreamOptions {
@@ -876,6 +1043,9 @@ pub struct ChatChoice {
     pub finish_reason: Option<FinishReason>,
     /// Log probability information for the choice.
     pub logprobs: Option<ChatChoiceLogprobs>,
+    /// Content filtering results of the completion, returned by Azure OpenAI only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_filter_results: Option<ContentFilterResult>,
 }
 
 /// Represents a chat completion response returned by model, based on the provided input.
@@ -889,8 +1059,8 @@ pub struct CreateChatCompletionResponse {
     pub created: u32,
     /// The model used for the chat completion.
     pub model: String,
-    /// The service tier used for processing the request. This field is only included if the `service_tier` parameter is specified in the request.
-    pub service_tier: Option<ServiceTierResponse>,
+    /// The service tier used for processing the request.
+    pub service_tier: Option<ServiceTier>,
     /// This fingerprint represents the backend configuration that the model runs with.
     ///
     /// Can be used in conjunction with the `seed` request parameter to understand when backend changes have been made that might impact determinism.
@@ -899,6 +1069,9 @@ pub struct CreateChatCompletionResponse {
     /// The object type, which is always `chat.completion`.
     pub object: String,
     pub usage: Option<CompletionUsage>,
+    /// Content filtering results of the prompts, returned by Azure OpenAI only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_filter_results: Option<Vec<PromptFilterResult>>,
 }
 
 /// Parsed server side events stream until an \[DONE\] is received from server.
@@ -974,8 +1147,8 @@ pub struct CreateChatCompletionStreamResponse {
     pub created: u32,
     /// The model to generate the completion.
     pub model: String,
-    /// The service tier used for processing the request. This field is only included if the `service_tier` parameter is specified in the request.
-    pub service_tier: Option<ServiceTierResponse>,
+    /// The service tier used for processing the request.
+    pub service_tier: Option<ServiceTier>,
     /// This fingerprint represents the backend configuration that the model runs with.
     /// Can be used in conjunction with the `seed` request parameter to understand when backend changes have been made that might impact determinism.
     pub system_fingerprint: Option<String>,
@@ -986,3 +1159,117 @@ pub struct CreateChatCompletionStreamResponse {
     /// When present, it contains a null value except for the last chunk which contains the token usage statistics for the entire request.
     pub usage: Option<CompletionUsage>,
 }
+
+/// Sort order of the items of a list, by their creation time
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ListOrder {
+    Asc,
+    Desc,
+}
+
+/// Query parameters of [crate::Chat::list]
+#[derive(Clone, Default, Debug, Builder, PartialEq)]
+#[builder(name = "ListChatCompletionsQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListChatCompletionsQuery {
+    /// The model used to generate the chat completions.
+    pub model: Option<String>,
+    /// Only list the chat completions with all of these metadata key-value pairs,
+    /// sent as `metadata[key]=value`.
+    pub metadata: Option<HashMap<String, String>>,
+    /// Identifier for the last chat completion from the previous pagination request.
+    pub after: Option<String>,
+    /// Number of chat completions to retrieve.
+    pub limit: Option<u32>,
+    /// Sort order for chat completions by timestamp, `asc` by default.
+    pub order: Option<ListOrder>,
+}
+
+impl Serialize for ListChatCompletionsQuery {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(model) = &self.model {
+            map.serialize_entry("model", model)?;
+        }
+        // Query strings have no nested values, each pair is a `metadata[key]` parameter
+        if let Some(metadata) = &self.metadata {
+            let mut pairs: Vec<_> = metadata.iter().collect();
+            pairs.sort();
+            for (key, value) in pairs {
+                map.serialize_entry(&format!("metadata[{key}]"), value)?;
+            }
+        }
+        if let Some(after) = &self.after {
+            map.serialize_entry("after", after)?;
+        }
+        if let Some(limit) = &self.limit {
+            map.serialize_entry("limit", limit)?;
+        }
+        if let Some(order) = &self.order {
+            map.serialize_entry("order", order)?;
+        }
+        map.end()
+    }
+}
+
+/// Query parameters of [crate::Chat::messages]
+#[derive(Clone, Serialize, Default, Debug, Builder, PartialEq)]
+#[builder(name = "ListChatMessagesQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListChatMessagesQuery {
+    /// Identifier for the last message from the previous pagination request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Number of messages to retrieve.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// Sort order for messages by timestamp, `asc` by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<ListOrder>,
+}
+
+/// A list of chat completions stored with `store: true`.
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct ChatCompletionList {
+    /// The type of this object. It is always set to "list".
+    pub object: String,
+    pub data: Vec<CreateChatCompletionResponse>,
+    /// The identifier of the first chat completion in the data array.
+    pub first_id: Option<String>,
+    /// The identifier of the last chat completion in the data array.
+    pub last_id: Option<String>,
+    /// Indicates whether there are more chat completions available.
+    pub has_more: bool,
+}
+
+/// A message of a stored chat completion, see [crate::Chat::messages]
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct ChatCompletionStoredMessage {
+    /// The identifier of the chat message.
+    pub id: String,
+    #[serde(flatten)]
+    pub message: ChatCompletionResponseMessage,
+}
+
+/// A list of the messages of a stored chat completion.
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct ChatCompletionMessageList {
+    /// The type of this object. It is always set to "list".
+    pub object: String,
+    pub data: Vec<ChatCompletionStoredMessage>,
+    /// The identifier of the first chat message in the data array.
+    pub first_id: Option<String>,
+    /// The identifier of the last chat message in the data array.
+    pub last_id: Option<String>,
+    /// Indicates whether there are more chat messages available.
+    pub has_more: bool,
+}
@@ -0,0 +1,489 @@
+use std::collections::BTreeMap;
+
+use super::{
+    ChatChoice, ChatChoiceLogprobs, ChatChoiceStream, ChatCompletionMessageToolCall,
+    ChatCompletionMessageToolCallChunk, ChatCompletionResponseMessage, ChatCompletionToolType,
+    CompletionUsage, CreateChatCompletionResponse, CreateChatCompletionStreamResponse,
+    FinishReason, FunctionCall, FunctionCallStream, Role, ServiceTier,
+};
+
+/// Content of a choice received in a chunk, returned by [ChatCompletionStreamAccumulator::push]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentDelta {
+    /// Index of the choice
+    pub index: u32,
+    pub content: String,
+}
+
+/// Reassembles the chunks of [crate::Chat::create_stream] into the response
+/// the request would have returned without streaming.
+///
+/// ```
+/// # async fn run(mut stream: async_openai::types::ChatCompletionResponseStream) -> Result<(), async_openai::error::OpenAIError> {
+/// use async_openai::types::ChatCompletionStreamAccumulator;
+/// use futures::StreamExt;
+///
+/// let mut accumulator = ChatCompletionStreamAccumulator::new();
+/// while let Some(chunk) = stream.next().await {
+///     for delta in accumulator.push(chunk?) {
+///         print!("{}", delta.content);
+///     }
+/// }
+/// let response = accumulator.finish();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChatCompletionStreamAccumulator {
+    id: String,
+    created: u32,
+    model: String,
+    service_tier: Option<ServiceTier>,
+    system_fingerprint: Option<String>,
+    usage: Option<CompletionUsage>,
+    choices: BTreeMap<u32, ChoiceState>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ChoiceState {
+    role: Option<Role>,
+    content: Option<String>,
+    refusal: Option<String>,
+    tool_calls: ToolCallChunkMerger,
+    function_call: Option<FunctionCallState>,
+    finish_reason: Option<FinishReason>,
+    logprobs: Option<ChatChoiceLogprobs>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ToolCallState {
+    id: Option<String>,
+    r#type: Option<ChatCompletionToolType>,
+    function: FunctionCallState,
+}
+
+#[derive(Debug, Clone, Default)]
+struct FunctionCallState {
+    name: String,
+    arguments: String,
+}
+
+impl FunctionCallState {
+    fn push(&mut self, delta: FunctionCallStream) {
+        self.name.extend(delta.name);
+        self.arguments.extend(delta.arguments);
+    }
+}
+
+/// Merges the [ChatCompletionMessageToolCallChunk]s of a streamed choice into
+/// complete tool calls. Chunks are merged by their `index`, and may carry the id,
+/// type or function name in any chunk of the tool call.
+///
+/// ```
+/// use async_openai::types::{ChatCompletionResponseStream, ToolCallChunkMerger};
+/// use futures::StreamExt;
+///
+/// # async fn run(mut stream: ChatCompletionResponseStream) -> Result<(), async_openai::error::OpenAIError> {
+/// let mut merger = ToolCallChunkMerger::new();
+/// while let Some(chunk) = stream.next().await {
+///     for choice in chunk?.choices {
+///         if let Some(tool_calls) = merger.push_choice(&choice) {
+///             for tool_call in tool_calls {
+///                 println!("{}({})", tool_call.function.name, tool_call.function.arguments);
+///             }
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ToolCallChunkMerger {
+    tool_calls: BTreeMap<u32, ToolCallState>,
+}
+
+impl ToolCallChunkMerger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merge a chunk into the tool call at its index
+    pub fn push(&mut self, chunk: ChatCompletionMessageToolCallChunk) {
+        let tool_call = self.tool_calls.entry(chunk.index).or_default();
+        if chunk.id.is_some() {
+            tool_call.id = chunk.id;
+        }
+        if chunk.r#type.is_some() {
+            tool_call.r#type = chunk.r#type;
+        }
+        if let Some(function) = chunk.function {
+            tool_call.function.push(function);
+        }
+    }
+
+    /// Merge the tool call chunks of a choice, returning the complete tool calls once
+    /// the choice finishes with [FinishReason::ToolCalls].
+    ///
+    /// Only push the choices of a single index to a merger, use one merger per
+    /// choice when `n` is greater than 1.
+    pub fn push_choice(
+        &mut self,
+        choice: &ChatChoiceStream,
+    ) -> Option<Vec<ChatCompletionMessageToolCall>> {
+        for chunk in choice.delta.tool_calls.iter().flatten() {
+            self.push(chunk.clone());
+        }
+        (choice.finish_reason == Some(FinishReason::ToolCalls)).then(|| self.take())
+    }
+
+    /// Whether no chunk was merged since the last [ToolCallChunkMerger::take]
+    pub fn is_empty(&self) -> bool {
+        self.tool_calls.is_empty()
+    }
+
+    /// Tool calls merged so far sorted by index, leaving the merger empty
+    pub fn take(&mut self) -> Vec<ChatCompletionMessageToolCall> {
+        std::mem::take(&mut self.tool_calls)
+            .into_values()
+            .map(|tool_call| ChatCompletionMessageToolCall {
+                id: tool_call.id.unwrap_or_default(),
+                r#type: tool_call.r#type.unwrap_or_default(),
+                function: tool_call.function.into(),
+            })
+            .collect()
+    }
+}
+
+impl From<FunctionCallState> for FunctionCall {
+    fn from(state: FunctionCallState) -> Self {
+        Self {
+            name: state.name,
+            arguments: state.arguments,
+        }
+    }
+}
+
+fn append<T>(target: &mut Option<Vec<T>>, items: Option<Vec<T>>) {
+    if let Some(items) = items {
+        target.get_or_insert_with(Vec::new).extend(items);
+    }
+}
+
+impl ChoiceState {
+    #[allow(deprecated)]
+    fn push(&mut self, choice: ChatChoiceStream) -> Option<String> {
+        let delta = choice.delta;
+
+        if delta.role.is_some() {
+            self.role = delta.role;
+        }
+        if let Some(refusal) = delta.refusal {
+            self.refusal
+                .get_or_insert_with(String::new)
+                .push_str(&refusal);
+        }
+        for chunk in delta.tool_calls.into_iter().flatten() {
+            self.tool_calls.push(chunk);
+        }
+        if let Some(function_call) = delta.function_call {
+            self.function_call
+                .get_or_insert_with(Default::default)
+                .push(function_call);
+        }
+        if choice.finish_reason.is_some() {
+            self.finish_reason = choice.finish_reason;
+        }
+        if let Some(logprobs) = choice.logprobs {
+            let target = self.logprobs.get_or_insert(ChatChoiceLogprobs {
+                content: None,
+                refusal: None,
+            });
+            append(&mut target.content, logprobs.content);
+            append(&mut target.refusal, logprobs.refusal);
+        }
+
+        let content = delta.content?;
+        self.content
+            .get_or_insert_with(String::new)
+            .push_str(&content);
+        Some(content)
+    }
+
+    #[allow(deprecated)]
+    fn finish(mut self, index: u32) -> ChatChoice {
+        let tool_calls = self.tool_calls.take();
+
+        ChatChoice {
+            index,
+            message: ChatCompletionResponseMessage {
+                content: self.content,
+                refusal: self.refusal,
+                tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+                role: self.role.unwrap_or(Role::Assistant),
+                function_call: self.function_call.map(Into::into),
+                audio: None,
+                annotations: None,
+            },
+            finish_reason: self.finish_reason,
+            logprobs: self.logprobs,
+            content_filter_results: None,
+        }
+    }
+}
+
+impl ChatCompletionStreamAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merge a chunk of the stream, returning the content it added to each choice
+    pub fn push(&mut self, chunk: CreateChatCompletionStreamResponse) -> Vec<ContentDelta> {
+        self.id = chunk.id;
+        self.created = chunk.created;
+        self.model = chunk.model;
+        if chunk.service_tier.is_some() {
+            self.service_tier = chunk.service_tier;
+        }
+        if chunk.system_fingerprint.is_some() {
+            self.system_fingerprint = chunk.system_fingerprint;
+        }
+        // Only sent in the last chunk when `stream_options.include_usage` is set
+        if chunk.usage.is_some() {
+            self.usage = chunk.usage;
+        }
+
+        chunk
+            .choices
+            .into_iter()
+            .filter_map(|choice| {
+                let index = choice.index;
+                let content = self.choices.entry(index).or_default().push(choice)?;
+                Some(ContentDelta { index, content })
+            })
+            .collect()
+    }
+
+    /// Token usage of the request, once received
+    pub fn usage(&self) -> Option<&CompletionUsage> {
+        self.usage.as_ref()
+    }
+
+    /// The response assembled from every chunk pushed, with choices sorted by index
+    pub fn finish(self) -> CreateChatCompletionResponse {
+        CreateChatCompletionResponse {
+            id: self.id,
+            choices: self
+                .choices
+                .into_iter()
+                .map(|(index, choice)| choice.finish(index))
+                .collect(),
+            created: self.created,
+            model: self.model,
+            service_tier: self.service_tier,
+            system_fingerprint: self.system_fingerprint,
+            object: "chat.completion".into(),
+            usage: self.usage,
+            prompt_filter_results: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accumulate(chunks: &[&str]) -> (ChatCompletionStreamAccumulator, Vec<ContentDelta>) {
+        let mut accumulator = ChatCompletionStreamAccumulator::new();
+        let mut deltas = vec![];
+        for chunk in chunks {
+            deltas.extend(accumulator.push(serde_json::from_str(chunk).unwrap()));
+        }
+        (accumulator, deltas)
+    }
+
+    #[test]
+    fn test_content_and_usage() {
+        let (accumulator, deltas) = accumulate(&[
+            r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1694268190,"model":"gpt-4o","system_fingerprint":"fp_44709d6fcb","choices":[{"index":0,"delta":{"role":"assistant","content":"","refusal":null},"logprobs":null,"finish_reason":null}],"usage":null}"#,
+            r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1694268190,"model":"gpt-4o","system_fingerprint":"fp_44709d6fcb","choices":[{"index":0,"delta":{"content":"Hello"},"logprobs":null,"finish_reason":null}],"usage":null}"#,
+            r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1694268190,"model":"gpt-4o","system_fingerprint":"fp_44709d6fcb","choices":[{"index":0,"delta":{"content":" there!"},"logprobs":null,"finish_reason":null}],"usage":null}"#,
+            r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1694268190,"model":"gpt-4o","system_fingerprint":"fp_44709d6fcb","choices":[{"index":0,"delta":{},"logprobs":null,"finish_reason":"stop"}],"usage":null}"#,
+            r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1694268190,"model":"gpt-4o","system_fingerprint":"fp_44709d6fcb","choices":[],"usage":{"prompt_tokens":9,"completion_tokens":3,"total_tokens":12}}"#,
+        ]);
+
+        let contents: Vec<_> = deltas.iter().map(|d| d.content.as_str()).collect();
+        assert_eq!(contents, ["", "Hello", " there!"]);
+        assert_eq!(accumulator.usage().unwrap().total_tokens, 12);
+
+        let expected: CreateChatCompletionResponse = serde_json::from_str(
+            r#"{
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1694268190,
+                "model": "gpt-4o",
+                "system_fingerprint": "fp_44709d6fcb",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "Hello there!", "refusal": null},
+                    "logprobs": null,
+                    "finish_reason": "stop"
+                }],
+                "usage": {"prompt_tokens": 9, "completion_tokens": 3, "total_tokens": 12}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(accumulator.finish(), expected);
+    }
+
+    #[test]
+    fn test_interleaved_tool_calls_of_multiple_choices() {
+        let (accumulator, deltas) = accumulate(&[
+            r#"{"id":"chatcmpl-2","object":"chat.completion.chunk","created":1694268190,"model":"gpt-4o","choices":[{"index":1,"delta":{"role":"assistant","content":"Checking"},"finish_reason":null},{"index":0,"delta":{"role":"assistant","tool_calls":[{"index":0,"id":"call_a","type":"function","function":{"name":"get_weather","arguments":""}}]},"finish_reason":null}]}"#,
+            r#"{"id":"chatcmpl-2","object":"chat.completion.chunk","created":1694268190,"model":"gpt-4o","choices":[{"index":0,"delta":{"tool_calls":[{"index":1,"id":"call_b","type":"function","function":{"name":"get_time","arguments":"{\"tz\":"}}]},"finish_reason":null}]}"#,
+            r#"{"id":"chatcmpl-2","object":"chat.completion.chunk","created":1694268190,"model":"gpt-4o","choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"city\":\"Paris\"}"}}]},"finish_reason":null}]}"#,
+            r#"{"id":"chatcmpl-2","object":"chat.completion.chunk","created":1694268190,"model":"gpt-4o","choices":[{"index":0,"delta":{"tool_calls":[{"index":1,"function":{"arguments":"\"CET\"}"}}]},"finish_reason":null},{"index":1,"delta":{"content":" the weather."},"finish_reason":null}]}"#,
+            r#"{"id":"chatcmpl-2","object":"chat.completion.chunk","created":1694268190,"model":"gpt-4o","choices":[{"index":0,"delta":{},"finish_reason":"tool_calls"},{"index":1,"delta":{},"finish_reason":"stop"}]}"#,
+        ]);
+
+        assert_eq!(
+            deltas,
+            [
+                ContentDelta {
+                    index: 1,
+                    content: "Checking".into()
+                },
+                ContentDelta {
+                    index: 1,
+                    content: " the weather.".into()
+                },
+            ]
+        );
+
+        let response = accumulator.finish();
+        assert!(response.usage.is_none());
+        assert_eq!(
+            serde_json::to_value(&response.choices).unwrap(),
+            serde_json::json!([
+                {
+                    "index": 0,
+                    "message": {
+                        "content": null,
+                        "refusal": null,
+                        "tool_calls": [
+                            {"id": "call_a", "type": "function", "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}},
+                            {"id": "call_b", "type": "function", "function": {"name": "get_time", "arguments": "{\"tz\":\"CET\"}"}}
+                        ],
+                        "role": "assistant",
+                        "function_call": null,
+                        "audio": null
+                    },
+                    "finish_reason": "tool_calls",
+                    "logprobs": null
+                },
+                {
+                    "index": 1,
+                    "message": {
+                        "content": "Checking the weather.",
+                        "refusal": null,
+                        "tool_calls": null,
+                        "role": "assistant",
+                        "function_call": null,
+                        "audio": null
+                    },
+                    "finish_reason": "stop",
+                    "logprobs": null
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_refusal_and_logprobs() {
+        let (accumulator, deltas) = accumulate(&[
+            r#"{"id":"chatcmpl-3","object":"chat.completion.chunk","created":1694268190,"model":"gpt-4o","choices":[{"index":0,"delta":{"role":"assistant","refusal":"I'm sorry, "},"logprobs":{"content":null,"refusal":[{"token":"I'm","logprob":-0.1,"bytes":null,"top_logprobs":[]}]},"finish_reason":null}]}"#,
+            r#"{"id":"chatcmpl-3","object":"chat.completion.chunk","created":1694268190,"model":"gpt-4o","choices":[{"index":0,"delta":{"refusal":"I can't help with that."},"logprobs":{"content":null,"refusal":[{"token":" sorry","logprob":-0.2,"bytes":null,"top_logprobs":[]}]},"finish_reason":"stop"}]}"#,
+        ]);
+
+        assert!(deltas.is_empty());
+        let choice = &accumulator.finish().choices[0];
+        assert_eq!(choice.message.content, None);
+        assert_eq!(
+            choice.message.refusal.as_deref(),
+            Some("I'm sorry, I can't help with that.")
+        );
+        let refusal_logprobs = choice.logprobs.as_ref().unwrap().refusal.as_ref().unwrap();
+        let tokens: Vec<_> = refusal_logprobs.iter().map(|l| l.token.as_str()).collect();
+        assert_eq!(tokens, ["I'm", " sorry"]);
+    }
+
+    fn choice(json: serde_json::Value) -> ChatChoiceStream {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_merger_with_late_ids() {
+        let mut merger = ToolCallChunkMerger::new();
+
+        let chunks = [
+            serde_json::json!({"index": 0, "delta": {"tool_calls": [{"index": 0, "function": {"arguments": "{\"city\":"}}]}}),
+            serde_json::json!({"index": 0, "delta": {"tool_calls": [{"index": 0, "id": "call_a", "type": "function", "function": {"name": "get_weather", "arguments": "\"Paris\"}"}}]}}),
+        ];
+        for chunk in chunks {
+            assert!(merger.push_choice(&choice(chunk)).is_none());
+        }
+        assert!(!merger.is_empty());
+
+        let tool_calls = merger
+            .push_choice(&choice(
+                serde_json::json!({"index": 0, "delta": {}, "finish_reason": "tool_calls"}),
+            ))
+            .unwrap();
+        assert_eq!(
+            tool_calls,
+            [ChatCompletionMessageToolCall {
+                id: "call_a".into(),
+                r#type: ChatCompletionToolType::Function,
+                function: FunctionCall {
+                    name: "get_weather".into(),
+                    arguments: r#"{"city":"Paris"}"#.into(),
+                },
+            }]
+        );
+        assert!(merger.is_empty());
+    }
+
+    #[test]
+    fn test_merger_with_many_parallel_calls() {
+        let mut merger = ToolCallChunkMerger::new();
+        // Arguments of the 20 calls arrive in reverse order of the calls
+        for index in 0..20u32 {
+            merger.push(ChatCompletionMessageToolCallChunk {
+                index,
+                id: Some(format!("call_{index}")),
+                r#type: Some(ChatCompletionToolType::Function),
+                function: Some(FunctionCallStream {
+                    name: Some("lookup".into()),
+                    arguments: Some("{".into()),
+                }),
+            });
+        }
+        for index in (0..20u32).rev() {
+            merger.push(ChatCompletionMessageToolCallChunk {
+                index,
+                id: None,
+                r#type: None,
+                function: Some(FunctionCallStream {
+                    name: None,
+                    arguments: Some(format!("\"n\":{index}}}")),
+                }),
+            });
+        }
+
+        let tool_calls = merger.take();
+        assert_eq!(tool_calls.len(), 20);
+        for (index, tool_call) in tool_calls.iter().enumerate() {
+            assert_eq!(tool_call.id, format!("call_{index}"));
+            assert_eq!(tool_call.function.arguments, format!("{{\"n\":{index}}}"));
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use super::{
+    ChatCompletionRequestMessage, ChatCompletionRequestSystemMessage,
+    ChatCompletionRequestSystemMessageContent, ChatCompletionRequestToolMessage,
+    ChatCompletionRequestToolMessageContent, ChatCompletionRequestUserMessage,
+    ChatCompletionRequestUserMessageContent, ChatCompletionResponseMessage,
+};
+
+/// Messages of a multi-turn conversation, to send with each
+/// [CreateChatCompletionRequest](super::CreateChatCompletionRequest).
+///
+/// ```no_run
+/// # #[cfg(feature = "chat-completion")]
+/// # async fn run() -> Result<(), async_openai::error::OpenAIError> {
+/// use async_openai::{
+///     types::{ChatHistory, CreateChatCompletionRequestArgs},
+///     Client,
+/// };
+///
+/// let client = Client::new();
+/// let mut history = ChatHistory::new();
+/// history.push_system("You are a helpful assistant.");
+///
+/// for question in ["Who won the world series in 2020?", "Where was it played?"] {
+///     history.push_user(question);
+///     let request = CreateChatCompletionRequestArgs::default()
+///         .model("gpt-4o-mini")
+///         .messages(&history)
+///         .build()?;
+///
+///     let response = client.chat().create(request).await?;
+///     history.push_response(&response.choices[0].message);
+///     history.truncate_keeping_system(20);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChatHistory {
+    messages: Vec<ChatCompletionRequestMessage>,
+}
+
+impl ChatHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, message: impl Into<ChatCompletionRequestMessage>) -> &mut Self {
+        self.messages.push(message.into());
+        self
+    }
+
+    pub fn push_system(
+        &mut self,
+        content: impl Into<ChatCompletionRequestSystemMessageContent>,
+    ) -> &mut Self {
+        self.push(ChatCompletionRequestSystemMessage::from(content.into()))
+    }
+
+    pub fn push_user(
+        &mut self,
+        content: impl Into<ChatCompletionRequestUserMessageContent>,
+    ) -> &mut Self {
+        self.push(ChatCompletionRequestUserMessage::from(content.into()))
+    }
+
+    /// Append the message of a response as an assistant message, with its tool
+    /// calls, refusal and the id of its audio.
+    pub fn push_response(&mut self, message: &ChatCompletionResponseMessage) -> &mut Self {
+        self.push(message.clone())
+    }
+
+    /// Append the result of the tool call `tool_call_id` of the last response
+    pub fn push_tool_result(
+        &mut self,
+        tool_call_id: impl Into<String>,
+        content: impl Into<ChatCompletionRequestToolMessageContent>,
+    ) -> &mut Self {
+        self.push(ChatCompletionRequestToolMessage {
+            content: content.into(),
+            tool_call_id: tool_call_id.into(),
+        })
+    }
+
+    pub fn messages(&self) -> &[ChatCompletionRequestMessage] {
+        &self.messages
+    }
+
+    pub fn into_messages(self) -> Vec<ChatCompletionRequestMessage> {
+        self.messages
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.messages.clear();
+    }
+
+    /// Keep the last `n` messages at most.
+    ///
+    /// Tool messages left at the start, without the assistant message of their
+    /// tool calls, are removed as the API would reject them.
+    pub fn truncate_to_messages(&mut self, n: usize) -> &mut Self {
+        let start = self.messages.len().saturating_sub(n);
+        self.messages.drain(..start);
+        let orphans = self.messages.iter().take_while(|m| is_tool(m)).count();
+        self.messages.drain(..orphans);
+        self
+    }
+
+    /// Keep the system and developer messages, and the last `n` other messages at
+    /// most, like [ChatHistory::truncate_to_messages].
+    pub fn truncate_keeping_system(&mut self, n: usize) -> &mut Self {
+        let (instructions, mut conversation): (Vec<_>, Vec<_>) = std::mem::take(&mut self.messages)
+            .into_iter()
+            .partition(is_instruction);
+
+        let start = conversation.len().saturating_sub(n);
+        conversation.drain(..start);
+        let orphans = conversation.iter().take_while(|m| is_tool(m)).count();
+        conversation.drain(..orphans);
+
+        self.messages = instructions;
+        self.messages.extend(conversation);
+        self
+    }
+}
+
+fn is_instruction(message: &ChatCompletionRequestMessage) -> bool {
+    matches!(
+        message,
+        ChatCompletionRequestMessage::System(_) | ChatCompletionRequestMessage::Developer(_)
+    )
+}
+
+fn is_tool(message: &ChatCompletionRequestMessage) -> bool {
+    matches!(
+        message,
+        ChatCompletionRequestMessage::Tool(_) | ChatCompletionRequestMessage::Function(_)
+    )
+}
+
+impl From<Vec<ChatCompletionRequestMessage>> for ChatHistory {
+    fn from(messages: Vec<ChatCompletionRequestMessage>) -> Self {
+        Self { messages }
+    }
+}
+
+impl From<ChatHistory> for Vec<ChatCompletionRequestMessage> {
+    fn from(history: ChatHistory) -> Self {
+        history.messages
+    }
+}
+
+impl From<&ChatHistory> for Vec<ChatCompletionRequestMessage> {
+    fn from(history: &ChatHistory) -> Self {
+        history.messages.clone()
+    }
+}
+
+impl Extend<ChatCompletionRequestMessage> for ChatHistory {
+    fn extend<I: IntoIterator<Item = ChatCompletionRequestMessage>>(&mut self, iter: I) {
+        self.messages.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::types::{
+        ChatCompletionRequestAssistantMessage, ChatCompletionRequestDeveloperMessage,
+    };
+
+    fn response_message(value: serde_json::Value) -> ChatCompletionResponseMessage {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_response_with_tool_calls_to_request() {
+        let message = response_message(json!({
+            "role": "assistant",
+            "content": null,
+            "tool_calls": [{
+                "id": "call_abc123",
+                "type": "function",
+                "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}
+            }]
+        }));
+
+        let request = ChatCompletionRequestMessage::from(message);
+
+        assert_eq!(
+            serde_json::to_value(request).unwrap(),
+            json!({
+                "role": "assistant",
+                "tool_calls": [{
+                    "id": "call_abc123",
+                    "type": "function",
+                    "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_response_with_audio_or_refusal_to_request() {
+        let message = response_message(json!({
+            "role": "assistant",
+            "content": null,
+            "audio": {
+                "id": "audio_abc123",
+                "expires_at": 1729018505,
+                "data": "UklGRg==",
+                "transcript": "Yes, golden retrievers are known to be friendly."
+            }
+        }));
+
+        let request = ChatCompletionRequestAssistantMessage::from(message);
+        assert_eq!(request.audio.unwrap().id, "audio_abc123");
+        assert!(request.content.is_none());
+
+        let message = response_message(json!({
+            "role": "assistant",
+            "content": null,
+            "refusal": "I'm sorry, I can't help with that."
+        }));
+
+        assert_eq!(
+            serde_json::to_value(ChatCompletionRequestMessage::from(message)).unwrap(),
+            json!({"role": "assistant", "refusal": "I'm sorry, I can't help with that."})
+        );
+    }
+
+    #[test]
+    fn test_truncate_drops_orphan_tool_results() {
+        let mut history = ChatHistory::new();
+        history
+            .push_system("Be brief.")
+            .push(ChatCompletionRequestDeveloperMessage::from("Use metric units."))
+            .push_user("Weather in Paris and Rome?")
+            .push_response(&response_message(json!({
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [
+                    {"id": "call_1", "type": "function", "function": {"name": "weather", "arguments": "{}"}},
+                    {"id": "call_2", "type": "function", "function": {"name": "weather", "arguments": "{}"}}
+                ]
+            })))
+            .push_tool_result("call_1", "18C")
+            .push_tool_result("call_2", "24C")
+            .push_response(&response_message(
+                json!({"role": "assistant", "content": "18C and 24C."}),
+            ));
+        assert_eq!(history.len(), 7);
+
+        let mut kept = history.clone();
+        kept.truncate_keeping_system(3);
+        assert_eq!(kept.len(), 3);
+        assert!(is_instruction(&kept.messages()[0]));
+        assert!(is_instruction(&kept.messages()[1]));
+        assert!(matches!(
+            kept.messages()[2],
+            ChatCompletionRequestMessage::Assistant(_)
+        ));
+
+        history.truncate_to_messages(4);
+        assert_eq!(history.len(), 4);
+        assert!(matches!(
+            history.messages()[0],
+            ChatCompletionRequestMessage::Assistant(_)
+        ));
+
+        history.truncate_to_messages(10);
+        assert_eq!(history.len(), 4);
+        history.truncate_to_messages(0);
+        assert!(history.is_empty());
+    }
+}
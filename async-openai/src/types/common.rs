@@ -1,13 +1,68 @@
-use std::path::PathBuf;
+use std::{
+    fmt,
+    path::PathBuf,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
 
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
+use tokio::io::AsyncRead;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum InputSource {
-    Path { path: PathBuf },
-    Bytes { filename: String, bytes: Bytes },
-    VecU8 { filename: String, vec: Vec<u8> },
+    Path {
+        path: PathBuf,
+    },
+    Bytes {
+        filename: String,
+        bytes: Bytes,
+    },
+    VecU8 {
+        filename: String,
+        vec: Vec<u8>,
+    },
+    /// `length` bytes streamed from `reader` when the request is sent
+    Reader {
+        filename: String,
+        length: u64,
+        reader: InputReader,
+    },
+}
+
+type BoxedReader = Pin<Box<dyn AsyncRead + Send + Sync>>;
+
+/// An [AsyncRead] input, like the body of a download, uploaded without buffering it.
+///
+/// The reader is read once: clones share it, and a request with an [InputSource::Reader]
+/// is not retried nor sent to a fallback config once the reader has been read. The call
+/// then fails with the error of the attempt which read it, like a 429 or 5xx status.
+#[derive(Clone)]
+pub struct InputReader(Arc<Mutex<Option<BoxedReader>>>);
+
+impl InputReader {
+    pub fn new<R: AsyncRead + Send + Sync + 'static>(reader: R) -> Self {
+        Self(Arc::new(Mutex::new(Some(Box::pin(reader)))))
+    }
+
+    /// The reader, `None` when it has already been taken
+    #[cfg(feature = "full")]
+    pub(crate) fn take(&self) -> Option<BoxedReader> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+impl fmt::Debug for InputReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InputReader").finish_non_exhaustive()
+    }
+}
+
+/// Readers are equal when they are clones of the same reader
+impl PartialEq for InputReader {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+/// Severity assigned by the Azure OpenAI content filtering system
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFilterSeverity {
+    Safe,
+    Low,
+    Medium,
+    High,
+}
+
+/// Result of a harm category, like hate or violence
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct ContentFilterSeverityResult {
+    /// Whether the content was filtered because of this category
+    pub filtered: bool,
+    pub severity: ContentFilterSeverity,
+}
+
+/// Result of a detection model, like jailbreak or protected material
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct ContentFilterDetectedResult {
+    /// Whether the content was filtered because of this detection
+    pub filtered: bool,
+    pub detected: bool,
+}
+
+/// Categories checked by the [Azure OpenAI content filtering](https://learn.microsoft.com/en-us/azure/ai-services/openai/concepts/content-filter)
+/// system, only the categories enabled on the deployment are present.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq, Serialize)]
+pub struct ContentFilterResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hate: Option<ContentFilterSeverityResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sexual: Option<ContentFilterSeverityResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub violence: Option<ContentFilterSeverityResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub self_harm: Option<ContentFilterSeverityResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profanity: Option<ContentFilterDetectedResult>,
+    /// Prompt only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jailbreak: Option<ContentFilterDetectedResult>,
+    /// Completion only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected_material_text: Option<ContentFilterDetectedResult>,
+    /// Completion only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected_material_code: Option<ContentFilterDetectedResult>,
+}
+
+impl ContentFilterResult {
+    /// Names of the categories which caused the content to be filtered
+    pub fn filtered_categories(&self) -> Vec<&'static str> {
+        let severities = [
+            ("hate", &self.hate),
+            ("sexual", &self.sexual),
+            ("violence", &self.violence),
+            ("self_harm", &self.self_harm),
+        ];
+        let detections = [
+            ("profanity", &self.profanity),
+            ("jailbreak", &self.jailbreak),
+            ("protected_material_text", &self.protected_material_text),
+            ("protected_material_code", &self.protected_material_code),
+        ];
+
+        severities
+            .into_iter()
+            .filter(|(_, result)| result.as_ref().is_some_and(|result| result.filtered))
+            .map(|(name, _)| name)
+            .chain(
+                detections
+                    .into_iter()
+                    .filter(|(_, result)| result.as_ref().is_some_and(|result| result.filtered))
+                    .map(|(name, _)| name),
+            )
+            .collect()
+    }
+}
+
+/// Content filtering result of a prompt of an Azure OpenAI request
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct PromptFilterResult {
+    pub prompt_index: u32,
+    pub content_filter_results: ContentFilterResult,
+}
+
+/// Details of an Azure OpenAI error, see [crate::error::ApiError::innererror]
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+pub struct InnerError {
+    /// Like `ResponsibleAIPolicyViolation`
+    pub code: Option<String>,
+    pub content_filter_result: Option<ContentFilterResult>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{ApiErrorCode, WrappedError};
+    use crate::types::CreateChatCompletionResponse;
+
+    use super::*;
+
+    #[test]
+    fn test_content_filter_error() {
+        let body = r#"{
+            "error": {
+                "message": "The response was filtered due to the prompt triggering Azure OpenAI's content management policy.",
+                "type": null,
+                "param": "prompt",
+                "code": "content_filter",
+                "status": 400,
+                "innererror": {
+                    "code": "ResponsibleAIPolicyViolation",
+                    "content_filter_result": {
+                        "hate": {"filtered": false, "severity": "safe"},
+                        "jailbreak": {"filtered": false, "detected": false},
+                        "self_harm": {"filtered": false, "severity": "safe"},
+                        "sexual": {"filtered": false, "severity": "safe"},
+                        "violence": {"filtered": true, "severity": "medium"}
+                    }
+                }
+            }
+        }"#;
+
+        let error = serde_json::from_str::<WrappedError>(body).unwrap().error;
+
+        assert_eq!(error.code_enum(), Some(ApiErrorCode::ContentFilter));
+        let result = error.content_filter_result().unwrap();
+        assert_eq!(
+            result.violence,
+            Some(ContentFilterSeverityResult {
+                filtered: true,
+                severity: ContentFilterSeverity::Medium
+            })
+        );
+        assert_eq!(result.filtered_categories(), ["violence"]);
+    }
+
+    #[test]
+    fn test_chat_response_filter_results() {
+        let body = r#"{
+            "choices": [{
+                "content_filter_results": {
+                    "hate": {"filtered": false, "severity": "safe"},
+                    "protected_material_code": {"filtered": false, "detected": false},
+                    "protected_material_text": {"filtered": false, "detected": false},
+                    "self_harm": {"filtered": false, "severity": "safe"},
+                    "sexual": {"filtered": false, "severity": "low"},
+                    "violence": {"filtered": false, "severity": "safe"}
+                },
+                "finish_reason": "stop",
+                "index": 0,
+                "logprobs": null,
+                "message": {"content": "Hello! How can I help you today?", "refusal": null, "role": "assistant"}
+            }],
+            "created": 1733409867,
+            "id": "chatcmpl-AbCdEf",
+            "model": "gpt-4o-2024-08-06",
+            "object": "chat.completion",
+            "prompt_filter_results": [{
+                "prompt_index": 0,
+                "content_filter_results": {
+                    "hate": {"filtered": false, "severity": "safe"},
+                    "jailbreak": {"filtered": false, "detected": false},
+                    "self_harm": {"filtered": false, "severity": "safe"},
+                    "sexual": {"filtered": false, "severity": "safe"},
+                    "violence": {"filtered": false, "severity": "safe"}
+                }
+            }],
+            "system_fingerprint": "fp_04751d0b65",
+            "usage": {"completion_tokens": 10, "prompt_tokens": 9, "total_tokens": 19}
+        }"#;
+
+        let response: CreateChatCompletionResponse = serde_json::from_str(body).unwrap();
+
+        let choice_result = response.choices[0].content_filter_results.as_ref().unwrap();
+        assert_eq!(
+            choice_result.sexual.as_ref().unwrap().severity,
+            ContentFilterSeverity::Low
+        );
+        assert!(choice_result.filtered_categories().is_empty());
+        let prompt_results = response.prompt_filter_results.as_ref().unwrap();
+        assert_eq!(prompt_results[0].prompt_index, 0);
+        assert_eq!(
+            prompt_results[0].content_filter_results.jailbreak,
+            Some(ContentFilterDetectedResult {
+                filtered: false,
+                detected: false
+            })
+        );
+
+        let serialized = serde_json::to_value(&response).unwrap();
+        let response: CreateChatCompletionResponse = serde_json::from_value(serialized).unwrap();
+        assert!(response.choices[0].content_filter_results.is_some());
+    }
+}
@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use base64::engine::{general_purpose, Engine};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
@@ -68,15 +70,40 @@ pub struct Embedding {
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Base64EmbeddingVector(pub String);
 
-impl From<Base64EmbeddingVector> for Vec<f32> {
-    fn from(value: Base64EmbeddingVector) -> Self {
+impl Base64EmbeddingVector {
+    /// Encode `floats` like the API, as base64 of their little-endian bytes
+    pub fn from_f32s(floats: &[f32]) -> Self {
+        let bytes: Vec<u8> = floats
+            .iter()
+            .flat_map(|float| float.to_le_bytes())
+            .collect();
+        Self(general_purpose::STANDARD.encode(bytes))
+    }
+
+    /// Decode the vector into floats
+    pub fn to_f32s(&self) -> Result<Vec<f32>, OpenAIError> {
         let bytes = general_purpose::STANDARD
-            .decode(value.0)
-            .expect("openai base64 encoding to be valid");
-        let chunks = bytes.chunks_exact(4);
-        chunks
+            .decode(&self.0)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid base64 embedding: {e}")))?;
+
+        if bytes.len() % 4 != 0 {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "base64 embedding of {} bytes is not a vector of f32",
+                bytes.len()
+            )));
+        }
+
+        Ok(bytes
+            .chunks_exact(4)
             .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
-            .collect()
+            .collect())
+    }
+}
+
+/// Panics when the vector is not valid, see [Base64EmbeddingVector::to_f32s] to handle it
+impl From<Base64EmbeddingVector> for Vec<f32> {
+    fn from(value: Base64EmbeddingVector) -> Self {
+        value.to_f32s().expect("openai base64 encoding to be valid")
     }
 }
 
@@ -91,6 +118,18 @@ pub struct Base64Embedding {
     pub embedding: Base64EmbeddingVector,
 }
 
+impl TryFrom<Base64Embedding> for Embedding {
+    type Error = OpenAIError;
+
+    fn try_from(value: Base64Embedding) -> Result<Self, Self::Error> {
+        Ok(Self {
+            index: value.index,
+            object: value.object,
+            embedding: value.embedding.to_f32s()?,
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct EmbeddingUsage {
     /// The number of tokens used by the prompt.
@@ -99,6 +138,14 @@ pub struct EmbeddingUsage {
     pub total_tokens: u32,
 }
 
+impl EmbeddingUsage {
+    /// Add the usage of another request
+    pub fn add(&mut self, usage: &EmbeddingUsage) {
+        self.prompt_tokens += usage.prompt_tokens;
+        self.total_tokens += usage.total_tokens;
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 pub struct CreateEmbeddingResponse {
     pub object: String,
@@ -120,3 +167,60 @@ pub struct CreateBase64EmbeddingResponse {
     /// The usage information for the request.
     pub usage: EmbeddingUsage,
 }
+
+/// Decodes the base64 embeddings of the response
+impl TryFrom<CreateBase64EmbeddingResponse> for CreateEmbeddingResponse {
+    type Error = OpenAIError;
+
+    fn try_from(value: CreateBase64EmbeddingResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            object: value.object,
+            model: value.model,
+            data: value
+                .data
+                .into_iter()
+                .map(Embedding::try_from)
+                .collect::<Result<_, _>>()?,
+            usage: value.usage,
+        })
+    }
+}
+
+/// Largest number of inputs of an embedding request
+pub const MAX_EMBEDDING_INPUTS: usize = 2048;
+
+/// How [crate::Embeddings::create_batched] splits inputs into requests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchOptions {
+    /// Most inputs sent in a request, at most [MAX_EMBEDDING_INPUTS]
+    pub max_items_per_request: usize,
+    /// Most requests in flight at once, on top of the concurrency limit of the client if any
+    pub max_concurrency: usize,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            max_items_per_request: MAX_EMBEDDING_INPUTS,
+            max_concurrency: 4,
+        }
+    }
+}
+
+/// Embeddings of the inputs of one request of [crate::Embeddings::create_batched]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddingChunk {
+    /// Indices of the inputs of the request
+    pub inputs: Range<usize>,
+    /// Embeddings in the order of the inputs
+    pub embeddings: Vec<Vec<f32>>,
+    pub usage: EmbeddingUsage,
+}
+
+/// A request of [crate::Embeddings::create_batched] which failed after its retries
+#[derive(Debug)]
+pub struct FailedEmbeddingChunk {
+    /// Indices of the inputs of the request
+    pub inputs: Range<usize>,
+    pub error: OpenAIError,
+}
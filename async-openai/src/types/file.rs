@@ -17,6 +17,7 @@ pub enum FilePurpose {
     #[default]
     FineTune,
     Vision,
+    UserData,
 }
 
 #[derive(Debug, Default, Clone, Builder, PartialEq)]
@@ -31,7 +32,7 @@ pub struct CreateFileRequest {
 
     /// The intended purpose of the uploaded file.
     ///
-    /// Use "assistants" for [Assistants](https://platform.openai.com/docs/api-reference/assistants) and [Message](https://platform.openai.com/docs/api-reference/messages) files, "vision" for Assistants image file inputs, "batch" for [Batch API](https://platform.openai.com/docs/guides/batch), and "fine-tune" for [Fine-tuning](https://platform.openai.com/docs/api-reference/fine-tuning).
+    /// Use "assistants" for [Assistants](https://platform.openai.com/docs/api-reference/assistants) and [Message](https://platform.openai.com/docs/api-reference/messages) files, "vision" for Assistants image file inputs, "batch" for [Batch API](https://platform.openai.com/docs/guides/batch), and "fine-tune" for [Fine-tuning](https://platform.openai.com/docs/api-reference/fine-tuning), and "user_data" for files used as model inputs.
     pub purpose: FilePurpose,
 }
 
@@ -39,6 +40,11 @@ pub struct CreateFileRequest {
 pub struct ListFilesResponse {
     pub object: String,
     pub data: Vec<OpenAIFile>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    /// Absent from responses of servers which do not paginate files
+    #[serde(default)]
+    pub has_more: bool,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
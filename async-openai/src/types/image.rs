@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +20,12 @@ pub enum ImageSize {
     S1792x1024,
     #[serde(rename = "1024x1792")]
     S1024x1792,
+    #[serde(rename = "1536x1024")]
+    S1536x1024,
+    #[serde(rename = "1024x1536")]
+    S1024x1536,
+    #[serde(rename = "auto")]
+    Auto,
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
@@ -47,6 +55,8 @@ pub enum ImageModel {
     DallE2,
     #[serde(rename = "dall-e-3")]
     DallE3,
+    #[serde(rename = "gpt-image-1")]
+    GptImage1,
     #[serde(untagged)]
     Other(String),
 }
@@ -57,6 +67,165 @@ pub enum ImageQuality {
     #[default]
     Standard,
     HD,
+    Low,
+    Medium,
+    High,
+    Auto,
+}
+
+/// The background of the generated images, with `gpt-image-1`.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageBackground {
+    /// Requires the `png` or `webp` output format
+    Transparent,
+    Opaque,
+    #[default]
+    Auto,
+}
+
+/// The format of the generated images, with `gpt-image-1`.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageOutputFormat {
+    #[default]
+    Png,
+    Jpeg,
+    Webp,
+}
+
+/// The content moderation level of the generated images, with `gpt-image-1`.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageModeration {
+    Low,
+    #[default]
+    Auto,
+}
+
+/// How closely an edit matches the style and features of the input images, with `gpt-image-1`.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageInputFidelity {
+    #[default]
+    Low,
+    High,
+}
+
+impl From<DallE2ImageSize> for ImageSize {
+    fn from(size: DallE2ImageSize) -> Self {
+        match size {
+            DallE2ImageSize::S256x256 => Self::S256x256,
+            DallE2ImageSize::S512x512 => Self::S512x512,
+            DallE2ImageSize::S1024x1024 => Self::S1024x1024,
+        }
+    }
+}
+
+/// Check the number, quality and size of the images of an image request against its `model`
+fn validate_model_options(
+    model: &ImageModel,
+    n: Option<u8>,
+    quality: Option<&ImageQuality>,
+    size: Option<ImageSize>,
+) -> Result<(), OpenAIError> {
+    if let Some(n) = n {
+        if !(1..=10).contains(&n) {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "n must be between 1 and 10, got {n}"
+            )));
+        }
+        if n > 1 && *model == ImageModel::DallE3 {
+            return Err(OpenAIError::InvalidArgument(
+                "dall-e-3 only supports n=1".into(),
+            ));
+        }
+    }
+
+    let (qualities, sizes): (&[ImageQuality], &[ImageSize]) = match model {
+        ImageModel::DallE2 => (
+            &[ImageQuality::Standard],
+            &[
+                ImageSize::S256x256,
+                ImageSize::S512x512,
+                ImageSize::S1024x1024,
+            ],
+        ),
+        ImageModel::DallE3 => (
+            &[ImageQuality::Standard, ImageQuality::HD],
+            &[
+                ImageSize::S1024x1024,
+                ImageSize::S1792x1024,
+                ImageSize::S1024x1792,
+            ],
+        ),
+        ImageModel::GptImage1 => (
+            &[
+                ImageQuality::Low,
+                ImageQuality::Medium,
+                ImageQuality::High,
+                ImageQuality::Auto,
+            ],
+            &[
+                ImageSize::S1024x1024,
+                ImageSize::S1536x1024,
+                ImageSize::S1024x1536,
+                ImageSize::Auto,
+            ],
+        ),
+        // Options of other models are left to the API to check
+        ImageModel::Other(_) => return Ok(()),
+    };
+
+    if let Some(quality) = quality {
+        if !qualities.contains(quality) {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "quality {quality} is not supported by {model}"
+            )));
+        }
+    }
+
+    if let Some(size) = size {
+        if !sizes.contains(&size) {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "size {size} is not supported by {model}"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check the `gpt-image-1` output options of an image request
+fn validate_output_options(
+    background: Option<ImageBackground>,
+    output_format: Option<ImageOutputFormat>,
+    output_compression: Option<u8>,
+) -> Result<(), OpenAIError> {
+    // png is the default output format
+    let output_format = output_format.unwrap_or_default();
+
+    if let Some(compression) = output_compression {
+        if compression > 100 {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "output_compression must be between 0 and 100, got {compression}"
+            )));
+        }
+        if output_format == ImageOutputFormat::Png {
+            return Err(OpenAIError::InvalidArgument(
+                "output_compression requires the jpeg or webp output format".into(),
+            ));
+        }
+    }
+
+    if background == Some(ImageBackground::Transparent) && output_format == ImageOutputFormat::Jpeg
+    {
+        return Err(OpenAIError::InvalidArgument(
+            "a transparent background requires the png or webp output format".into(),
+        ));
+    }
+
+    Ok(())
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
@@ -72,7 +241,7 @@ pub enum ImageStyle {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateImageRequest {
     /// A text description of the desired image(s). The maximum length is 1000 characters for `dall-e-2`
     /// and 4000 characters for `dall-e-3`.
@@ -87,7 +256,8 @@ pub struct CreateImageRequest {
     pub n: Option<u8>, // min:1 max:10 default:1
 
     /// The quality of the image that will be generated. `hd` creates images with finer details and greater
-    /// consistency across the image. This param is only supported for `dall-e-3`.
+    /// consistency across the image. `standard` and `hd` are only supported for `dall-e-3`, `low`, `medium`,
+    /// `high` and `auto` for `gpt-image-1`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quality: Option<ImageQuality>,
 
@@ -97,6 +267,7 @@ pub struct CreateImageRequest {
 
     /// The size of the generated images. Must be one of `256x256`, `512x512`, or `1024x1024` for `dall-e-2`.
     /// Must be one of `1024x1024`, `1792x1024`, or `1024x1792` for `dall-e-3` models.
+    /// Must be one of `1024x1024`, `1536x1024`, `1024x1536`, or `auto` for `gpt-image-1`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<ImageSize>,
 
@@ -107,11 +278,48 @@ pub struct CreateImageRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style: Option<ImageStyle>,
 
+    /// The background of the generated images. `transparent` requires the `png` or `webp` output format.
+    /// This param is only supported for `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<ImageBackground>,
+
+    /// The format of the generated images. This param is only supported for `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<ImageOutputFormat>,
+
+    /// The compression level, from 0 to 100, of the generated images. This param is only supported
+    /// for `gpt-image-1` with the `jpeg` or `webp` output format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_compression: Option<u8>,
+
+    /// The content moderation level of the generated images. This param is only supported for `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moderation: Option<ImageModeration>,
+
     /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/usage-policies/end-user-ids).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
 }
 
+impl CreateImageRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        // dall-e-2 is the default model
+        let model = self.model.clone().flatten().unwrap_or_default();
+        validate_model_options(
+            &model,
+            self.n.flatten(),
+            self.quality.as_ref().and_then(Option::as_ref),
+            self.size.flatten(),
+        )?;
+
+        validate_output_options(
+            self.background.flatten(),
+            self.output_format.flatten(),
+            self.output_compression.flatten(),
+        )
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum Image {
@@ -131,6 +339,46 @@ pub enum Image {
 pub struct ImagesResponse {
     pub created: u32,
     pub data: Vec<std::sync::Arc<Image>>,
+
+    /// The background of the generated images, only returned by `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<ImageBackground>,
+
+    /// The format of the generated images, only returned by `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<ImageOutputFormat>,
+
+    /// The quality of the generated images, only returned by `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<ImageQuality>,
+
+    /// The size of the generated images, only returned by `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<ImageSize>,
+
+    /// The token usage of the request, only returned by `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<ImageUsage>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct ImageUsage {
+    /// The number of tokens of the input prompt and images.
+    pub input_tokens: u32,
+    /// The number of image tokens generated.
+    pub output_tokens: u32,
+    /// The total number of tokens used.
+    pub total_tokens: u32,
+    /// The breakdown of the input tokens.
+    pub input_tokens_details: ImageInputTokensDetails,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct ImageInputTokensDetails {
+    /// The number of text tokens of the prompt.
+    pub text_tokens: u32,
+    /// The number of image tokens of the input images.
+    pub image_tokens: u32,
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -138,15 +386,77 @@ pub struct ImageInput {
     pub source: InputSource,
 }
 
+/// Maximum number of images of an edit, with `gpt-image-1`
+pub const MAX_EDIT_IMAGES: usize = 16;
+
+/// The image, or images with `gpt-image-1`, of an image edit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageEditInput {
+    Image(ImageInput),
+    /// Up to [MAX_EDIT_IMAGES] images, sent as `image[]`
+    Images(Vec<ImageInput>),
+}
+
+impl Default for ImageEditInput {
+    fn default() -> Self {
+        Self::Image(ImageInput::default())
+    }
+}
+
+impl ImageEditInput {
+    /// The images of the edit
+    pub fn images(&self) -> &[ImageInput] {
+        match self {
+            Self::Image(image) => std::slice::from_ref(image),
+            Self::Images(images) => images,
+        }
+    }
+}
+
+impl From<ImageInput> for ImageEditInput {
+    fn from(image: ImageInput) -> Self {
+        Self::Image(image)
+    }
+}
+
+/// `impl From<$from_typ> for ImageEditInput` of a single image file path
+macro_rules! impl_edit_input_from_path {
+    ($($from_typ:ty),*) => {
+        $(
+            impl From<$from_typ> for ImageEditInput {
+                fn from(path: $from_typ) -> Self {
+                    Self::Image(ImageInput::from(path))
+                }
+            }
+        )*
+    };
+}
+
+impl_edit_input_from_path!(&str, String, &String, &Path, PathBuf, &PathBuf);
+
+impl<T: Into<ImageInput>> From<Vec<T>> for ImageEditInput {
+    fn from(images: Vec<T>) -> Self {
+        Self::Images(images.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<ImageInput>, const N: usize> From<[T; N]> for ImageEditInput {
+    fn from(images: [T; N]) -> Self {
+        Self::Images(images.into_iter().map(Into::into).collect())
+    }
+}
+
 #[derive(Debug, Clone, Default, Builder, PartialEq)]
 #[builder(name = "CreateImageEditRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateImageEditRequest {
     /// The image to edit. Must be a valid PNG file, less than 4MB, and square. If mask is not provided, image must have transparency, which will be used as the mask.
-    pub image: ImageInput,
+    ///
+    /// With `gpt-image-1`, up to 16 PNG, WEBP or JPG images of less than 25MB each, the mask applying to the first one.
+    pub image: ImageEditInput,
 
     /// A text description of the desired image(s). The maximum length is 1000 characters.
     pub prompt: String,
@@ -154,22 +464,92 @@ pub struct CreateImageEditRequest {
     /// An additional image whose fully transparent areas (e.g. where alpha is zero) indicate where `image` should be edited. Must be a valid PNG file, less than 4MB, and have the same dimensions as `image`.
     pub mask: Option<ImageInput>,
 
-    /// The model to use for image generation. Only `dall-e-2` is supported at this time.
+    /// The model to use for image generation. `dall-e-2` or `gpt-image-1`.
     pub model: Option<ImageModel>,
 
     /// The number of images to generate. Must be between 1 and 10.
     pub n: Option<u8>, // min:1 max:10 default:1
 
-    /// The size of the generated images. Must be one of `256x256`, `512x512`, or `1024x1024`.
-    pub size: Option<DallE2ImageSize>,
+    /// The quality of the generated images. Only `standard` is supported for `dall-e-2`,
+    /// `low`, `medium`, `high` and `auto` for `gpt-image-1`.
+    pub quality: Option<ImageQuality>,
+
+    /// The size of the generated images. Must be one of `256x256`, `512x512`, or `1024x1024` for `dall-e-2`,
+    /// and one of `1024x1024`, `1536x1024`, `1024x1536`, or `auto` for `gpt-image-1`.
+    pub size: Option<ImageSize>,
 
     /// The format in which the generated images are returned. Must be one of `url` or `b64_json`.
     pub response_format: Option<ImageResponseFormat>,
 
+    /// How closely the edit matches the style and features, like faces, of the input images.
+    /// This param is only supported for `gpt-image-1`.
+    pub input_fidelity: Option<ImageInputFidelity>,
+
+    /// The background of the generated images. `transparent` requires the `png` or `webp` output format.
+    /// This param is only supported for `gpt-image-1`.
+    pub background: Option<ImageBackground>,
+
+    /// The format of the generated images. This param is only supported for `gpt-image-1`.
+    pub output_format: Option<ImageOutputFormat>,
+
+    /// The compression level, from 0 to 100, of the generated images. This param is only supported
+    /// for `gpt-image-1` with the `jpeg` or `webp` output format.
+    pub output_compression: Option<u8>,
+
     /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/usage-policies/end-user-ids).
     pub user: Option<String>,
 }
 
+impl CreateImageEditRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        // dall-e-2 is the default model
+        let model = self.model.clone().flatten().unwrap_or_default();
+        validate_model_options(
+            &model,
+            self.n.flatten(),
+            self.quality.as_ref().and_then(Option::as_ref),
+            self.size.flatten(),
+        )?;
+
+        validate_output_options(
+            self.background.flatten(),
+            self.output_format.flatten(),
+            self.output_compression.flatten(),
+        )?;
+
+        let Some(ImageEditInput::Images(images)) = &self.image else {
+            return Ok(());
+        };
+
+        if images.is_empty() {
+            return Err(OpenAIError::InvalidArgument(
+                "at least one image is required".into(),
+            ));
+        }
+
+        if images.len() > MAX_EDIT_IMAGES {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "at most {MAX_EDIT_IMAGES} images can be edited, got {}",
+                images.len()
+            )));
+        }
+
+        // Several images are only supported by gpt-image-1
+        if images.len() > 1
+            && matches!(
+                &self.model,
+                None | Some(None | Some(ImageModel::DallE2 | ImageModel::DallE3))
+            )
+        {
+            return Err(OpenAIError::InvalidArgument(
+                "several images can only be edited with gpt-image-1".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Default, Clone, Builder, PartialEq)]
 #[builder(name = "CreateImageVariationRequestArgs")]
 #[builder(pattern = "mutable")]
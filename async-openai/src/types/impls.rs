@@ -3,31 +3,48 @@ use std::{
     path::{Path, PathBuf},
 };
 
+#[cfg(feature = "full")]
+use crate::{
+    config::Config,
+    download::{download_url, save_b64, save_bytes},
+    util::{create_all_dir, create_file_part, mime_type, read_input, AsyncTryFrom},
+    Client,
+};
 use crate::{
-    download::{download_url, save_b64},
     error::OpenAIError,
-    types::InputSource,
-    util::{create_all_dir, create_file_part, AsyncTryFrom},
+    types::{InputReader, InputSource},
 };
 
+use base64::{engine::general_purpose, Engine as _};
 use bytes::Bytes;
+use tokio::io::AsyncRead;
 
+#[cfg(feature = "full")]
+use super::{
+    AddUploadPartRequest, BulkOutcome, CreateChatCompletionResponse, CreateFileRequest,
+    CreateImageEditRequest, CreateImageVariationRequest, CreateSpeechResponse,
+    CreateTranscriptionRequest, CreateTranslationRequest, Image, ImageEditInput, ImagesResponse,
+};
+#[allow(deprecated)]
 use super::{
-    AddUploadPartRequest, AudioInput, AudioResponseFormat, ChatCompletionFunctionCall,
-    ChatCompletionFunctions, ChatCompletionNamedToolChoice, ChatCompletionRequestAssistantMessage,
-    ChatCompletionRequestAssistantMessageContent, ChatCompletionRequestDeveloperMessage,
-    ChatCompletionRequestDeveloperMessageContent, ChatCompletionRequestFunctionMessage,
-    ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPartAudio,
-    ChatCompletionRequestMessageContentPartImage, ChatCompletionRequestMessageContentPartText,
-    ChatCompletionRequestSystemMessage, ChatCompletionRequestSystemMessageContent,
-    ChatCompletionRequestToolMessage, ChatCompletionRequestToolMessageContent,
-    ChatCompletionRequestUserMessage, ChatCompletionRequestUserMessageContent,
-    ChatCompletionRequestUserMessageContentPart, ChatCompletionToolChoiceOption, CreateFileRequest,
-    CreateImageEditRequest, CreateImageVariationRequest, CreateMessageRequestContent,
-    CreateSpeechResponse, CreateTranscriptionRequest, CreateTranslationRequest, DallE2ImageSize,
-    EmbeddingInput, FileInput, FilePurpose, FunctionName, Image, ImageInput, ImageModel,
-    ImageResponseFormat, ImageSize, ImageUrl, ImagesResponse, ModerationInput, Prompt, Role, Stop,
-    TimestampGranularity,
+    AudioInput, AudioResponseFormat, ChatCompletionAudio, ChatCompletionAudioFormat,
+    ChatCompletionAudioVoice, ChatCompletionFunctionCall, ChatCompletionFunctions,
+    ChatCompletionNamedToolChoice, ChatCompletionRequestAssistantMessage,
+    ChatCompletionRequestAssistantMessageAudio, ChatCompletionRequestAssistantMessageContent,
+    ChatCompletionRequestDeveloperMessage, ChatCompletionRequestDeveloperMessageContent,
+    ChatCompletionRequestFunctionMessage, ChatCompletionRequestMessage,
+    ChatCompletionRequestMessageContentPartAudio, ChatCompletionRequestMessageContentPartImage,
+    ChatCompletionRequestMessageContentPartText, ChatCompletionRequestSystemMessage,
+    ChatCompletionRequestSystemMessageContent, ChatCompletionRequestToolMessage,
+    ChatCompletionRequestToolMessageContent, ChatCompletionRequestUserMessage,
+    ChatCompletionRequestUserMessageContent, ChatCompletionRequestUserMessageContentPart,
+    ChatCompletionResponseMessage, ChatCompletionResponseMessageAudio,
+    ChatCompletionToolChoiceOption, CreateMessageRequestContent, CreateTranscriptionResponseJson,
+    DallE2ImageSize, EmbeddingInput, FileInput, FilePurpose, FunctionName, ImageBackground,
+    ImageInput, ImageInputFidelity, ImageModel, ImageOutputFormat, ImageQuality,
+    ImageResponseFormat, ImageSize, ImageUrl, InputAudio, InputAudioFormat, ModerationInput,
+    PredictionContent, PredictionContentContent, PredictionContentPart, Prompt, Role,
+    SpeechResponseFormat, Stop, TimestampGranularity, TranscriptionInclude, TranscriptionLogprob,
 };
 
 /// for `impl_from!(T, Enum)`, implements
@@ -126,7 +143,7 @@ impl Default for InputSource {
 ///     source: InputSource
 /// }
 /// ```
-/// implements methods `from_bytes` and `from_vec_u8`,
+/// implements methods `from_bytes`, `from_vec_u8` and `from_reader`,
 /// and `From<P>` for `P: AsRef<Path>`
 macro_rules! impl_input {
     ($for_typ:ty) => {
@@ -142,6 +159,22 @@ macro_rules! impl_input {
                     source: InputSource::VecU8 { filename, vec },
                 }
             }
+
+            /// Input of `length` bytes streamed from `reader` when the request is sent.
+            /// The request is not retried, see [InputReader].
+            pub fn from_reader<R: AsyncRead + Send + Sync + 'static>(
+                filename: String,
+                length: u64,
+                reader: R,
+            ) -> Self {
+                Self {
+                    source: InputSource::Reader {
+                        filename,
+                        length,
+                        reader: InputReader::new(reader),
+                    },
+                }
+            }
         }
 
         impl<P: AsRef<Path>> From<P> for $for_typ {
@@ -170,6 +203,9 @@ impl Display for ImageSize {
                 Self::S1024x1024 => "1024x1024",
                 Self::S1792x1024 => "1792x1024",
                 Self::S1024x1792 => "1024x1792",
+                Self::S1536x1024 => "1536x1024",
+                Self::S1024x1536 => "1024x1536",
+                Self::Auto => "auto",
             }
         )
     }
@@ -197,6 +233,7 @@ impl Display for ImageModel {
             match self {
                 Self::DallE2 => "dall-e-2",
                 Self::DallE3 => "dall-e-3",
+                Self::GptImage1 => "gpt-image-1",
                 Self::Other(other) => other,
             }
         )
@@ -216,6 +253,64 @@ impl Display for ImageResponseFormat {
     }
 }
 
+impl Display for ImageQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Standard => "standard",
+                Self::HD => "hd",
+                Self::Low => "low",
+                Self::Medium => "medium",
+                Self::High => "high",
+                Self::Auto => "auto",
+            }
+        )
+    }
+}
+
+impl Display for ImageBackground {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Transparent => "transparent",
+                Self::Opaque => "opaque",
+                Self::Auto => "auto",
+            }
+        )
+    }
+}
+
+impl Display for ImageOutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Png => "png",
+                Self::Jpeg => "jpeg",
+                Self::Webp => "webp",
+            }
+        )
+    }
+}
+
+impl Display for ImageInputFidelity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Low => "low",
+                Self::High => "high",
+            }
+        )
+    }
+}
+
 impl Display for AudioResponseFormat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -227,6 +322,7 @@ impl Display for AudioResponseFormat {
                 AudioResponseFormat::Text => "text",
                 AudioResponseFormat::VerboseJson => "verbose_json",
                 AudioResponseFormat::Vtt => "vtt",
+                AudioResponseFormat::DiarizedJson => "diarized_json",
             }
         )
     }
@@ -245,6 +341,40 @@ impl Display for TimestampGranularity {
     }
 }
 
+impl Display for TranscriptionInclude {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                TranscriptionInclude::Logprobs => "logprobs",
+            }
+        )
+    }
+}
+
+impl CreateTranscriptionResponseJson {
+    /// Average log probability of the tokens of the transcript,
+    /// `None` without [Self::logprobs] or tokens
+    pub fn avg_logprob(&self) -> Option<f32> {
+        let logprobs = self
+            .logprobs
+            .as_ref()
+            .filter(|logprobs| !logprobs.is_empty())?;
+        Some(logprobs.iter().map(|logprob| logprob.logprob).sum::<f32>() / logprobs.len() as f32)
+    }
+
+    /// Tokens of the transcript with a log probability below `threshold`,
+    /// like `(0.5_f32).ln()` for tokens less than 50% likely
+    pub fn low_confidence_tokens(&self, threshold: f32) -> Vec<&TranscriptionLogprob> {
+        self.logprobs
+            .iter()
+            .flatten()
+            .filter(|logprob| logprob.logprob < threshold)
+            .collect()
+    }
+}
+
 impl Display for Role {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -271,11 +401,13 @@ impl Display for FilePurpose {
                 Self::Batch => "batch",
                 Self::FineTune => "fine-tune",
                 Self::Vision => "vision",
+                Self::UserData => "user_data",
             }
         )
     }
 }
 
+#[cfg(feature = "full")]
 impl ImagesResponse {
     /// Save each image in a dedicated Tokio task and return paths to saved files.
     /// For [ResponseFormat::Url] each file is downloaded in dedicated Tokio task.
@@ -288,51 +420,215 @@ impl ImagesResponse {
             handles.push(tokio::spawn(async move { id.save(dir_buf).await }));
         }
 
-        let results = futures::future::join_all(handles).await;
-        let mut errors = vec![];
-        let mut paths = vec![];
+        let outcome: BulkOutcome<PathBuf> = futures::future::join_all(handles)
+            .await
+            .into_iter()
+            .map(|result| result.map_err(|e| OpenAIError::FileSaveError(e.to_string()))?)
+            .collect();
 
-        for result in results {
-            match result {
-                Ok(inner) => match inner {
-                    Ok(path) => paths.push(path),
-                    Err(e) => errors.push(e),
-                },
-                Err(e) => errors.push(OpenAIError::FileSaveError(e.to_string())),
+        outcome.into_result().map_err(|failures| {
+            OpenAIError::FileSaveError(
+                failures
+                    .into_iter()
+                    .map(|(_, e)| e.to_string())
+                    .collect::<Vec<String>>()
+                    .join("; "),
+            )
+        })
+    }
+
+    /// Save the images in `dir`, created if needed, and return their paths in the order of
+    /// [ImagesResponse::data]. Files are named after [ImagesResponse::created] and the index
+    /// of the image, with the extension of the image content, like `1713833628_0.png`.
+    ///
+    /// URL images are downloaded with the HTTP client of `client`.
+    pub async fn save_all<C: Config, P: AsRef<Path>>(
+        &self,
+        client: &Client<C>,
+        dir: P,
+    ) -> Result<Vec<PathBuf>, OpenAIError> {
+        let dir = dir.as_ref();
+        create_all_dir(dir)?;
+
+        let default_extension = self.output_format.unwrap_or_default().to_string();
+        let saves = self.data.iter().enumerate().map(|(index, image)| {
+            let default_extension = default_extension.as_str();
+            async move {
+                let bytes = image.bytes(client).await?;
+                let extension = sniff_image_mime(&bytes)
+                    .map(|mime| mime.trim_start_matches("image/"))
+                    .unwrap_or(default_extension);
+                let path = dir.join(format!("{}_{index}.{extension}", self.created));
+                save_bytes(&bytes, &path).await?;
+                Ok(path)
             }
-        }
+        });
+
+        let outcome: BulkOutcome<PathBuf> =
+            futures::future::join_all(saves).await.into_iter().collect();
 
-        if errors.is_empty() {
-            Ok(paths)
-        } else {
-            Err(OpenAIError::FileSaveError(
-                errors
+        outcome.into_result().map_err(|failures| {
+            OpenAIError::FileSaveError(
+                failures
                     .into_iter()
-                    .map(|e| e.to_string())
+                    .map(|(index, e)| format!("image {index}: {e}"))
                     .collect::<Vec<String>>()
                     .join("; "),
-            ))
+            )
+        })
+    }
+}
+
+impl SpeechResponseFormat {
+    /// File extension for audio in this format, without the leading dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SpeechResponseFormat::Mp3 => "mp3",
+            SpeechResponseFormat::Opus => "opus",
+            SpeechResponseFormat::Aac => "aac",
+            SpeechResponseFormat::Flac => "flac",
+            SpeechResponseFormat::Pcm => "pcm",
+            SpeechResponseFormat::Wav => "wav",
+        }
+    }
+
+    /// `path` with the extension of this format if it has none.
+    /// An extension of another format is kept, with a warning.
+    #[cfg(feature = "full")]
+    pub(crate) fn file_path(&self, path: &Path) -> PathBuf {
+        match path.extension() {
+            None => path.with_extension(self.extension()),
+            Some(extension) => {
+                if !extension.eq_ignore_ascii_case(self.extension()) {
+                    tracing::warn!(
+                        "saving {} audio to {}, which has the extension of another format",
+                        self.extension(),
+                        path.display()
+                    );
+                }
+                path.to_path_buf()
+            }
         }
     }
 }
 
+#[cfg(feature = "full")]
 impl CreateSpeechResponse {
-    pub async fn save<P: AsRef<Path>>(&self, file_path: P) -> Result<(), OpenAIError> {
-        let dir = file_path.as_ref().parent();
+    /// Save the audio to `file_path`, creating its parent directories. The extension of
+    /// the `response_format` is added when `file_path` has none; a different extension
+    /// is kept but logged as a warning. Returns the path of the saved file.
+    pub async fn save<P: AsRef<Path>>(&self, file_path: P) -> Result<PathBuf, OpenAIError> {
+        let path = self.response_format.file_path(file_path.as_ref());
+        if let Some(dir) = path.parent() {
+            create_all_dir(dir)?;
+        }
+
+        tokio::fs::write(&path, &self.bytes)
+            .await
+            .map_err(|e| OpenAIError::FileSaveError(format!("{}, path: {}", e, path.display())))?;
 
-        if let Some(dir) = dir {
+        Ok(path)
+    }
+}
+
+impl ChatCompletionAudio {
+    pub fn new(voice: ChatCompletionAudioVoice, format: ChatCompletionAudioFormat) -> Self {
+        Self { voice, format }
+    }
+}
+
+impl ChatCompletionAudioFormat {
+    /// Extension of the files of this format, `pcm` for raw [ChatCompletionAudioFormat::Pcm16]
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Mp3 => "mp3",
+            Self::Flac => "flac",
+            Self::Opus => "opus",
+            Self::Pcm16 => "pcm",
+        }
+    }
+}
+
+impl ChatCompletionResponseMessageAudio {
+    /// Decoded audio, in the format of the request
+    pub fn bytes(&self) -> Result<Bytes, OpenAIError> {
+        general_purpose::STANDARD
+            .decode(&self.data)
+            .map(Bytes::from)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid base64 audio data: {e}")))
+    }
+
+    /// Save the decoded audio to `file_path`, with the extension of `format`,
+    /// the format of the request. Returns the path of the saved file.
+    #[cfg(feature = "full")]
+    pub async fn save<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        format: ChatCompletionAudioFormat,
+    ) -> Result<PathBuf, OpenAIError> {
+        let path = file_path.as_ref().with_extension(format.extension());
+        if let Some(dir) = path.parent() {
             create_all_dir(dir)?;
         }
 
-        tokio::fs::write(file_path, &self.bytes)
+        tokio::fs::write(&path, self.bytes()?)
             .await
-            .map_err(|e| OpenAIError::FileSaveError(e.to_string()))?;
+            .map_err(|e| OpenAIError::FileSaveError(format!("{}, path: {}", e, path.display())))?;
 
-        Ok(())
+        Ok(path)
     }
 }
 
+#[cfg(feature = "full")]
+impl CreateChatCompletionResponse {
+    /// Save the audio of every choice, requested with the `audio` output modality in `format`.
+    /// With several choices, the index of each choice is appended to the file name, like
+    /// `answer_1.mp3`. Returns the paths of the saved files in the order of the choices.
+    pub async fn save_audio<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        format: ChatCompletionAudioFormat,
+    ) -> Result<Vec<PathBuf>, OpenAIError> {
+        if self.choices.is_empty() {
+            return Err(OpenAIError::FileSaveError("response has no choices".into()));
+        }
+
+        let file_path = file_path.as_ref();
+        let mut paths = Vec::with_capacity(self.choices.len());
+        for choice in &self.choices {
+            let audio = choice.message.audio.as_ref().ok_or_else(|| {
+                OpenAIError::FileSaveError(format!("choice {} has no audio", choice.index))
+            })?;
+
+            let path = if self.choices.len() == 1 {
+                file_path.to_path_buf()
+            } else {
+                let stem = file_path.file_stem().unwrap_or_default().to_string_lossy();
+                file_path.with_file_name(format!("{stem}_{}", choice.index))
+            };
+            paths.push(audio.save(path, format.clone()).await?);
+        }
+
+        Ok(paths)
+    }
+}
+
+#[cfg(feature = "full")]
 impl Image {
+    /// Content of the image, decoded from base64 or downloaded with the HTTP client of `client`
+    pub async fn bytes<C: Config>(&self, client: &Client<C>) -> Result<Bytes, OpenAIError> {
+        match self {
+            Image::Url { url, .. } => client.get_url(url).await,
+            Image::B64Json { b64_json, .. } => general_purpose::STANDARD
+                .decode(b64_json.as_str())
+                .map(Bytes::from)
+                .map_err(|e| {
+                    OpenAIError::InvalidArgument(format!("invalid base64 image data: {e}"))
+                }),
+        }
+    }
+
     async fn save<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf, OpenAIError> {
         match self {
             Image::Url { url, .. } => download_url(url, dir).await,
@@ -529,6 +825,7 @@ impl From<String> for ChatCompletionToolChoiceOption {
     }
 }
 
+#[allow(deprecated)]
 impl From<(String, serde_json::Value)> for ChatCompletionFunctions {
     fn from(value: (String, serde_json::Value)) -> Self {
         Self {
@@ -613,6 +910,32 @@ impl From<ChatCompletionRequestAssistantMessageContent> for ChatCompletionReques
     }
 }
 
+/// Assistant message replaying a response of the model in the next request, with
+/// its tool calls, refusal, and the id of its audio, if any.
+impl From<ChatCompletionResponseMessage> for ChatCompletionRequestAssistantMessage {
+    fn from(value: ChatCompletionResponseMessage) -> Self {
+        #[allow(deprecated)]
+        Self {
+            content: value
+                .content
+                .map(ChatCompletionRequestAssistantMessageContent::Text),
+            refusal: value.refusal,
+            name: None,
+            audio: value
+                .audio
+                .map(|audio| ChatCompletionRequestAssistantMessageAudio { id: audio.id }),
+            tool_calls: value.tool_calls,
+            function_call: value.function_call,
+        }
+    }
+}
+
+impl From<ChatCompletionResponseMessage> for ChatCompletionRequestMessage {
+    fn from(value: ChatCompletionResponseMessage) -> Self {
+        Self::Assistant(value.into())
+    }
+}
+
 impl From<&str> for ChatCompletionRequestUserMessageContent {
     fn from(value: &str) -> Self {
         ChatCompletionRequestUserMessageContent::Text(value.into())
@@ -673,6 +996,26 @@ impl From<String> for ChatCompletionRequestToolMessageContent {
     }
 }
 
+impl From<&str> for PredictionContent {
+    fn from(value: &str) -> Self {
+        PredictionContent::Content(PredictionContentContent::Text(value.into()))
+    }
+}
+
+impl From<String> for PredictionContent {
+    fn from(value: String) -> Self {
+        PredictionContent::Content(PredictionContentContent::Text(value))
+    }
+}
+
+impl From<Vec<ChatCompletionRequestMessageContentPartText>> for PredictionContent {
+    fn from(value: Vec<ChatCompletionRequestMessageContentPartText>) -> Self {
+        PredictionContent::Content(PredictionContentContent::Array(
+            value.into_iter().map(PredictionContentPart::Text).collect(),
+        ))
+    }
+}
+
 impl From<&str> for ChatCompletionRequestUserMessage {
     fn from(value: &str) -> Self {
         ChatCompletionRequestUserMessageContent::Text(value.into()).into()
@@ -783,6 +1126,145 @@ impl From<String> for ImageUrl {
     }
 }
 
+/// MIME types of the images accepted as base64 data
+const IMAGE_MIME_TYPES: [&str; 4] = ["image/png", "image/jpeg", "image/webp", "image/gif"];
+
+/// MIME type of an image from its magic bytes
+#[cfg(feature = "full")]
+fn sniff_image_mime(bytes: &[u8]) -> Option<&'static str> {
+    match bytes {
+        [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n', ..] => Some("image/png"),
+        [0xff, 0xd8, 0xff, ..] => Some("image/jpeg"),
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some("image/gif"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        _ => None,
+    }
+}
+
+fn check_image_mime(mime: &str) -> Result<(), OpenAIError> {
+    if IMAGE_MIME_TYPES.contains(&mime) {
+        Ok(())
+    } else {
+        Err(OpenAIError::InvalidArgument(format!(
+            "unsupported image type {mime}, expected one of {}",
+            IMAGE_MIME_TYPES.join(", ")
+        )))
+    }
+}
+
+impl ImageUrl {
+    /// Data URL of the image at `path`, a png, jpeg, webp or gif image
+    /// identified by its content.
+    #[cfg(feature = "full")]
+    pub async fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, OpenAIError> {
+        let path = path.as_ref();
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| OpenAIError::FileReadError(format!("{}, path: {}", e, path.display())))?;
+
+        let mime = sniff_image_mime(&bytes).ok_or_else(|| {
+            OpenAIError::InvalidArgument(format!(
+                "{} is not a png, jpeg, webp or gif image",
+                path.display()
+            ))
+        })?;
+        Self::from_bytes(bytes, mime)
+    }
+
+    /// Data URL of the image `bytes` of type `mime`, like `image/png`
+    pub fn from_bytes(bytes: impl AsRef<[u8]>, mime: &str) -> Result<Self, OpenAIError> {
+        check_image_mime(mime)?;
+        Ok(format!(
+            "data:{mime};base64,{}",
+            general_purpose::STANDARD.encode(bytes)
+        )
+        .into())
+    }
+
+    /// Data URL of the base64 encoded image `b64` of type `mime`, like `image/png`
+    pub fn from_base64(b64: impl Into<String>, mime: &str) -> Result<Self, OpenAIError> {
+        check_image_mime(mime)?;
+        let b64 = b64.into();
+        general_purpose::STANDARD
+            .decode(&b64)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid base64 image data: {e}")))?;
+        Ok(format!("data:{mime};base64,{b64}").into())
+    }
+}
+
+impl InputAudio {
+    /// Largest audio accepted by [InputAudio::from_path] and [InputAudio::from_bytes],
+    /// the 25 MB limit of the API for audio files.
+    pub const MAX_BYTES: u64 = 25 * 1024 * 1024;
+
+    /// Base64 encoded audio of the mp3 or wav file at `path`. The format is
+    /// identified by the content of the file, or else by its extension.
+    #[cfg(feature = "full")]
+    pub async fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, OpenAIError> {
+        let path = path.as_ref();
+        let read_error = |e: std::io::Error| {
+            OpenAIError::FileReadError(format!("{}, path: {}", e, path.display()))
+        };
+
+        let len = tokio::fs::metadata(path).await.map_err(read_error)?.len();
+        check_audio_len(len)?;
+        let bytes = tokio::fs::read(path).await.map_err(read_error)?;
+
+        let format = sniff_audio_format(&bytes)
+            .or_else(
+                || match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+                    "mp3" => Some(InputAudioFormat::Mp3),
+                    "wav" => Some(InputAudioFormat::Wav),
+                    _ => None,
+                },
+            )
+            .ok_or_else(|| {
+                OpenAIError::InvalidArgument(format!(
+                    "{} is not an mp3 or wav file",
+                    path.display()
+                ))
+            })?;
+        Self::from_bytes(bytes, format)
+    }
+
+    /// Base64 encoded audio of `bytes` in `format`
+    pub fn from_bytes(
+        bytes: impl AsRef<[u8]>,
+        format: InputAudioFormat,
+    ) -> Result<Self, OpenAIError> {
+        let bytes = bytes.as_ref();
+        check_audio_len(bytes.len() as u64)?;
+        Ok(Self {
+            data: general_purpose::STANDARD.encode(bytes),
+            format,
+        })
+    }
+}
+
+fn check_audio_len(len: u64) -> Result<(), OpenAIError> {
+    if len > InputAudio::MAX_BYTES {
+        return Err(OpenAIError::InvalidArgument(format!(
+            "audio of {len} bytes is larger than the limit of {} bytes",
+            InputAudio::MAX_BYTES
+        )));
+    }
+    Ok(())
+}
+
+/// Format of audio from its magic bytes
+#[cfg(feature = "full")]
+fn sniff_audio_format(bytes: &[u8]) -> Option<InputAudioFormat> {
+    match bytes {
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => {
+            Some(InputAudioFormat::Wav)
+        }
+        // ID3 tag, or the sync word of an MPEG audio frame
+        [b'I', b'D', b'3', ..] => Some(InputAudioFormat::Mp3),
+        [0xff, second, ..] if second & 0xe0 == 0xe0 => Some(InputAudioFormat::Mp3),
+        _ => None,
+    }
+}
+
 impl From<String> for CreateMessageRequestContent {
     fn from(value: String) -> Self {
         Self::Content(value)
@@ -827,6 +1309,7 @@ impl Default for ChatCompletionRequestToolMessageContent {
 
 // start: types to multipart from
 
+#[cfg(feature = "full")]
 impl AsyncTryFrom<CreateTranscriptionRequest> for reqwest::multipart::Form {
     type Error = OpenAIError;
 
@@ -859,10 +1342,84 @@ impl AsyncTryFrom<CreateTranscriptionRequest> for reqwest::multipart::Form {
             }
         }
 
+        if let Some(include) = request.include {
+            for include in include {
+                form = form.text("include[]", include.to_string());
+            }
+        }
+
+        if let Some(known_speakers) = request.known_speakers {
+            for speaker in known_speakers {
+                let reference = known_speaker_reference(speaker.reference_audio.source).await?;
+                form = form
+                    .text("known_speaker_names[]", speaker.name)
+                    .text("known_speaker_references[]", reference);
+            }
+        }
+
         Ok(form)
     }
 }
 
+/// Data URL of the reference audio of a [super::KnownSpeaker], which must be
+/// 2 to 10 seconds long. The duration is only checked for WAV files.
+#[cfg(feature = "full")]
+async fn known_speaker_reference(source: InputSource) -> Result<String, OpenAIError> {
+    let (file_name, bytes) = read_input(source).await?;
+    if bytes.is_empty() {
+        return Err(OpenAIError::InvalidArgument(format!(
+            "known speaker reference {file_name} is empty"
+        )));
+    }
+
+    if let Some(duration) = wav_duration(&bytes) {
+        if !(2.0..=10.0).contains(&duration) {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "known speaker reference {file_name} must be 2 to 10 seconds long, got {duration:.2}s"
+            )));
+        }
+    }
+
+    Ok(format!(
+        "data:{};base64,{}",
+        mime_type(&file_name),
+        general_purpose::STANDARD.encode(&bytes)
+    ))
+}
+
+/// Duration in seconds of WAV audio, `None` when `bytes` is not a WAV file
+#[cfg(feature = "full")]
+fn wav_duration(bytes: &[u8]) -> Option<f64> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return None;
+    }
+
+    let mut byte_rate = None;
+    let mut chunks = &bytes[12..];
+    while chunks.len() >= 8 {
+        let size = u32::from_le_bytes(chunks[4..8].try_into().unwrap()) as usize;
+        let data = &chunks[8..];
+        match &chunks[0..4] {
+            b"fmt " if data.len() >= 12 => {
+                byte_rate = Some(u32::from_le_bytes(data[8..12].try_into().unwrap()));
+            }
+            // The size of streamed WAV can be unknown, use the remaining bytes
+            b"data" => {
+                let size = size.min(data.len());
+                return byte_rate
+                    .filter(|byte_rate| *byte_rate > 0)
+                    .map(|byte_rate| size as f64 / byte_rate as f64);
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even size
+        chunks = data.get(size + size % 2..)?;
+    }
+
+    None
+}
+
+#[cfg(feature = "full")]
 impl AsyncTryFrom<CreateTranslationRequest> for reqwest::multipart::Form {
     type Error = OpenAIError;
 
@@ -888,15 +1445,24 @@ impl AsyncTryFrom<CreateTranslationRequest> for reqwest::multipart::Form {
     }
 }
 
+#[cfg(feature = "full")]
 impl AsyncTryFrom<CreateImageEditRequest> for reqwest::multipart::Form {
     type Error = OpenAIError;
 
     async fn try_from(request: CreateImageEditRequest) -> Result<Self, Self::Error> {
-        let image_part = create_file_part(request.image.source).await?;
-
-        let mut form = reqwest::multipart::Form::new()
-            .part("image", image_part)
-            .text("prompt", request.prompt);
+        let mut form = match request.image {
+            ImageEditInput::Image(image) => {
+                reqwest::multipart::Form::new().part("image", create_file_part(image.source).await?)
+            }
+            ImageEditInput::Images(images) => {
+                let mut form = reqwest::multipart::Form::new();
+                for image in images {
+                    form = form.part("image[]", create_file_part(image.source).await?);
+                }
+                form
+            }
+        };
+        form = form.text("prompt", request.prompt);
 
         if let Some(mask) = request.mask {
             let mask_part = create_file_part(mask.source).await?;
@@ -907,28 +1473,46 @@ impl AsyncTryFrom<CreateImageEditRequest> for reqwest::multipart::Form {
             form = form.text("model", model.to_string())
         }
 
-        if request.n.is_some() {
-            form = form.text("n", request.n.unwrap().to_string())
+        if let Some(n) = request.n {
+            form = form.text("n", n.to_string())
         }
 
-        if request.size.is_some() {
-            form = form.text("size", request.size.unwrap().to_string())
+        if let Some(quality) = request.quality {
+            form = form.text("quality", quality.to_string())
         }
 
-        if request.response_format.is_some() {
-            form = form.text(
-                "response_format",
-                request.response_format.unwrap().to_string(),
-            )
+        if let Some(size) = request.size {
+            form = form.text("size", size.to_string())
+        }
+
+        if let Some(response_format) = request.response_format {
+            form = form.text("response_format", response_format.to_string())
+        }
+
+        if let Some(input_fidelity) = request.input_fidelity {
+            form = form.text("input_fidelity", input_fidelity.to_string())
+        }
+
+        if let Some(background) = request.background {
+            form = form.text("background", background.to_string())
+        }
+
+        if let Some(output_format) = request.output_format {
+            form = form.text("output_format", output_format.to_string())
+        }
+
+        if let Some(output_compression) = request.output_compression {
+            form = form.text("output_compression", output_compression.to_string())
         }
 
-        if request.user.is_some() {
-            form = form.text("user", request.user.unwrap())
+        if let Some(user) = request.user {
+            form = form.text("user", user)
         }
         Ok(form)
     }
 }
 
+#[cfg(feature = "full")]
 impl AsyncTryFrom<CreateImageVariationRequest> for reqwest::multipart::Form {
     type Error = OpenAIError;
 
@@ -941,28 +1525,26 @@ impl AsyncTryFrom<CreateImageVariationRequest> for reqwest::multipart::Form {
             form = form.text("model", model.to_string())
         }
 
-        if request.n.is_some() {
-            form = form.text("n", request.n.unwrap().to_string())
+        if let Some(n) = request.n {
+            form = form.text("n", n.to_string())
         }
 
-        if request.size.is_some() {
-            form = form.text("size", request.size.unwrap().to_string())
+        if let Some(size) = request.size {
+            form = form.text("size", size.to_string())
         }
 
-        if request.response_format.is_some() {
-            form = form.text(
-                "response_format",
-                request.response_format.unwrap().to_string(),
-            )
+        if let Some(response_format) = request.response_format {
+            form = form.text("response_format", response_format.to_string())
         }
 
-        if request.user.is_some() {
-            form = form.text("user", request.user.unwrap())
+        if let Some(user) = request.user {
+            form = form.text("user", user)
         }
         Ok(form)
     }
 }
 
+#[cfg(feature = "full")]
 impl AsyncTryFrom<CreateFileRequest> for reqwest::multipart::Form {
     type Error = OpenAIError;
 
@@ -975,6 +1557,7 @@ impl AsyncTryFrom<CreateFileRequest> for reqwest::multipart::Form {
     }
 }
 
+#[cfg(feature = "full")]
 impl AsyncTryFrom<AddUploadPartRequest> for reqwest::multipart::Form {
     type Error = OpenAIError;
 
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
-use super::{ImageDetail, ImageUrl};
+use super::{ImageDetail, ImageUrl, Metadata};
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "lowercase")]
@@ -229,13 +229,14 @@ pub struct CreateMessageRequest {
     pub attachments: Option<Vec<MessageAttachment>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    #[builder(try_setter)]
+    pub metadata: Option<Metadata>,
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
 pub struct ModifyMessageRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
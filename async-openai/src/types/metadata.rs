@@ -0,0 +1,260 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::OpenAIError;
+
+/// Set of up to 16 key-value pairs that can be attached to an object, to store
+/// additional information about it in a structured format, and to query for
+/// objects via API or the dashboard.
+///
+/// Keys are strings of at most 64 characters, and values are strings of at most
+/// 512 characters. These limits are checked when building metadata, instead of
+/// by a `400` of the API.
+///
+/// ```
+/// use async_openai::types::Metadata;
+/// use serde_json::json;
+///
+/// let metadata = Metadata::try_from(json!({"department": "accounting"})).unwrap();
+/// assert_eq!(metadata.get("department"), Some("accounting"));
+///
+/// assert!(Metadata::try_from(json!({"nested": {"key": "value"}})).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "BTreeMap<String, String>")]
+pub struct Metadata(BTreeMap<String, String>);
+
+impl Metadata {
+    /// Maximum number of key-value pairs
+    pub const MAX_PAIRS: usize = 16;
+    /// Maximum number of characters of a key
+    pub const MAX_KEY_LEN: usize = 64;
+    /// Maximum number of characters of a value
+    pub const MAX_VALUE_LEN: usize = 512;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a pair, returning the previous value of `key`, or an error if the
+    /// pair is over the limits of the API.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Result<Option<String>, OpenAIError> {
+        let (key, value) = (key.into(), value.into());
+        check_pair(&key, &value)?;
+        if self.0.len() >= Self::MAX_PAIRS && !self.0.contains_key(&key) {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "metadata can have at most {} pairs",
+                Self::MAX_PAIRS
+            )));
+        }
+
+        Ok(self.0.insert(key, value))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.0.remove(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_inner(self) -> BTreeMap<String, String> {
+        self.0
+    }
+}
+
+fn check_pair(key: &str, value: &str) -> Result<(), OpenAIError> {
+    if key.chars().count() > Metadata::MAX_KEY_LEN {
+        return Err(OpenAIError::InvalidArgument(format!(
+            "metadata key {key} is longer than {} characters",
+            Metadata::MAX_KEY_LEN
+        )));
+    }
+    if value.chars().count() > Metadata::MAX_VALUE_LEN {
+        return Err(OpenAIError::InvalidArgument(format!(
+            "metadata value of {key} is longer than {} characters",
+            Metadata::MAX_VALUE_LEN
+        )));
+    }
+    Ok(())
+}
+
+fn from_pairs<K: Into<String>, V: Into<String>>(
+    pairs: impl IntoIterator<Item = (K, V)>,
+) -> Result<Metadata, OpenAIError> {
+    let mut metadata = Metadata::new();
+    for (key, value) in pairs {
+        metadata.insert(key, value)?;
+    }
+    Ok(metadata)
+}
+
+impl TryFrom<BTreeMap<String, String>> for Metadata {
+    type Error = OpenAIError;
+
+    fn try_from(value: BTreeMap<String, String>) -> Result<Self, Self::Error> {
+        from_pairs(value)
+    }
+}
+
+impl TryFrom<HashMap<String, String>> for Metadata {
+    type Error = OpenAIError;
+
+    fn try_from(value: HashMap<String, String>) -> Result<Self, Self::Error> {
+        from_pairs(value)
+    }
+}
+
+impl<K: Into<String>, V: Into<String>, const N: usize> TryFrom<[(K, V); N]> for Metadata {
+    type Error = OpenAIError;
+
+    fn try_from(value: [(K, V); N]) -> Result<Self, Self::Error> {
+        from_pairs(value)
+    }
+}
+
+/// Metadata of a JSON object of strings, like `json!({"department": "accounting"})`
+impl TryFrom<serde_json::Value> for Metadata {
+    type Error = OpenAIError;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        let serde_json::Value::Object(object) = value else {
+            return Err(OpenAIError::InvalidArgument(
+                "metadata must be a JSON object".into(),
+            ));
+        };
+
+        let pairs = object
+            .into_iter()
+            .map(|(key, value)| match value {
+                serde_json::Value::String(value) => Ok((key, value)),
+                _ => Err(OpenAIError::InvalidArgument(format!(
+                    "metadata value of {key} must be a string"
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        from_pairs(pairs)
+    }
+}
+
+impl From<Metadata> for BTreeMap<String, String> {
+    fn from(value: Metadata) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::types::CreateChatCompletionRequestArgs;
+
+    fn is_invalid<T>(result: Result<T, OpenAIError>) -> bool {
+        matches!(result, Err(OpenAIError::InvalidArgument(_)))
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut metadata = Metadata::new();
+        for i in 0..Metadata::MAX_PAIRS {
+            metadata.insert(format!("key_{i}"), "value").unwrap();
+        }
+        assert!(is_invalid(metadata.insert("one_more", "value")));
+        // Replacing a value does not add a pair
+        assert_eq!(
+            metadata.insert("key_0", "new value").unwrap(),
+            Some("value".into())
+        );
+
+        let mut metadata = Metadata::new();
+        let key = "k".repeat(Metadata::MAX_KEY_LEN);
+        metadata.insert(key.as_str(), "value").unwrap();
+        assert!(is_invalid(metadata.insert(key + "k", "value")));
+
+        // Lengths are in characters
+        let value = "é".repeat(Metadata::MAX_VALUE_LEN);
+        metadata.insert("value", value.as_str()).unwrap();
+        assert!(is_invalid(metadata.insert("value", value + "é")));
+    }
+
+    #[test]
+    fn test_try_from_json() {
+        let metadata =
+            Metadata::try_from(json!({"source": "homepage", "role": "manager"})).unwrap();
+        assert_eq!(
+            metadata.iter().collect::<Vec<_>>(),
+            [("role", "manager"), ("source", "homepage")]
+        );
+
+        assert!(is_invalid(Metadata::try_from(
+            json!({"nested": {"a": "b"}})
+        )));
+        assert!(is_invalid(Metadata::try_from(json!({"count": 1}))));
+        assert!(is_invalid(Metadata::try_from(json!(["a", "b"]))));
+
+        let too_many: serde_json::Map<_, _> = (0..=Metadata::MAX_PAIRS)
+            .map(|i| (format!("key_{i}"), json!("value")))
+            .collect();
+        assert!(is_invalid(Metadata::try_from(serde_json::Value::Object(
+            too_many
+        ))));
+    }
+
+    #[test]
+    fn test_serde() {
+        let metadata = Metadata::try_from([("department", "accounting")]).unwrap();
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            json!({"department": "accounting"})
+        );
+        assert_eq!(
+            serde_json::from_value::<Metadata>(json!({"department": "accounting"})).unwrap(),
+            metadata
+        );
+
+        let long = "v".repeat(Metadata::MAX_VALUE_LEN + 1);
+        assert!(serde_json::from_value::<Metadata>(json!({ "key": long })).is_err());
+        assert!(serde_json::from_value::<Metadata>(json!({"key": 1})).is_err());
+    }
+
+    #[test]
+    fn test_builder_try_setter() {
+        let request = CreateChatCompletionRequestArgs::default()
+            .model("gpt-4o")
+            .try_metadata(json!({"department": "accounting"}))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.metadata.unwrap().get("department"),
+            Some("accounting")
+        );
+
+        assert!(is_invalid(
+            CreateChatCompletionRequestArgs::default()
+                .try_metadata(json!({"department": {"name": "accounting"}}))
+                .map(|_| ())
+        ));
+    }
+}
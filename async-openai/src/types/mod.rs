@@ -7,9 +7,14 @@ mod assistant_stream;
 mod audio;
 mod audit_log;
 mod batch;
+mod bulk;
+#[allow(deprecated)]
 mod chat;
+mod chat_accumulator;
+mod chat_history;
 mod common;
 mod completion;
+mod content_filter;
 mod embedding;
 mod file;
 mod fine_tuning;
@@ -17,17 +22,26 @@ mod image;
 mod invites;
 mod message;
 mod message_file;
+mod metadata;
 mod model;
 mod moderation;
+mod pagination;
 mod project_api_key;
 mod project_service_account;
 mod project_users;
 mod projects;
+#[cfg(feature = "chat-completion")]
+mod race;
 #[cfg_attr(docsrs, doc(cfg(feature = "realtime")))]
 #[cfg(feature = "realtime")]
 pub mod realtime;
+mod redact;
 mod run;
 mod step;
+mod strict_schema;
+#[cfg(feature = "schemars")]
+mod structured_output;
+mod subtitles;
 mod thread;
 mod upload;
 mod users;
@@ -39,9 +53,13 @@ pub use assistant_stream::*;
 pub use audio::*;
 pub use audit_log::*;
 pub use batch::*;
+pub use bulk::*;
 pub use chat::*;
+pub use chat_accumulator::*;
+pub use chat_history::*;
 pub use common::*;
 pub use completion::*;
+pub use content_filter::*;
 pub use embedding::*;
 pub use file::*;
 pub use fine_tuning::*;
@@ -49,14 +67,23 @@ pub use image::*;
 pub use invites::*;
 pub use message::*;
 pub use message_file::*;
+pub use metadata::*;
 pub use model::*;
 pub use moderation::*;
+pub use pagination::*;
 pub use project_api_key::*;
 pub use project_service_account::*;
 pub use project_users::*;
 pub use projects::*;
+#[cfg(feature = "chat-completion")]
+pub use race::*;
+pub use redact::*;
 pub use run::*;
 pub use step::*;
+pub use strict_schema::*;
+#[cfg(feature = "schemars")]
+pub use structured_output::*;
+pub use subtitles::*;
 pub use thread::*;
 pub use upload::*;
 pub use users::*;
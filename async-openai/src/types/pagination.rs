@@ -0,0 +1,133 @@
+use super::{
+    AssistantObject, Batch, ChatCompletionList, ChatCompletionMessageList,
+    ChatCompletionStoredMessage, CreateChatCompletionResponse, FineTuningJob,
+    FineTuningJobCheckpoint, ListAssistantsResponse, ListBatchesResponse, ListFilesResponse,
+    ListFineTuningJobCheckpointsResponse, ListMessagesResponse,
+    ListPaginatedFineTuningJobsResponse, ListRunStepsResponse, ListRunsResponse,
+    ListVectorStoreFilesResponse, ListVectorStoresResponse, MessageObject, OpenAIFile, RunObject,
+    RunStepObject, VectorStoreFileObject, VectorStoreObject,
+};
+
+/// Page of a list endpoint paginated with an `after` cursor,
+/// like [ListFilesResponse] or [ListBatchesResponse].
+pub trait Paginated {
+    type Item;
+
+    /// Items of the page
+    fn items(&self) -> &[Self::Item];
+
+    /// Consume the page into its items
+    fn into_items(self) -> Vec<Self::Item>;
+
+    /// Whether there are more items after this page
+    fn has_more(&self) -> bool;
+
+    /// Cursor to pass as `after` to fetch the next page
+    fn next_cursor(&self) -> Option<&str>;
+}
+
+/// Options of the `list_all` methods, like [crate::Files::list_all]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pagination {
+    /// Number of items requested per page as `limit`, the API default when `None`
+    pub page_size: Option<u32>,
+    /// Stop after this many items, fetching no more pages than needed
+    pub max_items: Option<usize>,
+}
+
+impl Pagination {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+}
+
+/// Implement [Paginated] for pages whose cursor is their `last_id` field
+macro_rules! impl_paginated {
+    ($($page:ty => $item:ty),+ $(,)?) => {
+        $(
+            impl Paginated for $page {
+                type Item = $item;
+
+                fn items(&self) -> &[Self::Item] {
+                    &self.data
+                }
+
+                fn into_items(self) -> Vec<Self::Item> {
+                    self.data
+                }
+
+                fn has_more(&self) -> bool {
+                    self.has_more
+                }
+
+                fn next_cursor(&self) -> Option<&str> {
+                    self.last_id.as_deref()
+                }
+            }
+        )+
+    };
+}
+
+impl_paginated!(
+    ListFilesResponse => OpenAIFile,
+    ListBatchesResponse => Batch,
+    ChatCompletionList => CreateChatCompletionResponse,
+    ChatCompletionMessageList => ChatCompletionStoredMessage,
+    ListAssistantsResponse => AssistantObject,
+    ListMessagesResponse => MessageObject,
+    ListRunsResponse => RunObject,
+    ListRunStepsResponse => RunStepObject,
+    ListVectorStoresResponse => VectorStoreObject,
+    ListFineTuningJobCheckpointsResponse => FineTuningJobCheckpoint,
+);
+
+impl Paginated for ListVectorStoreFilesResponse {
+    type Item = VectorStoreFileObject;
+
+    fn items(&self) -> &[Self::Item] {
+        &self.data
+    }
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.data
+    }
+
+    fn has_more(&self) -> bool {
+        self.has_more
+    }
+
+    fn next_cursor(&self) -> Option<&str> {
+        Some(&self.last_id)
+    }
+}
+
+impl Paginated for ListPaginatedFineTuningJobsResponse {
+    type Item = FineTuningJob;
+
+    fn items(&self) -> &[Self::Item] {
+        &self.data
+    }
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.data
+    }
+
+    fn has_more(&self) -> bool {
+        self.has_more
+    }
+
+    /// Jobs are listed without `last_id`, the cursor is the id of the last job
+    fn next_cursor(&self) -> Option<&str> {
+        self.data.last().map(|job| job.id.as_str())
+    }
+}
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use crate::error::OpenAIError;
+
+use super::{CompletionUsage, CreateChatCompletionResponse};
+
+/// When [crate::Chat::race_models] stops waiting for the remaining requests.
+/// Requests still in flight at that point are cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RacePolicy {
+    /// Stop at the first successful response
+    FirstSuccess,
+    /// Wait for every request to complete
+    All,
+    /// Stop once this many responses succeeded
+    Quorum(usize),
+}
+
+impl RacePolicy {
+    /// Number of successful responses required, out of `requests`
+    pub(crate) fn required(&self, requests: usize) -> usize {
+        match self {
+            Self::FirstSuccess => 1,
+            Self::All => requests,
+            Self::Quorum(n) => *n,
+        }
+    }
+}
+
+/// A completed request of a race
+#[derive(Debug)]
+pub struct RaceEntry {
+    /// Index of the request in the input
+    pub index: usize,
+    /// Model of the request
+    pub model: String,
+    /// Time from sending the request to receiving its response, including retries
+    pub latency: Duration,
+    pub result: Result<CreateChatCompletionResponse, OpenAIError>,
+}
+
+impl RaceEntry {
+    /// Token usage of a successful response
+    pub fn usage(&self) -> Option<&CompletionUsage> {
+        self.result.as_ref().ok()?.usage.as_ref()
+    }
+}
+
+/// Outcome of [crate::Chat::race_models]
+#[derive(Debug)]
+pub struct RaceOutcome {
+    /// Completed requests, in completion order
+    pub completed: Vec<RaceEntry>,
+    /// Indexes of the requests cancelled once the policy was satisfied
+    pub cancelled: Vec<usize>,
+    /// Whether enough requests succeeded to satisfy the policy
+    pub satisfied: bool,
+}
+
+impl RaceOutcome {
+    /// Successful requests, in completion order
+    pub fn successes(&self) -> impl Iterator<Item = &RaceEntry> {
+        self.completed.iter().filter(|entry| entry.result.is_ok())
+    }
+
+    /// First successful request to complete
+    pub fn winner(&self) -> Option<&RaceEntry> {
+        self.successes().next()
+    }
+}
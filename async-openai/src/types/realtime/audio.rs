@@ -0,0 +1,391 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::error::OpenAIError;
+
+use super::{AudioFormat, ServerEvent};
+
+/// Decode the base64 `delta` of a `response.audio.delta` event in the
+/// [AudioFormat::PCM16] format, to 24kHz mono samples.
+pub fn decode_audio_delta(delta: &str) -> Result<Vec<i16>, OpenAIError> {
+    decode_audio(delta, &AudioFormat::PCM16)
+}
+
+/// Decode base64 audio in `format` to 16 bit samples, at the
+/// [AudioFormat::sample_rate] of the format.
+pub fn decode_audio(audio: &str, format: &AudioFormat) -> Result<Vec<i16>, OpenAIError> {
+    let bytes = STANDARD
+        .decode(audio)
+        .map_err(|e| OpenAIError::InvalidArgument(format!("invalid base64 audio: {e}")))?;
+
+    match format {
+        AudioFormat::PCM16 => {
+            if bytes.len() % 2 != 0 {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "pcm16 audio of odd length {}",
+                    bytes.len()
+                )));
+            }
+            Ok(bytes
+                .chunks_exact(2)
+                .map(|sample| i16::from_le_bytes([sample[0], sample[1]]))
+                .collect())
+        }
+        AudioFormat::G711ULAW => Ok(decode_g711_ulaw(&bytes)),
+        AudioFormat::G711ALAW => Ok(decode_g711_alaw(&bytes)),
+    }
+}
+
+/// Decode G.711 μ-law bytes to 16 bit samples
+pub fn decode_g711_ulaw(bytes: &[u8]) -> Vec<i16> {
+    bytes
+        .iter()
+        .map(|byte| {
+            let byte = !byte;
+            let magnitude = ((((byte & 0x0F) as i32) << 3) + 0x84) << ((byte & 0x70) >> 4);
+            if byte & 0x80 != 0 {
+                (0x84 - magnitude) as i16
+            } else {
+                (magnitude - 0x84) as i16
+            }
+        })
+        .collect()
+}
+
+/// Decode G.711 A-law bytes to 16 bit samples
+pub fn decode_g711_alaw(bytes: &[u8]) -> Vec<i16> {
+    bytes
+        .iter()
+        .map(|byte| {
+            let byte = byte ^ 0x55;
+            let mantissa = ((byte & 0x0F) as i32) << 4;
+            let magnitude = match (byte & 0x70) >> 4 {
+                0 => mantissa + 8,
+                segment => (mantissa + 0x108) << (segment - 1),
+            };
+            if byte & 0x80 != 0 {
+                magnitude as i16
+            } else {
+                -magnitude as i16
+            }
+        })
+        .collect()
+}
+
+/// A WAV file of 16 bit mono `samples`
+pub fn wav_bytes(samples: &[i16], sample_rate: u32) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    // PCM, 1 channel
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    // Byte rate, block align and bits per sample
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+/// Destination of the output audio of a realtime session, by response and item.
+pub trait AudioSink {
+    /// Append decoded samples to the audio of an item
+    fn append(
+        &mut self,
+        response_id: &str,
+        item_id: &str,
+        samples: &[i16],
+    ) -> Result<(), OpenAIError>;
+
+    /// The audio of an item is complete
+    fn finish(&mut self, response_id: &str, item_id: &str) -> Result<(), OpenAIError>;
+
+    /// Feed a `response.audio.delta` or `response.audio.done` event to the sink, named
+    /// `response.output_audio.*` in the GA API, with audio in the `output_audio_format`
+    /// of the session. Returns whether the event was an audio event.
+    fn handle_event(
+        &mut self,
+        event: &ServerEvent,
+        format: &AudioFormat,
+    ) -> Result<bool, OpenAIError> {
+        match event {
+            ServerEvent::ResponseAudioDelta(delta) => {
+                let samples = decode_audio(&delta.delta, format)?;
+                self.append(&delta.response_id, &delta.item_id, &samples)?;
+                Ok(true)
+            }
+            ServerEvent::ResponseAudioDone(done) => {
+                self.finish(&done.response_id, &done.item_id)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+/// Writes the audio of each item to `{response_id}_{item_id}.wav` in a directory,
+/// once the audio of the item is done.
+///
+/// ```no_run
+/// # fn run(events: Vec<async_openai::types::realtime::ServerEvent>) -> Result<(), async_openai::error::OpenAIError> {
+/// use async_openai::types::realtime::{AudioFormat, AudioSink, WavFileSink};
+///
+/// let format = AudioFormat::PCM16;
+/// let mut sink = WavFileSink::new("./data/audio", &format);
+/// for event in &events {
+///     sink.handle_event(event, &format)?;
+/// }
+/// println!("{:?}", sink.files());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct WavFileSink {
+    dir: PathBuf,
+    sample_rate: u32,
+    pending: HashMap<(String, String), Vec<i16>>,
+    files: Vec<PathBuf>,
+}
+
+impl WavFileSink {
+    /// Files are written in `dir` at the sample rate of `format`, the output audio
+    /// format of the session
+    pub fn new(dir: impl Into<PathBuf>, format: &AudioFormat) -> Self {
+        Self {
+            dir: dir.into(),
+            sample_rate: format.sample_rate(),
+            pending: HashMap::new(),
+            files: vec![],
+        }
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Paths of the files written so far
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+}
+
+impl AudioSink for WavFileSink {
+    fn append(
+        &mut self,
+        response_id: &str,
+        item_id: &str,
+        samples: &[i16],
+    ) -> Result<(), OpenAIError> {
+        self.pending
+            .entry((response_id.to_string(), item_id.to_string()))
+            .or_default()
+            .extend_from_slice(samples);
+        Ok(())
+    }
+
+    fn finish(&mut self, response_id: &str, item_id: &str) -> Result<(), OpenAIError> {
+        let samples = self
+            .pending
+            .remove(&(response_id.to_string(), item_id.to_string()))
+            .unwrap_or_default();
+
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| OpenAIError::FileSaveError(e.to_string()))?;
+        let path = self.dir.join(format!("{response_id}_{item_id}.wav"));
+        std::fs::write(&path, wav_bytes(&samples, self.sample_rate))
+            .map_err(|e| OpenAIError::FileSaveError(e.to_string()))?;
+        self.files.push(path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_wav_header() {
+        let wav = wav_bytes(&[0, 1, -1, i16::MAX], 24_000);
+        assert_eq!(wav.len(), 44 + 8);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(wav[4..8].try_into().unwrap()), 44);
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(
+            wav[16..36],
+            [
+                16, 0, 0, 0, // fmt chunk size
+                1, 0, // PCM
+                1, 0, // mono
+                0xC0, 0x5D, 0, 0, // 24000 Hz
+                0x80, 0xBB, 0, 0, // 48000 bytes per second
+                2, 0, // block align
+                16, 0, // bits per sample
+            ]
+        );
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 8);
+        assert_eq!(wav[44..], [0, 0, 1, 0, 0xFF, 0xFF, 0xFF, 0x7F]);
+    }
+
+    #[test]
+    fn test_g711_vectors() {
+        assert_eq!(
+            decode_g711_ulaw(&[0xFF, 0x7F, 0x00, 0x80, 0xF0, 0x70, 0xEF]),
+            [0, 0, -32124, 32124, 120, -120, 132]
+        );
+        assert_eq!(
+            decode_g711_alaw(&[0xD5, 0x55, 0xAA, 0x2A, 0xC5, 0x45]),
+            [8, -8, 32256, -32256, 264, -264]
+        );
+    }
+
+    #[test]
+    fn test_decode_audio_delta() {
+        let delta = STANDARD.encode([0x01, 0x00, 0x00, 0x80]);
+        assert_eq!(decode_audio_delta(&delta).unwrap(), [1, i16::MIN]);
+
+        let odd = STANDARD.encode([0x01, 0x00, 0x00]);
+        assert!(matches!(
+            decode_audio_delta(&odd),
+            Err(OpenAIError::InvalidArgument(_))
+        ));
+
+        let ulaw = STANDARD.encode([0xFF, 0x00]);
+        assert_eq!(
+            decode_audio(&ulaw, &AudioFormat::G711ULAW).unwrap(),
+            [0, -32124]
+        );
+    }
+
+    #[test]
+    fn test_wav_file_sink() {
+        let dir = std::env::temp_dir().join(format!("async-openai-wav-{}", std::process::id()));
+        let format = AudioFormat::G711ULAW;
+        let mut sink = WavFileSink::new(&dir, &format);
+        assert_eq!(sink.sample_rate(), 8_000);
+
+        let delta = |item_id: &str, audio: &[u8]| -> ServerEvent {
+            serde_json::from_value(json!({
+                "type": "response.audio.delta",
+                "event_id": "event_1",
+                "response_id": "resp_1",
+                "item_id": item_id,
+                "output_index": 0,
+                "content_index": 0,
+                "delta": STANDARD.encode(audio),
+            }))
+            .unwrap()
+        };
+        let done: ServerEvent = serde_json::from_value(json!({
+            "type": "response.audio.done",
+            "event_id": "event_2",
+            "response_id": "resp_1",
+            "item_id": "item_1",
+            "output_index": 0,
+            "content_index": 0,
+        }))
+        .unwrap();
+
+        assert!(sink
+            .handle_event(&delta("item_1", &[0xFF]), &format)
+            .unwrap());
+        assert!(sink
+            .handle_event(&delta("item_2", &[0x00]), &format)
+            .unwrap());
+        assert!(sink
+            .handle_event(&delta("item_1", &[0x80]), &format)
+            .unwrap());
+        assert!(sink.handle_event(&done, &format).unwrap());
+        let other: ServerEvent = serde_json::from_value(json!({"type": "some.event"})).unwrap();
+        assert!(!sink.handle_event(&other, &format).unwrap());
+
+        assert_eq!(sink.files(), [dir.join("resp_1_item_1.wav")]);
+        let wav = std::fs::read(&sink.files()[0]).unwrap();
+        assert_eq!(wav, wav_bytes(&[0, 32124], 8_000));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Samples and finished items, in the order of the events
+    #[derive(Default)]
+    struct RecordingSink(Vec<(String, Vec<i16>)>);
+
+    impl AudioSink for RecordingSink {
+        fn append(&mut self, _: &str, item_id: &str, samples: &[i16]) -> Result<(), OpenAIError> {
+            self.0.push((item_id.to_string(), samples.to_vec()));
+            Ok(())
+        }
+
+        fn finish(&mut self, _: &str, item_id: &str) -> Result<(), OpenAIError> {
+            self.0.push((item_id.to_string(), vec![]));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_ga_event_names() {
+        let event =
+            |value: serde_json::Value| -> ServerEvent { serde_json::from_value(value).unwrap() };
+        let ids = json!({
+            "event_id": "event_1",
+            "response_id": "resp_1",
+            "item_id": "item_1",
+            "output_index": 0,
+            "content_index": 0,
+        });
+        let with = |fields: serde_json::Value| {
+            let mut value = ids.clone();
+            value
+                .as_object_mut()
+                .unwrap()
+                .extend(fields.as_object().unwrap().clone());
+            value
+        };
+
+        let format = AudioFormat::PCM16;
+        let mut sink = RecordingSink::default();
+        let delta = event(with(json!({
+            "type": "response.output_audio.delta",
+            "delta": STANDARD.encode([0x01, 0x00]),
+        })));
+        let done = event(with(json!({"type": "response.output_audio.done"})));
+        assert!(sink.handle_event(&delta, &format).unwrap());
+        assert!(sink.handle_event(&done, &format).unwrap());
+        assert_eq!(
+            sink.0,
+            [
+                ("item_1".to_string(), vec![1]),
+                ("item_1".to_string(), vec![])
+            ]
+        );
+
+        let transcript_delta = event(with(json!({
+            "type": "response.output_audio_transcript.delta",
+            "delta": "Hel",
+        })));
+        assert!(matches!(
+            transcript_delta,
+            ServerEvent::ResponseAudioTranscriptDelta(e) if e.delta == "Hel"
+        ));
+        let transcript_done = event(with(json!({
+            "type": "response.output_audio_transcript.done",
+            "transcript": "Hello",
+        })));
+        assert!(matches!(
+            transcript_done,
+            ServerEvent::ResponseAudioTranscriptDone(e) if e.transcript == "Hello"
+        ));
+    }
+}
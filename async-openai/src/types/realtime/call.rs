@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct RejectCallRequest {
+    /// SIP response code sent to the caller, `603` (Decline) by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ReferCallRequest {
+    /// URI of the transfer target in the SIP `Refer-To` header, like `tel:+14155550123`
+    /// or `sip:agent@example.com`.
+    pub target_uri: String,
+}
+
+impl ReferCallRequest {
+    pub fn new(target_uri: impl Into<String>) -> Self {
+        Self {
+            target_uri: target_uri.into(),
+        }
+    }
+}
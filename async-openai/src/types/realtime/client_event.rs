@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use tokio_tungstenite::tungstenite::Message;
 
-use super::{item::Item, session_resource::SessionResource};
+use super::{
+    item::Item, response_resource::ResponseCreateOptions, session_resource::SessionResource,
+};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct SessionUpdateEvent {
@@ -81,8 +83,9 @@ pub struct ResponseCreateEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub event_id: Option<String>,
 
-    /// Configuration for the response.
-    pub response: Option<SessionResource>,
+    /// Configuration for the response, instead of the configuration of the session.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<ResponseCreateOptions>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -93,7 +96,7 @@ pub struct ResponseCancelEvent {
 }
 
 /// These are events that the OpenAI Realtime WebSocket server will accept from the client.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "type")]
 pub enum ClientEvent {
     /// Send this event to update the session’s default configuration.
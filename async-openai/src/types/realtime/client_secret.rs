@@ -0,0 +1,82 @@
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+
+use super::session_resource::SessionResource;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RealtimeSessionType {
+    #[default]
+    Realtime,
+    Transcription,
+}
+
+/// Session configuration sent over HTTP, for a client secret or an accepted call.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RealtimeSessionConfig {
+    /// The type of session, `realtime` by default.
+    pub r#type: RealtimeSessionType,
+
+    #[serde(flatten)]
+    pub session: SessionResource,
+}
+
+impl From<SessionResource> for RealtimeSessionConfig {
+    fn from(session: SessionResource) -> Self {
+        Self {
+            r#type: RealtimeSessionType::Realtime,
+            session,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientSecretAnchor {
+    #[default]
+    CreatedAt,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ClientSecretExpiresAfter {
+    /// The time the expiration is counted from, only `created_at` is supported.
+    pub anchor: ClientSecretAnchor,
+
+    /// Seconds until the client secret expires, between 10 and 7200.
+    pub seconds: u32,
+}
+
+impl ClientSecretExpiresAfter {
+    /// Expiration `seconds` after the creation of the client secret
+    pub fn seconds(seconds: u32) -> Self {
+        Self {
+            anchor: ClientSecretAnchor::CreatedAt,
+            seconds,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CreateRealtimeClientSecretRequest {
+    /// Expiration of the client secret, 10 minutes by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<ClientSecretExpiresAfter>,
+
+    /// Configuration of the sessions started with the client secret.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<RealtimeSessionConfig>,
+}
+
+/// An ephemeral key for browser and mobile clients to connect to the Realtime API.
+#[derive(Debug, Deserialize, Clone)]
+pub struct RealtimeClientSecret {
+    /// The ephemeral key, used by the client in place of an API key.
+    pub value: SecretString,
+
+    /// The Unix timestamp (in seconds) of the expiration of the key.
+    pub expires_at: u64,
+
+    /// The session configuration of the key, as returned by the API.
+    #[serde(default)]
+    pub session: Option<serde_json::Value>,
+}
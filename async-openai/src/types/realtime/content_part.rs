@@ -13,6 +13,7 @@ pub enum ContentPart {
         /// Base64-encoded audio data
         audio: Option<String>,
         /// The transcript of the audio
+        #[serde(default)]
         transcript: String,
     },
 }
@@ -1,11 +1,17 @@
 use serde::{Deserialize, Serialize};
 
+use super::mcp::McpListedTool;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum ItemType {
     Message,
     FunctionCall,
     FunctionCallOutput,
+    McpListTools,
+    McpCall,
+    McpApprovalRequest,
+    McpApprovalResponse,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -51,13 +57,13 @@ pub struct ItemContent {
     pub transcript: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Item {
     /// The unique ID of the item.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 
-    /// The type of the item ("message", "function_call", "function_call_output").
+    /// The type of the item ("message", "function_call", "function_call_output", or an MCP item).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub r#type: Option<ItemType>,
 
@@ -77,17 +83,79 @@ pub struct Item {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub call_id: Option<String>,
 
-    /// The name of the function being called (for "function_call" items).
+    /// The name of the function or MCP tool being called (for "function_call", "mcp_call"
+    /// and "mcp_approval_request" items).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
-    /// The arguments of the function call (for "function_call" items).
+    /// The arguments of the call (for "function_call", "mcp_call" and "mcp_approval_request" items).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub arguments: Option<String>,
 
-    /// The output of the function call (for "function_call_output" items).
+    /// The output of the call (for "function_call_output" and "mcp_call" items).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output: Option<String>,
+
+    /// The label of the MCP server (for "mcp_list_tools", "mcp_call" and "mcp_approval_request" items).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_label: Option<String>,
+
+    /// The tools of the MCP server (for "mcp_list_tools" items).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<McpListedTool>>,
+
+    /// The ID of the approval request of the call (for "mcp_call" and "mcp_approval_response" items).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_request_id: Option<String>,
+
+    /// Whether the call is approved (for "mcp_approval_response" items).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approve: Option<bool>,
+
+    /// The reason of the approval decision (for "mcp_approval_response" items).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// The error of the call or of the listing of tools (for "mcp_call" and "mcp_list_tools" items).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<serde_json::Value>,
+}
+
+impl Item {
+    /// An "mcp_approval_response" item answering the "mcp_approval_request" item
+    /// `approval_request_id`, to create in the conversation with a
+    /// [ConversationItemCreateEvent](super::ConversationItemCreateEvent).
+    ///
+    /// ```
+    /// use async_openai::types::realtime::{ConversationItemCreateEvent, Item};
+    ///
+    /// let event = ConversationItemCreateEvent::from(Item::mcp_approval_response(
+    ///     "mcpr_123",
+    ///     true,
+    ///     None,
+    /// ));
+    /// assert_eq!(
+    ///     serde_json::to_value(&event).unwrap()["item"],
+    ///     serde_json::json!({
+    ///         "type": "mcp_approval_response",
+    ///         "approval_request_id": "mcpr_123",
+    ///         "approve": true
+    ///     })
+    /// );
+    /// ```
+    pub fn mcp_approval_response(
+        approval_request_id: impl Into<String>,
+        approve: bool,
+        reason: Option<String>,
+    ) -> Self {
+        Self {
+            r#type: Some(ItemType::McpApprovalResponse),
+            approval_request_id: Some(approval_request_id.into()),
+            approve: Some(approve),
+            reason,
+            ..Default::default()
+        }
+    }
 }
 
 impl TryFrom<serde_json::Value> for Item {
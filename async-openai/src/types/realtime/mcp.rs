@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A remote [MCP](https://modelcontextprotocol.io) server whose tools are available
+/// to the model, in the `tools` of a session or response.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct McpTool {
+    /// A label identifying the server in tool calls.
+    pub server_label: String,
+
+    /// The URL of the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_url: Option<String>,
+
+    /// Description of the server, to give the model more context.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_description: Option<String>,
+
+    /// OAuth access token for the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization: Option<String>,
+
+    /// HTTP headers sent to the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<HashMap<String, String>>,
+
+    /// Names of the tools of the server available to the model, all tools when not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_tools: Option<Vec<String>>,
+
+    /// Which tools need an approval before being called, all tools when not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_approval: Option<McpToolApproval>,
+}
+
+impl McpTool {
+    pub fn new(server_label: impl Into<String>, server_url: impl Into<String>) -> Self {
+        Self {
+            server_label: server_label.into(),
+            server_url: Some(server_url.into()),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum McpApprovalSetting {
+    Always,
+    Never,
+}
+
+/// Approval policy of the tools of an MCP server.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum McpToolApproval {
+    /// The same policy for all tools.
+    Setting(McpApprovalSetting),
+    /// Policies by tool.
+    Filter(McpToolApprovalFilter),
+}
+
+impl From<McpApprovalSetting> for McpToolApproval {
+    fn from(value: McpApprovalSetting) -> Self {
+        Self::Setting(value)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct McpToolApprovalFilter {
+    /// Tools which always need an approval.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub always: Option<McpToolFilter>,
+    /// Tools which never need an approval.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub never: Option<McpToolFilter>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct McpToolFilter {
+    pub tool_names: Vec<String>,
+}
+
+/// A tool of an MCP server, in a `mcp_list_tools` item.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct McpListedTool {
+    /// The name of the tool.
+    pub name: String,
+    /// The description of the tool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The JSON schema of the input of the tool.
+    pub input_schema: Value,
+    /// Additional information about the tool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Value>,
+}
@@ -1,18 +1,26 @@
+mod audio;
+mod call;
 mod client_event;
+mod client_secret;
 mod content_part;
 mod conversation;
 mod error;
 mod item;
+mod mcp;
 mod rate_limit;
 mod response_resource;
 mod server_event;
 mod session_resource;
 
+pub use audio::*;
+pub use call::*;
 pub use client_event::*;
+pub use client_secret::*;
 pub use content_part::*;
 pub use conversation::*;
 pub use error::*;
 pub use item::*;
+pub use mcp::*;
 pub use rate_limit::*;
 pub use response_resource::*;
 pub use server_event::*;
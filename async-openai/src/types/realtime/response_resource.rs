@@ -1,12 +1,53 @@
+use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use super::item::Item;
+use crate::{error::OpenAIError, types::Metadata};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use super::{
+    client_event::ResponseCreateEvent,
+    item::Item,
+    session_resource::{
+        AudioFormat, MaxResponseOutputTokens, RealtimeVoice, ToolChoice, ToolDefinition,
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct Usage {
     pub total_tokens: u32,
     pub input_tokens: u32,
     pub output_tokens: u32,
+    /// Breakdown of the input tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_token_details: Option<InputTokenDetails>,
+    /// Breakdown of the output tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_token_details: Option<OutputTokenDetails>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct InputTokenDetails {
+    pub text_tokens: u32,
+    pub audio_tokens: u32,
+    /// Input tokens read from the cache, which are included in the text and audio tokens.
+    pub cached_tokens: u32,
+    /// Breakdown of the cached tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached_tokens_details: Option<CachedTokensDetails>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct CachedTokensDetails {
+    pub text_tokens: u32,
+    pub audio_tokens: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct OutputTokenDetails {
+    pub text_tokens: u32,
+    pub audio_tokens: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -56,4 +97,106 @@ pub struct ResponseResource {
     pub output: Vec<Item>,
     /// Usage statistics for the response.
     pub usage: Option<Usage>,
+    /// The metadata of the `response.create` event of the response.
+    pub metadata: Option<Metadata>,
+}
+
+/// The conversation a response is added to.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseConversation {
+    /// The default conversation of the session.
+    #[default]
+    Auto,
+    /// No conversation: an out-of-band response, which doesn't change the conversation
+    /// and only sees the items of its `input`.
+    None,
+}
+
+/// Options of a `response.create` event, overriding the session configuration for
+/// a single response.
+///
+/// ```
+/// use async_openai::types::{
+///     realtime::{Item, RealtimeResponseCreateArgs, ResponseConversation},
+///     Metadata,
+/// };
+///
+/// let event = RealtimeResponseCreateArgs::default()
+///     .conversation(ResponseConversation::None)
+///     .modalities(["text".to_string()])
+///     .instructions("Classify the topic of the conversation in one word.")
+///     .metadata(Metadata::try_from(serde_json::json!({"purpose": "topic"}))?)
+///     .input(Vec::<Item>::new())
+///     .build()?;
+///
+/// assert_eq!(
+///     serde_json::to_value(&event)?["response"]["conversation"],
+///     "none"
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Builder)]
+#[builder(name = "RealtimeResponseCreateArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(name = "build_options", error = "OpenAIError"))]
+pub struct ResponseCreateOptions {
+    /// The set of modalities of the response, like `["text"]` or `["text", "audio"]`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modalities: Option<Vec<String>>,
+
+    /// The instructions of the response, instead of the instructions of the session.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// The voice of the response audio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voice: Option<RealtimeVoice>,
+
+    /// The format of the response audio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_audio_format: Option<AudioFormat>,
+
+    /// Tools (functions) available to the model for the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ToolDefinition>>,
+
+    /// How the model chooses tools.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+
+    /// Sampling temperature for the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// Maximum number of output tokens of the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_response_output_tokens: Option<MaxResponseOutputTokens>,
+
+    /// The conversation the response is added to, [ResponseConversation::None] for an
+    /// out-of-band response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversation: Option<ResponseConversation>,
+
+    /// Metadata of the response, returned in its `response.created` and `response.done`
+    /// events to correlate out-of-band responses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+
+    /// The input of the response instead of the conversation, items which are not added
+    /// to the conversation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<Vec<Item>>,
+}
+
+impl RealtimeResponseCreateArgs {
+    /// Build the `response.create` event of the response options
+    pub fn build(&self) -> Result<ResponseCreateEvent, OpenAIError> {
+        Ok(ResponseCreateEvent {
+            event_id: None,
+            response: Some(self.build_options()?),
+        })
+    }
 }
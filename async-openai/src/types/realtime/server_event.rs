@@ -1,4 +1,4 @@
-use serde::{Deserialize, Serialize};
+use serde::{de, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
 
 use super::{
     content_part::ContentPart, conversation::Conversation, error::RealtimeAPIError, item::Item,
@@ -41,8 +41,10 @@ pub struct ConversationCreatedEvent {
 pub struct InputAudioBufferCommitedEvent {
     /// The unique ID of the server event.
     pub event_id: String,
-    /// The ID of the preceding item after which the new item will be inserted.
-    pub previous_item_id: String,
+    /// The ID of the preceding item after which the new item will be inserted,
+    /// null for the first item of the conversation.
+    #[serde(default)]
+    pub previous_item_id: Option<String>,
     /// The ID of the user message item that will be created.
     pub item_id: String,
 }
@@ -321,10 +323,59 @@ pub struct ResponseFunctionCallArgumentsDoneEvent {
     pub output_index: u32,
     /// The ID of the function call.
     pub call_id: String,
+    /// The name of the function, missing in events of older API versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The final arguments as a JSON string.
+    pub arguments: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct McpListToolsEvent {
+    /// The unique ID of the server event.
+    pub event_id: String,
+    /// The ID of the "mcp_list_tools" item.
+    pub item_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResponseMcpCallArgumentsDeltaEvent {
+    /// The unique ID of the server event.
+    pub event_id: String,
+    /// The ID of the response.
+    pub response_id: String,
+    /// The ID of the "mcp_call" item.
+    pub item_id: String,
+    /// The index of the output item in the response.
+    pub output_index: u32,
+    /// The arguments delta as a JSON string.
+    pub delta: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResponseMcpCallArgumentsDoneEvent {
+    /// The unique ID of the server event.
+    pub event_id: String,
+    /// The ID of the response.
+    pub response_id: String,
+    /// The ID of the "mcp_call" item.
+    pub item_id: String,
+    /// The index of the output item in the response.
+    pub output_index: u32,
     /// The final arguments as a JSON string.
     pub arguments: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResponseMcpCallEvent {
+    /// The unique ID of the server event.
+    pub event_id: String,
+    /// The ID of the "mcp_call" item.
+    pub item_id: String,
+    /// The index of the output item in the response.
+    pub output_index: u32,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RateLimitsUpdatedEvent {
     /// The unique ID of the server event.
@@ -334,7 +385,7 @@ pub struct RateLimitsUpdatedEvent {
 
 /// These are events emitted from the OpenAI Realtime WebSocket server to the client.
 #[derive(Debug, Serialize, Deserialize, Clone)]
-#[serde(tag = "type")]
+#[serde(tag = "type", remote = "Self")]
 pub enum ServerEvent {
     /// Returned when an error occurs.
     #[serde(rename = "error")]
@@ -427,21 +478,27 @@ pub enum ServerEvent {
     ResponseTextDone(ResponseTextDoneEvent),
 
     /// Returned when the model-generated transcription of audio output is updated.
-    #[serde(rename = "response.audio_transcript.delta")]
+    #[serde(
+        rename = "response.audio_transcript.delta",
+        alias = "response.output_audio_transcript.delta"
+    )]
     ResponseAudioTranscriptDelta(ResponseAudioTranscriptDeltaEvent),
 
     /// Returned when the model-generated transcription of audio output is done streaming.
     /// Also emitted when a Response is interrupted, incomplete, or cancelled.
-    #[serde(rename = "response.audio_transcript.done")]
+    #[serde(
+        rename = "response.audio_transcript.done",
+        alias = "response.output_audio_transcript.done"
+    )]
     ResponseAudioTranscriptDone(ResponseAudioTranscriptDoneEvent),
 
     /// Returned when the model-generated audio is updated.
-    #[serde(rename = "response.audio.delta")]
+    #[serde(rename = "response.audio.delta", alias = "response.output_audio.delta")]
     ResponseAudioDelta(ResponseAudioDeltaEvent),
 
     /// Returned when the model-generated audio is done.
     /// Also emitted when a Response is interrupted, incomplete, or cancelled.
-    #[serde(rename = "response.audio.done")]
+    #[serde(rename = "response.audio.done", alias = "response.output_audio.done")]
     ResponseAudioDone(ResponseAudioDoneEvent),
 
     /// Returned when the model-generated function call arguments are updated.
@@ -456,4 +513,117 @@ pub enum ServerEvent {
     /// Emitted after every "response.done" event to indicate the updated rate limits.
     #[serde(rename = "rate_limits.updated")]
     RateLimitsUpdated(RateLimitsUpdatedEvent),
+
+    /// Returned when the tools of an MCP server are being listed, in an "mcp_list_tools" item.
+    #[serde(rename = "mcp_list_tools.in_progress")]
+    McpListToolsInProgress(McpListToolsEvent),
+
+    /// Returned when the tools of an MCP server are listed.
+    #[serde(rename = "mcp_list_tools.completed")]
+    McpListToolsCompleted(McpListToolsEvent),
+
+    /// Returned when the tools of an MCP server could not be listed.
+    #[serde(rename = "mcp_list_tools.failed")]
+    McpListToolsFailed(McpListToolsEvent),
+
+    /// Returned when the model-generated arguments of an MCP tool call are updated.
+    #[serde(rename = "response.mcp_call_arguments.delta")]
+    ResponseMcpCallArgumentsDelta(ResponseMcpCallArgumentsDeltaEvent),
+
+    /// Returned when the model-generated arguments of an MCP tool call are done streaming.
+    #[serde(rename = "response.mcp_call_arguments.done")]
+    ResponseMcpCallArgumentsDone(ResponseMcpCallArgumentsDoneEvent),
+
+    /// Returned when an MCP tool call starts, after its approval when one is required.
+    #[serde(rename = "response.mcp_call.in_progress")]
+    ResponseMcpCallInProgress(ResponseMcpCallEvent),
+
+    /// Returned when an MCP tool call succeeded.
+    #[serde(rename = "response.mcp_call.completed")]
+    ResponseMcpCallCompleted(ResponseMcpCallEvent),
+
+    /// Returned when an MCP tool call failed.
+    #[serde(rename = "response.mcp_call.failed")]
+    ResponseMcpCallFailed(ResponseMcpCallEvent),
+
+    /// An event of a type unknown to this crate, like an event added to the API
+    /// after its release. Events of a known type which fail to deserialize are
+    /// errors instead.
+    #[serde(skip)]
+    Other {
+        event_type: String,
+        /// The other fields of the event, kept as a map rather than a single value so
+        /// that the event serializes back to the same JSON object.
+        payload: serde_json::Map<String, serde_json::Value>,
+    },
+}
+
+impl Serialize for ServerEvent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let ServerEvent::Other {
+            event_type,
+            payload,
+        } = self
+        else {
+            return ServerEvent::serialize(self, serializer);
+        };
+
+        let mut map = serializer.serialize_map(Some(payload.len() + 1))?;
+        map.serialize_entry("type", event_type)?;
+        for (key, value) in payload {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ServerEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut payload = serde_json::Map::deserialize(deserializer)?;
+        let event_type = match payload.get("type") {
+            Some(serde_json::Value::String(event_type)) => event_type.clone(),
+            Some(_) => return Err(de::Error::custom("invalid type of server event")),
+            None => return Err(de::Error::missing_field("type")),
+        };
+
+        if is_known_type(&event_type) {
+            return ServerEvent::deserialize(serde_json::Value::Object(payload))
+                .map_err(de::Error::custom);
+        }
+
+        payload.remove("type");
+        Ok(ServerEvent::Other {
+            event_type,
+            payload,
+        })
+    }
+}
+
+/// Whether `event_type` is the tag of a typed variant, found by deserializing an
+/// event made of the tag alone: only unknown tags fail with an unknown variant.
+fn is_known_type(event_type: &str) -> bool {
+    #[derive(Debug)]
+    struct UnknownVariant(bool);
+
+    impl std::fmt::Display for UnknownVariant {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("server event probe")
+        }
+    }
+
+    impl std::error::Error for UnknownVariant {}
+
+    impl de::Error for UnknownVariant {
+        fn custom<T: std::fmt::Display>(_: T) -> Self {
+            UnknownVariant(false)
+        }
+
+        fn unknown_variant(_: &str, _: &'static [&'static str]) -> Self {
+            UnknownVariant(true)
+        }
+    }
+
+    let tag =
+        de::value::MapDeserializer::<_, UnknownVariant>::new(std::iter::once(("type", event_type)));
+    !matches!(ServerEvent::deserialize(tag), Err(UnknownVariant(true)))
 }
@@ -1,38 +1,154 @@
+use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use crate::error::OpenAIError;
+
+use super::{client_event::SessionUpdateEvent, mcp::McpTool};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub enum AudioFormat {
+    #[default]
     #[serde(rename = "pcm16")]
     PCM16,
-    #[serde(rename = "g711-ulaw")]
+    #[serde(rename = "g711_ulaw", alias = "g711-ulaw")]
     G711ULAW,
-    #[serde(rename = "g711-alaw")]
+    #[serde(rename = "g711_alaw", alias = "g711-alaw")]
     G711ALAW,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+impl AudioFormat {
+    /// Samples per second of the audio, mono
+    pub fn sample_rate(&self) -> u32 {
+        match self {
+            AudioFormat::PCM16 => 24_000,
+            AudioFormat::G711ULAW | AudioFormat::G711ALAW => 8_000,
+        }
+    }
+
+    /// Bytes of each encoded sample
+    pub fn sample_len(&self) -> usize {
+        match self {
+            AudioFormat::PCM16 => 2,
+            AudioFormat::G711ULAW | AudioFormat::G711ALAW => 1,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct AudioTranscription {
-    /// Whether to enable input audio transcription.
-    pub enabled: bool,
-    /// The model to use for transcription (e.g., "whisper-1").
-    pub model: String,
+    /// The model to use for transcription, like "whisper-1" or "gpt-4o-transcribe".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// The language of the input audio in ISO-639-1 format (e.g. "en"), to improve
+    /// accuracy and latency.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Text to guide the style of the transcription, or to continue a previous segment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+impl AudioTranscription {
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: Some(model.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// How eagerly the model responds with semantic VAD, `auto` is `medium`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Eagerness {
+    Low,
+    Medium,
+    High,
+    #[default]
+    Auto,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(tag = "type")]
 pub enum TurnDetection {
-    /// Type of turn detection, only "server_vad" is currently supported.
+    /// Detects the end of speech from the silence in the audio.
     #[serde(rename = "server_vad")]
     ServerVAD {
-        /// Activation threshold for VAD (0.0 to 1.0).
+        /// Activation threshold for VAD (0.0 to 1.0), 0.5 by default.
         threshold: f32,
-        /// Amount of audio to include before speech starts (in milliseconds).
+        /// Amount of audio to include before speech starts (in milliseconds), 300 by default.
         prefix_padding_ms: u32,
-        /// Duration of silence to detect speech stop (in milliseconds).
+        /// Duration of silence to detect speech stop (in milliseconds), 500 by default.
         silence_duration_ms: u32,
+        /// Whether to create a response when speech stops, `true` by default.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        create_response: Option<bool>,
+        /// Whether to interrupt the ongoing response when speech starts, `true` by default.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        interrupt_response: Option<bool>,
+    },
+    /// Detects the end of speech from the words of the user, with a model.
+    #[serde(rename = "semantic_vad")]
+    SemanticVAD {
+        #[serde(default)]
+        eagerness: Eagerness,
+        /// Whether to create a response when speech stops, `true` by default.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        create_response: Option<bool>,
+        /// Whether to interrupt the ongoing response when speech starts, `true` by default.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        interrupt_response: Option<bool>,
     },
 }
 
+impl TurnDetection {
+    /// Server VAD with the default threshold, padding and silence duration of the API
+    pub fn server_vad() -> Self {
+        Self::ServerVAD {
+            threshold: 0.5,
+            prefix_padding_ms: 300,
+            silence_duration_ms: 500,
+            create_response: None,
+            interrupt_response: None,
+        }
+    }
+
+    pub fn semantic_vad(eagerness: Eagerness) -> Self {
+        Self::SemanticVAD {
+            eagerness,
+            create_response: None,
+            interrupt_response: None,
+        }
+    }
+}
+
+impl Default for TurnDetection {
+    fn default() -> Self {
+        Self::server_vad()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NoiseReductionType {
+    /// For close-talking microphones such as headphones.
+    NearField,
+    /// For far-field microphones such as laptop or conference room microphones.
+    FarField,
+}
+
+/// Noise reduction of the input audio, before VAD and the model.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct NoiseReduction {
+    pub r#type: NoiseReductionType,
+}
+
+impl From<NoiseReductionType> for NoiseReduction {
+    fn from(r#type: NoiseReductionType) -> Self {
+        Self { r#type }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum MaxResponseOutputTokens {
     #[serde(rename = "inf")]
@@ -53,6 +169,15 @@ pub enum ToolDefinition {
         /// Parameters of the function in JSON Schema.
         parameters: serde_json::Value,
     },
+    /// The tools of a remote MCP server.
+    #[serde(rename = "mcp")]
+    Mcp(McpTool),
+}
+
+impl From<McpTool> for ToolDefinition {
+    fn from(value: McpTool) -> Self {
+        Self::Mcp(value)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -74,15 +199,52 @@ pub enum ToolChoice {
     },
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum RealtimeVoice {
+    #[default]
     Alloy,
-    Shimmer,
+    Ash,
+    Ballad,
+    Coral,
     Echo,
+    Sage,
+    Shimmer,
+    Verse,
+    /// A voice added to the API after this version
+    #[serde(untagged)]
+    Other(String),
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+/// Configuration of a session, sent with [RealtimeSessionUpdateArgs] in a `session.update`
+/// event. Fields which are not set keep their value in the session.
+///
+/// ```
+/// use async_openai::types::realtime::{
+///     AudioTranscription, Eagerness, NoiseReductionType, RealtimeSessionUpdateArgs,
+///     RealtimeVoice, TurnDetection,
+/// };
+///
+/// let event = RealtimeSessionUpdateArgs::default()
+///     .instructions("You are a helpful assistant.")
+///     .voice(RealtimeVoice::Alloy)
+///     .turn_detection(TurnDetection::semantic_vad(Eagerness::High))
+///     .input_audio_noise_reduction(NoiseReductionType::NearField)
+///     .input_audio_transcription(AudioTranscription::new("whisper-1"))
+///     .build()?;
+///
+/// assert_eq!(
+///     serde_json::to_value(&event)?["session"]["turn_detection"],
+///     serde_json::json!({"type": "semantic_vad", "eagerness": "high"})
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Builder)]
+#[builder(name = "RealtimeSessionUpdateArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(name = "build_session", error = "OpenAIError"))]
 pub struct SessionResource {
     /// The default model used for this session.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -92,7 +254,7 @@ pub struct SessionResource {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub modalities: Option<Vec<String>>,
 
-    //// The default system instructions prepended to model calls.
+    /// The default system instructions prepended to model calls.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub instructions: Option<String>,
 
@@ -112,6 +274,11 @@ pub struct SessionResource {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub input_audio_transcription: Option<AudioTranscription>,
 
+    /// Noise reduction of the input audio. `None` is omitted from a `session.update`,
+    /// leaving the current setting of the session unchanged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_audio_noise_reduction: Option<NoiseReduction>,
+
     /// Configuration for turn detection. Can be set to null to turn off.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub turn_detection: Option<TurnDetection>,
@@ -134,3 +301,13 @@ pub struct SessionResource {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_response_output_tokens: Option<MaxResponseOutputTokens>,
 }
+
+impl RealtimeSessionUpdateArgs {
+    /// Build the `session.update` event of the session configuration
+    pub fn build(&self) -> Result<SessionUpdateEvent, OpenAIError> {
+        Ok(SessionUpdateEvent {
+            event_id: None,
+            session: self.build_session()?,
+        })
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt::{Debug, Display};
+
+use serde_json::{Map, Value};
+
+use super::{
+    AudioInput, CreateChatCompletionRequest, CreateEmbeddingRequest, CreateSpeechRequest,
+    CreateTranscriptionRequest, CreateTranslationRequest, InputSource,
+};
+
+/// Keys whose string values describe the structure of a request rather than its content
+const STRUCTURAL_KEYS: &[&str] = &[
+    "model",
+    "role",
+    "type",
+    "detail",
+    "format",
+    "voice",
+    "encoding_format",
+    "response_format",
+    "reasoning_effort",
+    "service_tier",
+    "modalities",
+    "language",
+    "timestamp_granularities",
+];
+
+/// Requests which can be logged or attached to error reports without their content.
+///
+/// The redacted form keeps the structure, model, parameters and counts of a request,
+/// content strings and base64 payloads are replaced with `"<redacted: N bytes>"`.
+/// ```
+/// use async_openai::types::{CreateEmbeddingRequestArgs, Redact};
+///
+/// let request = CreateEmbeddingRequestArgs::default()
+///     .model("text-embedding-3-small")
+///     .input("some private text")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(
+///     format!("{:?}", request.redacted()),
+///     r#"{"input":"<redacted: 17 bytes>","model":"text-embedding-3-small"}"#
+/// );
+/// ```
+pub trait Redact {
+    /// JSON representation of the request with its content redacted
+    fn redacted_json(&self) -> Value;
+
+    /// Wrapper whose [Debug] and [Display] print [Redact::redacted_json]
+    fn redacted(&self) -> Redacted<'_, Self> {
+        Redacted(self)
+    }
+}
+
+/// Formats a request with its content redacted, see [Redact]
+pub struct Redacted<'a, T: ?Sized>(&'a T);
+
+impl<T: Redact + ?Sized> Debug for Redacted<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.redacted_json())
+    }
+}
+
+impl<T: Redact + ?Sized> Display for Redacted<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.redacted_json())
+    }
+}
+
+fn redacted_bytes(len: usize) -> Value {
+    Value::String(format!("<redacted: {len} bytes>"))
+}
+
+/// Redact every string of `value`, except the values of [STRUCTURAL_KEYS]
+fn redact_value(value: Value) -> Value {
+    match value {
+        Value::String(content) => redacted_bytes(content.len()),
+        Value::Array(values) => Value::Array(values.into_iter().map(redact_value).collect()),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let value = if STRUCTURAL_KEYS.contains(&key.as_str()) {
+                        value
+                    } else {
+                        redact_value(value)
+                    };
+                    (key, value)
+                })
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+/// Redact requests sent as JSON from their serialized form
+macro_rules! impl_redact_serialize {
+    ($($typ:ty),+) => {
+        $(
+            impl Redact for $typ {
+                fn redacted_json(&self) -> Value {
+                    redact_value(serde_json::to_value(self).unwrap_or_default())
+                }
+            }
+        )+
+    };
+}
+
+impl_redact_serialize!(
+    CreateChatCompletionRequest,
+    CreateEmbeddingRequest,
+    CreateSpeechRequest
+);
+
+fn redact_audio_input(file: &AudioInput) -> Value {
+    match &file.source {
+        InputSource::Path { .. } => Value::String("<redacted: file>".into()),
+        InputSource::Bytes { bytes, .. } => redacted_bytes(bytes.len()),
+        InputSource::VecU8 { vec, .. } => redacted_bytes(vec.len()),
+        InputSource::Reader { length, .. } => redacted_bytes(*length as usize),
+    }
+}
+
+/// Redact a multipart audio request, which is not serializable as a whole
+macro_rules! impl_redact_audio {
+    ($typ:ty, [$($field:ident),*]) => {
+        impl Redact for $typ {
+            fn redacted_json(&self) -> Value {
+                let mut map = Map::new();
+                map.insert("model".into(), Value::String(self.model.clone()));
+                $(
+                    if let Some(value) = &self.$field {
+                        let value = serde_json::to_value(value).unwrap_or_default();
+                        map.insert(stringify!($field).into(), value);
+                    }
+                )*
+
+                let mut redacted = redact_value(Value::Object(map));
+                redacted["file"] = redact_audio_input(&self.file);
+                redacted
+            }
+        }
+    };
+}
+
+impl_redact_audio!(
+    CreateTranscriptionRequest,
+    [
+        prompt,
+        response_format,
+        temperature,
+        language,
+        timestamp_granularities
+    ]
+);
+impl_redact_audio!(
+    CreateTranslationRequest,
+    [prompt, response_format, temperature]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        ChatCompletionRequestMessageContentPartImageArgs,
+        ChatCompletionRequestMessageContentPartTextArgs, ChatCompletionRequestUserMessageArgs,
+        CreateChatCompletionRequestArgs, CreateTranscriptionRequestArgs, ImageUrlArgs,
+    };
+
+    #[test]
+    fn test_redact_chat_request() {
+        let request = CreateChatCompletionRequestArgs::default()
+            .model("gpt-4o")
+            .max_completion_tokens(64u32)
+            .messages([ChatCompletionRequestUserMessageArgs::default()
+                .content(vec![
+                    ChatCompletionRequestMessageContentPartTextArgs::default()
+                        .text("What is in this image?")
+                        .build()
+                        .unwrap()
+                        .into(),
+                    ChatCompletionRequestMessageContentPartImageArgs::default()
+                        .image_url(
+                            ImageUrlArgs::default()
+                                .url("data:image/png;base64,iVBORw0KGgo=")
+                                .build()
+                                .unwrap(),
+                        )
+                        .build()
+                        .unwrap()
+                        .into(),
+                ])
+                .build()
+                .unwrap()
+                .into()])
+            .build()
+            .unwrap();
+
+        let redacted = request.redacted_json();
+
+        assert_eq!(redacted["model"], "gpt-4o");
+        assert_eq!(redacted["max_completion_tokens"], 64);
+        let message = &redacted["messages"][0];
+        assert_eq!(message["role"], "user");
+        assert_eq!(message["content"][0]["type"], "text");
+        assert_eq!(message["content"][0]["text"], "<redacted: 22 bytes>");
+        assert_eq!(message["content"][1]["type"], "image_url");
+        assert_eq!(
+            message["content"][1]["image_url"]["url"],
+            "<redacted: 34 bytes>"
+        );
+        assert!(!request.redacted().to_string().contains("image?"));
+    }
+
+    #[test]
+    fn test_redact_transcription_request() {
+        let request = CreateTranscriptionRequestArgs::default()
+            .file(AudioInput::from_vec_u8("call.mp3".into(), vec![0; 1024]))
+            .model("whisper-1")
+            .prompt("Names: Alice, Bob")
+            .language("en")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            format!("{:?}", request.redacted()),
+            r#"{"file":"<redacted: 1024 bytes>","language":"en","model":"whisper-1","prompt":"<redacted: 17 bytes>"}"#
+        );
+    }
+}
@@ -7,7 +7,7 @@ use crate::{error::OpenAIError, types::FunctionCall};
 
 use super::{
     AssistantTools, AssistantsApiResponseFormatOption, AssistantsApiToolChoiceOption,
-    CreateMessageRequest,
+    CreateMessageRequest, Metadata,
 };
 
 /// Represents an execution run on a [thread](https://platform.openai.com/docs/api-reference/threads).
@@ -209,7 +209,8 @@ pub struct CreateRunRequest {
     pub tools: Option<Vec<AssistantTools>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    #[builder(try_setter)]
+    pub metadata: Option<Metadata>,
 
     /// The sampling temperature used for this run. If not set, defaults to 1.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -251,7 +252,7 @@ pub struct CreateRunRequest {
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
 pub struct ModifyRunRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
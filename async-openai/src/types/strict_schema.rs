@@ -0,0 +1,341 @@
+use std::collections::BTreeSet;
+
+use serde_json::{Map, Value};
+
+use super::ResponseFormatJsonSchema;
+
+/// Keywords rejected by Structured Outputs in strict mode
+const UNSUPPORTED_KEYWORDS: &[&str] = &[
+    "$schema",
+    "default",
+    "examples",
+    "format",
+    "minLength",
+    "maxLength",
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "multipleOf",
+    "minItems",
+    "maxItems",
+    "uniqueItems",
+    "minProperties",
+    "maxProperties",
+    "patternProperties",
+    "readOnly",
+    "writeOnly",
+];
+
+/// Rewrite a JSON schema, like one generated by `schemars`, into the subset accepted by
+/// [Structured Outputs](https://platform.openai.com/docs/guides/structured-outputs#supported-schemas)
+/// in strict mode:
+/// - local `$ref`s to `definitions` or `$defs` are inlined
+/// - unsupported keywords like `format`, `default` or `minLength` are removed
+/// - every object gets `additionalProperties: false`
+/// - optional properties become nullable and are added to `required`
+/// - `oneOf` becomes `anyOf`
+///
+/// Recursive references cannot be inlined: references to the root schema become
+/// `"$ref": "#"`, and other recursive definitions are kept in the root `$defs`.
+pub fn sanitize_for_strict(schema: &mut Value) {
+    let Some(root) = schema.as_object_mut() else {
+        return;
+    };
+
+    let mut definitions = Map::new();
+    for key in ["definitions", "$defs"] {
+        if let Some(Value::Object(defs)) = root.remove(key) {
+            definitions.extend(defs);
+        }
+    }
+
+    let mut inliner = Inliner {
+        root_name: root.get("title").and_then(Value::as_str).map(Into::into),
+        definitions,
+        stack: vec![],
+        recursive: BTreeSet::new(),
+    };
+    inliner.inline(schema);
+
+    // Recursive definitions may reference further recursive definitions
+    let mut kept = Map::new();
+    while let Some(name) = inliner
+        .recursive
+        .iter()
+        .find(|name| !kept.contains_key(*name))
+        .cloned()
+    {
+        let mut definition = inliner.definitions[&name].clone();
+        inliner.stack = vec![name.clone()];
+        inliner.inline(&mut definition);
+        kept.insert(name, definition);
+    }
+    if !kept.is_empty() {
+        if let Some(root) = schema.as_object_mut() {
+            root.insert("$defs".into(), Value::Object(kept));
+        }
+    }
+
+    sanitize(schema);
+}
+
+struct Inliner {
+    /// Title of the root schema, the name of its definition when it references itself
+    root_name: Option<String>,
+    definitions: Map<String, Value>,
+    /// Definitions being inlined
+    stack: Vec<String>,
+    /// Definitions referencing themselves
+    recursive: BTreeSet<String>,
+}
+
+impl Inliner {
+    fn inline(&mut self, schema: &mut Value) {
+        match schema {
+            Value::Object(object) => {
+                object
+                    .iter_mut()
+                    .filter(|(key, _)| *key != "$ref")
+                    .for_each(|(_, value)| self.inline(value));
+
+                let Some(name) = object
+                    .get("$ref")
+                    .and_then(Value::as_str)
+                    .and_then(|reference| {
+                        reference
+                            .strip_prefix("#/definitions/")
+                            .or_else(|| reference.strip_prefix("#/$defs/"))
+                    })
+                    .map(String::from)
+                else {
+                    return;
+                };
+
+                if self.root_name.as_ref() == Some(&name) {
+                    object.insert("$ref".into(), "#".into());
+                } else if self.stack.contains(&name) {
+                    object.insert("$ref".into(), format!("#/$defs/{name}").into());
+                    self.recursive.insert(name);
+                } else if let Some(mut definition) = self.definitions.get(&name).cloned() {
+                    self.stack.push(name);
+                    self.inline(&mut definition);
+                    self.stack.pop();
+
+                    object.remove("$ref");
+                    match definition {
+                        // Keywords next to the `$ref`, like `description`, take precedence
+                        Value::Object(definition) => {
+                            for (key, value) in definition {
+                                object.entry(key).or_insert(value);
+                            }
+                        }
+                        definition => *schema = definition,
+                    }
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(|value| self.inline(value)),
+            _ => {}
+        }
+    }
+}
+
+fn sanitize(schema: &mut Value) {
+    let Value::Object(object) = schema else {
+        return;
+    };
+
+    for keyword in UNSUPPORTED_KEYWORDS {
+        object.remove(*keyword);
+    }
+    if let Some(one_of) = object.remove("oneOf") {
+        object.insert("anyOf".into(), one_of);
+    }
+    // `allOf` of a single schema, generated for a `$ref` with a description
+    if matches!(object.get("allOf"), Some(Value::Array(all_of)) if all_of.len() == 1 && all_of[0].is_object())
+    {
+        if let Some(Value::Array(mut all_of)) = object.remove("allOf") {
+            if let Some(Value::Object(inner)) = all_of.pop() {
+                for (key, value) in inner {
+                    object.entry(key).or_insert(value);
+                }
+            }
+        }
+        // The merged schema is sanitized as part of this one
+        sanitize(schema);
+        return;
+    }
+
+    let required: BTreeSet<String> = object
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|name| name.as_str().map(String::from))
+        .collect();
+    if let Some(Value::Object(properties)) = object.get_mut("properties") {
+        for (name, property) in properties.iter_mut() {
+            sanitize(property);
+            if !required.contains(name) {
+                make_nullable(property);
+            }
+        }
+        let names = properties.keys().cloned().map(Value::String).collect();
+        object.insert("required".into(), Value::Array(names));
+        object.insert("additionalProperties".into(), Value::Bool(false));
+    } else if is_object(object) {
+        object.insert("additionalProperties".into(), Value::Bool(false));
+    }
+
+    for key in ["items", "not"] {
+        if let Some(value) = object.get_mut(key) {
+            match value {
+                Value::Array(values) => values.iter_mut().for_each(sanitize),
+                value => sanitize(value),
+            }
+        }
+    }
+    for key in ["anyOf", "allOf", "prefixItems"] {
+        if let Some(Value::Array(values)) = object.get_mut(key) {
+            values.iter_mut().for_each(sanitize);
+        }
+    }
+    if let Some(Value::Object(definitions)) = object.get_mut("$defs") {
+        definitions.values_mut().for_each(sanitize);
+    }
+}
+
+fn is_object(object: &Map<String, Value>) -> bool {
+    match object.get("type") {
+        Some(Value::String(t)) => t == "object",
+        Some(Value::Array(types)) => types.iter().any(|t| t == "object"),
+        _ => false,
+    }
+}
+
+fn make_nullable(schema: &mut Value) {
+    let null = || Value::String("null".into());
+    let Value::Object(object) = schema else {
+        return;
+    };
+
+    if let Some(Value::Array(values)) = object.get_mut("enum") {
+        if !values.contains(&Value::Null) {
+            values.push(Value::Null);
+        }
+    }
+    match object.get_mut("type") {
+        Some(Value::String(t)) if t == "null" => {}
+        Some(Value::String(t)) => {
+            let t = Value::String(std::mem::take(t));
+            object.insert("type".into(), Value::Array(vec![t, null()]));
+        }
+        Some(Value::Array(types)) => {
+            if !types.contains(&null()) {
+                types.push(null());
+            }
+        }
+        _ => {
+            let null_schema = Value::Object(Map::from_iter([("type".into(), null())]));
+            let is_enum = object.contains_key("enum");
+            match object.get_mut("anyOf") {
+                Some(Value::Array(any_of)) => {
+                    if !any_of.contains(&null_schema) {
+                        any_of.push(null_schema);
+                    }
+                }
+                _ if is_enum => {}
+                _ => {
+                    let inner = std::mem::take(object);
+                    object.insert(
+                        "anyOf".into(),
+                        Value::Array(vec![Value::Object(inner), null_schema]),
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl ResponseFormatJsonSchema {
+    /// Strict response format of `schema`, rewritten with [sanitize_for_strict]
+    pub fn from_schema(name: impl Into<String>, mut schema: Value) -> Self {
+        sanitize_for_strict(&mut schema);
+        Self {
+            description: None,
+            name: name.into(),
+            schema: Some(schema),
+            strict: Some(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_sanitize_keeps_properties_named_like_keywords() {
+        let mut schema = json!({
+            "type": "object",
+            "properties": {
+                "format": {"type": "string", "format": "date-time"},
+                "default": {"type": "integer", "format": "uint32", "minimum": 0}
+            },
+            "required": ["format"]
+        });
+
+        sanitize_for_strict(&mut schema);
+
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "format": {"type": "string"},
+                    "default": {"type": ["integer", "null"]}
+                },
+                "required": ["default", "format"],
+                "additionalProperties": false
+            })
+        );
+    }
+
+    #[test]
+    fn test_optional_refs_and_enums_become_nullable() {
+        let mut schema = json!({
+            "type": "object",
+            "properties": {
+                "color": {"$ref": "#/$defs/Color"},
+                "point": {"description": "Where", "allOf": [{"$ref": "#/$defs/Point"}]}
+            },
+            "$defs": {
+                "Color": {"type": "string", "enum": ["red", "green"]},
+                "Point": {"type": "object", "properties": {"x": {"type": "number"}}, "required": ["x"]}
+            }
+        });
+
+        sanitize_for_strict(&mut schema);
+
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "color": {"type": ["string", "null"], "enum": ["red", "green", null]},
+                    "point": {
+                        "description": "Where",
+                        "type": ["object", "null"],
+                        "properties": {"x": {"type": "number"}},
+                        "required": ["x"],
+                        "additionalProperties": false
+                    }
+                },
+                "required": ["color", "point"],
+                "additionalProperties": false
+            })
+        );
+    }
+}
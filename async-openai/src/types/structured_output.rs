@@ -0,0 +1,211 @@
+use schemars::{gen::SchemaSettings, JsonSchema};
+use serde::de::DeserializeOwned;
+
+use crate::error::{deserialize, OpenAIError};
+
+use super::{CreateChatCompletionResponse, ResponseFormat, ResponseFormatJsonSchema};
+
+/// Message of a choice of [ParsedChatCompletion]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedMessage<T> {
+    /// The content deserialized into `T`
+    Parsed(T),
+    /// The model refused to answer in the requested format
+    Refusal(String),
+}
+
+impl<T> ParsedMessage<T> {
+    pub fn parsed(&self) -> Option<&T> {
+        match self {
+            Self::Parsed(parsed) => Some(parsed),
+            Self::Refusal(_) => None,
+        }
+    }
+
+    pub fn refusal(&self) -> Option<&str> {
+        match self {
+            Self::Parsed(_) => None,
+            Self::Refusal(refusal) => Some(refusal),
+        }
+    }
+}
+
+/// Response of [crate::Chat::create_parsed]
+#[derive(Debug, Clone)]
+pub struct ParsedChatCompletion<T> {
+    /// Message of each choice, in the order of `response.choices`
+    pub choices: Vec<ParsedMessage<T>>,
+    pub response: CreateChatCompletionResponse,
+}
+
+impl<T: DeserializeOwned> ParsedChatCompletion<T> {
+    pub(crate) fn from_response(
+        response: CreateChatCompletionResponse,
+    ) -> Result<Self, OpenAIError> {
+        let choices = response
+            .choices
+            .iter()
+            .map(|choice| match &choice.message.refusal {
+                Some(refusal) => Ok(ParsedMessage::Refusal(refusal.clone())),
+                None => deserialize(
+                    choice
+                        .message
+                        .content
+                        .as_deref()
+                        .unwrap_or_default()
+                        .as_bytes(),
+                )
+                .map(ParsedMessage::Parsed),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { choices, response })
+    }
+}
+
+impl<T> ParsedChatCompletion<T> {
+    /// The parsed content of the first choice, `None` if the model refused
+    pub fn parsed(&self) -> Option<&T> {
+        self.choices.first()?.parsed()
+    }
+}
+
+/// Maximum length of [ResponseFormatJsonSchema::name]
+const MAX_NAME_LEN: usize = 64;
+
+impl ResponseFormatJsonSchema {
+    /// Strict response format of `T`, with the schema generated by `schemars` and
+    /// rewritten with [sanitize_for_strict](crate::types::sanitize_for_strict).
+    ///
+    /// The name is the snake cased schema name of `T`, like `math_reasoning`
+    /// or `array_of_step` for `Vec<Step>`, truncated to 64 characters.
+    pub fn from_type<T: JsonSchema>() -> Self {
+        let mut generator = SchemaSettings::draft07().into_generator();
+        let schema = serde_json::to_value(generator.root_schema_for::<T>())
+            .expect("schema serialization cannot fail");
+
+        Self::from_schema(schema_name::<T>(), schema)
+    }
+}
+
+impl From<ResponseFormatJsonSchema> for ResponseFormat {
+    fn from(json_schema: ResponseFormatJsonSchema) -> Self {
+        Self::JsonSchema { json_schema }
+    }
+}
+
+/// Snake cased schema name of `T`, with only the characters allowed by the API
+fn schema_name<T: JsonSchema>() -> String {
+    let type_name = T::schema_name();
+    let chars: Vec<char> = type_name.chars().collect();
+    let mut name = String::with_capacity(chars.len());
+
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let previous = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            // Words start at `mR` in `MathReasoning` and at `Jp` in `JSONParser`
+            let starts_word = previous
+                .is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit())
+                || (previous.is_some_and(|p| p.is_ascii_uppercase())
+                    && next.is_some_and(|n| n.is_ascii_lowercase()));
+            if starts_word {
+                name.push('_');
+            }
+            name.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            name.push(*c);
+        } else if !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+
+    let mut name = name.trim_matches('_').replace("__", "_");
+    name.truncate(MAX_NAME_LEN);
+    if name.is_empty() {
+        name.push_str("response");
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use schemars::JsonSchema;
+
+    use super::*;
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct MathReasoning {
+        steps: Vec<String>,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct JSONParserOutput2 {
+        valid: bool,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Wrapper<T> {
+        inner: T,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct AVeryLongStructNameThatKeepsGoingAndGoingWellPastTheLimitOfTheApiForNames {
+        value: u8,
+    }
+
+    fn is_valid(name: &str) -> bool {
+        !name.is_empty()
+            && name.len() <= MAX_NAME_LEN
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    }
+
+    #[test]
+    fn test_schema_names() {
+        assert_eq!(schema_name::<MathReasoning>(), "math_reasoning");
+        assert_eq!(schema_name::<JSONParserOutput2>(), "json_parser_output2");
+        assert_eq!(
+            schema_name::<Vec<MathReasoning>>(),
+            "array_of_math_reasoning"
+        );
+        assert_eq!(
+            schema_name::<Wrapper<Vec<MathReasoning>>>(),
+            "wrapper_for_array_of_math_reasoning"
+        );
+
+        let long = schema_name::<
+            Wrapper<AVeryLongStructNameThatKeepsGoingAndGoingWellPastTheLimitOfTheApiForNames>,
+        >();
+        assert_eq!(long.len(), MAX_NAME_LEN);
+
+        for name in [
+            long,
+            schema_name::<HashMap<String, Option<Wrapper<(u8, String)>>>>(),
+            schema_name::<[Wrapper<bool>; 3]>(),
+        ] {
+            assert!(is_valid(&name), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_from_type() {
+        let format = ResponseFormatJsonSchema::from_type::<Wrapper<MathReasoning>>();
+
+        assert_eq!(format.name, "wrapper_for_math_reasoning");
+        assert_eq!(format.strict, Some(true));
+        let schema = format.schema.unwrap();
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(
+            schema["properties"]["inner"]["properties"]["steps"]["type"],
+            "array"
+        );
+    }
+}
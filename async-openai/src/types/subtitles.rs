@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use crate::error::OpenAIError;
+
+/// A cue of a subtitle transcript, from the `srt` or `vtt` response format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptCue {
+    /// Position of the cue in the transcript, starting at 1.
+    pub index: usize,
+    /// Start of the cue, from the start of the audio.
+    pub start: Duration,
+    /// End of the cue, from the start of the audio.
+    pub end: Duration,
+    /// Text of the cue, lines separated by `\n`.
+    pub text: String,
+}
+
+/// Transcript in the `srt` response format of transcriptions and translations,
+/// like the output of [crate::Audio::transcribe_raw].
+///
+/// ```
+/// use async_openai::types::SrtTranscript;
+///
+/// let srt = "1\n00:00:00,000 --> 00:00:02,500\nHello there.\n\n";
+/// let transcript = SrtTranscript::parse(srt)?;
+/// assert_eq!(transcript.cues[0].text, "Hello there.");
+/// # Ok::<(), async_openai::error::OpenAIError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SrtTranscript {
+    pub cues: Vec<TranscriptCue>,
+}
+
+/// Transcript in the `vtt` response format of transcriptions and translations,
+/// like the output of [crate::Audio::transcribe_raw].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VttTranscript {
+    pub cues: Vec<TranscriptCue>,
+}
+
+impl SrtTranscript {
+    pub fn parse(srt: &str) -> Result<Self, OpenAIError> {
+        let cues = blocks(srt)
+            .enumerate()
+            .map(|(position, lines)| {
+                let (index, timing) = match lines.as_slice() {
+                    [index, timing, ..] => (index, timing),
+                    _ => return Err(invalid("SRT", position + 1, "missing timing line")),
+                };
+                let index = index
+                    .parse()
+                    .map_err(|_| invalid("SRT", position + 1, "invalid index"))?;
+                let (start, end) =
+                    timing_of(timing).ok_or_else(|| invalid("SRT", index, "invalid timing"))?;
+
+                Ok(TranscriptCue {
+                    index,
+                    start,
+                    end,
+                    text: lines[2..].join("\n"),
+                })
+            })
+            .collect::<Result<_, OpenAIError>>()?;
+
+        Ok(Self { cues })
+    }
+}
+
+impl VttTranscript {
+    pub fn parse(vtt: &str) -> Result<Self, OpenAIError> {
+        let mut blocks = blocks(vtt);
+        match blocks.next() {
+            Some(header) if header[0].starts_with("WEBVTT") => {}
+            _ => return Err(invalid("WebVTT", 0, "missing WEBVTT header")),
+        }
+
+        let cues = blocks
+            // Comments, styles and regions are not cues
+            .filter(|lines| {
+                !["NOTE", "STYLE", "REGION"]
+                    .iter()
+                    .any(|block| lines[0] == *block || lines[0].starts_with(&format!("{block} ")))
+            })
+            .enumerate()
+            .map(|(position, lines)| {
+                let index = position + 1;
+                // The identifier line of the cue is optional
+                let timing_line = lines
+                    .iter()
+                    .position(|line| line.contains("-->"))
+                    .filter(|line| *line <= 1)
+                    .ok_or_else(|| invalid("WebVTT", index, "missing timing line"))?;
+                let (start, end) = timing_of(lines[timing_line])
+                    .ok_or_else(|| invalid("WebVTT", index, "invalid timing"))?;
+
+                Ok(TranscriptCue {
+                    index,
+                    start,
+                    end,
+                    text: lines[timing_line + 1..].join("\n"),
+                })
+            })
+            .collect::<Result<_, OpenAIError>>()?;
+
+        Ok(Self { cues })
+    }
+}
+
+/// Non-empty lines of the blocks of `text`, which are separated by blank lines
+fn blocks(text: &str) -> impl Iterator<Item = Vec<&str>> {
+    let mut lines = text
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .map(str::trim_end)
+        .peekable();
+
+    std::iter::from_fn(move || {
+        while lines.next_if(|line| line.is_empty()).is_some() {}
+        let block: Vec<_> = std::iter::from_fn(|| lines.next_if(|line| !line.is_empty())).collect();
+        (!block.is_empty()).then_some(block)
+    })
+}
+
+/// Start and end of a `start --> end` timing line, ignoring WebVTT cue settings
+fn timing_of(line: &str) -> Option<(Duration, Duration)> {
+    let (start, end) = line.split_once("-->")?;
+    let end = end.split_whitespace().next()?;
+    Some((timestamp(start.trim())?, timestamp(end)?))
+}
+
+/// `hh:mm:ss,mmm` (SRT) or `[hh:]mm:ss.mmm` (WebVTT) timestamp
+fn timestamp(timestamp: &str) -> Option<Duration> {
+    let (clock, millis) = timestamp.split_once([',', '.'])?;
+    let millis: u64 = millis.parse().ok().filter(|_| millis.len() == 3)?;
+
+    let mut seconds = 0;
+    let units: Vec<_> = clock.split(':').collect();
+    if !(2..=3).contains(&units.len()) {
+        return None;
+    }
+    for unit in units {
+        seconds = seconds * 60 + unit.parse::<u64>().ok()?;
+    }
+
+    Some(Duration::from_millis(seconds * 1000 + millis))
+}
+
+fn invalid(format: &str, cue: usize, reason: &str) -> OpenAIError {
+    OpenAIError::InvalidArgument(format!(
+        "invalid {format} transcript at cue {cue}: {reason}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue(index: usize, start_ms: u64, end_ms: u64, text: &str) -> TranscriptCue {
+        TranscriptCue {
+            index,
+            start: Duration::from_millis(start_ms),
+            end: Duration::from_millis(end_ms),
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn test_parse_srt() {
+        // Whisper output, with CRLF line endings and trailing whitespace
+        let srt = "1\r\n00:00:00,000 --> 00:00:04,240\r\nThe stale smell of old beer lingers.\r\n\r\n\
+                   2\r\n00:00:04,240 --> 00:00:08,960 \r\nIt takes heat\r\nto bring out the odor.  \r\n\r\n\
+                   3\n01:02:03,004 --> 01:02:05,000\nA cold dip restores health and zest.\n\n \n";
+
+        assert_eq!(
+            SrtTranscript::parse(srt).unwrap().cues,
+            [
+                cue(1, 0, 4240, "The stale smell of old beer lingers."),
+                cue(2, 4240, 8960, "It takes heat\nto bring out the odor."),
+                cue(
+                    3,
+                    3_723_004,
+                    3_725_000,
+                    "A cold dip restores health and zest."
+                ),
+            ]
+        );
+        assert!(SrtTranscript::parse("").unwrap().cues.is_empty());
+    }
+
+    #[test]
+    fn test_parse_srt_errors() {
+        for srt in [
+            "one\n00:00:00,000 --> 00:00:01,000\nHello",
+            "1\nHello",
+            "1\n00:00:00 --> 00:00:01,000\nHello",
+            "1\n00:00:00,000 -> 00:00:01,000\nHello",
+        ] {
+            assert!(SrtTranscript::parse(srt).is_err(), "{srt}");
+        }
+    }
+
+    #[test]
+    fn test_parse_vtt() {
+        let vtt = "\u{feff}WEBVTT\n\n\
+                   00:00:00.000 --> 00:00:04.240\nThe stale smell of old beer lingers.\n\n\
+                   NOTE Cues can have identifiers\n\n\
+                   intro\n00:04.240 --> 00:08.960 align:start\nIt takes heat\nto bring out the odor.\t\n\n";
+
+        assert_eq!(
+            VttTranscript::parse(vtt).unwrap().cues,
+            [
+                cue(1, 0, 4240, "The stale smell of old beer lingers."),
+                cue(2, 4240, 8960, "It takes heat\nto bring out the odor."),
+            ]
+        );
+        assert!(VttTranscript::parse("00:00.000 --> 00:01.000\nHello").is_err());
+        assert!(VttTranscript::parse("WEBVTT\n\nHello").is_err());
+    }
+}
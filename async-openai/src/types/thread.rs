@@ -7,7 +7,7 @@ use crate::error::OpenAIError;
 
 use super::{
     AssistantToolResources, AssistantTools, AssistantsApiResponseFormatOption,
-    AssistantsApiToolChoiceOption, CreateAssistantToolResources, CreateMessageRequest,
+    AssistantsApiToolChoiceOption, CreateAssistantToolResources, CreateMessageRequest, Metadata,
     TruncationObject,
 };
 
@@ -43,13 +43,14 @@ pub struct CreateThreadRequest {
     pub tool_resources: Option<CreateAssistantToolResources>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    #[builder(try_setter)]
+    pub metadata: Option<Metadata>,
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
 pub struct ModifyThreadRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 
     /// A set of resources that are made available to the assistant's tools in this thread. The resources are specific to the type of tool. For example, the `code_interpreter` tool requires a list of file IDs, while the `file_search` tool requires a list of vector store IDs.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -94,7 +95,8 @@ pub struct CreateThreadAndRunRequest {
     pub tool_resources: Option<AssistantToolResources>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    #[builder(try_setter)]
+    pub metadata: Option<Metadata>,
 
     /// What sampling temperature to use, between 0 and 2. Higher values like 0.8 will make the output more random, while lower values like 0.2 will make it more focused and deterministic.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -28,6 +28,10 @@ impl<'c, C: Config> Uploads<'c, C> {
     ///
     /// For guidance on the proper filename extensions for each purpose, please follow the documentation on
     /// [creating a File](https://platform.openai.com/docs/api-reference/files/create).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.uploads.create", skip_all)
+    )]
     pub async fn create(&self, request: CreateUploadRequest) -> Result<Upload, OpenAIError> {
         self.client.post("/uploads", request).await
     }
@@ -40,6 +44,10 @@ impl<'c, C: Config> Uploads<'c, C> {
     ///
     /// It is possible to add multiple Parts in parallel. You can decide the intended order of the Parts
     /// when you [complete the Upload](https://platform.openai.com/docs/api-reference/uploads/complete).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.uploads.add_part", skip_all)
+    )]
     pub async fn add_part(
         &self,
         upload_id: &str,
@@ -59,6 +67,10 @@ impl<'c, C: Config> Uploads<'c, C> {
     ///
     /// The number of bytes uploaded upon completion must match the number of bytes initially specified
     /// when creating the Upload object. No Parts may be added after an Upload is completed.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.uploads.complete", skip_all)
+    )]
     pub async fn complete(
         &self,
         upload_id: &str,
@@ -70,6 +82,10 @@ impl<'c, C: Config> Uploads<'c, C> {
     }
 
     /// Cancels the Upload. No Parts may be added after an Upload is cancelled.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.uploads.cancel", skip_all)
+    )]
     pub async fn cancel(&self, upload_id: &str) -> Result<Upload, OpenAIError> {
         self.client
             .post(
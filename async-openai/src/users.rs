@@ -18,6 +18,10 @@ impl<'c, C: Config> Users<'c, C> {
     }
 
     /// Lists all of the users in the organization.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.users.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<UserListResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -28,6 +32,10 @@ impl<'c, C: Config> Users<'c, C> {
     }
 
     /// Modifies a user's role in the organization.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.users.modify", skip_all)
+    )]
     pub async fn modify(
         &self,
         user_id: &str,
@@ -39,6 +47,10 @@ impl<'c, C: Config> Users<'c, C> {
     }
 
     /// Retrieve a user by their identifier
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.users.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, user_id: &str) -> Result<User, OpenAIError> {
         self.client
             .get(format!("/organization/users/{user_id}").as_str())
@@ -46,6 +58,10 @@ impl<'c, C: Config> Users<'c, C> {
     }
 
     /// Deletes a user from the organization.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.users.delete", skip_all)
+    )]
     pub async fn delete(&self, user_id: &str) -> Result<UserDeleteResponse, OpenAIError> {
         self.client
             .delete(format!("/organizations/users/{user_id}").as_str())
@@ -1,8 +1,9 @@
 use std::path::Path;
 
-use reqwest::Body;
-use tokio::fs::File;
-use tokio_util::codec::{BytesCodec, FramedRead};
+use bytes::Bytes;
+use reqwest::{multipart::Part, Body};
+use tokio::{fs::File, io::AsyncReadExt};
+use tokio_util::io::ReaderStream;
 
 use crate::error::OpenAIError;
 use crate::types::InputSource;
@@ -15,29 +16,17 @@ pub(crate) trait AsyncTryFrom<T>: Sized {
     async fn try_from(value: T) -> Result<Self, Self::Error>;
 }
 
-pub(crate) async fn file_stream_body(source: InputSource) -> Result<Body, OpenAIError> {
-    let body = match source {
-        InputSource::Path { path } => {
-            let file = File::open(path)
-                .await
-                .map_err(|e| OpenAIError::FileReadError(e.to_string()))?;
-            let stream = FramedRead::new(file, BytesCodec::new());
-            Body::wrap_stream(stream)
-        }
-        _ => {
-            return Err(OpenAIError::FileReadError(
-                "Cannot create stream from non-file source".to_string(),
-            ))
-        }
-    };
-    Ok(body)
-}
+/// Size of the chunks read from files and readers of multipart uploads
+const CHUNK_SIZE: usize = 64 * 1024;
 
 /// Creates the part for the given file for multipart upload.
+///
+/// Files and readers are streamed in chunks instead of being read into memory,
+/// with their length so that the request has a `Content-Length`.
 pub(crate) async fn create_file_part(
     source: InputSource,
 ) -> Result<reqwest::multipart::Part, OpenAIError> {
-    let (stream, file_name) = match source {
+    let (part, file_name) = match source {
         InputSource::Path { path } => {
             let file_name = path
                 .file_name()
@@ -51,21 +40,109 @@ pub(crate) async fn create_file_part(
                 .unwrap()
                 .to_string();
 
+            let file = File::open(&path)
+                .await
+                .map_err(|e| OpenAIError::FileReadError(e.to_string()))?;
+            let length = file
+                .metadata()
+                .await
+                .map_err(|e| OpenAIError::FileReadError(e.to_string()))?
+                .len();
+            let stream = ReaderStream::with_capacity(file, CHUNK_SIZE);
+
             (
-                file_stream_body(InputSource::Path { path }).await?,
+                Part::stream_with_length(Body::wrap_stream(stream), length),
                 file_name,
             )
         }
-        InputSource::Bytes { filename, bytes } => (Body::from(bytes), filename),
-        InputSource::VecU8 { filename, vec } => (Body::from(vec), filename),
+        InputSource::Bytes { filename, bytes } => (Part::stream(bytes), filename),
+        InputSource::VecU8 { filename, vec } => (Part::stream(vec), filename),
+        InputSource::Reader {
+            filename,
+            length,
+            reader,
+        } => {
+            let reader = reader.take().ok_or_else(|| {
+                OpenAIError::FileReadError(format!("{filename} has already been read"))
+            })?;
+            let stream = ReaderStream::with_capacity(reader, CHUNK_SIZE);
+
+            (
+                Part::stream_with_length(Body::wrap_stream(stream), length),
+                filename,
+            )
+        }
     };
 
-    let file_part = reqwest::multipart::Part::stream(stream)
-        .file_name(file_name)
-        .mime_str("application/octet-stream")
-        .unwrap();
+    Ok(with_file_name(part, file_name))
+}
+
+/// Set the file name of the part, and its MIME type guessed from the extension
+fn with_file_name(part: Part, file_name: String) -> Part {
+    let mime_type = mime_type(&file_name);
+    part.file_name(file_name).mime_str(mime_type).unwrap()
+}
+
+/// MIME type of the audio, image and document files accepted by the API,
+/// `application/octet-stream` for other files
+pub(crate) fn mime_type(file_name: &str) -> &'static str {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    match extension.as_str() {
+        "flac" => "audio/flac",
+        "m4a" => "audio/mp4",
+        "mp3" | "mpga" | "mpeg" => "audio/mpeg",
+        "mp4" => "video/mp4",
+        "ogg" | "oga" => "audio/ogg",
+        "wav" => "audio/wav",
+        "webm" => "video/webm",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "pdf" => "application/pdf",
+        "json" => "application/json",
+        "jsonl" => "application/jsonl",
+        "txt" => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
 
-    Ok(file_part)
+/// File name and content of the input, read into memory
+pub(crate) async fn read_input(source: InputSource) -> Result<(String, Bytes), OpenAIError> {
+    match source {
+        InputSource::Path { path } => {
+            let file_name = path
+                .file_name()
+                .map(|file_name| file_name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let bytes = tokio::fs::read(&path).await.map_err(|e| {
+                OpenAIError::FileReadError(format!("{}, path: {}", e, path.display()))
+            })?;
+            Ok((file_name, bytes.into()))
+        }
+        InputSource::Bytes { filename, bytes } => Ok((filename, bytes)),
+        InputSource::VecU8 { filename, vec } => Ok((filename, vec.into())),
+        InputSource::Reader {
+            filename,
+            length,
+            reader,
+        } => {
+            let mut reader = reader.take().ok_or_else(|| {
+                OpenAIError::FileReadError(format!("{filename} has already been read"))
+            })?;
+            let mut bytes = Vec::with_capacity(length as usize);
+            reader
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(|e| OpenAIError::FileReadError(format!("{e}, file: {filename}")))?;
+            Ok((filename, bytes.into()))
+        }
+    }
 }
 
 pub(crate) fn create_all_dir<P: AsRef<Path>>(dir: P) -> Result<(), OpenAIError> {
@@ -26,6 +26,10 @@ impl<'c, C: Config> VectorStoreFileBatches<'c, C> {
     }
 
     /// Create vector store file batch
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.vector_store_file_batches.create", skip_all)
+    )]
     pub async fn create(
         &self,
         request: CreateVectorStoreFileBatchRequest,
@@ -39,6 +43,10 @@ impl<'c, C: Config> VectorStoreFileBatches<'c, C> {
     }
 
     /// Retrieves a vector store file batch.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.vector_store_file_batches.retrieve", skip_all)
+    )]
     pub async fn retrieve(
         &self,
         batch_id: &str,
@@ -52,6 +60,10 @@ impl<'c, C: Config> VectorStoreFileBatches<'c, C> {
     }
 
     /// Cancel a vector store file batch. This attempts to cancel the processing of files in this batch as soon as possible.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.vector_store_file_batches.cancel", skip_all)
+    )]
     pub async fn cancel(&self, batch_id: &str) -> Result<VectorStoreFileBatchObject, OpenAIError> {
         self.client
             .post(
@@ -65,6 +77,10 @@ impl<'c, C: Config> VectorStoreFileBatches<'c, C> {
     }
 
     /// Returns a list of vector store files in a batch.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.vector_store_file_batches.list", skip_all)
+    )]
     pub async fn list<Q>(
         &self,
         batch_id: &str,
@@ -1,11 +1,13 @@
+use futures::Stream;
 use serde::Serialize;
 
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::paginate,
     types::{
         CreateVectorStoreFileRequest, DeleteVectorStoreFileResponse, ListVectorStoreFilesResponse,
-        VectorStoreFileObject,
+        Pagination, VectorStoreFileObject,
     },
     Client,
 };
@@ -27,6 +29,10 @@ impl<'c, C: Config> VectorStoreFiles<'c, C> {
     }
 
     /// Create a vector store file by attaching a [File](https://platform.openai.com/docs/api-reference/files) to a [vector store](https://platform.openai.com/docs/api-reference/vector-stores/object).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.vector_store_files.create", skip_all)
+    )]
     pub async fn create(
         &self,
         request: CreateVectorStoreFileRequest,
@@ -40,6 +46,10 @@ impl<'c, C: Config> VectorStoreFiles<'c, C> {
     }
 
     /// Retrieves a vector store file.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.vector_store_files.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, file_id: &str) -> Result<VectorStoreFileObject, OpenAIError> {
         self.client
             .get(&format!(
@@ -50,6 +60,10 @@ impl<'c, C: Config> VectorStoreFiles<'c, C> {
     }
 
     /// Delete a vector store file. This will remove the file from the vector store but the file itself will not be deleted. To delete the file, use the [delete file](https://platform.openai.com/docs/api-reference/files/delete) endpoint.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.vector_store_files.delete", skip_all)
+    )]
     pub async fn delete(
         &self,
         file_id: &str,
@@ -63,6 +77,10 @@ impl<'c, C: Config> VectorStoreFiles<'c, C> {
     }
 
     /// Returns a list of vector store files.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.vector_store_files.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListVectorStoreFilesResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -74,6 +92,24 @@ impl<'c, C: Config> VectorStoreFiles<'c, C> {
             )
             .await
     }
+
+    /// Stream all the files of the vector store, fetching the pages of [VectorStoreFiles::list] as the stream
+    /// is consumed. `query` holds the other parameters of [VectorStoreFiles::list].
+    pub fn list_all<'a, Q>(
+        &'a self,
+        query: &'a Q,
+        pagination: Pagination,
+    ) -> impl Stream<Item = Result<VectorStoreFileObject, OpenAIError>> + 'a
+    where
+        Q: Serialize + ?Sized,
+    {
+        paginate::<_, _, ListVectorStoreFilesResponse>(
+            self.client,
+            format!("/vector_stores/{}/files", &self.vector_store_id),
+            query,
+            pagination,
+        )
+    }
 }
 
 #[cfg(test)]
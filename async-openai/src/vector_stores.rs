@@ -1,10 +1,12 @@
+use futures::Stream;
 use serde::Serialize;
 
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::paginate,
     types::{
-        CreateVectorStoreRequest, DeleteVectorStoreResponse, ListVectorStoresResponse,
+        CreateVectorStoreRequest, DeleteVectorStoreResponse, ListVectorStoresResponse, Pagination,
         UpdateVectorStoreRequest, VectorStoreObject,
     },
     vector_store_file_batches::VectorStoreFileBatches,
@@ -21,16 +23,20 @@ impl<'c, C: Config> VectorStores<'c, C> {
     }
 
     /// [VectorStoreFiles] API group
-    pub fn files(&self, vector_store_id: &str) -> VectorStoreFiles<C> {
+    pub fn files(&self, vector_store_id: &str) -> VectorStoreFiles<'_, C> {
         VectorStoreFiles::new(self.client, vector_store_id)
     }
 
     /// [VectorStoreFileBatches] API group
-    pub fn file_batches(&self, vector_store_id: &str) -> VectorStoreFileBatches<C> {
+    pub fn file_batches(&self, vector_store_id: &str) -> VectorStoreFileBatches<'_, C> {
         VectorStoreFileBatches::new(self.client, vector_store_id)
     }
 
     /// Create a vector store.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.vector_stores.create", skip_all)
+    )]
     pub async fn create(
         &self,
         request: CreateVectorStoreRequest,
@@ -39,6 +45,10 @@ impl<'c, C: Config> VectorStores<'c, C> {
     }
 
     /// Retrieves a vector store.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.vector_stores.retrieve", skip_all)
+    )]
     pub async fn retrieve(&self, vector_store_id: &str) -> Result<VectorStoreObject, OpenAIError> {
         self.client
             .get(&format!("/vector_stores/{vector_store_id}"))
@@ -46,6 +56,10 @@ impl<'c, C: Config> VectorStores<'c, C> {
     }
 
     /// Returns a list of vector stores.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.vector_stores.list", skip_all)
+    )]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListVectorStoresResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
@@ -53,7 +67,29 @@ impl<'c, C: Config> VectorStores<'c, C> {
         self.client.get_with_query("/vector_stores", query).await
     }
 
+    /// Stream all the vector stores, fetching the pages of [VectorStores::list] as the stream
+    /// is consumed. `query` holds the other parameters of [VectorStores::list].
+    pub fn list_all<'a, Q>(
+        &'a self,
+        query: &'a Q,
+        pagination: Pagination,
+    ) -> impl Stream<Item = Result<VectorStoreObject, OpenAIError>> + 'a
+    where
+        Q: Serialize + ?Sized,
+    {
+        paginate::<_, _, ListVectorStoresResponse>(
+            self.client,
+            "/vector_stores".into(),
+            query,
+            pagination,
+        )
+    }
+
     /// Delete a vector store.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.vector_stores.delete", skip_all)
+    )]
     pub async fn delete(
         &self,
         vector_store_id: &str,
@@ -64,6 +100,10 @@ impl<'c, C: Config> VectorStores<'c, C> {
     }
 
     /// Modifies a vector store.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "openai.vector_stores.update", skip_all)
+    )]
     pub async fn update(
         &self,
         vector_store_id: &str,
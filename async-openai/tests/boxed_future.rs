@@ -1,3 +1,4 @@
+#![cfg(feature = "full")]
 use futures::future::{BoxFuture, FutureExt};
 use futures::StreamExt;
 
@@ -0,0 +1,179 @@
+#![cfg(feature = "full")]
+mod common;
+
+use async_openai::{
+    error::OpenAIError,
+    types::{
+        ChatCompletionAudio, ChatCompletionAudioFormat, ChatCompletionAudioVoice,
+        CreateChatCompletionResponse, InputAudio, InputAudioFormat,
+    },
+};
+use common::temp_dir;
+use serde_json::json;
+
+/// Response with a choice for each of `audio`, a base64 string or null
+fn response(audio: &[Option<&str>]) -> CreateChatCompletionResponse {
+    let choices: Vec<_> = audio
+        .iter()
+        .enumerate()
+        .map(|(index, data)| {
+            json!({
+                "index": index,
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "audio": data.map(|data| json!({
+                        "id": format!("audio_{index}"),
+                        "expires_at": 1729018505,
+                        "data": data,
+                        "transcript": "Hello"
+                    }))
+                },
+                "finish_reason": "stop"
+            })
+        })
+        .collect();
+
+    serde_json::from_value(json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1677652288,
+        "model": "gpt-4o-audio-preview",
+        "choices": choices
+    }))
+    .unwrap()
+}
+
+#[test]
+fn audio_params_constructor() {
+    let audio = ChatCompletionAudio::new(
+        ChatCompletionAudioVoice::Alloy,
+        ChatCompletionAudioFormat::Pcm16,
+    );
+
+    assert_eq!(
+        serde_json::to_value(audio).unwrap(),
+        json!({"voice": "alloy", "format": "pcm16"})
+    );
+}
+
+#[test]
+fn audio_bytes_are_decoded() {
+    let response = response(&[Some("UklGRg==")]);
+    let audio = response.choices[0].message.audio.as_ref().unwrap();
+
+    assert_eq!(audio.bytes().unwrap().as_ref(), b"RIFF");
+}
+
+#[test]
+fn invalid_audio_data_is_an_error() {
+    let response = response(&[Some("not base64!")]);
+    let audio = response.choices[0].message.audio.as_ref().unwrap();
+
+    assert!(matches!(
+        audio.bytes(),
+        Err(OpenAIError::InvalidArgument(_))
+    ));
+}
+
+#[tokio::test]
+async fn saves_audio_with_extension_of_format() {
+    let dir = temp_dir("chat-audio-single");
+
+    let paths = response(&[Some("UklGRg==")])
+        .save_audio(dir.join("answer"), ChatCompletionAudioFormat::Wav)
+        .await
+        .unwrap();
+
+    assert_eq!(paths, [dir.join("answer.wav")]);
+    assert_eq!(std::fs::read(&paths[0]).unwrap(), b"RIFF");
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn saves_audio_of_each_choice() {
+    let dir = temp_dir("chat-audio-choices");
+
+    let paths = response(&[Some("SUQz"), Some("SUQzBA==")])
+        .save_audio(dir.join("answer.mp3"), ChatCompletionAudioFormat::Mp3)
+        .await
+        .unwrap();
+
+    assert_eq!(paths, [dir.join("answer_0.mp3"), dir.join("answer_1.mp3")]);
+    assert_eq!(std::fs::read(&paths[1]).unwrap(), b"ID3\x04");
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn missing_audio_is_an_error() {
+    let dir = temp_dir("chat-audio-missing");
+
+    let error = response(&[Some("SUQz"), None])
+        .save_audio(dir.join("answer"), ChatCompletionAudioFormat::Mp3)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, OpenAIError::FileSaveError(ref message) if message == "choice 1 has no audio")
+    );
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn input_audio_format_is_detected() {
+    let dir = temp_dir("input-audio");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    for (name, bytes, format) in [
+        (
+            "hello.wav",
+            &b"RIFF\x24\x00\x00\x00WAVEfmt "[..],
+            InputAudioFormat::Wav,
+        ),
+        ("hello.mp3", b"ID3\x04\x00\x00", InputAudioFormat::Mp3),
+        // MPEG frame without a tag, the content wins over the extension
+        ("hello.wav.bin", b"\xff\xfb\x90\x64", InputAudioFormat::Mp3),
+        // Unknown content, from the extension
+        ("silence.WAV", b"\x00\x00\x00\x00", InputAudioFormat::Wav),
+    ] {
+        let path = dir.join(name);
+        std::fs::write(&path, bytes).unwrap();
+
+        let audio = InputAudio::from_path(&path).await.unwrap();
+        assert_eq!(audio.format, format, "{name}");
+        assert_eq!(
+            audio.data,
+            InputAudio::from_bytes(bytes, format).unwrap().data
+        );
+    }
+
+    let path = dir.join("notes.txt");
+    std::fs::write(&path, "not audio").unwrap();
+    assert!(matches!(
+        InputAudio::from_path(&path).await,
+        Err(OpenAIError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        InputAudio::from_path(dir.join("missing.mp3")).await,
+        Err(OpenAIError::FileReadError(_))
+    ));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn oversized_input_audio_is_an_error() {
+    let dir = temp_dir("input-audio-oversized");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("long.mp3");
+    let file = std::fs::File::create(&path).unwrap();
+    file.set_len(InputAudio::MAX_BYTES + 1).unwrap();
+
+    assert!(matches!(
+        InputAudio::from_path(&path).await,
+        Err(OpenAIError::InvalidArgument(_))
+    ));
+
+    file.set_len(InputAudio::MAX_BYTES).unwrap();
+    assert!(InputAudio::from_path(&path).await.is_ok());
+    std::fs::remove_dir_all(dir).unwrap();
+}
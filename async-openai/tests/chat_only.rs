@@ -0,0 +1,45 @@
+//! Builds only with `--no-default-features --features rustls,chat-completion`, where reqwest is
+//! compiled without its `multipart` feature. CI checks that feature stays off for this build.
+#![cfg(all(feature = "chat-completion", not(feature = "full")))]
+mod common;
+
+use async_openai::{
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+    Client,
+};
+use common::MockTransport;
+
+#[tokio::test]
+async fn chat_create_without_full() {
+    let transport = MockTransport::ok(
+        r#"{
+            "id": "chatcmpl-123",
+            "object": "chat.completion",
+            "created": 1677652288,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "Hi there!" },
+                "finish_reason": "stop"
+            }]
+        }"#,
+    );
+    let client = Client::new().with_transport(transport.clone());
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+    let response = client.chat().create(request).await.unwrap();
+
+    assert_eq!(
+        response.choices[0].message.content.as_deref(),
+        Some("Hi there!")
+    );
+    assert_eq!(transport.requests()[0].path(), "/v1/chat/completions");
+}
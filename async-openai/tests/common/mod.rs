@@ -0,0 +1,155 @@
+//! In-memory transport serving canned responses, shared by integration tests.
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_openai::{error::OpenAIError, transport::HttpTransport};
+use futures::future::BoxFuture;
+use http_body_util::BodyExt;
+
+#[derive(Clone, Debug)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+    pub delay: Option<Duration>,
+}
+
+impl MockResponse {
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: vec![],
+            body: body.into(),
+            delay: None,
+        }
+    }
+
+    pub fn header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    /// Respond only after `delay`
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+/// Request as seen by the transport
+#[derive(Clone, Debug)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: reqwest::Url,
+    pub headers: reqwest::header::HeaderMap,
+    pub body: Option<Vec<u8>>,
+}
+
+impl RecordedRequest {
+    pub fn path(&self) -> &str {
+        self.url.path()
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(self.body.as_deref().unwrap()).unwrap()
+    }
+
+    pub fn body_text(&self) -> String {
+        String::from_utf8_lossy(self.body.as_deref().unwrap()).into_owned()
+    }
+}
+
+type Responder = dyn Fn(&RecordedRequest) -> MockResponse + Send + Sync;
+
+/// Serves queued responses in order, the last response is repeated once the queue is drained
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    responses: Arc<Mutex<VecDeque<MockResponse>>>,
+    responder: Option<Arc<Responder>>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockTransport {
+    pub fn new(responses: impl IntoIterator<Item = MockResponse>) -> Self {
+        Self {
+            responses: Arc::new(Mutex::new(responses.into_iter().collect())),
+            ..Default::default()
+        }
+    }
+
+    pub fn ok(body: impl Into<String>) -> Self {
+        Self::new([MockResponse::new(200, body)])
+    }
+
+    /// Serves the response returned by `responder` for each request instead of a queue
+    pub fn with_responder(
+        responder: impl Fn(&RecordedRequest) -> MockResponse + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            responder: Some(Arc::new(responder)),
+            ..Default::default()
+        }
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    fn next_response(&self, request: &RecordedRequest) -> MockResponse {
+        if let Some(responder) = &self.responder {
+            return responder(request);
+        }
+
+        let mut responses = self.responses.lock().unwrap();
+        if responses.len() > 1 {
+            responses.pop_front().unwrap()
+        } else {
+            responses.front().cloned().expect("no mock response")
+        }
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn execute(
+        &self,
+        mut request: reqwest::Request,
+    ) -> BoxFuture<'_, Result<reqwest::Response, OpenAIError>> {
+        let mut recorded = RecordedRequest {
+            method: request.method().to_string(),
+            url: request.url().clone(),
+            headers: request.headers().clone(),
+            body: None,
+        };
+        // Streamed bodies, like multipart forms, are read below
+        let body = request.body_mut().take();
+
+        Box::pin(async move {
+            if let Some(body) = body {
+                recorded.body = Some(body.collect().await?.to_bytes().to_vec());
+            }
+            let mock = self.next_response(&recorded);
+            self.requests.lock().unwrap().push(recorded);
+
+            if let Some(delay) = mock.delay {
+                tokio::time::sleep(delay).await;
+            }
+
+            let mut builder = http::Response::builder().status(mock.status);
+            for (name, value) in mock.headers {
+                builder = builder.header(name, value);
+            }
+            Ok(reqwest::Response::from(builder.body(mock.body).unwrap()))
+        })
+    }
+}
+
+/// Directory named after `name` and the process in the temp dir, removed if it exists
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("async-openai-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "chat-completion")]
+mod common;
+
+use std::time::Duration;
+
+use async_openai::{
+    error::OpenAIError,
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+    Client,
+};
+use common::{MockResponse, MockTransport};
+
+const RATE_LIMITED: &str = r#"{"error": {"message": "Rate limit reached", "type": "requests", "param": null, "code": "rate_limit_exceeded"}}"#;
+
+fn request() -> async_openai::types::CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+fn backoff() -> backoff::ExponentialBackoff {
+    backoff::ExponentialBackoffBuilder::new()
+        .with_initial_interval(Duration::from_secs(1))
+        .with_multiplier(2.0)
+        .with_randomization_factor(0.0)
+        .with_max_elapsed_time(None)
+        .build()
+}
+
+#[tokio::test(start_paused = true)]
+async fn deadline_stops_before_retry_after_overruns_it() {
+    let transport =
+        MockTransport::new([MockResponse::new(429, RATE_LIMITED).header("retry-after-ms", "1000")]);
+    let client = Client::new()
+        .with_transport(transport.clone())
+        .with_deadline(Duration::from_millis(3500));
+
+    let error = client.chat().create(request()).await.unwrap_err();
+
+    // Attempts at 0s, 1s, 2s and 3s, waiting one more second would overrun 3.5s
+    assert!(matches!(
+        error,
+        OpenAIError::DeadlineExceeded { attempts: 4, last_error: Some(ref last_error) }
+            if matches!(**last_error, OpenAIError::RateLimited { .. })
+    ));
+    assert_eq!(transport.requests().len(), 4);
+}
+
+#[tokio::test(start_paused = true)]
+async fn deadline_stops_before_backoff_overruns_it() {
+    let transport = MockTransport::new([MockResponse::new(429, RATE_LIMITED)]);
+    let client = Client::new()
+        .with_transport(transport.clone())
+        .with_backoff(backoff())
+        .with_deadline(Duration::from_secs(5));
+
+    let error = client.chat().create(request()).await.unwrap_err();
+
+    // Attempts at 0s, 1s and 3s, the next backoff of 4s would overrun 5s
+    assert!(matches!(
+        error,
+        OpenAIError::DeadlineExceeded {
+            attempts: 3,
+            last_error: Some(_)
+        }
+    ));
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn deadline_bounds_a_slow_attempt() {
+    let transport = MockTransport::new([
+        MockResponse::new(429, RATE_LIMITED).header("retry-after-ms", "100"),
+        MockResponse::new(200, "{}").delay(Duration::from_secs(60)),
+    ]);
+    let client = Client::new()
+        .with_transport(transport.clone())
+        .with_deadline(Duration::from_secs(2));
+
+    let started = tokio::time::Instant::now();
+    let error = client.chat().create(request()).await.unwrap_err();
+
+    assert_eq!(started.elapsed(), Duration::from_secs(2));
+    assert!(matches!(
+        error,
+        OpenAIError::DeadlineExceeded { attempts: 2, last_error: Some(ref last_error) }
+            if matches!(**last_error, OpenAIError::RateLimited { .. })
+    ));
+    assert_eq!(
+        error.to_string(),
+        "deadline exceeded after 2 attempt(s): rate limited: Rate limit reached (retry after 0.100s)"
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn no_deadline_keeps_retrying() {
+    let transport = MockTransport::new([
+        MockResponse::new(429, RATE_LIMITED).header("retry-after", "60"),
+        MockResponse::new(429, RATE_LIMITED).header("retry-after", "60"),
+        MockResponse::new(
+            200,
+            r#"{"id": "chatcmpl-123", "object": "chat.completion", "created": 1677652288, "model": "gpt-4o", "choices": []}"#,
+        ),
+    ]);
+    let client = Client::new().with_transport(transport.clone());
+
+    client.chat().create(request()).await.unwrap();
+
+    assert_eq!(transport.requests().len(), 3);
+}
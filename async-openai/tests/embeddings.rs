@@ -1,5 +1,20 @@
-//! This test is primarily to make sure that macros_rules for From traits are correct.
-use async_openai::types::EmbeddingInput;
+//! From traits of the embedding input, decoding of base64 embeddings and batched embeddings.
+#![cfg(feature = "full")]
+
+mod common;
+
+use std::time::Duration;
+
+use async_openai::{
+    error::OpenAIError,
+    types::{
+        Base64EmbeddingVector, BatchOptions, CreateEmbeddingRequestArgs, CreateEmbeddingResponse,
+        EmbeddingInput, EncodingFormat,
+    },
+    Client,
+};
+use base64::{engine::general_purpose, Engine as _};
+use common::{MockResponse, MockTransport, RecordedRequest};
 
 fn embedding_input<T>(input: T) -> EmbeddingInput
 where
@@ -44,3 +59,232 @@ fn create_embedding_input() {
     let _ = embedding_input(&input);
     let _ = embedding_input(input);
 }
+
+#[test]
+fn base64_embedding_round_trip() {
+    let floats = [0.5, -1.25, 3.0e-3, f32::MIN_POSITIVE];
+    let bytes: Vec<u8> = floats.iter().flat_map(|f| f.to_le_bytes()).collect();
+
+    let vector = Base64EmbeddingVector(general_purpose::STANDARD.encode(&bytes));
+    assert_eq!(vector.to_f32s().unwrap(), floats);
+    assert_eq!(Base64EmbeddingVector::from_f32s(&floats), vector);
+
+    let vector = Base64EmbeddingVector(general_purpose::STANDARD.encode(&bytes[..6]));
+    assert!(vector
+        .to_f32s()
+        .unwrap_err()
+        .to_string()
+        .contains("6 bytes"));
+    assert!(Base64EmbeddingVector("not base64!".into())
+        .to_f32s()
+        .is_err());
+}
+
+#[tokio::test]
+async fn base64_embedding_response_decoding() {
+    let embedding = Base64EmbeddingVector::from_f32s(&[0.25, -0.5]);
+    let transport = MockTransport::ok(
+        serde_json::json!({
+            "object": "list",
+            "model": "text-embedding-3-small",
+            "data": [{"index": 0, "object": "embedding", "embedding": embedding.0}],
+            "usage": {"prompt_tokens": 2, "total_tokens": 2}
+        })
+        .to_string(),
+    );
+    let client = Client::new().with_transport(transport.clone());
+
+    let request = CreateEmbeddingRequestArgs::default()
+        .model("text-embedding-3-small")
+        .input("Hello")
+        .encoding_format(EncodingFormat::Base64)
+        .build()
+        .unwrap();
+    let response = client.embeddings().create_base64(request).await.unwrap();
+    assert_eq!(transport.requests()[0].json()["encoding_format"], "base64");
+
+    let response = CreateEmbeddingResponse::try_from(response).unwrap();
+    assert_eq!(response.data[0].embedding, [0.25, -0.5]);
+    assert_eq!(response.usage.total_tokens, 2);
+}
+
+/// Embeds each input, a number, as a vector of that number. The first request is the
+/// slowest so that its response comes last, and inputs containing "bad" are rejected.
+fn embed_numbers(request: &RecordedRequest) -> MockResponse {
+    let inputs: Vec<String> = serde_json::from_value(request.json()["input"].clone()).unwrap();
+    if inputs.iter().any(|input| input.contains("bad")) {
+        return MockResponse::new(
+            400,
+            r#"{"error": {"message": "bad input", "type": "invalid_request_error"}}"#,
+        );
+    }
+
+    let data: Vec<_> = inputs
+        .iter()
+        .enumerate()
+        // The API doesn't guarantee the order of the embeddings of a response
+        .rev()
+        .map(|(index, input)| {
+            serde_json::json!({
+                "index": index,
+                "object": "embedding",
+                "embedding": [input.parse::<f32>().unwrap()]
+            })
+        })
+        .collect();
+    let delay = if inputs[0] == "0" { 50 } else { 0 };
+
+    MockResponse::new(
+        200,
+        serde_json::json!({
+            "object": "list",
+            "model": "text-embedding-3-small",
+            "data": data,
+            "usage": {"prompt_tokens": inputs.len(), "total_tokens": inputs.len()}
+        })
+        .to_string(),
+    )
+    .delay(Duration::from_millis(delay))
+}
+
+#[tokio::test]
+async fn create_batched_keeps_input_order() {
+    let transport = MockTransport::with_responder(embed_numbers);
+    let client = Client::new().with_transport(transport.clone());
+
+    let inputs: Vec<String> = (0..7).map(|i| i.to_string()).collect();
+    let options = BatchOptions {
+        max_items_per_request: 3,
+        max_concurrency: 3,
+    };
+    let chunks = client
+        .embeddings()
+        .create_batched(inputs, "text-embedding-3-small", options)
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+
+    let mut chunk_sizes: Vec<usize> = transport
+        .requests()
+        .iter()
+        .map(|request| request.json()["input"].as_array().unwrap().len())
+        .collect();
+    chunk_sizes.sort();
+    assert_eq!(chunk_sizes, [1, 3, 3]);
+
+    let ranges: Vec<_> = chunks.iter().map(|chunk| chunk.inputs.clone()).collect();
+    assert_eq!(ranges, [0..3, 3..6, 6..7]);
+    let embeddings: Vec<Vec<f32>> = chunks
+        .iter()
+        .flat_map(|chunk| chunk.embeddings.clone())
+        .collect();
+    let expected: Vec<Vec<f32>> = (0..7).map(|i| vec![i as f32]).collect();
+    assert_eq!(embeddings, expected);
+    assert_eq!(chunks[0].usage.total_tokens, 3);
+}
+
+#[tokio::test]
+async fn create_batched_reports_failed_inputs() {
+    let transport = MockTransport::with_responder(embed_numbers);
+    let client = Client::new().with_transport(transport);
+
+    let inputs = ["0", "1", "2", "bad", "4", "5"].map(String::from).to_vec();
+    let options = BatchOptions {
+        max_items_per_request: 2,
+        max_concurrency: 1,
+    };
+    let outcome = client
+        .embeddings()
+        .create_batched(inputs, "text-embedding-3-small", options)
+        .await
+        .unwrap();
+
+    // The requests after the failed one are still sent
+    let succeeded: Vec<_> = outcome
+        .successes
+        .iter()
+        .map(|(index, chunk)| (*index, chunk.inputs.clone()))
+        .collect();
+    assert_eq!(succeeded, [(0, 0..2), (2, 4..6)]);
+    assert_eq!(outcome.successes[1].1.embeddings, [vec![4.0], vec![5.0]]);
+
+    let [(1, failed)] = &outcome.failures[..] else {
+        panic!(
+            "expected the second request to fail, got {:?}",
+            outcome.failures
+        );
+    };
+    assert_eq!(failed.inputs, 2..4);
+    assert!(matches!(failed.error, OpenAIError::ApiError(_)));
+}
+
+#[tokio::test]
+async fn create_batched_rejects_missing_or_duplicate_indices() {
+    for indices in [vec![0], vec![0, 0], vec![0, 1, 2]] {
+        let data: Vec<_> = indices
+            .iter()
+            .map(|index| {
+                serde_json::json!({"index": index, "object": "embedding", "embedding": [0.0]})
+            })
+            .collect();
+        let body = serde_json::json!({
+            "object": "list",
+            "model": "text-embedding-3-small",
+            "data": data,
+            "usage": {"prompt_tokens": 2, "total_tokens": 2}
+        });
+        let client = Client::new().with_transport(MockTransport::ok(body.to_string()));
+
+        let outcome = client
+            .embeddings()
+            .create_batched(
+                vec!["0".into(), "1".into()],
+                "text-embedding-3-small",
+                BatchOptions::default(),
+            )
+            .await
+            .unwrap();
+
+        let [(0, failed)] = &outcome.failures[..] else {
+            panic!("expected a failed request for {indices:?}, got {outcome:?}");
+        };
+        assert_eq!(failed.inputs, 0..2);
+        assert!(matches!(failed.error, OpenAIError::InvalidArgument(_)));
+    }
+}
+
+#[tokio::test]
+async fn create_batched_options_validation() {
+    let transport = MockTransport::with_responder(embed_numbers);
+    let client = Client::new().with_transport(transport.clone());
+
+    for options in [
+        BatchOptions {
+            max_items_per_request: 0,
+            ..Default::default()
+        },
+        BatchOptions {
+            max_items_per_request: 2049,
+            ..Default::default()
+        },
+        BatchOptions {
+            max_concurrency: 0,
+            ..Default::default()
+        },
+    ] {
+        let result = client
+            .embeddings()
+            .create_batched(vec!["0".into()], "text-embedding-3-small", options)
+            .await;
+        assert!(matches!(result, Err(OpenAIError::InvalidArgument(_))));
+    }
+
+    let outcome = client
+        .embeddings()
+        .create_batched(vec![], "text-embedding-3-small", BatchOptions::default())
+        .await
+        .unwrap();
+    assert!(outcome.successes.is_empty() && outcome.is_complete());
+    assert!(transport.requests().is_empty());
+}
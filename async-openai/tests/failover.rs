@@ -0,0 +1,236 @@
+#![cfg(feature = "chat-completion")]
+mod common;
+
+use std::time::Duration;
+
+use async_openai::{
+    config::{OpenAIConfig, FAILOVER_BACKEND_HEADER},
+    error::OpenAIError,
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+    Client,
+};
+use common::{MockResponse, MockTransport, RecordedRequest};
+use futures::StreamExt;
+
+const PRIMARY: &str = "https://primary.example.com/v1";
+const SECONDARY: &str = "https://secondary.example.com/v1";
+
+const CHAT_RESPONSE: &str = r#"{
+    "id": "chatcmpl-123",
+    "object": "chat.completion",
+    "created": 1677652288,
+    "model": "gpt-4o",
+    "choices": [{
+        "index": 0,
+        "message": {"role": "assistant", "content": "Hello there"},
+        "finish_reason": "stop"
+    }]
+}"#;
+const CHAT_CHUNK: &str = "data: {\"id\":\"chatcmpl-123\",\"object\":\"chat.completion.chunk\",\"created\":1694268190,\"model\":\"gpt-4o\",\"choices\":[]}\n\ndata: [DONE]\n\n";
+const SERVER_ERROR: &str = r#"{"error": {"message": "The server had an error", "type": "server_error", "param": null, "code": null}}"#;
+const BAD_REQUEST: &str = r#"{"error": {"message": "Invalid model", "type": "invalid_request_error", "param": "model", "code": null}}"#;
+const RATE_LIMITED: &str = r#"{"error": {"message": "Rate limit reached", "type": "requests", "param": null, "code": "rate_limit_exceeded"}}"#;
+
+fn request() -> async_openai::types::CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+/// Client sending requests to the primary config first, then to the secondary one
+fn client(transport: &MockTransport) -> Client<OpenAIConfig> {
+    Client::with_config(
+        OpenAIConfig::new()
+            .with_api_base(PRIMARY)
+            .with_api_key("sk-primary"),
+    )
+    .with_failover([OpenAIConfig::new()
+        .with_api_base(SECONDARY)
+        .with_api_key("sk-secondary")
+        .with_query_param("region", "eu")])
+    .with_transport(transport.clone())
+}
+
+/// Responds with `primary` to requests sent to the primary config, and `secondary` otherwise
+fn transport(primary: MockResponse, secondary: MockResponse) -> MockTransport {
+    MockTransport::with_responder(move |request: &RecordedRequest| {
+        if request.url.host_str() == Some("primary.example.com") {
+            primary.clone()
+        } else {
+            secondary.clone()
+        }
+    })
+}
+
+#[tokio::test]
+async fn server_error_fails_over_to_next_config() {
+    let transport = transport(
+        MockResponse::new(503, SERVER_ERROR),
+        MockResponse::new(200, CHAT_RESPONSE),
+    );
+
+    let (response, headers) = client(&transport)
+        .chat()
+        .create_with_headers(request())
+        .await
+        .unwrap();
+
+    assert_eq!(response.id, "chatcmpl-123");
+    assert_eq!(headers[FAILOVER_BACKEND_HEADER], SECONDARY);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[1].url.as_str(),
+        "https://secondary.example.com/v1/chat/completions?region=eu"
+    );
+    assert_eq!(requests[1].headers["authorization"], "Bearer sk-secondary");
+    assert_eq!(requests[0].json(), requests[1].json());
+}
+
+#[tokio::test]
+async fn bearer_auth_is_not_sent_to_fallback_configs() {
+    let transport = MockTransport::with_responder(|request: &RecordedRequest| {
+        match (request.url.host_str(), request.json()["stream"].as_bool()) {
+            (Some("primary.example.com"), _) => MockResponse::new(503, SERVER_ERROR),
+            (_, Some(true)) => MockResponse::new(200, CHAT_CHUNK),
+            _ => MockResponse::new(200, CHAT_RESPONSE),
+        }
+    });
+    let client = client(&transport).with_bearer_auth("sk-customer").unwrap();
+
+    client.chat().create(request()).await.unwrap();
+    let mut request = request();
+    request.stream = Some(true);
+    let mut stream = client.chat().create_stream(request).await.unwrap();
+    while stream.next().await.is_some() {}
+
+    let authorization: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|request| {
+            (
+                request.url.host_str().unwrap().to_string(),
+                request.headers["authorization"]
+                    .to_str()
+                    .unwrap()
+                    .to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        authorization,
+        [
+            ("primary.example.com", "Bearer sk-customer"),
+            ("secondary.example.com", "Bearer sk-secondary"),
+            ("primary.example.com", "Bearer sk-customer"),
+            ("secondary.example.com", "Bearer sk-secondary"),
+        ]
+        .map(|(host, value)| (host.to_string(), value.to_string()))
+    );
+}
+
+#[tokio::test]
+async fn primary_config_serves_when_it_succeeds() {
+    let transport = transport(
+        MockResponse::new(200, CHAT_RESPONSE),
+        MockResponse::new(503, SERVER_ERROR),
+    );
+
+    let (_, headers) = client(&transport)
+        .chat()
+        .create_with_headers(request())
+        .await
+        .unwrap();
+
+    assert_eq!(headers[FAILOVER_BACKEND_HEADER], PRIMARY);
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn client_error_does_not_fail_over() {
+    let transport = transport(
+        MockResponse::new(400, BAD_REQUEST),
+        MockResponse::new(200, CHAT_RESPONSE),
+    );
+
+    let error = client(&transport)
+        .chat()
+        .create(request())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, OpenAIError::ApiError(ref e) if e.message == "Invalid model"));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn rate_limit_fails_over_once_retries_are_exhausted() {
+    let transport = transport(
+        MockResponse::new(429, RATE_LIMITED),
+        MockResponse::new(200, CHAT_RESPONSE),
+    );
+    // Gives up retrying right after the first attempt
+    let backoff = backoff::ExponentialBackoffBuilder::new()
+        .with_max_elapsed_time(Some(Duration::ZERO))
+        .build();
+
+    let (_, headers) = client(&transport)
+        .with_backoff(backoff)
+        .chat()
+        .create_with_headers(request())
+        .await
+        .unwrap();
+
+    assert_eq!(headers[FAILOVER_BACKEND_HEADER], SECONDARY);
+    let hosts: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|request| request.url.host_str().unwrap().to_string())
+        .collect();
+    assert_eq!(hosts, ["primary.example.com", "secondary.example.com"]);
+}
+
+#[tokio::test]
+async fn last_error_is_returned_when_every_config_fails() {
+    let transport = transport(
+        MockResponse::new(503, SERVER_ERROR),
+        MockResponse::new(502, SERVER_ERROR),
+    );
+
+    let error = client(&transport)
+        .chat()
+        .create(request())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, OpenAIError::ApiError(_)));
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn stream_fails_over_before_first_event() {
+    let transport = transport(
+        MockResponse::new(429, RATE_LIMITED),
+        MockResponse::new(200, CHAT_CHUNK),
+    );
+
+    let mut stream = client(&transport)
+        .chat()
+        .create_stream(request())
+        .await
+        .unwrap();
+
+    assert_eq!(stream.next().await.unwrap().unwrap().id, "chatcmpl-123");
+    assert!(stream.next().await.is_none());
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].headers["authorization"], "Bearer sk-secondary");
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "full")]
+use std::path::PathBuf;
+
+use async_openai::{
+    error::OpenAIError,
+    types::{ImageUrl, ImageUrlArgs},
+};
+use base64::{engine::general_purpose, Engine as _};
+
+/// 1x1 transparent PNG
+const PNG: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+/// 1x1 GIF
+const GIF: &str = "R0lGODlhAQABAIAAAAAAAP///yH5BAEAAAAALAAAAAABAAEAAAIBRAA7";
+/// 1x1 lossless WebP
+const WEBP: &[u8] = b"RIFF\x1a\x00\x00\x00WEBPVP8L\x0d\x00\x00\x00\x2f\x00\x00\x00\x10\x07\x10\x11\x11\x88\x88\xfe\x07\x00";
+/// Start of a JFIF JPEG
+const JPEG: &[u8] = b"\xff\xd8\xff\xe0\x00\x10JFIF\x00\x01\x01\x00\x00\x01\x00\x01\x00\x00";
+
+fn fixture(name: &str, bytes: &[u8]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("async-openai-image-url-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, bytes).unwrap();
+    path
+}
+
+#[tokio::test]
+async fn from_path_sniffs_mime_type() {
+    let png = general_purpose::STANDARD.decode(PNG).unwrap();
+    let gif = general_purpose::STANDARD.decode(GIF).unwrap();
+
+    // Extensions are ignored, the content identifies the image
+    for (name, bytes, mime) in [
+        ("pixel.png", png.as_slice(), "image/png"),
+        ("pixel.bin", gif.as_slice(), "image/gif"),
+        ("pixel.webp", WEBP, "image/webp"),
+        ("pixel.png.jpg", JPEG, "image/jpeg"),
+    ] {
+        let image_url = ImageUrl::from_path(fixture(name, bytes)).await.unwrap();
+        let expected = format!(
+            "data:{mime};base64,{}",
+            general_purpose::STANDARD.encode(bytes)
+        );
+        assert_eq!(image_url.url, expected, "{name}");
+        assert_eq!(image_url.detail, None);
+    }
+}
+
+#[tokio::test]
+async fn from_path_rejects_unsupported_images() {
+    let path = fixture("image.bmp", b"BM\x3a\x00\x00\x00\x00\x00\x00\x00");
+    assert!(matches!(
+        ImageUrl::from_path(path).await,
+        Err(OpenAIError::InvalidArgument(_))
+    ));
+
+    assert!(matches!(
+        ImageUrl::from_path("does/not/exist.png").await,
+        Err(OpenAIError::FileReadError(_))
+    ));
+}
+
+#[test]
+fn from_bytes_and_base64() {
+    let png = general_purpose::STANDARD.decode(PNG).unwrap();
+
+    assert_eq!(
+        ImageUrl::from_bytes(&png, "image/png").unwrap().url,
+        format!("data:image/png;base64,{PNG}")
+    );
+    assert_eq!(
+        ImageUrl::from_base64(GIF, "image/gif").unwrap().url,
+        format!("data:image/gif;base64,{GIF}")
+    );
+
+    assert!(matches!(
+        ImageUrl::from_bytes(&png, "image/tiff"),
+        Err(OpenAIError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        ImageUrl::from_base64("not base64!", "image/png"),
+        Err(OpenAIError::InvalidArgument(_))
+    ));
+}
+
+#[test]
+fn builder_rejects_raw_base64() {
+    let error = ImageUrlArgs::default().url(PNG).build().unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+
+    for url in [
+        "https://upload.wikimedia.org/wikipedia/commons/a/a7/Example.jpg",
+        &format!("data:image/png;base64,{PNG}"),
+    ] {
+        assert!(ImageUrlArgs::default().url(url).build().is_ok(), "{url}");
+    }
+}
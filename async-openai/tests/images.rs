@@ -0,0 +1,485 @@
+#![cfg(feature = "full")]
+mod common;
+
+use async_openai::{
+    types::{
+        CreateImageEditRequestArgs, CreateImageRequestArgs, DallE2ImageSize, Image,
+        ImageBackground, ImageInput, ImageInputFidelity, ImageInputTokensDetails, ImageModel,
+        ImageModeration, ImageOutputFormat, ImageQuality, ImageSize, ImageUsage, ImagesResponse,
+        InputSource,
+    },
+    Client,
+};
+use common::{MockResponse, MockTransport};
+
+const IMAGES: &str = r#"{"created": 1713833628, "data": [{"b64_json": "aGVsbG8="}]}"#;
+
+fn png(name: &str) -> ImageInput {
+    ImageInput::from_vec_u8(name.into(), b"PNG".to_vec())
+}
+
+#[tokio::test]
+async fn images_response_save_reports_failures() {
+    let response: ImagesResponse = serde_json::from_value(serde_json::json!({
+        "created": 1700000000,
+        "data": [
+            { "b64_json": "aGVsbG8=" },
+            { "b64_json": "not base64!" },
+        ]
+    }))
+    .unwrap();
+
+    let dir = std::env::temp_dir().join("async-openai-images-save");
+    let _ = std::fs::remove_dir_all(&dir);
+    let error = response.save(&dir).await.unwrap_err();
+
+    assert!(error.to_string().starts_with("failed to save file"));
+    // The valid image is still written
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn image_edit_single_image_part() {
+    let transport = MockTransport::ok(IMAGES);
+    let client = Client::new().with_transport(transport.clone());
+
+    let request = CreateImageEditRequestArgs::default()
+        .image(png("lounge.png"))
+        .prompt("Add a duck")
+        .build()
+        .unwrap();
+    client.images().create_edit(request).await.unwrap();
+
+    let body = transport.requests()[0].body_text();
+    assert!(body.contains("name=\"image\"; filename=\"lounge.png\""));
+    assert!(!body.contains("image[]"));
+}
+
+#[tokio::test]
+async fn image_edit_multiple_image_parts() {
+    let transport = MockTransport::ok(IMAGES);
+    let client = Client::new().with_transport(transport.clone());
+
+    let request = CreateImageEditRequestArgs::default()
+        .image([png("soap.png"), png("lotion.png")])
+        .mask(png("mask.png"))
+        .model(ImageModel::GptImage1)
+        .prompt("A gift basket with these items")
+        .build()
+        .unwrap();
+    assert_eq!(request.image.images().len(), 2);
+    client.images().create_edit(request).await.unwrap();
+
+    let body = transport.requests()[0].body_text();
+    let soap = body
+        .find("name=\"image[]\"; filename=\"soap.png\"")
+        .unwrap();
+    let lotion = body
+        .find("name=\"image[]\"; filename=\"lotion.png\"")
+        .unwrap();
+    assert!(soap < lotion);
+    assert!(body.contains("name=\"mask\"; filename=\"mask.png\""));
+    assert!(body.contains("name=\"model\"\r\n\r\ngpt-image-1\r\n"));
+}
+
+#[test]
+fn image_edit_images_validation() {
+    let images = |count: usize| -> Vec<ImageInput> {
+        (0..count).map(|i| png(&format!("{i}.png"))).collect()
+    };
+    let is_valid = |images: Vec<ImageInput>, model: Option<ImageModel>| {
+        let mut args = CreateImageEditRequestArgs::default();
+        args.image(images).prompt("Combine");
+        if let Some(model) = model {
+            args.model(model);
+        }
+        args.build().is_ok()
+    };
+
+    assert!(is_valid(images(16), Some(ImageModel::GptImage1)));
+    assert!(!is_valid(images(17), Some(ImageModel::GptImage1)));
+    assert!(!is_valid(images(0), Some(ImageModel::GptImage1)));
+    // Only gpt-image-1 edits several images, dall-e-2 is the default model
+    assert!(!is_valid(images(2), Some(ImageModel::DallE2)));
+    assert!(!is_valid(images(2), None));
+    assert!(is_valid(images(1), None));
+}
+
+#[test]
+fn image_request_gpt_image_1_options() {
+    let request = CreateImageRequestArgs::default()
+        .prompt("A cat")
+        .model(ImageModel::GptImage1)
+        .quality(ImageQuality::High)
+        .size(ImageSize::S1536x1024)
+        .background(ImageBackground::Opaque)
+        .output_format(ImageOutputFormat::Webp)
+        .output_compression(80)
+        .moderation(ImageModeration::Low)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(request).unwrap(),
+        serde_json::json!({
+            "prompt": "A cat",
+            "model": "gpt-image-1",
+            "quality": "high",
+            "size": "1536x1024",
+            "background": "opaque",
+            "output_format": "webp",
+            "output_compression": 80,
+            "moderation": "low"
+        })
+    );
+}
+
+#[test]
+fn image_request_output_options_validation() {
+    let is_valid = |background: Option<ImageBackground>,
+                    output_format: Option<ImageOutputFormat>,
+                    output_compression: Option<u8>| {
+        let mut args = CreateImageRequestArgs::default();
+        args.prompt("A cat").model(ImageModel::GptImage1);
+        if let Some(background) = background {
+            args.background(background);
+        }
+        if let Some(output_format) = output_format {
+            args.output_format(output_format);
+        }
+        if let Some(output_compression) = output_compression {
+            args.output_compression(output_compression);
+        }
+        args.build().is_ok()
+    };
+
+    assert!(is_valid(None, Some(ImageOutputFormat::Jpeg), Some(0)));
+    assert!(is_valid(None, Some(ImageOutputFormat::Webp), Some(100)));
+    assert!(!is_valid(None, Some(ImageOutputFormat::Webp), Some(101)));
+    // png is the default format, and isn't compressed
+    assert!(!is_valid(None, Some(ImageOutputFormat::Png), Some(50)));
+    assert!(!is_valid(None, None, Some(50)));
+
+    let transparent = Some(ImageBackground::Transparent);
+    assert!(is_valid(transparent, None, None));
+    assert!(is_valid(transparent, Some(ImageOutputFormat::Webp), None));
+    assert!(!is_valid(transparent, Some(ImageOutputFormat::Jpeg), None));
+    assert!(is_valid(
+        Some(ImageBackground::Opaque),
+        Some(ImageOutputFormat::Jpeg),
+        None
+    ));
+}
+
+#[tokio::test]
+async fn image_edit_gpt_image_1_options() {
+    let transport = MockTransport::ok(IMAGES);
+    let client = Client::new().with_transport(transport.clone());
+
+    let request = CreateImageEditRequestArgs::default()
+        .image(png("lounge.png"))
+        .prompt("Add a duck")
+        .model(ImageModel::GptImage1)
+        .input_fidelity(ImageInputFidelity::High)
+        .background(ImageBackground::Transparent)
+        .output_format(ImageOutputFormat::Webp)
+        .output_compression(60)
+        .build()
+        .unwrap();
+    client.images().create_edit(request).await.unwrap();
+
+    let body = transport.requests()[0].body_text();
+    assert!(body.contains("name=\"input_fidelity\"\r\n\r\nhigh\r\n"));
+    assert!(body.contains("name=\"background\"\r\n\r\ntransparent\r\n"));
+    assert!(body.contains("name=\"output_format\"\r\n\r\nwebp\r\n"));
+    assert!(body.contains("name=\"output_compression\"\r\n\r\n60\r\n"));
+
+    let error = CreateImageEditRequestArgs::default()
+        .image(png("lounge.png"))
+        .prompt("Add a duck")
+        .model(ImageModel::GptImage1)
+        .background(ImageBackground::Transparent)
+        .output_format(ImageOutputFormat::Jpeg)
+        .build()
+        .unwrap_err();
+    assert!(error.to_string().contains("transparent background"));
+}
+
+#[test]
+fn images_response_gpt_image_1_fields() {
+    let response: ImagesResponse = serde_json::from_value(serde_json::json!({
+        "created": 1713833628,
+        "background": "transparent",
+        "data": [{"b64_json": "aGVsbG8="}],
+        "output_format": "png",
+        "quality": "high",
+        "size": "1024x1024",
+        "usage": {
+            "total_tokens": 100,
+            "input_tokens": 50,
+            "output_tokens": 50,
+            "input_tokens_details": {"text_tokens": 10, "image_tokens": 40}
+        }
+    }))
+    .unwrap();
+
+    assert_eq!(response.background, Some(ImageBackground::Transparent));
+    assert_eq!(response.output_format, Some(ImageOutputFormat::Png));
+    assert_eq!(response.quality, Some(ImageQuality::High));
+    assert_eq!(response.size, Some(ImageSize::S1024x1024));
+    let usage = response.usage.unwrap();
+    assert_eq!(usage.total_tokens, 100);
+    assert_eq!(usage.input_tokens_details.image_tokens, 40);
+
+    // dall-e responses don't have them
+    let response: ImagesResponse = serde_json::from_str(IMAGES).unwrap();
+    assert!(response.usage.is_none() && response.size.is_none());
+}
+
+/// The start of a png file, base64 encoded
+const PNG_B64: &str = "iVBORw0KGgoAAAANSUhEUg==";
+
+#[tokio::test]
+async fn images_response_save_all_names_and_downloads() {
+    let transport = MockTransport::ok("GIF89a image");
+    let client = Client::new()
+        .with_bearer_auth("sk-test")
+        .unwrap()
+        .with_transport(transport.clone());
+
+    let response: ImagesResponse = serde_json::from_value(serde_json::json!({
+        "created": 1713833628,
+        "data": [
+            {"url": "https://images.example.com/private/img-1?sig=abc"},
+            {"b64_json": PNG_B64},
+        ]
+    }))
+    .unwrap();
+
+    let dir = std::env::temp_dir().join("async-openai-images-save-all");
+    let _ = std::fs::remove_dir_all(&dir);
+    let paths = response.save_all(&client, &dir).await.unwrap();
+
+    assert_eq!(
+        paths,
+        [dir.join("1713833628_0.gif"), dir.join("1713833628_1.png")]
+    );
+    assert_eq!(std::fs::read(&paths[0]).unwrap(), b"GIF89a image");
+    assert!(std::fs::read(&paths[1]).unwrap().starts_with(b"\x89PNG"));
+
+    // The image is downloaded from its URL, without the API key
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].url.as_str(),
+        "https://images.example.com/private/img-1?sig=abc"
+    );
+    assert!(!requests[0].headers.contains_key("authorization"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn images_response_save_all_reports_failed_downloads() {
+    let transport = MockTransport::new([MockResponse::new(403, "AuthenticationFailed")]);
+    let client = Client::new().with_transport(transport);
+
+    let response: ImagesResponse = serde_json::from_value(serde_json::json!({
+        "created": 1713833628,
+        "output_format": "webp",
+        "data": [
+            {"b64_json": "aGVsbG8="},
+            {"url": "https://images.example.com/expired"},
+        ]
+    }))
+    .unwrap();
+
+    let dir = std::env::temp_dir().join("async-openai-images-save-all-failures");
+    let _ = std::fs::remove_dir_all(&dir);
+    let error = response.save_all(&client, &dir).await.unwrap_err();
+
+    assert!(error.to_string().contains("image 1: "));
+    // Content not recognized as an image has the extension of the output format
+    assert!(dir.join("1713833628_0.webp").exists());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn image_bytes() {
+    let client = Client::new().with_transport(MockTransport::ok("GIF89a image"));
+
+    let image: Image = serde_json::from_value(serde_json::json!({"b64_json": "aGVsbG8="})).unwrap();
+    assert_eq!(image.bytes(&client).await.unwrap(), "hello");
+
+    let image: Image =
+        serde_json::from_value(serde_json::json!({"url": "https://images.example.com/1"})).unwrap();
+    assert_eq!(image.bytes(&client).await.unwrap(), "GIF89a image");
+}
+
+#[tokio::test]
+async fn image_edit_in_memory_mask() {
+    let transport = MockTransport::ok(IMAGES);
+    let client = Client::new().with_transport(transport.clone());
+
+    let mask = ImageInput {
+        source: InputSource::Bytes {
+            filename: "mask.png".into(),
+            bytes: b"PNG mask".as_slice().into(),
+        },
+    };
+    let request = CreateImageEditRequestArgs::default()
+        .image(ImageInput::from_vec_u8(
+            "lounge.png".into(),
+            b"PNG".to_vec(),
+        ))
+        .mask(mask)
+        .prompt("Add a duck")
+        .model(ImageModel::GptImage1)
+        .quality(ImageQuality::Medium)
+        .size(ImageSize::S1024x1536)
+        .build()
+        .unwrap();
+    client.images().create_edit(request).await.unwrap();
+
+    let body = transport.requests()[0].body_text();
+    assert!(body.contains(
+        "name=\"mask\"; filename=\"mask.png\"\r\nContent-Type: image/png\r\n\r\nPNG mask\r\n"
+    ));
+    assert!(body.contains("name=\"quality\"\r\n\r\nmedium\r\n"));
+    assert!(body.contains("name=\"size\"\r\n\r\n1024x1536\r\n"));
+}
+
+#[test]
+fn image_request_model_options_validation() {
+    let is_valid = |model: Option<ImageModel>,
+                    n: Option<u8>,
+                    quality: Option<ImageQuality>,
+                    size: Option<ImageSize>| {
+        let mut args = CreateImageRequestArgs::default();
+        args.prompt("A cat");
+        if let Some(model) = model {
+            args.model(model);
+        }
+        if let Some(n) = n {
+            args.n(n);
+        }
+        if let Some(quality) = quality {
+            args.quality(quality);
+        }
+        if let Some(size) = size {
+            args.size(size);
+        }
+        args.build().is_ok()
+    };
+    let gpt_image_1 = Some(ImageModel::GptImage1);
+    let dall_e_3 = Some(ImageModel::DallE3);
+
+    assert!(is_valid(None, Some(10), None, Some(ImageSize::S256x256)));
+    assert!(!is_valid(None, Some(0), None, None));
+    assert!(!is_valid(None, Some(11), None, None));
+    assert!(!is_valid(dall_e_3.clone(), Some(2), None, None));
+
+    // dall-e-2 is the default model
+    assert!(!is_valid(None, None, Some(ImageQuality::HD), None));
+    assert!(!is_valid(None, None, None, Some(ImageSize::S1536x1024)));
+    assert!(is_valid(
+        dall_e_3.clone(),
+        None,
+        Some(ImageQuality::HD),
+        Some(ImageSize::S1792x1024)
+    ));
+    assert!(!is_valid(dall_e_3, None, Some(ImageQuality::High), None));
+
+    assert!(is_valid(
+        gpt_image_1.clone(),
+        Some(4),
+        Some(ImageQuality::Low),
+        Some(ImageSize::Auto)
+    ));
+    assert!(!is_valid(
+        gpt_image_1.clone(),
+        None,
+        Some(ImageQuality::HD),
+        None
+    ));
+    assert!(!is_valid(
+        gpt_image_1,
+        None,
+        None,
+        Some(ImageSize::S512x512)
+    ));
+
+    // Options of other models are not checked
+    assert!(is_valid(
+        Some(ImageModel::Other("image-model".into())),
+        None,
+        Some(ImageQuality::HD),
+        Some(ImageSize::S1536x1024)
+    ));
+}
+
+#[test]
+fn image_edit_model_options_validation() {
+    let edit = || {
+        let mut args = CreateImageEditRequestArgs::default();
+        args.image(png("lounge.png")).prompt("Add a duck");
+        args
+    };
+
+    // Sizes of dall-e-2 are still accepted
+    assert!(edit().size(DallE2ImageSize::S512x512).build().is_ok());
+    assert!(edit().size(ImageSize::S1536x1024).build().is_err());
+    assert!(edit()
+        .model(ImageModel::GptImage1)
+        .size(ImageSize::S1536x1024)
+        .quality(ImageQuality::High)
+        .build()
+        .is_ok());
+    assert!(edit()
+        .model(ImageModel::GptImage1)
+        .size(ImageSize::S256x256)
+        .build()
+        .is_err());
+    assert!(edit().quality(ImageQuality::Low).build().is_err());
+    assert!(edit().n(11).build().is_err());
+}
+
+#[test]
+fn images_response_usage_round_trip() {
+    // Shape of a gpt-image-1 generation response
+    let payload = serde_json::json!({
+        "created": 1747935480,
+        "background": "opaque",
+        "data": [{"b64_json": PNG_B64}],
+        "output_format": "png",
+        "quality": "medium",
+        "size": "1024x1536",
+        "usage": {
+            "input_tokens": 14,
+            "input_tokens_details": {"image_tokens": 0, "text_tokens": 14},
+            "output_tokens": 1584,
+            "total_tokens": 1598
+        }
+    });
+
+    let response: ImagesResponse = serde_json::from_value(payload.clone()).unwrap();
+    assert_eq!(
+        response.usage,
+        Some(ImageUsage {
+            input_tokens: 14,
+            output_tokens: 1584,
+            total_tokens: 1598,
+            input_tokens_details: ImageInputTokensDetails {
+                text_tokens: 14,
+                image_tokens: 0,
+            },
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(&response).unwrap()["usage"],
+        payload["usage"]
+    );
+}
@@ -0,0 +1,273 @@
+#![cfg(feature = "full")]
+mod common;
+
+use async_openai::{
+    error::OpenAIError,
+    types::{ListChatCompletionsQueryArgs, ListChatMessagesQuery, ListOrder, Pagination},
+    Client,
+};
+use common::{MockResponse, MockTransport, RecordedRequest};
+use futures::{StreamExt, TryStreamExt};
+use serde_json::json;
+
+fn file(id: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "object": "file",
+        "bytes": 120000,
+        "created_at": 1677610602,
+        "filename": "data.jsonl",
+        "purpose": "batch"
+    })
+}
+
+/// Page of files `ids`, followed by more pages when `has_more`
+fn page(ids: &[&str], has_more: bool) -> MockResponse {
+    let body = json!({
+        "object": "list",
+        "data": ids.iter().map(|id| file(id)).collect::<Vec<_>>(),
+        "first_id": ids.first(),
+        "last_id": ids.last(),
+        "has_more": has_more,
+    });
+    MockResponse::new(200, body.to_string())
+}
+
+fn query_param(request: &RecordedRequest, key: &str) -> Option<String> {
+    request
+        .url
+        .query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, value)| value.into_owned())
+}
+
+/// Serves files file-1 to file-5, two per page
+fn paged_transport() -> MockTransport {
+    MockTransport::with_responder(|request| match query_param(request, "after").as_deref() {
+        None => page(&["file-1", "file-2"], true),
+        Some("file-2") => page(&["file-3", "file-4"], true),
+        Some("file-4") => page(&["file-5"], false),
+        Some(after) => panic!("unexpected cursor {after}"),
+    })
+}
+
+async fn ids(
+    client: &Client<async_openai::config::OpenAIConfig>,
+    pagination: Pagination,
+) -> Vec<String> {
+    let files = client.files();
+    files
+        .list_all(&[("purpose", "batch")], pagination)
+        .map_ok(|file| file.id)
+        .try_collect()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn empty_list_yields_no_items() {
+    let transport = MockTransport::new([page(&[], false)]);
+    let client = Client::new().with_transport(transport.clone());
+
+    assert!(ids(&client, Pagination::new()).await.is_empty());
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn single_page() {
+    let transport = MockTransport::new([page(&["file-1", "file-2"], false)]);
+    let client = Client::new().with_transport(transport.clone());
+
+    assert_eq!(ids(&client, Pagination::new()).await, ["file-1", "file-2"]);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(query_param(&requests[0], "after"), None);
+    assert_eq!(query_param(&requests[0], "purpose").unwrap(), "batch");
+}
+
+#[tokio::test]
+async fn follows_cursor_across_pages() {
+    let transport = paged_transport();
+    let client = Client::new().with_transport(transport.clone());
+
+    assert_eq!(
+        ids(&client, Pagination::new().page_size(2)).await,
+        ["file-1", "file-2", "file-3", "file-4", "file-5"]
+    );
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    for request in &requests {
+        assert_eq!(query_param(request, "limit").unwrap(), "2");
+        assert_eq!(query_param(request, "purpose").unwrap(), "batch");
+    }
+    assert_eq!(query_param(&requests[2], "after").unwrap(), "file-4");
+}
+
+#[tokio::test]
+async fn max_items_stops_fetching_pages() {
+    let transport = paged_transport();
+    let client = Client::new().with_transport(transport.clone());
+
+    assert_eq!(
+        ids(&client, Pagination::new().page_size(2).max_items(3)).await,
+        ["file-1", "file-2", "file-3"]
+    );
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    // Only the remaining item is requested from the last page
+    assert_eq!(query_param(&requests[1], "limit").unwrap(), "1");
+}
+
+#[tokio::test]
+async fn stream_ends_after_error() {
+    let transport = MockTransport::with_responder(|request| {
+        match query_param(request, "after").as_deref() {
+            None => page(&["file-1"], true),
+            _ => MockResponse::new(
+                400,
+                r#"{"error": {"message": "Invalid cursor", "type": "invalid_request_error", "param": "after", "code": null}}"#,
+            ),
+        }
+    });
+    let client = Client::new().with_transport(transport);
+    let files = client.files();
+
+    let results: Vec<_> = files.list_all(&(), Pagination::new()).collect().await;
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap().id, "file-1");
+    assert!(matches!(results[1], Err(OpenAIError::ApiError(_))));
+}
+
+fn chat_completion(id: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "object": "chat.completion",
+        "created": 1738960610,
+        "model": "gpt-4o-mini",
+        "metadata": {"department": "accounting"},
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "Hello"},
+            "finish_reason": "stop"
+        }]
+    })
+}
+
+#[tokio::test]
+async fn chat_list_serializes_metadata_filters() {
+    let transport = MockTransport::new([MockResponse::new(
+        200,
+        json!({
+            "object": "list",
+            "data": [chat_completion("chatcmpl-1")],
+            "first_id": "chatcmpl-1",
+            "last_id": "chatcmpl-1",
+            "has_more": false
+        })
+        .to_string(),
+    )]);
+    let client = Client::new().with_transport(transport.clone());
+    let query = ListChatCompletionsQueryArgs::default()
+        .model("gpt-4o-mini")
+        .metadata([
+            ("source".to_string(), "homepage".to_string()),
+            ("department".to_string(), "it & accounting".to_string()),
+        ])
+        .order(ListOrder::Desc)
+        .limit(10u32)
+        .build()
+        .unwrap();
+
+    let list = client.chat().list(&query).await.unwrap();
+
+    assert_eq!(list.data[0].id, "chatcmpl-1");
+    assert_eq!(
+        transport.requests()[0].url.as_str(),
+        "https://api.openai.com/v1/chat/completions?model=gpt-4o-mini\
+         &metadata%5Bdepartment%5D=it+%26+accounting&metadata%5Bsource%5D=homepage\
+         &limit=10&order=desc"
+    );
+}
+
+#[tokio::test]
+async fn chat_list_all_keeps_filters_across_pages() {
+    let transport = MockTransport::with_responder(|request| {
+        let (ids, has_more) = match query_param(request, "after").as_deref() {
+            None => (["chatcmpl-1", "chatcmpl-2"], true),
+            Some("chatcmpl-2") => (["chatcmpl-3", "chatcmpl-4"], false),
+            Some(after) => panic!("unexpected cursor {after}"),
+        };
+        let body = json!({
+            "object": "list",
+            "data": ids.iter().map(|id| chat_completion(id)).collect::<Vec<_>>(),
+            "first_id": ids[0],
+            "last_id": ids[1],
+            "has_more": has_more
+        });
+        MockResponse::new(200, body.to_string())
+    });
+    let client = Client::new().with_transport(transport.clone());
+    let query = ListChatCompletionsQueryArgs::default()
+        .metadata([("department".to_string(), "accounting".to_string())])
+        .build()
+        .unwrap();
+    let chat = client.chat();
+
+    let ids: Vec<_> = chat
+        .list_all(&query, Pagination::new().page_size(2))
+        .map_ok(|completion| completion.id)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        ids,
+        ["chatcmpl-1", "chatcmpl-2", "chatcmpl-3", "chatcmpl-4"]
+    );
+    for request in transport.requests() {
+        assert_eq!(
+            query_param(&request, "metadata[department]").unwrap(),
+            "accounting"
+        );
+    }
+}
+
+#[tokio::test]
+async fn chat_messages_all() {
+    let transport = MockTransport::with_responder(|request| {
+        assert_eq!(request.path(), "/v1/chat/completions/chatcmpl-1/messages");
+        let (id, content, has_more) = match query_param(request, "after").as_deref() {
+            None => ("chatcmpl-1-0", "Hello", true),
+            Some("chatcmpl-1-0") => ("chatcmpl-1-1", "How can I help?", false),
+            Some(after) => panic!("unexpected cursor {after}"),
+        };
+        let body = json!({
+            "object": "list",
+            "data": [{"id": id, "role": "user", "content": content, "name": null, "content_parts": null}],
+            "first_id": id,
+            "last_id": id,
+            "has_more": has_more
+        });
+        MockResponse::new(200, body.to_string())
+    });
+    let client = Client::new().with_transport(transport);
+    let chat = client.chat();
+    let query = ListChatMessagesQuery::default();
+
+    let messages: Vec<_> = chat
+        .messages_all("chatcmpl-1", &query, Pagination::new())
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[1].id, "chatcmpl-1-1");
+    assert_eq!(
+        messages[1].message.content.as_deref(),
+        Some("How can I help?")
+    );
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "full")]
+mod common;
+
+use async_openai::{config::OpenAIConfig, Client};
+use common::MockTransport;
+
+fn client(transport: &MockTransport) -> Client<OpenAIConfig> {
+    let config = OpenAIConfig::new()
+        .with_query_param("api-version", "2024-10-21")
+        .with_query_param("tenant", "a b&c");
+
+    Client::with_config(config).with_transport(transport.clone())
+}
+
+#[tokio::test]
+async fn config_query_params_are_added_to_every_request() {
+    let transport = MockTransport::ok(r#"{"object": "list", "data": []}"#);
+
+    client(&transport).models().list().await.unwrap();
+
+    assert_eq!(
+        transport.requests()[0].url.as_str(),
+        "https://api.openai.com/v1/models?api-version=2024-10-21&tenant=a+b%26c"
+    );
+}
+
+#[tokio::test]
+async fn request_query_overrides_config_query_params() {
+    let transport = MockTransport::ok(r#"{"object": "list", "data": []}"#);
+
+    client(&transport)
+        .files()
+        .list(&[("tenant", "other tenant"), ("purpose", "batch")])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        transport.requests()[0].url.as_str(),
+        "https://api.openai.com/v1/files?tenant=other+tenant&purpose=batch&api-version=2024-10-21"
+    );
+}
@@ -0,0 +1,157 @@
+#![cfg(feature = "chat-completion")]
+mod common;
+
+use std::time::Duration;
+
+use async_openai::{
+    error::OpenAIError,
+    types::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+        CreateChatCompletionRequestArgs, RacePolicy,
+    },
+    Client,
+};
+use common::{MockResponse, MockTransport};
+
+fn request(model: &str) -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model(model)
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+/// Models answer after their latency in seconds, "broken" fails right away
+fn transport() -> MockTransport {
+    MockTransport::with_responder(|request| {
+        let model = request.json()["model"].as_str().unwrap().to_string();
+        let latency = match model.as_str() {
+            "fast" => 1,
+            "medium" => 5,
+            "slow" => 60,
+            _ => {
+                return MockResponse::new(
+                    400,
+                    r#"{"error": {"message": "Invalid model", "type": "invalid_request_error", "param": "model", "code": null}}"#,
+                )
+            }
+        };
+
+        MockResponse::new(
+            200,
+            format!(
+                r#"{{"id": "chatcmpl-{model}", "object": "chat.completion", "created": 1677652288, "model": "{model}", "choices": [],
+                    "usage": {{"prompt_tokens": 5, "completion_tokens": {latency}, "total_tokens": {}}}}}"#,
+                5 + latency
+            ),
+        )
+        .delay(Duration::from_secs(latency))
+    })
+}
+
+#[tokio::test(start_paused = true)]
+async fn first_success_cancels_slower_requests() {
+    let transport = transport();
+    let client = Client::new().with_transport(transport.clone());
+    let started = tokio::time::Instant::now();
+
+    let outcome = client
+        .chat()
+        .race_models(
+            vec![request("slow"), request("broken"), request("fast")],
+            RacePolicy::FirstSuccess,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(started.elapsed(), Duration::from_secs(1));
+    assert!(outcome.satisfied);
+    let winner = outcome.winner().unwrap();
+    assert_eq!((winner.index, winner.model.as_str()), (2, "fast"));
+    assert_eq!(winner.latency, Duration::from_secs(1));
+    assert_eq!(winner.usage().unwrap().total_tokens, 6);
+    // The failed request completed before the winner, the slow one was cancelled
+    assert_eq!(outcome.completed.len(), 2);
+    assert_eq!(outcome.cancelled, vec![0]);
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn quorum_waits_for_enough_successes() {
+    let client = Client::new().with_transport(transport());
+
+    let outcome = client
+        .chat()
+        .race_models(
+            vec![request("slow"), request("medium"), request("fast")],
+            RacePolicy::Quorum(2),
+        )
+        .await
+        .unwrap();
+
+    let models: Vec<_> = outcome.successes().map(|e| e.model.as_str()).collect();
+    assert_eq!(models, vec!["fast", "medium"]);
+    assert_eq!(outcome.cancelled, vec![0]);
+    assert!(outcome.satisfied);
+}
+
+#[tokio::test(start_paused = true)]
+async fn all_reports_every_request() {
+    let client = Client::new().with_transport(transport());
+
+    let outcome = client
+        .chat()
+        .race_models(
+            vec![request("medium"), request("broken"), request("fast")],
+            RacePolicy::All,
+        )
+        .await
+        .unwrap();
+
+    let indexes: Vec<_> = outcome.completed.iter().map(|e| e.index).collect();
+    assert_eq!(indexes, vec![1, 2, 0]);
+    assert!(outcome.cancelled.is_empty());
+    assert!(!outcome.satisfied);
+}
+
+#[tokio::test(start_paused = true)]
+async fn race_respects_concurrency_limit() {
+    let client = Client::new()
+        .with_transport(transport())
+        .with_concurrency_limit(1)
+        .unwrap();
+    let started = tokio::time::Instant::now();
+
+    let outcome = client
+        .chat()
+        .race_models(vec![request("medium"), request("fast")], RacePolicy::All)
+        .await
+        .unwrap();
+
+    // Requests are sent one after the other
+    assert_eq!(started.elapsed(), Duration::from_secs(6));
+    assert_eq!(outcome.successes().count(), 2);
+}
+
+#[tokio::test]
+async fn race_validates_policy() {
+    let client = Client::new().with_transport(transport());
+
+    let error = client
+        .chat()
+        .race_models(vec![request("fast")], RacePolicy::Quorum(2))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+
+    let error = client
+        .chat()
+        .race_models(vec![], RacePolicy::FirstSuccess)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "realtime")]
+
+mod common;
+
+use async_openai::{
+    types::realtime::{
+        RealtimeSessionConfig, RealtimeVoice, ReferCallRequest, RejectCallRequest, SessionResource,
+    },
+    Client,
+};
+use common::{MockResponse, MockTransport};
+use serde_json::json;
+
+#[tokio::test]
+async fn call_control_requests() {
+    let transport = MockTransport::new([MockResponse::new(200, "")]);
+    let client = Client::new().with_transport(transport.clone());
+    let calls = client.realtime().calls();
+
+    calls
+        .accept(
+            "rtc_123",
+            RealtimeSessionConfig::from(SessionResource {
+                model: Some("gpt-4o-realtime-preview".into()),
+                instructions: Some("Answer as a support agent.".into()),
+                voice: Some(RealtimeVoice::Alloy),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+    calls
+        .reject(
+            "rtc_123",
+            RejectCallRequest {
+                status_code: Some(486),
+            },
+        )
+        .await
+        .unwrap();
+    calls
+        .refer("rtc_123", ReferCallRequest::new("tel:+14155550123"))
+        .await
+        .unwrap();
+    calls.hangup("rtc_123").await.unwrap();
+
+    let requests = transport.requests();
+    let sent: Vec<_> = requests
+        .iter()
+        .map(|request| (request.method.as_str(), request.path(), request.json()))
+        .collect();
+    assert_eq!(
+        sent,
+        [
+            (
+                "POST",
+                "/v1/realtime/calls/rtc_123/accept",
+                json!({
+                    "type": "realtime",
+                    "model": "gpt-4o-realtime-preview",
+                    "instructions": "Answer as a support agent.",
+                    "voice": "alloy"
+                })
+            ),
+            (
+                "POST",
+                "/v1/realtime/calls/rtc_123/reject",
+                json!({"status_code": 486})
+            ),
+            (
+                "POST",
+                "/v1/realtime/calls/rtc_123/refer",
+                json!({"target_uri": "tel:+14155550123"})
+            ),
+            ("POST", "/v1/realtime/calls/rtc_123/hangup", json!({})),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn call_errors_are_returned() {
+    let transport = MockTransport::new([MockResponse::new(
+        404,
+        json!({"error": {"message": "Call not found", "type": "invalid_request_error", "param": null, "code": null}})
+            .to_string(),
+    )]);
+    let client = Client::new().with_transport(transport);
+
+    let error = client
+        .realtime()
+        .calls()
+        .hangup("rtc_missing")
+        .await
+        .unwrap_err();
+    assert_eq!(error.status(), Some(reqwest::StatusCode::NOT_FOUND));
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "realtime")]
+
+mod common;
+
+use async_openai::{
+    types::realtime::{
+        ClientSecretExpiresAfter, CreateRealtimeClientSecretRequest, RealtimeSessionConfig,
+        RealtimeVoice, SessionResource, TurnDetection,
+    },
+    Client,
+};
+use common::{MockResponse, MockTransport};
+use secrecy::ExposeSecret;
+use serde_json::json;
+
+fn request() -> CreateRealtimeClientSecretRequest {
+    CreateRealtimeClientSecretRequest {
+        expires_after: Some(ClientSecretExpiresAfter::seconds(600)),
+        session: Some(RealtimeSessionConfig::from(SessionResource {
+            model: Some("gpt-4o-realtime-preview".into()),
+            modalities: Some(vec!["audio".into(), "text".into()]),
+            voice: Some(RealtimeVoice::Alloy),
+            turn_detection: Some(TurnDetection::server_vad()),
+            ..Default::default()
+        })),
+    }
+}
+
+#[test]
+fn client_secret_request_serializes() {
+    assert_eq!(
+        serde_json::to_value(request()).unwrap(),
+        json!({
+            "expires_after": {"anchor": "created_at", "seconds": 600},
+            "session": {
+                "type": "realtime",
+                "model": "gpt-4o-realtime-preview",
+                "modalities": ["audio", "text"],
+                "voice": "alloy",
+                "turn_detection": {
+                    "type": "server_vad",
+                    "threshold": 0.5,
+                    "prefix_padding_ms": 300,
+                    "silence_duration_ms": 500
+                }
+            }
+        })
+    );
+
+    assert_eq!(
+        serde_json::to_value(CreateRealtimeClientSecretRequest::default()).unwrap(),
+        json!({})
+    );
+}
+
+#[tokio::test]
+async fn creates_client_secret() {
+    let transport = MockTransport::new([MockResponse::new(
+        200,
+        json!({
+            "value": "ek_68af296e8e408191a1120ab6383263c2",
+            "expires_at": 1756310470,
+            "session": {
+                "type": "realtime",
+                "object": "realtime.session",
+                "id": "sess_C9CiUVUzUzYIssh3ELY1d",
+                "model": "gpt-4o-realtime-preview"
+            }
+        })
+        .to_string(),
+    )]);
+    let client = Client::new().with_transport(transport.clone());
+
+    let secret = client
+        .realtime()
+        .client_secrets()
+        .create(request())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        secret.value.expose_secret(),
+        "ek_68af296e8e408191a1120ab6383263c2"
+    );
+    assert_eq!(secret.expires_at, 1756310470);
+    assert_eq!(secret.session.unwrap()["id"], "sess_C9CiUVUzUzYIssh3ELY1d");
+    // The API key is not leaked by Debug
+    assert!(!format!("{:?}", secret.value).contains("ek_"));
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path(), "/v1/realtime/client_secrets");
+    assert_eq!(requests[0].json(), serde_json::to_value(request()).unwrap());
+}
@@ -0,0 +1,194 @@
+#![cfg(feature = "realtime")]
+
+use async_openai::types::realtime::{
+    ClientEvent, ConversationItemCreateEvent, Item, ItemType, McpApprovalSetting, McpTool,
+    McpToolApproval, McpToolApprovalFilter, McpToolFilter, RealtimeSessionUpdateArgs, ServerEvent,
+    ToolDefinition,
+};
+use serde_json::{json, Value};
+
+/// Serializing the deserialized `payload` gives back `payload`
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(payload: Value) -> T {
+    let value: T = serde_json::from_value(payload.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&value).unwrap(), payload);
+    value
+}
+
+#[test]
+fn mcp_tool_in_session() {
+    let event = RealtimeSessionUpdateArgs::default()
+        .tools([
+            ToolDefinition::from(McpTool {
+                authorization: Some("token".into()),
+                require_approval: Some(McpToolApproval::Filter(McpToolApprovalFilter {
+                    never: Some(McpToolFilter {
+                        tool_names: vec!["ask_question".into()],
+                    }),
+                    ..Default::default()
+                })),
+                ..McpTool::new("deepwiki", "https://mcp.deepwiki.com/mcp")
+            }),
+            McpTool {
+                require_approval: Some(McpApprovalSetting::Always.into()),
+                ..McpTool::new("docs", "https://example.com/mcp")
+            }
+            .into(),
+        ])
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(ClientEvent::from(event)).unwrap(),
+        json!({
+            "type": "session.update",
+            "session": {
+                "tools": [
+                    {
+                        "type": "mcp",
+                        "server_label": "deepwiki",
+                        "server_url": "https://mcp.deepwiki.com/mcp",
+                        "authorization": "token",
+                        "require_approval": {"never": {"tool_names": ["ask_question"]}}
+                    },
+                    {
+                        "type": "mcp",
+                        "server_label": "docs",
+                        "server_url": "https://example.com/mcp",
+                        "require_approval": "always"
+                    }
+                ]
+            }
+        })
+    );
+}
+
+#[test]
+fn mcp_list_tools_events() {
+    let event: ServerEvent = round_trip(json!({
+        "type": "mcp_list_tools.in_progress",
+        "event_id": "event_1",
+        "item_id": "mcpl_1"
+    }));
+    assert!(matches!(event, ServerEvent::McpListToolsInProgress(e) if e.item_id == "mcpl_1"));
+
+    let event: ServerEvent = round_trip(json!({
+        "type": "conversation.item.created",
+        "event_id": "event_2",
+        "previous_item_id": null,
+        "item": {
+            "id": "mcpl_1",
+            "type": "mcp_list_tools",
+            "server_label": "deepwiki",
+            "tools": [{
+                "name": "ask_question",
+                "description": "Ask a question about a repository",
+                "input_schema": {
+                    "type": "object",
+                    "properties": {"question": {"type": "string"}}
+                },
+                "annotations": {"read_only": true}
+            }]
+        }
+    }));
+    let ServerEvent::ConversationItemCreated(created) = event else {
+        panic!("expected conversation.item.created, got {event:?}");
+    };
+    let tools = created.item.tools.unwrap();
+    assert_eq!(tools[0].name, "ask_question");
+
+    let event: ServerEvent = round_trip(json!({
+        "type": "mcp_list_tools.completed",
+        "event_id": "event_3",
+        "item_id": "mcpl_1"
+    }));
+    assert!(matches!(event, ServerEvent::McpListToolsCompleted(_)));
+}
+
+#[test]
+fn mcp_approval_round_trip() {
+    let event: ServerEvent = round_trip(json!({
+        "type": "response.output_item.done",
+        "event_id": "event_1",
+        "response_id": "resp_1",
+        "output_index": 0,
+        "item": {
+            "id": "mcpr_1",
+            "type": "mcp_approval_request",
+            "server_label": "docs",
+            "name": "delete_page",
+            "arguments": "{\"page\":\"home\"}"
+        }
+    }));
+    let ServerEvent::ResponseOutputItemDone(done) = event else {
+        panic!("expected response.output_item.done, got {event:?}");
+    };
+    assert!(matches!(
+        done.item.r#type,
+        Some(ItemType::McpApprovalRequest)
+    ));
+
+    let answer = ConversationItemCreateEvent::from(Item::mcp_approval_response(
+        done.item.id.unwrap(),
+        false,
+        Some("Pages can't be deleted".into()),
+    ));
+    assert_eq!(
+        serde_json::to_value(ClientEvent::from(answer)).unwrap(),
+        json!({
+            "type": "conversation.item.create",
+            "item": {
+                "type": "mcp_approval_response",
+                "approval_request_id": "mcpr_1",
+                "approve": false,
+                "reason": "Pages can't be deleted"
+            }
+        })
+    );
+}
+
+#[test]
+fn mcp_call_events() {
+    let event: ServerEvent = round_trip(json!({
+        "type": "response.mcp_call_arguments.delta",
+        "event_id": "event_1",
+        "response_id": "resp_1",
+        "item_id": "mcp_1",
+        "output_index": 0,
+        "delta": "{\"question\":"
+    }));
+    assert!(matches!(
+        event,
+        ServerEvent::ResponseMcpCallArgumentsDelta(_)
+    ));
+
+    let event: ServerEvent = round_trip(json!({
+        "type": "response.mcp_call_arguments.done",
+        "event_id": "event_2",
+        "response_id": "resp_1",
+        "item_id": "mcp_1",
+        "output_index": 0,
+        "arguments": "{\"question\":\"What is async-openai?\"}"
+    }));
+    assert!(matches!(
+        event,
+        ServerEvent::ResponseMcpCallArgumentsDone(_)
+    ));
+
+    let event: ServerEvent = round_trip(json!({
+        "type": "response.mcp_call.failed",
+        "event_id": "event_3",
+        "item_id": "mcp_1",
+        "output_index": 0
+    }));
+    assert!(matches!(event, ServerEvent::ResponseMcpCallFailed(_)));
+
+    let item: Item = round_trip(json!({
+        "id": "mcp_1",
+        "type": "mcp_call",
+        "server_label": "deepwiki",
+        "name": "ask_question",
+        "arguments": "{\"question\":\"What is async-openai?\"}",
+        "error": {"type": "protocol_error", "code": -32600, "message": "Invalid request"}
+    }));
+    assert_eq!(item.error.unwrap()["code"], -32600);
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "realtime")]
+
+use async_openai::types::{
+    realtime::{
+        ClientEvent, Item, RealtimeResponseCreateArgs, ResponseConversation, ResponseCreateEvent,
+        ServerEvent,
+    },
+    Metadata,
+};
+use serde_json::json;
+
+#[test]
+fn out_of_band_response() {
+    let event = RealtimeResponseCreateArgs::default()
+        .conversation(ResponseConversation::None)
+        .modalities(["text".to_string()])
+        .instructions("Classify the sentiment of the user message as positive or negative.")
+        .metadata(Metadata::try_from(json!({"purpose": "sentiment"})).unwrap())
+        .input([serde_json::from_value::<Item>(json!({
+            "type": "message",
+            "role": "user",
+            "content": [{"type": "input_text", "text": "I love this!"}]
+        }))
+        .unwrap()])
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(ClientEvent::from(event)).unwrap(),
+        json!({
+            "type": "response.create",
+            "response": {
+                "modalities": ["text"],
+                "instructions": "Classify the sentiment of the user message as positive or negative.",
+                "conversation": "none",
+                "metadata": {"purpose": "sentiment"},
+                "input": [{
+                    "type": "message",
+                    "role": "user",
+                    "content": [{"type": "input_text", "text": "I love this!"}]
+                }]
+            }
+        })
+    );
+}
+
+#[test]
+fn default_response_create() {
+    assert_eq!(
+        serde_json::to_value(ClientEvent::from(ResponseCreateEvent::default())).unwrap(),
+        json!({"type": "response.create"})
+    );
+
+    let event = RealtimeResponseCreateArgs::default().build().unwrap();
+    assert_eq!(
+        serde_json::to_value(&event).unwrap(),
+        json!({"response": {}})
+    );
+}
+
+#[test]
+fn response_done_metadata() {
+    let event: ServerEvent = serde_json::from_value(json!({
+        "type": "response.done",
+        "event_id": "event_1",
+        "response": {
+            "id": "resp_1",
+            "object": "realtime.response",
+            "status": "completed",
+            "status_details": null,
+            "output": [],
+            "conversation_id": null,
+            "metadata": {"purpose": "sentiment"},
+            "usage": null
+        }
+    }))
+    .unwrap();
+
+    let ServerEvent::ResponseDone(done) = event else {
+        panic!("expected response.done, got {event:?}");
+    };
+    let metadata = done.response.metadata.unwrap();
+    assert_eq!(metadata.get("purpose"), Some("sentiment"));
+}
@@ -0,0 +1,146 @@
+#![cfg(feature = "realtime")]
+
+use async_openai::types::realtime::{AudioFormat, RealtimeVoice, ServerEvent};
+use serde_json::{json, Value};
+
+fn server_event(payload: Value) -> ServerEvent {
+    serde_json::from_value(payload).unwrap()
+}
+
+#[test]
+fn previously_failing_payloads() {
+    // No previous item for the first item of the conversation
+    let event = server_event(json!({
+        "type": "input_audio_buffer.committed",
+        "event_id": "event_1121",
+        "previous_item_id": null,
+        "item_id": "msg_001"
+    }));
+    let ServerEvent::InputAudioBufferCommited(event) = event else {
+        panic!("expected a typed event, got {event:?}");
+    };
+    assert_eq!(event.previous_item_id, None);
+
+    // Voices and G.711 formats of the API
+    let event = server_event(json!({
+        "type": "session.created",
+        "event_id": "event_1234",
+        "session": {
+            "model": "gpt-4o-realtime-preview",
+            "modalities": ["text", "audio"],
+            "voice": "verse",
+            "input_audio_format": "g711_ulaw",
+            "output_audio_format": "g711_alaw",
+            "turn_detection": null,
+            "tools": [],
+            "tool_choice": "auto"
+        }
+    }));
+    let ServerEvent::SessionCreated(event) = event else {
+        panic!("expected a typed event, got {event:?}");
+    };
+    assert!(matches!(event.session.voice, Some(RealtimeVoice::Verse)));
+    assert!(matches!(
+        event.session.input_audio_format,
+        Some(AudioFormat::G711ULAW)
+    ));
+    assert_eq!(
+        serde_json::to_value(&event.session).unwrap()["output_audio_format"],
+        "g711_alaw"
+    );
+
+    let event = server_event(json!({
+        "type": "session.updated",
+        "event_id": "event_5678",
+        "session": {"voice": "marin"}
+    }));
+    assert!(matches!(
+        event,
+        ServerEvent::SessionUpdated(event)
+            if matches!(event.session.voice, Some(RealtimeVoice::Other(ref voice)) if voice == "marin")
+    ));
+
+    // Audio part without a transcript yet
+    let event = server_event(json!({
+        "type": "response.content_part.added",
+        "event_id": "event_3738",
+        "response_id": "resp_001",
+        "item_id": "msg_007",
+        "output_index": 0,
+        "content_index": 0,
+        "part": {"type": "audio"}
+    }));
+    assert!(matches!(event, ServerEvent::ResponseContentPartAdded(_)));
+}
+
+#[test]
+fn ga_audio_events_decode_to_typed_variants() {
+    let event = server_event(json!({
+        "type": "response.output_audio.delta",
+        "event_id": "event_4950",
+        "response_id": "resp_001",
+        "item_id": "msg_008",
+        "output_index": 0,
+        "content_index": 0,
+        "delta": "Base64EncodedAudioDelta"
+    }));
+    assert!(
+        matches!(event, ServerEvent::ResponseAudioDelta(ref e) if e.delta == "Base64EncodedAudioDelta")
+    );
+    // Serialized with the name of the beta API
+    assert_eq!(
+        serde_json::to_value(&event).unwrap()["type"],
+        "response.audio.delta"
+    );
+
+    let event = server_event(json!({
+        "type": "response.output_audio.done",
+        "event_id": "event_5152",
+        "response_id": "resp_001",
+        "item_id": "msg_008",
+        "output_index": 0,
+        "content_index": 0
+    }));
+    assert!(matches!(event, ServerEvent::ResponseAudioDone(ref e) if e.item_id == "msg_008"));
+}
+
+#[test]
+fn unknown_events_fall_back_to_other() {
+    // Event added in a newer API version
+    let payload = json!({
+        "type": "response.output_video.delta",
+        "event_id": "event_4950",
+        "response_id": "resp_001",
+        "item_id": "msg_008",
+        "output_index": 0,
+        "content_index": 0,
+        "delta": "Base64EncodedVideoDelta"
+    });
+    let event = server_event(payload.clone());
+    let ServerEvent::Other {
+        event_type,
+        payload: fields,
+    } = &event
+    else {
+        panic!("expected an unknown event, got {event:?}");
+    };
+    assert_eq!(event_type, "response.output_video.delta");
+    assert_eq!(fields["delta"], "Base64EncodedVideoDelta");
+    assert!(!fields.contains_key("type"));
+    assert_eq!(serde_json::to_value(&event).unwrap(), payload);
+
+    // Known type with a payload that doesn't match is an error
+    let error = serde_json::from_value::<ServerEvent>(
+        json!({"type": "conversation.item.deleted", "event_id": "event_2728"}),
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("item_id"), "{error}");
+    // Including the GA name of a known type
+    assert!(serde_json::from_value::<ServerEvent>(
+        json!({"type": "response.output_audio.delta", "event_id": "event_4950"})
+    )
+    .is_err());
+
+    // The type is still required
+    assert!(serde_json::from_value::<ServerEvent>(json!({"event_id": "event_1"})).is_err());
+}
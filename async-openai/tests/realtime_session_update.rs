@@ -0,0 +1,136 @@
+#![cfg(feature = "realtime")]
+
+use async_openai::types::realtime::{
+    AudioFormat, AudioTranscription, ClientEvent, Eagerness, NoiseReductionType,
+    RealtimeSessionUpdateArgs, RealtimeVoice, SessionResource, TurnDetection,
+};
+use serde_json::{json, Value};
+
+/// Serializing the deserialized `payload` gives back `payload`
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(payload: Value) -> T {
+    let value: T = serde_json::from_value(payload.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&value).unwrap(), payload);
+    value
+}
+
+#[test]
+fn session_update_builder() {
+    let event = RealtimeSessionUpdateArgs::default()
+        .modalities(["text".to_string(), "audio".to_string()])
+        .instructions("You are a helpful assistant.")
+        .voice(RealtimeVoice::Alloy)
+        .input_audio_format(AudioFormat::PCM16)
+        .output_audio_format(AudioFormat::PCM16)
+        .input_audio_transcription(AudioTranscription {
+            model: Some("whisper-1".into()),
+            language: Some("en".into()),
+            prompt: Some("Expect words related to technology".into()),
+        })
+        .input_audio_noise_reduction(NoiseReductionType::FarField)
+        .turn_detection(TurnDetection::ServerVAD {
+            threshold: 0.5,
+            prefix_padding_ms: 300,
+            silence_duration_ms: 500,
+            create_response: Some(true),
+            interrupt_response: Some(false),
+        })
+        .temperature(0.75)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(ClientEvent::from(event)).unwrap(),
+        json!({
+            "type": "session.update",
+            "session": {
+                "modalities": ["text", "audio"],
+                "instructions": "You are a helpful assistant.",
+                "voice": "alloy",
+                "input_audio_format": "pcm16",
+                "output_audio_format": "pcm16",
+                "input_audio_transcription": {
+                    "model": "whisper-1",
+                    "language": "en",
+                    "prompt": "Expect words related to technology"
+                },
+                "input_audio_noise_reduction": {"type": "far_field"},
+                "turn_detection": {
+                    "type": "server_vad",
+                    "threshold": 0.5,
+                    "prefix_padding_ms": 300,
+                    "silence_duration_ms": 500,
+                    "create_response": true,
+                    "interrupt_response": false
+                },
+                "temperature": 0.75
+            }
+        })
+    );
+
+    // Nothing set, nothing changed in the session
+    let event = RealtimeSessionUpdateArgs::default().build().unwrap();
+    assert_eq!(
+        serde_json::to_value(ClientEvent::from(event)).unwrap(),
+        json!({"type": "session.update", "session": {}})
+    );
+}
+
+#[test]
+fn turn_detection_serde() {
+    assert_eq!(
+        serde_json::to_value(TurnDetection::default()).unwrap(),
+        json!({
+            "type": "server_vad",
+            "threshold": 0.5,
+            "prefix_padding_ms": 300,
+            "silence_duration_ms": 500
+        })
+    );
+
+    let semantic: TurnDetection = round_trip(json!({
+        "type": "semantic_vad",
+        "eagerness": "low",
+        "create_response": true,
+        "interrupt_response": true
+    }));
+    assert!(matches!(
+        semantic,
+        TurnDetection::SemanticVAD {
+            eagerness: Eagerness::Low,
+            ..
+        }
+    ));
+
+    // Eagerness is `auto` when left out
+    let semantic: TurnDetection = serde_json::from_value(json!({"type": "semantic_vad"})).unwrap();
+    assert_eq!(semantic, TurnDetection::semantic_vad(Eagerness::Auto));
+}
+
+#[test]
+fn session_of_server_round_trips() {
+    // Session of a `session.created` event
+    let session: SessionResource = round_trip(json!({
+        "model": "gpt-4o-realtime-preview",
+        "modalities": ["text", "audio"],
+        "instructions": "",
+        "voice": "alloy",
+        "input_audio_format": "pcm16",
+        "output_audio_format": "pcm16",
+        "input_audio_transcription": {"model": "whisper-1"},
+        "input_audio_noise_reduction": {"type": "near_field"},
+        "turn_detection": {
+            "type": "server_vad",
+            "threshold": 0.5,
+            "prefix_padding_ms": 300,
+            "silence_duration_ms": 200
+        },
+        "tools": [],
+        "tool_choice": "auto",
+        "temperature": 0.75,
+        "max_response_output_tokens": "inf"
+    }));
+    assert_eq!(
+        session.input_audio_transcription,
+        Some(AudioTranscription::new("whisper-1"))
+    );
+}
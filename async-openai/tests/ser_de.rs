@@ -1,7 +1,14 @@
-use async_openai::types::{
-    ChatCompletionRequestSystemMessageArgs, ChatCompletionRequestUserMessageArgs,
-    CreateChatCompletionRequest, CreateChatCompletionRequestArgs,
+use async_openai::{
+    error::OpenAIError,
+    types::{
+        ChatCompletionRequestMessageContentPartText, ChatCompletionRequestSystemMessageArgs,
+        ChatCompletionRequestUserMessageArgs, ChatCompletionResponseMessageAnnotation,
+        CompletionUsage, CreateChatCompletionRequest, CreateChatCompletionRequestArgs,
+        CreateChatCompletionResponse, ReasoningEffort, SearchContextSize, ServiceTier, Verbosity,
+        WebSearchLocationArgs, WebSearchOptionsArgs,
+    },
 };
+use serde_json::json;
 
 #[tokio::test]
 async fn chat_types_serde() {
@@ -26,3 +33,301 @@ async fn chat_types_serde() {
     let deserialized: CreateChatCompletionRequest = serde_json::from_str(&serialized).unwrap();
     assert_eq!(request, deserialized);
 }
+
+#[test]
+fn chat_prediction_request_shape() {
+    let code = "class User {\n  firstName: string = \"\";\n}";
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Replace the firstName property with an email property.")
+            .build()
+            .unwrap()
+            .into()])
+        .prediction(code)
+        .build()
+        .unwrap();
+
+    let serialized = serde_json::to_value(&request).unwrap();
+    assert_eq!(
+        serialized["prediction"],
+        json!({"type": "content", "content": code})
+    );
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Refactor")
+            .build()
+            .unwrap()
+            .into()])
+        .prediction(vec![ChatCompletionRequestMessageContentPartText::from(
+            code,
+        )])
+        .build()
+        .unwrap();
+
+    let serialized = serde_json::to_value(&request).unwrap();
+    assert_eq!(
+        serialized["prediction"],
+        json!({"type": "content", "content": [{"type": "text", "text": code}]})
+    );
+    let deserialized: CreateChatCompletionRequest = serde_json::from_value(serialized).unwrap();
+    assert_eq!(request, deserialized);
+}
+
+#[test]
+fn chat_prediction_usage() {
+    let usage: CompletionUsage = serde_json::from_value(json!({
+        "prompt_tokens": 129,
+        "completion_tokens": 220,
+        "total_tokens": 349,
+        "completion_tokens_details": {
+            "reasoning_tokens": 0,
+            "accepted_prediction_tokens": 79,
+            "rejected_prediction_tokens": 18
+        }
+    }))
+    .unwrap();
+    let details = usage.completion_tokens_details.unwrap();
+    assert_eq!(details.accepted_prediction_tokens, Some(79));
+    assert_eq!(details.rejected_prediction_tokens, Some(18));
+
+    // Responses from before Predicted Outputs
+    let usage: CompletionUsage = serde_json::from_value(json!({
+        "prompt_tokens": 9,
+        "completion_tokens": 12,
+        "total_tokens": 21,
+        "completion_tokens_details": {"reasoning_tokens": 0}
+    }))
+    .unwrap();
+    let details = usage.completion_tokens_details.unwrap();
+    assert_eq!(details.accepted_prediction_tokens, None);
+    assert_eq!(details.rejected_prediction_tokens, None);
+}
+
+#[test]
+fn chat_web_search_options_request_shape() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o-search-preview")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("What was a positive news story from today?")
+            .build()
+            .unwrap()
+            .into()])
+        .web_search_options(
+            WebSearchOptionsArgs::default()
+                .search_context_size(SearchContextSize::Low)
+                .user_location(
+                    WebSearchLocationArgs::default()
+                        .country("GB")
+                        .city("London")
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(
+        value["web_search_options"],
+        json!({
+            "search_context_size": "low",
+            "user_location": {
+                "type": "approximate",
+                "approximate": {"city": "London", "country": "GB"}
+            }
+        })
+    );
+
+    let deserialized: CreateChatCompletionRequest = serde_json::from_value(value).unwrap();
+    assert_eq!(request, deserialized);
+
+    // Omitted unless set
+    let request = CreateChatCompletionRequestArgs::default().build().unwrap();
+    assert!(serde_json::to_value(request)
+        .unwrap()
+        .get("web_search_options")
+        .is_none());
+}
+
+#[test]
+fn chat_url_citation_annotations() {
+    let response: CreateChatCompletionResponse = serde_json::from_value(json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1741569952,
+        "model": "gpt-4o-search-preview",
+        "choices": [{
+            "index": 0,
+            "message": {
+                "role": "assistant",
+                "content": "A local bakery donated bread. ([example.com](https://example.com/bread))",
+                "annotations": [{
+                    "type": "url_citation",
+                    "url_citation": {
+                        "end_index": 72,
+                        "start_index": 31,
+                        "title": "Bakery donates bread",
+                        "url": "https://example.com/bread"
+                    }
+                }]
+            },
+            "finish_reason": "stop"
+        }]
+    }))
+    .unwrap();
+
+    let annotations = response.choices[0].message.annotations.as_ref().unwrap();
+    let ChatCompletionResponseMessageAnnotation::UrlCitation { url_citation } = &annotations[0];
+    assert_eq!(url_citation.url, "https://example.com/bread");
+    assert_eq!(url_citation.start_index, 31);
+    assert_eq!(url_citation.end_index, 72);
+}
+
+#[test]
+fn chat_reasoning_effort_and_verbosity() {
+    let payload = json!({
+        "model": "gpt-5",
+        "messages": [{"role": "user", "content": "Write a haiku about code."}],
+        "reasoning_effort": "minimal",
+        "verbosity": "low",
+        "max_completion_tokens": 1000
+    });
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-5")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Write a haiku about code.")
+            .build()
+            .unwrap()
+            .into()])
+        .reasoning_effort(ReasoningEffort::Minimal)
+        .verbosity(Verbosity::Low)
+        .max_completion_tokens(1000u32)
+        .build()
+        .unwrap();
+
+    assert_eq!(serde_json::to_value(&request).unwrap(), payload);
+    let deserialized: CreateChatCompletionRequest = serde_json::from_value(payload).unwrap();
+    assert_eq!(request, deserialized);
+
+    let usage: CompletionUsage = serde_json::from_value(json!({
+        "prompt_tokens": 13,
+        "completion_tokens": 594,
+        "total_tokens": 607,
+        "completion_tokens_details": {"reasoning_tokens": 576}
+    }))
+    .unwrap();
+    assert_eq!(
+        usage.completion_tokens_details.unwrap().reasoning_tokens,
+        Some(576)
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn chat_max_tokens_and_max_completion_tokens_are_exclusive() {
+    let error = CreateChatCompletionRequestArgs::default()
+        .model("o3-mini")
+        .max_tokens(100u32)
+        .max_completion_tokens(100u32)
+        .build()
+        .unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+
+    assert!(CreateChatCompletionRequestArgs::default()
+        .max_tokens(100u32)
+        .build()
+        .is_ok());
+}
+
+#[test]
+fn chat_service_tier() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("o3")
+        .service_tier(ServiceTier::Flex)
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap()["service_tier"],
+        "flex"
+    );
+
+    let tiers: Vec<ServiceTier> = serde_json::from_value(json!([
+        "auto", "default", "flex", "scale", "priority", "batch"
+    ]))
+    .unwrap();
+    assert_eq!(
+        tiers,
+        [
+            ServiceTier::Auto,
+            ServiceTier::Default,
+            ServiceTier::Flex,
+            ServiceTier::Scale,
+            ServiceTier::Priority,
+            ServiceTier::Other("batch".into()),
+        ]
+    );
+    assert_eq!(
+        serde_json::to_value(ServiceTier::Other("batch".into())).unwrap(),
+        "batch"
+    );
+
+    // An unknown tier does not fail the whole response
+    let response: CreateChatCompletionResponse = serde_json::from_value(json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1677652288,
+        "model": "o3",
+        "service_tier": "turbo",
+        "choices": []
+    }))
+    .unwrap();
+    assert_eq!(
+        response.service_tier,
+        Some(ServiceTier::Other("turbo".into()))
+    );
+}
+
+#[test]
+fn chat_safety_identifier_and_prompt_cache_key() {
+    let payload = json!({
+        "model": "gpt-4o",
+        "messages": [{"role": "user", "content": "Hello"}],
+        "safety_identifier": "c2f5b2a1e9",
+        "prompt_cache_key": "support-bot-v2"
+    });
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello")
+            .build()
+            .unwrap()
+            .into()])
+        .safety_identifier("c2f5b2a1e9")
+        .prompt_cache_key("support-bot-v2")
+        .build()
+        .unwrap();
+
+    assert_eq!(serde_json::to_value(&request).unwrap(), payload);
+    let deserialized: CreateChatCompletionRequest = serde_json::from_value(payload).unwrap();
+    assert_eq!(request, deserialized);
+
+    let usage: CompletionUsage = serde_json::from_value(json!({
+        "prompt_tokens": 2006,
+        "completion_tokens": 300,
+        "total_tokens": 2306,
+        "prompt_tokens_details": {"cached_tokens": 1920, "audio_tokens": 0},
+        "completion_tokens_details": {"reasoning_tokens": 0}
+    }))
+    .unwrap();
+    assert_eq!(
+        usage.prompt_tokens_details.unwrap().cached_tokens,
+        Some(1920)
+    );
+}
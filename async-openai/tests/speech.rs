@@ -0,0 +1,294 @@
+#![cfg(feature = "full")]
+use async_openai::{
+    types::{
+        CreateSpeechRequest, CreateSpeechRequestArgs, CreateSpeechResponse, SpeechModel,
+        SpeechResponseFormat, SpeechStreamEvent, SpeechStreamFormat, Voice,
+    },
+    Client,
+};
+use futures::StreamExt;
+
+mod common;
+use common::{temp_dir, MockResponse, MockTransport};
+
+fn stream_request() -> CreateSpeechRequest {
+    CreateSpeechRequestArgs::default()
+        .input("Hello")
+        .model(SpeechModel::Gpt4oMiniTts)
+        .response_format(SpeechResponseFormat::Pcm)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn speech_speed_range() {
+    for speed in [0.25, 1.0, 4.0] {
+        assert!(CreateSpeechRequestArgs::default()
+            .input("hello")
+            .speed(speed)
+            .build()
+            .is_ok());
+    }
+
+    for speed in [0.0, 0.24, 4.01] {
+        assert!(CreateSpeechRequestArgs::default()
+            .input("hello")
+            .speed(speed)
+            .build()
+            .is_err());
+    }
+}
+
+#[test]
+fn speech_instructions_model_support() {
+    let request = CreateSpeechRequestArgs::default()
+        .input("hello")
+        .model(SpeechModel::Gpt4oMiniTts)
+        .instructions("Speak like a pirate")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&request).unwrap()["model"],
+        "gpt-4o-mini-tts"
+    );
+
+    for model in [SpeechModel::Tts1, SpeechModel::Tts1Hd] {
+        assert!(CreateSpeechRequestArgs::default()
+            .input("hello")
+            .model(model)
+            .instructions("Speak like a pirate")
+            .build()
+            .is_err());
+    }
+
+    // Without instructions every model is accepted
+    for model in [
+        SpeechModel::Tts1,
+        SpeechModel::Tts1Hd,
+        SpeechModel::Gpt4oMiniTts,
+        SpeechModel::Other("custom-tts".into()),
+    ] {
+        assert!(CreateSpeechRequestArgs::default()
+            .input("hello")
+            .model(model)
+            .build()
+            .is_ok());
+    }
+}
+
+#[test]
+fn speech_response_format_extension() {
+    assert_eq!(SpeechResponseFormat::Mp3.extension(), "mp3");
+    assert_eq!(SpeechResponseFormat::Opus.extension(), "opus");
+    assert_eq!(SpeechResponseFormat::Aac.extension(), "aac");
+    assert_eq!(SpeechResponseFormat::Flac.extension(), "flac");
+    assert_eq!(SpeechResponseFormat::Pcm.extension(), "pcm");
+    assert_eq!(SpeechResponseFormat::Wav.extension(), "wav");
+}
+
+#[test]
+fn speech_sse_stream_format_model_support() {
+    for model in [SpeechModel::Tts1, SpeechModel::Tts1Hd] {
+        assert!(CreateSpeechRequestArgs::default()
+            .input("hello")
+            .model(model.clone())
+            .stream_format(SpeechStreamFormat::Sse)
+            .build()
+            .is_err());
+        assert!(CreateSpeechRequestArgs::default()
+            .input("hello")
+            .model(model)
+            .stream_format(SpeechStreamFormat::Audio)
+            .build()
+            .is_ok());
+    }
+}
+
+#[tokio::test]
+async fn speech_stream_decodes_audio_chunks() {
+    // "AAEC" and "AwQ=" are base64 of [0, 1, 2] and [3, 4]
+    let body = concat!(
+        "data: {\"type\":\"speech.audio.delta\",\"audio\":\"AAEC\"}\n\n",
+        "data: {\"type\":\"speech.audio.delta\",\"audio\":\"AwQ=\"}\n\n",
+        "data: {\"type\":\"speech.audio.done\",\"usage\":{\"input_tokens\":14,\"output_tokens\":101,\"total_tokens\":115}}\n\n",
+    );
+    let transport = MockTransport::new([
+        MockResponse::new(200, body).header("content-type", "text/event-stream")
+    ]);
+    let client = Client::new().with_transport(transport.clone());
+
+    let mut stream = client
+        .audio()
+        .speech_stream(stream_request())
+        .await
+        .unwrap();
+
+    let mut audio = Vec::new();
+    let mut usage = None;
+    while let Some(event) = stream.next().await {
+        match event.unwrap() {
+            SpeechStreamEvent::Audio(delta) => audio.extend_from_slice(&delta.bytes().unwrap()),
+            SpeechStreamEvent::Done(done) => usage = done.usage,
+        }
+    }
+
+    assert_eq!(audio, [0, 1, 2, 3, 4]);
+    assert_eq!(usage.unwrap().total_tokens, 115);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path(), "/v1/audio/speech");
+    assert_eq!(requests[0].json()["stream_format"], "sse");
+}
+
+#[tokio::test]
+async fn speech_stream_raw_returns_audio_bytes() {
+    let transport = MockTransport::new([MockResponse::new(200, "RIFF audio")]);
+    let client = Client::new().with_transport(transport.clone());
+
+    let mut stream = client
+        .audio()
+        .speech_stream_raw(stream_request())
+        .await
+        .unwrap();
+
+    let mut audio = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        audio.extend_from_slice(&chunk.unwrap());
+    }
+
+    assert_eq!(audio, b"RIFF audio");
+    assert_eq!(transport.requests()[0].json()["stream_format"], "audio");
+}
+
+#[tokio::test]
+async fn speech_stream_rejects_other_stream_format() {
+    let client = Client::new().with_transport(MockTransport::new([]));
+
+    let mut request = stream_request();
+    request.stream_format = Some(SpeechStreamFormat::Audio);
+    assert!(client.audio().speech_stream(request).await.is_err());
+
+    let mut request = stream_request();
+    request.stream_format = Some(SpeechStreamFormat::Sse);
+    assert!(client.audio().speech_stream_raw(request).await.is_err());
+}
+
+#[tokio::test]
+async fn speech_save_creates_dirs_and_adds_extension() {
+    let dir = temp_dir("speech-save");
+    let client = Client::new().with_transport(MockTransport::ok("flac audio"));
+
+    let request = CreateSpeechRequestArgs::default()
+        .input("Hello")
+        .response_format(SpeechResponseFormat::Flac)
+        .build()
+        .unwrap();
+    let response = client.audio().speech(request).await.unwrap();
+    assert_eq!(response.response_format, SpeechResponseFormat::Flac);
+
+    let path = response.save(dir.join("nested/speech")).await.unwrap();
+    assert_eq!(path, dir.join("nested/speech.flac"));
+    assert_eq!(std::fs::read(&path).unwrap(), b"flac audio");
+
+    // A mismatched extension is kept
+    let response = CreateSpeechResponse {
+        bytes: "mp3 audio".into(),
+        response_format: SpeechResponseFormat::Mp3,
+    };
+    let path = response.save(dir.join("speech.wav")).await.unwrap();
+    assert_eq!(path, dir.join("speech.wav"));
+    assert!(!std::fs::read(&path).unwrap().is_empty());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn speech_to_writer_writes_body() {
+    let dir = temp_dir("speech-to-writer");
+    std::fs::create_dir_all(&dir).unwrap();
+    let transport = MockTransport::ok("mp3 audio");
+    let client = Client::new().with_transport(transport.clone());
+
+    let path = dir.join("speech.mp3");
+    let mut file = tokio::fs::File::create(&path).await.unwrap();
+    let written = client
+        .audio()
+        .speech_to_writer(stream_request(), &mut file)
+        .await
+        .unwrap();
+
+    assert_eq!(written, 9);
+    assert_eq!(std::fs::read(&path).unwrap(), b"mp3 audio");
+    assert_eq!(transport.requests()[0].json()["stream_format"], "audio");
+
+    let mut request = stream_request();
+    request.stream_format = Some(SpeechStreamFormat::Sse);
+    assert!(client
+        .audio()
+        .speech_to_writer(request, &mut file)
+        .await
+        .is_err());
+    assert_eq!(transport.requests().len(), 1);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn speech_voices_serde() {
+    for (voice, name) in [
+        (Voice::Ash, "ash"),
+        (Voice::Ballad, "ballad"),
+        (Voice::Coral, "coral"),
+        (Voice::Sage, "sage"),
+        (Voice::Verse, "verse"),
+        (Voice::Marin, "marin"),
+        (Voice::Cedar, "cedar"),
+        (Voice::Other("custom".into()), "custom"),
+    ] {
+        let value = serde_json::to_value(&voice).unwrap();
+        assert_eq!(value, name);
+        assert_eq!(serde_json::from_value::<Voice>(value).unwrap(), voice);
+    }
+}
+
+#[test]
+fn speech_model_validation() {
+    // speed is not supported by gpt-4o-mini-tts
+    let error = CreateSpeechRequestArgs::default()
+        .input("hello")
+        .model(SpeechModel::Gpt4oMiniTts)
+        .speed(1.5)
+        .build()
+        .unwrap_err();
+    assert!(error.to_string().contains("gpt-4o-mini-tts"));
+    assert!(CreateSpeechRequestArgs::default()
+        .input("hello")
+        .model(SpeechModel::Tts1)
+        .speed(1.5)
+        .build()
+        .is_ok());
+
+    // The newest voices are not supported by tts-1 and tts-1-hd
+    for voice in [Voice::Ballad, Voice::Verse, Voice::Marin, Voice::Cedar] {
+        assert!(CreateSpeechRequestArgs::default()
+            .input("hello")
+            .model(SpeechModel::Tts1Hd)
+            .voice(voice.clone())
+            .build()
+            .is_err());
+        assert!(CreateSpeechRequestArgs::default()
+            .input("hello")
+            .model(SpeechModel::Gpt4oMiniTts)
+            .voice(voice)
+            .build()
+            .is_ok());
+    }
+    assert!(CreateSpeechRequestArgs::default()
+        .input("hello")
+        .model(SpeechModel::Tts1)
+        .voice(Voice::Coral)
+        .build()
+        .is_ok());
+}
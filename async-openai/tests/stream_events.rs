@@ -0,0 +1,93 @@
+#![cfg(feature = "full")]
+mod common;
+
+use async_openai::{
+    types::{AssistantStreamEvent, CreateRunRequestArgs},
+    Client,
+};
+use common::{MockResponse, MockTransport};
+use futures::StreamExt;
+use serde_json::json;
+
+#[tokio::test]
+async fn unknown_assistant_events_are_kept() {
+    let body = [
+        "event: thread.message.delta\n",
+        "data: {\"id\":\"msg_1\",\"object\":\"thread.message.delta\",\"delta\":{\"content\":[{\"index\":0,\"type\":\"text\",\"text\":{\"value\":\"Hi\"}}]}}\n\n",
+        "event: thread.run.step.something_new\n",
+        "data: {\"id\":\"step_1\",\"detail\":1}\n\n",
+        "event: thread.heartbeat\n",
+        "data: ping\n\n",
+        "event: done\n",
+        "data: [DONE]\n\n",
+    ]
+    .concat();
+    let transport = MockTransport::new([
+        MockResponse::new(200, body).header("content-type", "text/event-stream")
+    ]);
+    let client = Client::new().with_transport(transport);
+
+    let events: Vec<_> = client
+        .threads()
+        .runs("thread_abc")
+        .create_stream(
+            CreateRunRequestArgs::default()
+                .assistant_id("asst_abc")
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 4);
+    assert!(matches!(
+        &events[0],
+        AssistantStreamEvent::ThreadMessageDelta(delta) if delta.id == "msg_1"
+    ));
+    assert!(matches!(
+        &events[1],
+        AssistantStreamEvent::Other { event_type, data }
+            if event_type == "thread.run.step.something_new"
+                && *data == json!({"id": "step_1", "detail": 1})
+    ));
+    assert!(matches!(
+        &events[2],
+        AssistantStreamEvent::Other { event_type, data }
+            if event_type == "thread.heartbeat" && *data == json!("ping")
+    ));
+    assert!(matches!(&events[3], AssistantStreamEvent::Done(_)));
+}
+
+#[cfg(feature = "realtime")]
+#[test]
+fn unknown_realtime_events_are_kept() {
+    use async_openai::types::realtime::ServerEvent;
+
+    let event: ServerEvent = serde_json::from_value(json!({
+        "type": "rate_limits.updated",
+        "event_id": "event_1",
+        "rate_limits": [{"name": "requests", "limit": 1000, "remaining": 999, "reset_seconds": 60}]
+    }))
+    .unwrap();
+    assert!(matches!(event, ServerEvent::RateLimitsUpdated(_)));
+
+    let unknown = json!({
+        "type": "conversation.item.new_thing",
+        "event_id": "event_2",
+        "item_id": "item_1"
+    });
+    let event: ServerEvent = serde_json::from_value(unknown.clone()).unwrap();
+    let ServerEvent::Other {
+        event_type,
+        payload: data,
+    } = &event
+    else {
+        panic!("expected an unknown event, got {event:?}");
+    };
+    assert_eq!(event_type, "conversation.item.new_thing");
+    assert_eq!(data["item_id"], "item_1");
+    assert_eq!(serde_json::to_value(&event).unwrap(), unknown);
+}
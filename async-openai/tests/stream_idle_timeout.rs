@@ -0,0 +1,82 @@
+#![cfg(feature = "chat-completion")]
+use std::time::Duration;
+
+use async_openai::{
+    error::OpenAIError,
+    transport::HttpTransport,
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+    Client,
+};
+use bytes::Bytes;
+use futures::{
+    future::BoxFuture,
+    stream::{self, BoxStream},
+    StreamExt,
+};
+
+/// Sends one chunk then hangs without closing the connection
+struct HangingTransport;
+
+impl HttpTransport for HangingTransport {
+    fn execute(
+        &self,
+        _request: reqwest::Request,
+    ) -> BoxFuture<'_, Result<reqwest::Response, OpenAIError>> {
+        unimplemented!("only streaming requests are sent")
+    }
+
+    fn execute_stream(
+        &self,
+        _request: reqwest::Request,
+    ) -> BoxFuture<'_, Result<BoxStream<'static, Result<Bytes, OpenAIError>>, OpenAIError>> {
+        let chunk = Bytes::from_static(
+            b"data: {\"id\":\"chatcmpl-123\",\"object\":\"chat.completion.chunk\",\"created\":1694268190,\"model\":\"gpt-4o\",\"choices\":[]}\n\n",
+        );
+        Box::pin(async move {
+            Ok(stream::once(async { Ok(chunk) })
+                .chain(stream::pending())
+                .boxed())
+        })
+    }
+}
+
+fn request() -> async_openai::types::CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+#[tokio::test(start_paused = true)]
+async fn stream_ends_after_idle_timeout() {
+    let client = Client::new()
+        .with_transport(HangingTransport)
+        .with_stream_idle_timeout(Duration::from_secs(30));
+    let started = tokio::time::Instant::now();
+
+    let mut stream = client.chat().create_stream(request()).await.unwrap();
+
+    assert!(stream.next().await.unwrap().is_ok());
+    let error = stream.next().await.unwrap().unwrap_err();
+    assert!(
+        matches!(error, OpenAIError::StreamIdleTimeout(timeout) if timeout == Duration::from_secs(30))
+    );
+    assert!(stream.next().await.is_none());
+    assert_eq!(started.elapsed(), Duration::from_secs(30));
+}
+
+#[tokio::test(start_paused = true)]
+async fn stream_without_idle_timeout_keeps_waiting() {
+    let client = Client::new().with_transport(HangingTransport);
+
+    let mut stream = client.chat().create_stream(request()).await.unwrap();
+
+    assert!(stream.next().await.unwrap().is_ok());
+    let next = tokio::time::timeout(Duration::from_secs(3600), stream.next()).await;
+    assert!(next.is_err());
+}
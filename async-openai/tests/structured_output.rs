@@ -0,0 +1,289 @@
+#![cfg(feature = "schemars")]
+
+mod common;
+
+use async_openai::{
+    error::OpenAIError,
+    types::{
+        sanitize_for_strict, ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs,
+        ParsedMessage, ResponseFormat,
+    },
+    Client,
+};
+use common::{MockResponse, MockTransport};
+use schemars::{schema_for, JsonSchema};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+struct Step {
+    output: String,
+    explanation: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+struct MathReasoning {
+    final_answer: String,
+    steps: Vec<Step>,
+}
+
+fn request() -> async_openai::types::CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("how can I solve 8x + 7 = -23")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+fn response(messages: &[serde_json::Value]) -> MockResponse {
+    let choices: Vec<_> = messages
+        .iter()
+        .enumerate()
+        .map(
+            |(index, message)| json!({"index": index, "message": message, "finish_reason": "stop"}),
+        )
+        .collect();
+    let body = json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1677652288,
+        "model": "gpt-4o",
+        "choices": choices,
+    });
+    MockResponse::new(200, body.to_string())
+}
+
+#[tokio::test]
+async fn parses_content_and_sends_strict_schema() {
+    let content = json!({
+        "final_answer": "x = -3.75",
+        "steps": [{"output": "8x = -30", "explanation": "Subtract 7 from both sides"}]
+    });
+    let transport = MockTransport::new([response(&[
+        json!({"role": "assistant", "content": content.to_string()}),
+    ])]);
+    let client = Client::new().with_transport(transport.clone());
+
+    let completion = client
+        .chat()
+        .create_parsed::<MathReasoning>(request())
+        .await
+        .unwrap();
+
+    assert_eq!(completion.parsed().unwrap().final_answer, "x = -3.75");
+    assert_eq!(completion.response.id, "chatcmpl-123");
+
+    let response_format = &transport.requests()[0].json()["response_format"];
+    assert_eq!(response_format["type"], "json_schema");
+    assert_eq!(response_format["json_schema"]["name"], "math_reasoning");
+    assert_eq!(response_format["json_schema"]["strict"], true);
+    let schema = &response_format["json_schema"]["schema"];
+    assert_eq!(schema["additionalProperties"], false);
+    assert_eq!(
+        schema["properties"]["steps"]["items"]["additionalProperties"],
+        false
+    );
+}
+
+#[tokio::test]
+async fn refusal_is_not_a_deserialization_error() {
+    let transport = MockTransport::new([response(&[
+        json!({"role": "assistant", "content": null, "refusal": "I'm sorry, I can't help with that."}),
+        json!({"role": "assistant", "content": r#"{"final_answer": "-3.75", "steps": []}"#}),
+    ])]);
+    let client = Client::new().with_transport(transport);
+
+    let completion = client
+        .chat()
+        .create_parsed::<MathReasoning>(request())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        completion.choices[0],
+        ParsedMessage::Refusal("I'm sorry, I can't help with that.".into())
+    );
+    assert!(completion.parsed().is_none());
+    assert_eq!(
+        completion.choices[1].parsed().unwrap().final_answer,
+        "-3.75"
+    );
+}
+
+#[tokio::test]
+async fn invalid_content_is_a_deserialization_error() {
+    let transport = MockTransport::new([response(&[
+        json!({"role": "assistant", "content": r#"{"final_answer": 42}"#}),
+    ])]);
+    let client = Client::new().with_transport(transport);
+
+    let error = client
+        .chat()
+        .create_parsed::<MathReasoning>(request())
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, OpenAIError::JSONDeserialize { ref path, .. } if path == "final_answer")
+    );
+}
+
+#[tokio::test]
+async fn rejects_request_with_response_format() {
+    let transport = MockTransport::new([]);
+    let client = Client::new().with_transport(transport.clone());
+    let mut request = request();
+    request.response_format = Some(ResponseFormat::JsonObject);
+
+    let error = client
+        .chat()
+        .create_parsed::<MathReasoning>(request)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+    assert!(transport.requests().is_empty());
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[allow(dead_code)]
+enum Shape {
+    Circle { radius: f64 },
+    Square(Box<Square>),
+    Empty,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[allow(dead_code)]
+struct Square {
+    side: u32,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[allow(dead_code)]
+struct Drawing {
+    /// Shapes of each layer
+    layers: Vec<Vec<Shape>>,
+    background: Option<Color>,
+    #[serde(default)]
+    title: String,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[allow(dead_code)]
+enum Color {
+    Red,
+    Green,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[allow(dead_code)]
+struct Section {
+    heading: String,
+    subsections: Vec<Section>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[allow(dead_code)]
+struct Document {
+    sections: Vec<Section>,
+}
+
+/// Keys of every object of `schema`, with the JSON pointer of the object
+fn objects<'a>(
+    schema: &'a serde_json::Value,
+    pointer: String,
+    out: &mut Vec<(String, &'a serde_json::Map<String, serde_json::Value>)>,
+) {
+    match schema {
+        serde_json::Value::Object(object) => {
+            out.push((pointer.clone(), object));
+            for (key, value) in object {
+                objects(value, format!("{pointer}/{key}"), out);
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                objects(value, format!("{pointer}/{index}"), out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Check the constraints of strict mode on every schema object of `schema`
+fn assert_strict(schema: &serde_json::Value) {
+    let mut all = vec![];
+    objects(schema, String::new(), &mut all);
+    for (pointer, object) in all {
+        for keyword in ["definitions", "oneOf", "format", "minimum", "default"] {
+            // `properties` may contain a field named like a keyword
+            if !pointer.ends_with("/properties") {
+                assert!(!object.contains_key(keyword), "{keyword} at {pointer}");
+            }
+        }
+        if let Some(serde_json::Value::Object(properties)) = object.get("properties") {
+            assert_eq!(object["additionalProperties"], false, "at {pointer}");
+            let required: Vec<_> = object["required"].as_array().unwrap().iter().collect();
+            assert_eq!(required.len(), properties.len(), "at {pointer}");
+        }
+    }
+}
+
+#[test]
+fn sanitizes_enums_and_nested_vecs() {
+    let mut schema = serde_json::to_value(schema_for!(Drawing)).unwrap();
+
+    sanitize_for_strict(&mut schema);
+
+    assert_strict(&schema);
+    assert!(!schema.to_string().contains("$ref"));
+    let shape = &schema["properties"]["layers"]["items"]["items"];
+    assert_eq!(shape["anyOf"].as_array().unwrap().len(), 3);
+    assert_eq!(
+        schema["properties"]["background"]["anyOf"][1],
+        json!({"type": "null"})
+    );
+    assert_eq!(
+        schema["properties"]["title"]["type"],
+        json!(["string", "null"])
+    );
+    assert_eq!(schema["required"], json!(["background", "layers", "title"]));
+
+    // Sanitizing is idempotent
+    let sanitized = schema.clone();
+    sanitize_for_strict(&mut schema);
+    assert_eq!(schema, sanitized);
+}
+
+#[test]
+fn keeps_recursive_references() {
+    let mut root = serde_json::to_value(schema_for!(Section)).unwrap();
+    sanitize_for_strict(&mut root);
+
+    assert_strict(&root);
+    assert_eq!(
+        root["properties"]["subsections"]["items"],
+        json!({"$ref": "#"})
+    );
+    assert!(root.get("$defs").is_none());
+
+    let mut nested = serde_json::to_value(schema_for!(Document)).unwrap();
+    sanitize_for_strict(&mut nested);
+
+    assert_strict(&nested);
+    let section = &nested["$defs"]["Section"];
+    assert_eq!(section["additionalProperties"], false);
+    assert_eq!(
+        section["properties"]["subsections"]["items"],
+        json!({"$ref": "#/$defs/Section"})
+    );
+    assert_eq!(
+        nested["properties"]["sections"]["items"]["properties"]["subsections"]["items"],
+        json!({"$ref": "#/$defs/Section"})
+    );
+}
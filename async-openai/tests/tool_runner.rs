@@ -0,0 +1,223 @@
+#![cfg(feature = "tool-runner")]
+
+mod common;
+
+use async_openai::{
+    types::{
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+        CreateChatCompletionRequestArgs, FunctionObjectArgs,
+    },
+    ChatToolRunner, Client,
+};
+use common::{MockResponse, MockTransport};
+use serde_json::{json, Value};
+
+fn request(stream: bool) -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .stream(stream)
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("What's the weather like in Boston and Atlanta?")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+fn runner() -> ChatToolRunner {
+    ChatToolRunner::new()
+        .tool(
+            FunctionObjectArgs::default()
+                .name("get_current_weather")
+                .parameters(json!({
+                    "type": "object",
+                    "properties": {"location": {"type": "string"}},
+                    "required": ["location"]
+                }))
+                .build()
+                .unwrap(),
+            |args: Value| async move {
+                match args["location"].as_str() {
+                    Some("Atlanta") => Err("weather service unavailable"),
+                    location => Ok(json!({"location": location, "temperature": 22})),
+                }
+            },
+        )
+        .tool(
+            FunctionObjectArgs::default()
+                .name("get_time")
+                .build()
+                .unwrap(),
+            |_| async { Ok::<_, String>(json!("12:00")) },
+        )
+}
+
+fn tool_call(id: &str, name: &str, arguments: &str) -> Value {
+    json!({"id": id, "type": "function", "function": {"name": name, "arguments": arguments}})
+}
+
+fn completion(message: Value) -> MockResponse {
+    let finish_reason = if message.get("tool_calls").is_some() {
+        "tool_calls"
+    } else {
+        "stop"
+    };
+    let body = json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1677652288,
+        "model": "gpt-4o",
+        "choices": [{"index": 0, "message": message, "finish_reason": finish_reason}]
+    });
+    MockResponse::new(200, body.to_string())
+}
+
+fn tool_calls(calls: Vec<Value>) -> MockResponse {
+    completion(json!({"role": "assistant", "content": null, "tool_calls": calls}))
+}
+
+#[tokio::test]
+async fn runs_parallel_tool_calls_until_answer() {
+    let transport = MockTransport::new([
+        tool_calls(vec![
+            tool_call("call_1", "get_current_weather", r#"{"location":"Boston"}"#),
+            tool_call("call_2", "get_current_weather", r#"{"location":"Atlanta"}"#),
+            tool_call("call_3", "get_time", ""),
+        ]),
+        tool_calls(vec![
+            tool_call("call_4", "get_forecast", "{}"),
+            tool_call("call_5", "get_current_weather", "{not json"),
+        ]),
+        completion(json!({"role": "assistant", "content": "It is 22C in Boston."})),
+    ]);
+    let client = Client::new().with_transport(transport.clone());
+
+    let run = runner().run(&client.chat(), request(false)).await.unwrap();
+
+    assert_eq!(
+        run.response.choices[0].message.content.as_deref(),
+        Some("It is 22C in Boston.")
+    );
+    assert!(!run.max_iterations_reached);
+    let outputs: Vec<_> = run
+        .calls
+        .iter()
+        .map(|record| (record.call.id.as_str(), record.is_error))
+        .collect();
+    assert_eq!(
+        outputs,
+        [
+            ("call_1", false),
+            ("call_2", true),
+            ("call_3", false),
+            ("call_4", true),
+            ("call_5", true)
+        ]
+    );
+    assert_eq!(
+        run.calls[1].output,
+        json!({"error": "weather service unavailable"})
+    );
+    assert_eq!(
+        run.calls[3].output,
+        json!({"error": "unknown tool get_forecast"})
+    );
+    // user, then assistant and tool outputs of each turn, and the answer
+    assert_eq!(run.messages.len(), 1 + 4 + 3 + 1);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    let first = requests[0].json();
+    assert_eq!(first["tools"][0]["function"]["name"], "get_current_weather");
+    assert_eq!(first["tools"][1]["function"]["name"], "get_time");
+
+    let messages = requests[1].json()["messages"].clone();
+    assert_eq!(messages[1]["role"], "assistant");
+    assert_eq!(messages[1]["tool_calls"][2]["id"], "call_3");
+    assert_eq!(
+        messages[2],
+        json!({
+            "role": "tool",
+            "tool_call_id": "call_1",
+            "content": r#"{"location":"Boston","temperature":22}"#
+        })
+    );
+    assert_eq!(
+        messages[3]["content"],
+        r#"{"error":"weather service unavailable"}"#
+    );
+    // String outputs are sent as is
+    assert_eq!(messages[4]["content"], "12:00");
+}
+
+#[tokio::test]
+async fn stops_at_max_iterations() {
+    let transport = MockTransport::new([tool_calls(vec![tool_call("call_1", "get_time", "")])]);
+    let client = Client::new().with_transport(transport.clone());
+
+    let run = runner()
+        .with_max_iterations(2)
+        .run(&client.chat(), request(false))
+        .await
+        .unwrap();
+
+    assert!(run.max_iterations_reached);
+    assert_eq!(run.calls.len(), 1);
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn reassembles_streamed_tool_calls() {
+    let chunk = |delta: Value, finish_reason: Value| {
+        let chunk = json!({
+            "id": "chatcmpl-123",
+            "object": "chat.completion.chunk",
+            "created": 1694268190,
+            "model": "gpt-4o",
+            "choices": [{"index": 0, "delta": delta, "finish_reason": finish_reason}]
+        });
+        format!("data: {chunk}\n\n")
+    };
+    let tool_calls = [
+        chunk(
+            json!({"role": "assistant", "tool_calls": [{"index": 0, "id": "call_1", "type": "function", "function": {"name": "get_current_weather", "arguments": ""}}]}),
+            Value::Null,
+        ),
+        chunk(
+            json!({"tool_calls": [{"index": 0, "function": {"arguments": "{\"location\":"}}]}),
+            Value::Null,
+        ),
+        chunk(
+            json!({"tool_calls": [{"index": 0, "function": {"arguments": "\"Boston\"}"}}]}),
+            Value::Null,
+        ),
+        chunk(json!({}), json!("tool_calls")),
+        "data: [DONE]\n\n".into(),
+    ]
+    .concat();
+    let answer = [
+        chunk(json!({"role": "assistant", "content": "22C"}), Value::Null),
+        chunk(json!({}), json!("stop")),
+        "data: [DONE]\n\n".into(),
+    ]
+    .concat();
+
+    let transport = MockTransport::new([
+        MockResponse::new(200, tool_calls).header("content-type", "text/event-stream"),
+        MockResponse::new(200, answer).header("content-type", "text/event-stream"),
+    ]);
+    let client = Client::new().with_transport(transport.clone());
+
+    let run = runner().run(&client.chat(), request(true)).await.unwrap();
+
+    assert_eq!(
+        run.response.choices[0].message.content.as_deref(),
+        Some("22C")
+    );
+    assert_eq!(
+        run.calls[0].output,
+        json!({"location": "Boston", "temperature": 22})
+    );
+    assert_eq!(transport.requests()[1].json()["stream"], true);
+}
@@ -0,0 +1,192 @@
+#![cfg(all(feature = "tracing", feature = "chat-completion"))]
+mod common;
+
+use std::collections::HashMap;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+
+use async_openai::{
+    config::OpenAIConfig,
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+    Client,
+};
+use common::{MockResponse, MockTransport};
+use futures::StreamExt;
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Level, Metadata, Subscriber,
+};
+
+/// Name and recorded fields of a span
+type RecordedSpan = (String, HashMap<String, String>);
+
+/// Spans in creation order, span ids are their position + 1, and the level and
+/// message of events
+#[derive(Clone, Default)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<RecordedSpan>>>,
+    events: Arc<Mutex<Vec<(Level, String)>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl Recorder {
+    fn spans(&self) -> Vec<RecordedSpan> {
+        self.spans.lock().unwrap().clone()
+    }
+
+    fn span(&self, name: &str) -> HashMap<String, String> {
+        self.spans()
+            .into_iter()
+            .find(|(span, _)| span == name)
+            .unwrap_or_else(|| panic!("no {name} span"))
+            .1
+    }
+}
+
+struct Fields<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for Fields<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(
+            field.name().to_string(),
+            format!("{value:?}").replace('"', ""),
+        );
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = HashMap::new();
+        span.record(&mut Fields(&mut fields));
+        self.spans
+            .lock()
+            .unwrap()
+            .push((span.metadata().name().to_string(), fields));
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        let (_, fields) = &mut spans[span.into_u64() as usize - 1];
+        values.record(&mut Fields(fields));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = HashMap::new();
+        event.record(&mut Fields(&mut fields));
+        self.events.lock().unwrap().push((
+            *event.metadata().level(),
+            fields.remove("message").unwrap_or_default(),
+        ));
+    }
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+fn request(stream: bool) -> async_openai::types::CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .stream(stream)
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("secret prompt")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+fn client(transport: &MockTransport) -> Client<OpenAIConfig> {
+    Client::with_config(OpenAIConfig::new().with_api_key("sk-secret"))
+        .with_transport(transport.clone())
+}
+
+#[tokio::test]
+async fn chat_create_span() {
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    let transport = MockTransport::new([MockResponse::new(
+        200,
+        r#"{"id": "chatcmpl-123", "object": "chat.completion", "created": 1677652288, "model": "gpt-4o", "choices": []}"#,
+    )
+    .header("x-request-id", "req_123")]);
+
+    client(&transport)
+        .chat()
+        .create(request(false))
+        .await
+        .unwrap();
+
+    let names: Vec<_> = recorder.spans().into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, vec!["openai.chat.create", "openai.http"]);
+
+    let http = recorder.span("openai.http");
+    assert_eq!(http["http.request.method"], "POST");
+    assert_eq!(http["url.path"], "/v1/chat/completions");
+    assert_eq!(http["model"], "gpt-4o");
+    assert_eq!(http["http.status_code"], "200");
+    assert_eq!(http["openai.request_id"], "req_123");
+    assert!(http.contains_key("latency_ms"));
+
+    let recorded = format!("{:?}", recorder.spans());
+    assert!(!recorded.contains("secret"));
+}
+
+#[tokio::test]
+async fn chat_create_stream_span() {
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    let transport = MockTransport::ok(concat!(
+        "data: {\"id\":\"chatcmpl-123\",\"object\":\"chat.completion.chunk\",\"created\":1694268190,\"model\":\"gpt-4o\",\"choices\":[]}\n\n",
+        "data: {\"id\":\"chatcmpl-123\",\"object\":\"chat.completion.chunk\",\"created\":1694268190,\"model\":\"gpt-4o\",\"choices\":[]}\n\n",
+        "data: [DONE]\n\n",
+    ));
+
+    let mut stream = client(&transport)
+        .chat()
+        .create_stream(request(true))
+        .await
+        .unwrap();
+    while stream.next().await.is_some() {}
+
+    let stream = recorder.span("openai.stream");
+    assert_eq!(stream["url.path"], "/chat/completions");
+    assert_eq!(stream["model"], "gpt-4o");
+    // Two chunks and [DONE]
+    assert_eq!(stream["openai.events"], "3");
+}
+
+#[tokio::test]
+async fn deserialization_error_logs_no_body() {
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    let transport = MockTransport::ok(
+        r#"{"id": "chatcmpl-123", "object": "chat.completion", "created": "secret answer", "model": "gpt-4o", "choices": []}"#,
+    );
+
+    let error = client(&transport)
+        .chat()
+        .create(request(false))
+        .await
+        .unwrap_err();
+    assert!(error.body_snippet().unwrap().contains("secret answer"));
+
+    let events = recorder.events.lock().unwrap().clone();
+    assert!(events
+        .iter()
+        .any(|(level, message)| *level == Level::ERROR && message.contains("created")));
+    for (level, message) in events {
+        assert!(
+            level == Level::TRACE || !message.contains("secret"),
+            "{level} {message}"
+        );
+    }
+}
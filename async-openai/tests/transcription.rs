@@ -0,0 +1,239 @@
+#![cfg(feature = "full")]
+mod common;
+
+use async_openai::{
+    error::OpenAIError,
+    types::{
+        AudioInput, AudioResponseFormat, CreateTranscriptionRequest,
+        CreateTranscriptionRequestArgs, KnownSpeaker, TranscriptionInclude,
+    },
+    Client,
+};
+use common::MockTransport;
+
+const TRANSCRIPTION_WITH_LOGPROBS: &str = r#"{
+  "text": "Hey there.",
+  "logprobs": [
+    {"token": "Hey", "logprob": -0.0078125, "bytes": [72, 101, 121]},
+    {"token": " there", "logprob": -0.25, "bytes": [32, 116, 104, 101, 114, 101]},
+    {"token": ".", "logprob": -1.5, "bytes": [46]}
+  ],
+  "usage": {
+    "type": "tokens",
+    "input_tokens": 14,
+    "input_token_details": {"text_tokens": 0, "audio_tokens": 14},
+    "output_tokens": 3,
+    "total_tokens": 17
+  }
+}"#;
+
+#[tokio::test]
+async fn transcription_logprobs() {
+    let transport = MockTransport::ok(TRANSCRIPTION_WITH_LOGPROBS);
+    let client = Client::new().with_transport(transport.clone());
+
+    let request = CreateTranscriptionRequestArgs::default()
+        .file(AudioInput::from_vec_u8(
+            "audio.mp3".into(),
+            b"audio".to_vec(),
+        ))
+        .model("gpt-4o-transcribe")
+        .include([TranscriptionInclude::Logprobs])
+        .build()
+        .unwrap();
+
+    let response = client.audio().transcribe(request).await.unwrap();
+
+    let body = transport.requests()[0].body_text();
+    assert!(body.contains("name=\"include[]\"\r\n\r\nlogprobs\r\n"));
+
+    let logprobs = response.logprobs.as_ref().unwrap();
+    assert_eq!(logprobs.len(), 3);
+    assert_eq!(logprobs[1].token, " there");
+    assert_eq!(logprobs[1].bytes, b" there");
+
+    assert_eq!(response.avg_logprob(), Some(-0.5859375));
+    let low_confidence = response.low_confidence_tokens(-1.0);
+    assert_eq!(low_confidence.len(), 1);
+    assert_eq!(low_confidence[0].token, ".");
+}
+
+#[test]
+fn transcription_without_logprobs() {
+    let response: async_openai::types::CreateTranscriptionResponseJson =
+        serde_json::from_str(r#"{"text": "Hey there."}"#).unwrap();
+
+    assert!(response.logprobs.is_none());
+    assert_eq!(response.avg_logprob(), None);
+    assert!(response.low_confidence_tokens(0.0).is_empty());
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        serde_json::json!({"text": "Hey there."})
+    );
+}
+
+#[tokio::test]
+async fn transcribe_rejects_verbose_json() {
+    let transport = MockTransport::new([]);
+    let client = Client::new().with_transport(transport.clone());
+
+    let request = CreateTranscriptionRequestArgs::default()
+        .file(AudioInput::from_vec_u8(
+            "audio.mp3".into(),
+            b"audio".to_vec(),
+        ))
+        .model("whisper-1")
+        .response_format(AudioResponseFormat::VerboseJson)
+        .build()
+        .unwrap();
+
+    let error = client.audio().transcribe(request).await.unwrap_err();
+
+    assert!(matches!(error, OpenAIError::InvalidArgument(ref message)
+        if message == "When response_format is verbose_json, use Audio::transcribe_verbose_json"));
+    assert!(transport.requests().is_empty());
+}
+
+/// Silent 8kHz 16-bit mono WAV of `seconds`
+fn wav(seconds: u32) -> Vec<u8> {
+    let data_len = seconds * 16_000;
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&8_000u32.to_le_bytes());
+    wav.extend_from_slice(&16_000u32.to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.resize(wav.len() + data_len as usize, 0);
+    wav
+}
+
+fn diarize_request(known_speakers: Vec<KnownSpeaker>) -> CreateTranscriptionRequest {
+    CreateTranscriptionRequestArgs::default()
+        .file(AudioInput::from_vec_u8(
+            "call.mp3".into(),
+            b"audio".to_vec(),
+        ))
+        .model("gpt-4o-transcribe-diarize")
+        .known_speakers(known_speakers)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn transcribe_diarized_with_known_speakers() {
+    let transport = MockTransport::ok(
+        r#"{
+  "task": "transcribe",
+  "duration": 6.5,
+  "text": "Thanks for calling. Hi, my order is late.",
+  "segments": [
+    {"type": "transcript.text.segment", "id": "seg_001", "start": 0.0, "end": 2.5, "text": "Thanks for calling.", "speaker": "agent"},
+    {"type": "transcript.text.segment", "id": "seg_002", "start": 2.5, "end": 6.5, "text": "Hi, my order is late.", "speaker": "customer"}
+  ],
+  "usage": {"type": "duration", "seconds": 7}
+}"#,
+    );
+    let client = Client::new().with_transport(transport.clone());
+
+    let request = diarize_request(vec![
+        KnownSpeaker::new("agent", AudioInput::from_vec_u8("agent.wav".into(), wav(3))),
+        KnownSpeaker::new(
+            "customer",
+            AudioInput::from_bytes("customer.mp3".into(), "ID3".into()),
+        ),
+    ]);
+
+    let response = client
+        .audio()
+        .transcribe_diarized_json(request)
+        .await
+        .unwrap();
+
+    let speakers: Vec<_> = response
+        .segments
+        .iter()
+        .map(|s| s.speaker.as_str())
+        .collect();
+    assert_eq!(speakers, ["agent", "customer"]);
+    assert_eq!(response.segments[1].end, 6.5);
+
+    let body = transport.requests()[0].body_text();
+    assert!(body.contains("name=\"response_format\"\r\n\r\ndiarized_json\r\n"));
+    assert!(body.contains("name=\"known_speaker_names[]\"\r\n\r\nagent\r\n"));
+    assert!(body.contains("name=\"known_speaker_names[]\"\r\n\r\ncustomer\r\n"));
+    assert!(body.contains("name=\"known_speaker_references[]\"\r\n\r\ndata:audio/wav;base64,UklGR"));
+    assert!(
+        body.contains("name=\"known_speaker_references[]\"\r\n\r\ndata:audio/mpeg;base64,SUQz\r\n")
+    );
+    // Names and references are in the same order
+    assert!(body.find("agent\r\n").unwrap() < body.find("customer\r\n").unwrap());
+}
+
+#[test]
+fn known_speakers_validation() {
+    let speakers = |count: usize| -> Vec<KnownSpeaker> {
+        (0..count)
+            .map(|i| {
+                KnownSpeaker::new(
+                    format!("speaker_{i}"),
+                    AudioInput::from_vec_u8("s.wav".into(), wav(3)),
+                )
+            })
+            .collect()
+    };
+
+    assert!(CreateTranscriptionRequestArgs::default()
+        .known_speakers(speakers(4))
+        .build()
+        .is_ok());
+    assert!(CreateTranscriptionRequestArgs::default()
+        .known_speakers(speakers(5))
+        .build()
+        .is_err());
+    assert!(CreateTranscriptionRequestArgs::default()
+        .known_speakers([KnownSpeaker::new(
+            "",
+            AudioInput::from_vec_u8("s.wav".into(), wav(3))
+        )])
+        .build()
+        .is_err());
+}
+
+#[tokio::test]
+async fn known_speaker_reference_duration() {
+    let transport = MockTransport::new([]);
+    let client = Client::new().with_transport(transport.clone());
+
+    for seconds in [1, 11] {
+        let request = diarize_request(vec![KnownSpeaker::new(
+            "agent",
+            AudioInput::from_vec_u8("agent.wav".into(), wav(seconds)),
+        )]);
+        let error = client
+            .audio()
+            .transcribe_diarized_json(request)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+    }
+
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn transcription_language_and_duration() {
+    let payload = serde_json::json!({"text": "Hola.", "language": "spanish", "duration": 1.5});
+    let response: async_openai::types::CreateTranscriptionResponseJson =
+        serde_json::from_value(payload.clone()).unwrap();
+
+    assert_eq!(response.language.as_deref(), Some("spanish"));
+    assert_eq!(response.duration, Some(1.5));
+    assert_eq!(serde_json::to_value(&response).unwrap(), payload);
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "full")]
+mod common;
+
+use async_openai::{
+    error::OpenAIError,
+    types::{
+        AudioInput, AudioResponseFormat, CreateTranslationRequest, CreateTranslationRequestArgs,
+    },
+    Client,
+};
+use common::MockTransport;
+
+const TRANSLATION_VERBOSE_JSON: &str = r#"{
+  "task": "translate",
+  "language": "english",
+  "duration": 8.5,
+  "text": "Hello, my name is Wolfgang and I come from Germany.",
+  "segments": [
+    {
+      "id": 0,
+      "seek": 0,
+      "start": 0.0,
+      "end": 4.0,
+      "text": " Hello, my name is Wolfgang and I come from Germany.",
+      "tokens": [50364, 2425, 11, 452, 1315, 307, 16634, 293, 286, 808, 490, 7244, 13, 50564],
+      "temperature": 0.0,
+      "avg_logprob": -0.25,
+      "compression_ratio": 0.875,
+      "no_speech_prob": 0.0078125
+    }
+  ]
+}"#;
+
+fn request(response_format: Option<AudioResponseFormat>) -> CreateTranslationRequest {
+    let mut request = CreateTranslationRequestArgs::default();
+    request
+        .file(AudioInput::from_vec_u8(
+            "german.mp3".into(),
+            b"audio".to_vec(),
+        ))
+        .model("whisper-1");
+    if let Some(response_format) = response_format {
+        request.response_format(response_format);
+    }
+    request.build().unwrap()
+}
+
+#[tokio::test]
+async fn translate_verbose_json() {
+    let transport = MockTransport::ok(TRANSLATION_VERBOSE_JSON);
+    let client = Client::new().with_transport(transport.clone());
+
+    let response = client
+        .audio()
+        .translate_verbose_json(request(None))
+        .await
+        .unwrap();
+
+    assert_eq!(response.language, "english");
+    assert_eq!(response.duration, 8.5);
+    let segments = response.segments.unwrap();
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].end, 4.0);
+    assert_eq!(segments[0].avg_logprob, -0.25);
+
+    let body = transport.requests()[0].body_text();
+    assert!(body.contains("name=\"response_format\"\r\n\r\nverbose_json\r\n"));
+}
+
+#[tokio::test]
+async fn translate_raw_srt() {
+    let srt =
+        "1\n00:00:00,000 --> 00:00:04,000\nHello, my name is Wolfgang and I come from Germany.\n\n";
+    let transport = MockTransport::ok(srt);
+    let client = Client::new().with_transport(transport.clone());
+
+    let response = client
+        .audio()
+        .translate_raw(request(Some(AudioResponseFormat::Srt)))
+        .await
+        .unwrap();
+
+    assert_eq!(response, srt.as_bytes());
+    let body = transport.requests()[0].body_text();
+    assert!(body.contains("name=\"response_format\"\r\n\r\nsrt\r\n"));
+}
+
+#[tokio::test]
+async fn response_format_must_match_method() {
+    let transport = MockTransport::new([]);
+    let client = Client::new().with_transport(transport.clone());
+    let audio = client.audio();
+
+    for response_format in [AudioResponseFormat::VerboseJson, AudioResponseFormat::Vtt] {
+        let error = audio
+            .translate(request(Some(response_format)))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+    }
+
+    for response_format in [AudioResponseFormat::Json, AudioResponseFormat::Text] {
+        let error = audio
+            .translate_verbose_json(request(Some(response_format)))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+    }
+
+    assert!(transport.requests().is_empty());
+}
@@ -0,0 +1,268 @@
+#![cfg(feature = "chat-completion")]
+mod common;
+
+use std::time::Duration;
+
+use async_openai::{
+    config::OpenAIConfig,
+    error::{OpenAIError, RateLimitCode},
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+    Client,
+};
+use common::{MockResponse, MockTransport};
+use futures::StreamExt;
+use reqwest::StatusCode;
+use secrecy::SecretString;
+
+fn request(stream: bool) -> async_openai::types::CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .stream(stream)
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn chat_create_with_mock_transport() {
+    let transport = MockTransport::ok(
+        r#"{
+            "id": "chatcmpl-123",
+            "object": "chat.completion",
+            "created": 1677652288,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "Hi there!" },
+                "finish_reason": "stop"
+            }]
+        }"#,
+    );
+    let client = Client::new().with_transport(transport.clone());
+
+    let response = client.chat().create(request(false)).await.unwrap();
+
+    assert_eq!(
+        response.choices[0].message.content.as_deref(),
+        Some("Hi there!")
+    );
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(
+        requests[0].url.as_str(),
+        "https://api.openai.com/v1/chat/completions"
+    );
+    assert_eq!(requests[0].json()["model"], "gpt-4o");
+}
+
+#[tokio::test]
+async fn chat_create_stream_with_mock_transport() {
+    let transport = MockTransport::ok(
+        concat!(
+            "data: {\"id\":\"chatcmpl-123\",\"object\":\"chat.completion.chunk\",\"created\":1694268190,\"model\":\"gpt-4o\",\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"Hi\"},\"finish_reason\":null}]}\n\n",
+            "data: {\"id\":\"chatcmpl-123\",\"object\":\"chat.completion.chunk\",\"created\":1694268190,\"model\":\"gpt-4o\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\" there!\"},\"finish_reason\":\"stop\"}]}\n\n",
+            "data: [DONE]\n\n",
+        ),
+    );
+    let client = Client::new().with_transport(transport);
+
+    let mut stream = client.chat().create_stream(request(true)).await.unwrap();
+
+    let mut content = String::new();
+    while let Some(chunk) = stream.next().await {
+        content.push_str(chunk.unwrap().choices[0].delta.content.as_deref().unwrap());
+    }
+
+    assert_eq!(content, "Hi there!");
+}
+
+#[tokio::test]
+async fn api_error_with_mock_transport() {
+    let body = r#"{"error": {"message": "Invalid model", "type": "invalid_request_error", "param": "model", "code": null}}"#;
+
+    let client = Client::new().with_transport(MockTransport::new([MockResponse::new(400, body)]));
+    let error = client.chat().create(request(false)).await.unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+    assert!(matches!(error, OpenAIError::ApiError(e) if e.message == "Invalid model"));
+
+    let client = Client::new().with_transport(MockTransport::new([MockResponse::new(400, body)]));
+    let mut stream = client.chat().create_stream(request(true)).await.unwrap();
+    let error = stream.next().await.unwrap().unwrap_err();
+    assert!(matches!(error, OpenAIError::ApiError(e) if e.message == "Invalid model"));
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn non_json_error_body_keeps_status() {
+    let page = "<html><head><title>502 Bad Gateway</title></head></html>";
+
+    let client = Client::new().with_transport(MockTransport::new([MockResponse::new(502, page)]));
+    let error = client.chat().create(request(false)).await.unwrap_err();
+    assert!(matches!(
+        error,
+        OpenAIError::HttpError { status: StatusCode::BAD_GATEWAY, ref body, .. } if body == page
+    ));
+
+    let client = Client::new().with_transport(MockTransport::new([MockResponse::new(502, page)]));
+    let mut stream = client.chat().create_stream(request(true)).await.unwrap();
+    let error = stream.next().await.unwrap().unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::BAD_GATEWAY));
+}
+
+#[tokio::test]
+async fn deserialization_error_of_stream_chunk() {
+    let transport = MockTransport::ok(
+        "data: {\"id\":\"chatcmpl-123\",\"object\":\"chat.completion.chunk\",\"created\":1694268190,\"model\":\"gpt-4o\",\"choices\":[{\"index\":\"zero\",\"delta\":{}}]}\n\n",
+    );
+    let client = Client::new().with_transport(transport);
+
+    let mut stream = client.chat().create_stream(request(true)).await.unwrap();
+    let error = stream.next().await.unwrap().unwrap_err();
+
+    assert!(matches!(
+        error,
+        OpenAIError::JSONDeserialize { ref path, .. } if path == "choices[0].index"
+    ));
+    assert!(error.body_snippet().unwrap().contains("chatcmpl-123"));
+}
+
+#[tokio::test]
+async fn rate_limited_is_retried_after_wait_hint() {
+    let transport = MockTransport::new([
+        MockResponse::new(
+            429,
+            r#"{"error": {"message": "Rate limit reached", "type": "requests", "param": null, "code": "rate_limit_exceeded"}}"#,
+        )
+        .header("retry-after-ms", "10"),
+        MockResponse::new(
+            200,
+            r#"{"id": "chatcmpl-123", "object": "chat.completion", "created": 1677652288, "model": "gpt-4o", "choices": []}"#,
+        ),
+    ]);
+    let client = Client::new().with_transport(transport.clone());
+
+    client.chat().create(request(false)).await.unwrap();
+
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn insufficient_quota_is_not_retried() {
+    let transport = MockTransport::new([MockResponse::new(
+        429,
+        r#"{"error": {"message": "You exceeded your current quota", "type": "insufficient_quota", "param": null, "code": "insufficient_quota"}}"#,
+    )
+    .header("retry-after", "2")]);
+    let client = Client::new().with_transport(transport.clone());
+
+    let error = client.chat().create(request(false)).await.unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "rate limited: You exceeded your current quota (retry after 2.000s)"
+    );
+    assert!(matches!(
+        error,
+        OpenAIError::RateLimited {
+            retry_after: Some(retry_after),
+            code: RateLimitCode::InsufficientQuota,
+            ..
+        } if retry_after == Duration::from_secs(2)
+    ));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn chat_create_with_headers() {
+    let transport = MockTransport::new([MockResponse::new(
+        200,
+        r#"{"id": "chatcmpl-123", "object": "chat.completion", "created": 1677652288, "model": "gpt-4o", "choices": []}"#,
+    )
+    .header("openai-processing-ms", "42")
+    .header("openai-version", "2020-10-01")]);
+    let client = Client::new().with_transport(transport);
+
+    let (response, headers) = client
+        .chat()
+        .create_with_headers(request(false))
+        .await
+        .unwrap();
+
+    assert_eq!(response.id, "chatcmpl-123");
+    assert_eq!(headers["openai-processing-ms"], "42");
+    assert_eq!(headers["openai-version"], "2020-10-01");
+}
+
+#[tokio::test]
+async fn bearer_auth_overrides_config_api_key() {
+    let transport = MockTransport::ok(
+        r#"{"id": "chatcmpl-123", "object": "chat.completion", "created": 1677652288, "model": "gpt-4o", "choices": []}"#,
+    );
+    let client = Client::with_config(OpenAIConfig::new().with_api_key("sk-config"))
+        .with_transport(transport.clone());
+
+    client
+        .clone()
+        .with_bearer_auth("sk-org-a")
+        .unwrap()
+        .chat()
+        .create(request(false))
+        .await
+        .unwrap();
+    let mut stream = client
+        .clone()
+        .with_bearer_auth("sk-org-b")
+        .unwrap()
+        .chat()
+        .create_stream(request(true))
+        .await
+        .unwrap();
+    while stream.next().await.is_some() {}
+    client.chat().create(request(false)).await.unwrap();
+
+    let authorization: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|request| {
+            request.headers["authorization"]
+                .to_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(
+        authorization,
+        vec!["Bearer sk-org-a", "Bearer sk-org-b", "Bearer sk-config"]
+    );
+    assert!(
+        !format!("{:?}", client.clone().with_bearer_auth("sk-org-a").unwrap()).contains("sk-org-a")
+    );
+}
+
+#[tokio::test]
+async fn invalid_key_of_provider_fails_requests() {
+    let transport = MockTransport::ok("{}");
+    let config = OpenAIConfig::new().with_api_key_provider(|| SecretString::from("sk-rotated\n"));
+    let client = Client::with_config(config).with_transport(transport.clone());
+
+    let error = client.chat().create(request(false)).await.unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+    let mut stream = client.chat().create_stream(request(true)).await.unwrap();
+    let error = stream.next().await.unwrap().unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn bearer_auth_rejects_invalid_key() {
+    let error = Client::new()
+        .with_bearer_auth("sk-org\nX-Injected: 1")
+        .unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+    assert!(!error.to_string().contains("sk-org"));
+}
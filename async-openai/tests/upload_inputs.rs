@@ -0,0 +1,287 @@
+#![cfg(feature = "full")]
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use async_openai::{
+    config::OpenAIConfig,
+    error::OpenAIError,
+    transport::HttpTransport,
+    types::{
+        AudioInput, CreateFileRequestArgs, CreateImageEditRequestArgs,
+        CreateTranscriptionRequestArgs, CreateTranslationRequestArgs, FileInput, FilePurpose,
+        ImageInput,
+    },
+    Client,
+};
+use bytes::Bytes;
+use common::{MockResponse, MockTransport};
+use futures::future::BoxFuture;
+use http_body_util::BodyExt;
+use tokio::io::AsyncReadExt;
+
+const RATE_LIMITED: &str = r#"{"error": {"message": "Rate limit reached", "type": "requests", "param": null, "code": "rate_limit_exceeded"}}"#;
+
+const SERVER_ERROR: &str = r#"{"error": {"message": "The server had an error", "type": "server_error", "param": null, "code": null}}"#;
+
+const FILE: &str = r#"{"id": "file-abc123", "object": "file", "bytes": 4, "created_at": 1677610602, "filename": "image.png", "purpose": "vision"}"#;
+
+async fn write_file(name: &str, len: usize) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join("async-openai-upload-inputs");
+    tokio::fs::create_dir_all(&dir).await.unwrap();
+    let path = dir.join(name);
+    tokio::fs::write(&path, vec![0u8; len]).await.unwrap();
+    path
+}
+
+#[tokio::test]
+async fn upload_image_sets_vision_purpose() {
+    let transport = MockTransport::ok(FILE);
+    let client = Client::new().with_transport(transport.clone());
+    let path = write_file("image.png", 4).await;
+
+    let file_id = client.files().upload_image(&path).await.unwrap();
+
+    assert_eq!(file_id, "file-abc123");
+    let request = &transport.requests()[0];
+    assert_eq!(request.path(), "/v1/files");
+    let body = request.body_text();
+    assert!(body.contains("name=\"file\"; filename=\"image.png\""));
+    assert!(body.contains("name=\"purpose\"\r\n\r\nvision"));
+}
+
+#[tokio::test]
+async fn upload_pdf_sets_user_data_purpose() {
+    let transport = MockTransport::ok(FILE);
+    let client = Client::new().with_transport(transport.clone());
+    let path = write_file("document.PDF", 4).await;
+
+    client.files().upload_pdf(&path).await.unwrap();
+
+    let body = transport.requests()[0].body_text();
+    assert!(body.contains("name=\"file\"; filename=\"document.PDF\""));
+    assert!(body.contains("name=\"purpose\"\r\n\r\nuser_data"));
+}
+
+#[tokio::test]
+async fn upload_image_validates_extension_and_size() {
+    let transport = MockTransport::ok(FILE);
+    let client = Client::new().with_transport(transport.clone());
+
+    let path = write_file("notes.txt", 4).await;
+    let error = client.files().upload_image(&path).await.unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+
+    let path = write_file("large.jpg", 20 * 1024 * 1024 + 1).await;
+    let error = client.files().upload_image(&path).await.unwrap_err();
+    assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+
+    assert!(transport.requests().is_empty());
+}
+
+#[tokio::test]
+async fn transcribe_bytes_without_file() {
+    let transport = MockTransport::ok(r#"{"text": "Hello"}"#);
+    let client = Client::new().with_transport(transport.clone());
+
+    // Audio fetched from object storage
+    let audio = Bytes::from_static(b"ID3 audio");
+    let request = CreateTranscriptionRequestArgs::default()
+        .file(AudioInput::from_bytes("speech.mp3".into(), audio))
+        .model("gpt-4o-transcribe")
+        .build()
+        .unwrap();
+
+    let response = client.audio().transcribe(request).await.unwrap();
+
+    assert_eq!(response.text, "Hello");
+    let request = &transport.requests()[0];
+    assert_eq!(request.path(), "/v1/audio/transcriptions");
+    let body = request.body_text();
+    assert!(body.contains(
+        "name=\"file\"; filename=\"speech.mp3\"\r\nContent-Type: audio/mpeg\r\n\r\nID3 audio\r\n"
+    ));
+}
+
+#[tokio::test]
+async fn translate_from_reader() {
+    let transport = MockTransport::ok(r#"{"text": "Hello"}"#);
+    let client = Client::new().with_transport(transport.clone());
+
+    let audio: &[u8] = b"RIFF audio";
+    let request = CreateTranslationRequestArgs::default()
+        .file(AudioInput::from_reader("speech.WAV".into(), 10, audio))
+        .model("whisper-1")
+        .build()
+        .unwrap();
+
+    client.audio().translate(request).await.unwrap();
+
+    let body = transport.requests()[0].body_text();
+    assert!(body.contains(
+        "name=\"file\"; filename=\"speech.WAV\"\r\nContent-Type: audio/wav\r\n\r\nRIFF audio\r\n"
+    ));
+}
+
+#[tokio::test]
+async fn reader_is_not_sent_again_on_retry() {
+    let transport = MockTransport::new([
+        MockResponse::new(429, RATE_LIMITED).header("retry-after-ms", "10"),
+        MockResponse::new(200, r#"{"text": "Hello"}"#),
+    ]);
+    let client = Client::new().with_transport(transport.clone());
+
+    let audio: &[u8] = b"audio";
+    let request = CreateTranscriptionRequestArgs::default()
+        .file(AudioInput::from_reader("speech.ogg".into(), 5, audio))
+        .model("whisper-1")
+        .build()
+        .unwrap();
+
+    let error = client.audio().transcribe(request).await.unwrap_err();
+
+    // The error of the request which read the input, not a read error of the retry
+    assert!(matches!(error, OpenAIError::RateLimited { .. }));
+    assert_eq!(transport.requests().len(), 1);
+
+    let transport = MockTransport::new([MockResponse::new(503, SERVER_ERROR)]);
+    let client = Client::new()
+        .with_failover([OpenAIConfig::new().with_api_base("https://secondary.example.com/v1")])
+        .with_transport(transport.clone());
+    let request = CreateTranscriptionRequestArgs::default()
+        .file(AudioInput::from_reader("speech.ogg".into(), 5, audio))
+        .model("whisper-1")
+        .build()
+        .unwrap();
+
+    let error = client.audio().transcribe(request).await.unwrap_err();
+
+    assert_eq!(
+        error.status(),
+        Some(reqwest::StatusCode::SERVICE_UNAVAILABLE)
+    );
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn image_edit_from_memory() {
+    let transport = MockTransport::ok(
+        r#"{"created": 1589478378, "data": [{"url": "https://example.com/image.png"}]}"#,
+    );
+    let client = Client::new().with_transport(transport.clone());
+
+    let image: &[u8] = b"PNG image";
+    let request = CreateImageEditRequestArgs::default()
+        .image(ImageInput::from_reader("image.png".into(), 9, image))
+        .mask(ImageInput::from_vec_u8(
+            "mask.png".into(),
+            b"PNG mask".to_vec(),
+        ))
+        .prompt("A cat")
+        .build()
+        .unwrap();
+
+    client.images().create_edit(request).await.unwrap();
+
+    let body = transport.requests()[0].body_text();
+    assert!(body.contains(
+        "name=\"image\"; filename=\"image.png\"\r\nContent-Type: image/png\r\n\r\nPNG image\r\n"
+    ));
+    assert!(body.contains(
+        "name=\"mask\"; filename=\"mask.png\"\r\nContent-Type: image/png\r\n\r\nPNG mask\r\n"
+    ));
+}
+
+/// A request body, read chunk by chunk
+#[derive(Clone, Copy)]
+struct CountedBody {
+    content_length: Option<u64>,
+    length: u64,
+    largest_chunk: usize,
+}
+
+#[derive(Clone, Default)]
+struct CountingTransport {
+    bodies: Arc<Mutex<Vec<CountedBody>>>,
+}
+
+impl HttpTransport for CountingTransport {
+    fn execute(
+        &self,
+        mut request: reqwest::Request,
+    ) -> BoxFuture<'_, Result<reqwest::Response, OpenAIError>> {
+        let content_length = request
+            .headers()
+            .get("content-length")
+            .map(|length| length.to_str().unwrap().parse().unwrap());
+        let mut body = request.body_mut().take().unwrap();
+
+        Box::pin(async move {
+            let (mut length, mut largest_chunk) = (0, 0);
+            while let Some(frame) = body.frame().await {
+                let chunk = frame?.into_data().unwrap();
+                length += chunk.len() as u64;
+                largest_chunk = largest_chunk.max(chunk.len());
+            }
+            self.bodies.lock().unwrap().push(CountedBody {
+                content_length,
+                length,
+                largest_chunk,
+            });
+
+            Ok(http::Response::builder()
+                .status(200)
+                .body(FILE)
+                .unwrap()
+                .into())
+        })
+    }
+}
+
+#[tokio::test]
+async fn large_file_is_streamed_with_content_length() {
+    let dir = std::env::temp_dir().join("async-openai-upload-inputs");
+    tokio::fs::create_dir_all(&dir).await.unwrap();
+    let path = dir.join("large.jsonl");
+    // Sparse, without taking the disk space
+    let size = 256 * 1024 * 1024;
+    std::fs::File::create(&path).unwrap().set_len(size).unwrap();
+
+    let transport = CountingTransport::default();
+    let client = Client::new().with_transport(transport.clone());
+    let request = CreateFileRequestArgs::default()
+        .file(&path)
+        .purpose(FilePurpose::FineTune)
+        .build()
+        .unwrap();
+
+    client.files().create(request).await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let body = transport.bodies.lock().unwrap()[0];
+    assert!(body.length > size);
+    assert_eq!(body.content_length, Some(body.length));
+    // The file is never held in memory as a whole
+    assert!(body.largest_chunk <= 64 * 1024);
+}
+
+#[tokio::test]
+async fn reader_is_streamed_with_content_length() {
+    let transport = CountingTransport::default();
+    let client = Client::new().with_transport(transport.clone());
+
+    let size = 16 * 1024 * 1024;
+    let reader = tokio::io::repeat(0).take(size);
+    let request = CreateFileRequestArgs::default()
+        .file(FileInput::from_reader("batch.jsonl".into(), size, reader))
+        .purpose(FilePurpose::Batch)
+        .build()
+        .unwrap();
+
+    client.files().create(request).await.unwrap();
+
+    let body = transport.bodies.lock().unwrap()[0];
+    assert!(body.length > size);
+    assert_eq!(body.content_length, Some(body.length));
+    assert!(body.largest_chunk <= 64 * 1024);
+}
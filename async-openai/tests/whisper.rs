@@ -1,3 +1,4 @@
+#![cfg(feature = "full")]
 use async_openai::types::CreateTranslationRequestArgs;
 use async_openai::{types::CreateTranscriptionRequestArgs, Client};
 use tokio_test::assert_err;
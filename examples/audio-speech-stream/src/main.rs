@@ -0,0 +1,39 @@
+use async_openai::{
+    types::{CreateSpeechRequestArgs, SpeechModel, SpeechResponseFormat, SpeechStreamEvent, Voice},
+    Client,
+};
+use futures::StreamExt;
+use std::error::Error;
+use tokio::io::AsyncWriteExt;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let client = Client::new();
+
+    let request = CreateSpeechRequestArgs::default()
+        .input("Today is a wonderful day to build something people love!")
+        .voice(Voice::Alloy)
+        .model(SpeechModel::Gpt4oMiniTts)
+        .response_format(SpeechResponseFormat::Pcm)
+        .build()?;
+
+    let mut stream = client.audio().speech_stream(request).await?;
+
+    tokio::fs::create_dir_all("./data").await?;
+    let mut file = tokio::fs::File::create("./data/audio.pcm").await?;
+
+    while let Some(event) = stream.next().await {
+        match event? {
+            SpeechStreamEvent::Audio(delta) => file.write_all(&delta.bytes()?).await?,
+            SpeechStreamEvent::Done(done) => {
+                if let Some(usage) = done.usage {
+                    println!("Total tokens: {}", usage.total_tokens);
+                }
+            }
+        }
+    }
+
+    file.flush().await?;
+
+    Ok(())
+}
@@ -27,10 +27,14 @@ async fn translate_verbose_json() -> Result<(), Box<dyn Error>> {
         .model("whisper-1")
         .build()?;
 
-    let response = client.audio().translate(request).await?;
+    let response = client.audio().translate_verbose_json(request).await?;
 
     println!("translate_verbose_json:");
     println!("{}", response.text);
+    println!("- {}s of {} audio", response.duration, response.language);
+    if let Some(segments) = &response.segments {
+        println!("- {} segments", segments.len());
+    }
 
     Ok(())
 }
@@ -16,11 +16,11 @@ async fn main() -> Result<(), Box<dyn Error>> {
         .max_tokens(512u32)
         .model("gpt-3.5-turbo")
         .store(true)
-        .metadata(json!({
+        .try_metadata(json!({
             "role": "manager",
             "department": "accounting",
             "source": "homepage",
-        }))
+        }))?
         .messages([
             ChatCompletionRequestSystemMessageArgs::default()
                 .content("You are a corporate IT support expert.")
@@ -19,10 +19,10 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     let response = client.images().create(request).await?;
 
-    // Download and save images to ./data directory.
-    // Each url is downloaded and saved in dedicated Tokio task.
+    // Download and save images to ./data directory, with the HTTP client of the client.
+    // Files are named after the creation time and index of the images.
     // Directory is created if it doesn't exist.
-    let paths = response.save("./data").await?;
+    let paths = response.save_all(&client, "./data").await?;
 
     paths
         .iter()
@@ -1,3 +1,6 @@
+// Function calling is deprecated in favor of tool calls, see the tool-call-stream example.
+#![allow(deprecated)]
+
 use std::collections::HashMap;
 use std::error::Error;
 use std::io::{stdout, Write};
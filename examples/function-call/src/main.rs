@@ -1,3 +1,6 @@
+// Function calling is deprecated in favor of tool calls, see the tool-call example.
+#![allow(deprecated)]
+
 use async_openai::{
     types::{
         ChatCompletionFunctionsArgs, ChatCompletionRequestFunctionMessageArgs,
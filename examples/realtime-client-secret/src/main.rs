@@ -0,0 +1,52 @@
+use async_openai::{
+    config::OpenAIConfig,
+    types::realtime::{
+        ClientSecretExpiresAfter, CreateRealtimeClientSecretRequest, RealtimeSessionConfig,
+        RealtimeVoice, SessionResource,
+    },
+    Client,
+};
+use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+use secrecy::ExposeSecret;
+use serde_json::{json, Value};
+
+/// Mints a short-lived key for a browser to connect to the Realtime API over WebRTC,
+/// without exposing the API key of the server.
+async fn client_secret(
+    State(client): State<Client<OpenAIConfig>>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let request = CreateRealtimeClientSecretRequest {
+        expires_after: Some(ClientSecretExpiresAfter::seconds(60)),
+        session: Some(RealtimeSessionConfig::from(SessionResource {
+            model: Some("gpt-4o-realtime-preview".into()),
+            instructions: Some("You are a friendly assistant.".into()),
+            voice: Some(RealtimeVoice::Alloy),
+            ..Default::default()
+        })),
+    };
+
+    let secret = client
+        .realtime()
+        .client_secrets()
+        .create(request)
+        .await
+        .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+
+    Ok(Json(json!({
+        "value": secret.value.expose_secret(),
+        "expires_at": secret.expires_at,
+    })))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let app = Router::new()
+        .route("/client-secret", post(client_secret))
+        .with_state(Client::new());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await?;
+    println!("POST http://127.0.0.1:3000/client-secret to mint a realtime key");
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
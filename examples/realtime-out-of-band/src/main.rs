@@ -0,0 +1,122 @@
+use std::error::Error;
+
+use async_openai::{
+    types::{
+        realtime::{
+            Item, ItemContent, ItemContentType, ItemRole, ItemType, RealtimeResponseCreateArgs,
+            ResponseConversation, ServerEvent,
+        },
+        Metadata,
+    },
+    RealtimeClient, RealtimeSender,
+};
+use futures::StreamExt;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+const CLASSIFICATION: &str = "classification";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let session = RealtimeClient::new()
+        .connect("gpt-4o-realtime-preview-2024-12-17")
+        .await?;
+
+    eprintln!("WebSocket handshake complete");
+
+    let (sender, mut events) = session.split();
+    tokio::spawn(read_stdin(sender));
+
+    while let Some(event) = events.next().await {
+        let server_event = match event {
+            Ok(server_event) => server_event,
+            Err(error) => {
+                eprintln!("{error}");
+                continue;
+            }
+        };
+
+        match server_event {
+            ServerEvent::ResponseAudioTranscriptDone(event) => {
+                eprintln!("[assistant]: {}", event.transcript.trim());
+            }
+            ServerEvent::ResponseDone(event) => {
+                // Out-of-band responses are recognized by their metadata, they
+                // are not part of the conversation
+                let purpose = event
+                    .response
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.get("purpose"));
+                if purpose == Some(CLASSIFICATION) {
+                    let text: String = event
+                        .response
+                        .output
+                        .iter()
+                        .flat_map(|item| item.content.iter().flatten())
+                        .filter_map(|content| content.text.as_deref())
+                        .collect();
+                    eprintln!("[classification]: {}", text.trim());
+                }
+            }
+            ServerEvent::Error(e) => {
+                eprintln!("{e:?}");
+            }
+            _ => {}
+        }
+    }
+
+    eprintln!("Close");
+    Ok(())
+}
+
+fn user_message(text: &str) -> Item {
+    Item {
+        r#type: Some(ItemType::Message),
+        role: Some(ItemRole::User),
+        content: Some(vec![ItemContent {
+            r#type: ItemContentType::InputText,
+            text: Some(text.to_string()),
+            audio: None,
+            transcript: None,
+        }]),
+        ..Default::default()
+    }
+}
+
+// For every line of stdin, the conversation continues with a response to the line,
+// and the line is classified by an out-of-band response which only sees the line.
+// Type "quit" to stop.
+async fn read_stdin(mut sender: RealtimeSender) {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim() == "quit" {
+            break;
+        }
+
+        let classify = RealtimeResponseCreateArgs::default()
+            .conversation(ResponseConversation::None)
+            .modalities(["text".to_string()])
+            .instructions(
+                "Classify the topic of the user message as one of: \
+                 science, sports, politics, other. Answer with the topic only.",
+            )
+            .metadata(Metadata::try_from(serde_json::json!({"purpose": CLASSIFICATION})).unwrap())
+            .input([user_message(&line)])
+            .build()
+            .unwrap();
+
+        let result = async {
+            sender.send_text(&line).await?;
+            sender.create_response(None).await?;
+            sender.send(classify).await
+        }
+        .await;
+        if let Err(error) = result {
+            eprintln!("{error}");
+            return;
+        }
+    }
+
+    let _ = sender.close().await;
+}
@@ -3,59 +3,48 @@ use std::error::Error;
 use async_openai::{
     types::{
         ChatCompletionRequestMessage, ChatCompletionRequestSystemMessage,
-        ChatCompletionRequestUserMessage, CreateChatCompletionRequestArgs, ResponseFormat,
-        ResponseFormatJsonSchema,
+        ChatCompletionRequestUserMessage, CreateChatCompletionRequestArgs, ParsedMessage,
     },
     Client,
 };
-use schemars::{schema_for, JsonSchema};
+use schemars::JsonSchema;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields)]
 pub struct Step {
     pub output: String,
     pub explanation: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
-#[serde(deny_unknown_fields)]
 pub struct MathReasoningResponse {
     pub final_answer: String,
     pub steps: Vec<Step>,
 }
 
-pub async fn structured_output<T: serde::Serialize + DeserializeOwned + JsonSchema>(
+pub async fn structured_output<T: JsonSchema + DeserializeOwned>(
     messages: Vec<ChatCompletionRequestMessage>,
 ) -> Result<Option<T>, Box<dyn Error>> {
-    let schema = schema_for!(T);
-    let schema_value = serde_json::to_value(&schema)?;
-    let response_format = ResponseFormat::JsonSchema {
-        json_schema: ResponseFormatJsonSchema {
-            description: None,
-            name: "math_reasoning".into(),
-            schema: Some(schema_value),
-            strict: Some(true),
-        },
-    };
-
     let request = CreateChatCompletionRequestArgs::default()
         .max_tokens(512u32)
         .model("gpt-4o-mini")
         .messages(messages)
-        .response_format(response_format)
         .build()?;
 
     let client = Client::new();
-    let response = client.chat().create(request).await?;
-
-    for choice in response.choices {
-        if let Some(content) = choice.message.content {
-            return Ok(Some(serde_json::from_str::<T>(&content)?));
-        }
-    }
+    // The strict JSON schema of `T` is set as the response format
+    let completion = client.chat().create_parsed::<T>(request).await?;
 
-    Ok(None)
+    Ok(completion
+        .choices
+        .into_iter()
+        .find_map(|choice| match choice {
+            ParsedMessage::Parsed(parsed) => Some(parsed),
+            ParsedMessage::Refusal(refusal) => {
+                eprintln!("refused: {refusal}");
+                None
+            }
+        }))
 }
 
 #[tokio::main]
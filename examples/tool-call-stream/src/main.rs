@@ -1,20 +1,18 @@
 use std::collections::HashMap;
 use std::error::Error;
 use std::io::{stdout, Write};
-use std::sync::Arc;
 
 use async_openai::types::{
-    ChatCompletionMessageToolCall, ChatCompletionRequestAssistantMessageArgs,
-    ChatCompletionRequestMessage, ChatCompletionRequestToolMessageArgs,
-    ChatCompletionRequestUserMessageArgs, ChatCompletionToolArgs, ChatCompletionToolType,
-    FinishReason, FunctionCall, FunctionObjectArgs,
+    ChatCompletionRequestAssistantMessageArgs, ChatCompletionRequestMessage,
+    ChatCompletionRequestToolMessageArgs, ChatCompletionRequestUserMessageArgs,
+    ChatCompletionResponseStream, ChatCompletionToolArgs, ChatCompletionToolType,
+    FunctionObjectArgs, ToolCallChunkMerger,
 };
 use async_openai::{types::CreateChatCompletionRequestArgs, Client};
 use futures::StreamExt;
 use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
 use serde_json::{json, Value};
-use tokio::sync::Mutex;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -52,168 +50,80 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut stream = client.chat().create_stream(request).await?;
 
-    let tool_call_states: Arc<Mutex<HashMap<(u32, u32), ChatCompletionMessageToolCall>>> =
-        Arc::new(Mutex::new(HashMap::new()));
+    // Request is sent with the default n = 1, so every chunk is of the same choice
+    let mut merger = ToolCallChunkMerger::new();
+    let mut tool_calls = vec![];
 
     while let Some(result) = stream.next().await {
-        match result {
-            Ok(response) => {
-                for chat_choice in response.choices {
-                    let function_responses: Arc<
-                        Mutex<Vec<(ChatCompletionMessageToolCall, Value)>>,
-                    > = Arc::new(Mutex::new(Vec::new()));
-                    if let Some(tool_calls) = chat_choice.delta.tool_calls {
-                        for tool_call_chunk in tool_calls.into_iter() {
-                            let key = (chat_choice.index, tool_call_chunk.index);
-                            let states = tool_call_states.clone();
-                            let tool_call_data = tool_call_chunk.clone();
-
-                            let mut states_lock = states.lock().await;
-                            let state = states_lock.entry(key).or_insert_with(|| {
-                                ChatCompletionMessageToolCall {
-                                    id: tool_call_data.id.clone().unwrap_or_default(),
-                                    r#type: ChatCompletionToolType::Function,
-                                    function: FunctionCall {
-                                        name: tool_call_data
-                                            .function
-                                            .as_ref()
-                                            .and_then(|f| f.name.clone())
-                                            .unwrap_or_default(),
-                                        arguments: tool_call_data
-                                            .function
-                                            .as_ref()
-                                            .and_then(|f| f.arguments.clone())
-                                            .unwrap_or_default(),
-                                    },
-                                }
-                            });
-                            if let Some(arguments) = tool_call_chunk
-                                .function
-                                .as_ref()
-                                .and_then(|f| f.arguments.as_ref())
-                            {
-                                state.function.arguments.push_str(arguments);
-                            }
-                        }
-                    }
-                    if let Some(finish_reason) = &chat_choice.finish_reason {
-                        if matches!(finish_reason, FinishReason::ToolCalls) {
-                            let tool_call_states_clone = tool_call_states.clone();
-
-                            let tool_calls_to_process = {
-                                let states_lock = tool_call_states_clone.lock().await;
-                                states_lock
-                                    .iter()
-                                    .map(|(_key, tool_call)| {
-                                        let name = tool_call.function.name.clone();
-                                        let args = tool_call.function.arguments.clone();
-                                        let tool_call_clone = tool_call.clone();
-                                        (name, args, tool_call_clone)
-                                    })
-                                    .collect::<Vec<_>>()
-                            };
-
-                            let mut handles = Vec::new();
-                            for (name, args, tool_call_clone) in tool_calls_to_process {
-                                let response_content_clone = function_responses.clone();
-                                let handle = tokio::spawn(async move {
-                                    let response_content = call_fn(&name, &args).await.unwrap();
-                                    let mut function_responses_lock =
-                                        response_content_clone.lock().await;
-                                    function_responses_lock
-                                        .push((tool_call_clone, response_content));
-                                });
-                                handles.push(handle);
-                            }
-
-                            for handle in handles {
-                                handle.await.unwrap();
-                            }
-
-                            let function_responses_clone = function_responses.clone();
-                            let function_responses_lock = function_responses_clone.lock().await;
-                            let mut messages: Vec<ChatCompletionRequestMessage> =
-                                vec![ChatCompletionRequestUserMessageArgs::default()
-                                    .content(user_prompt)
-                                    .build()?
-                                    .into()];
-
-                            let tool_calls: Vec<ChatCompletionMessageToolCall> =
-                                function_responses_lock
-                                    .iter()
-                                    .map(|tc| tc.0.clone())
-                                    .collect();
-
-                            let assistant_messages: ChatCompletionRequestMessage =
-                                ChatCompletionRequestAssistantMessageArgs::default()
-                                    .tool_calls(tool_calls)
-                                    .build()
-                                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
-                                    .unwrap()
-                                    .into();
-
-                            let tool_messages: Vec<ChatCompletionRequestMessage> =
-                                function_responses_lock
-                                    .iter()
-                                    .map(|tc| {
-                                        ChatCompletionRequestToolMessageArgs::default()
-                                            .content(tc.1.to_string())
-                                            .tool_call_id(tc.0.id.clone())
-                                            .build()
-                                            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
-                                            .unwrap()
-                                            .into()
-                                    })
-                                    .collect();
-
-                            messages.push(assistant_messages);
-                            messages.extend(tool_messages);
-
-                            let request = CreateChatCompletionRequestArgs::default()
-                                .max_tokens(512u32)
-                                .model("gpt-4-1106-preview")
-                                .messages(messages)
-                                .build()
-                                .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
-
-                            let mut stream = client.chat().create_stream(request).await?;
-
-                            let mut response_content = String::new();
-                            let mut lock = stdout().lock();
-                            while let Some(result) = stream.next().await {
-                                match result {
-                                    Ok(response) => {
-                                        for chat_choice in response.choices.iter() {
-                                            if let Some(ref content) = chat_choice.delta.content {
-                                                write!(lock, "{}", content).unwrap();
-                                                response_content.push_str(content);
-                                            }
-                                        }
-                                    }
-                                    Err(err) => {
-                                        return Err(Box::new(err) as Box<dyn std::error::Error>);
-                                    }
-                                }
-                            }
-                        }
-                    }
-
-                    if let Some(content) = &chat_choice.delta.content {
-                        let mut lock = stdout().lock();
-                        write!(lock, "{}", content).unwrap();
-                    }
-                }
+        for chat_choice in result?.choices {
+            if let Some(content) = &chat_choice.delta.content {
+                print!("{content}");
             }
-            Err(err) => {
-                let mut lock = stdout().lock();
-                writeln!(lock, "error: {err}").unwrap();
+            if let Some(completed) = merger.push_choice(&chat_choice) {
+                tool_calls = completed;
             }
         }
-        stdout()
-            .flush()
-            .map_err(|e| Box::new(e) as Box<dyn Error>)?;
+        stdout().flush()?;
     }
 
+    if tool_calls.is_empty() {
+        return Ok(());
+    }
+
+    // Call the functions concurrently
+    let handles: Vec<_> = tool_calls
+        .iter()
+        .cloned()
+        .map(|tool_call| {
+            tokio::spawn(async move {
+                let response = call_fn(&tool_call.function.name, &tool_call.function.arguments)
+                    .await
+                    .unwrap();
+                (tool_call.id, response)
+            })
+        })
+        .collect();
+
+    let mut messages: Vec<ChatCompletionRequestMessage> = vec![
+        ChatCompletionRequestUserMessageArgs::default()
+            .content(user_prompt)
+            .build()?
+            .into(),
+        ChatCompletionRequestAssistantMessageArgs::default()
+            .tool_calls(tool_calls)
+            .build()?
+            .into(),
+    ];
+    for handle in handles {
+        let (tool_call_id, response) = handle.await?;
+        messages.push(
+            ChatCompletionRequestToolMessageArgs::default()
+                .content(response.to_string())
+                .tool_call_id(tool_call_id)
+                .build()?
+                .into(),
+        );
+    }
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .max_tokens(512u32)
+        .model("gpt-4-1106-preview")
+        .messages(messages)
+        .build()?;
+
+    print_stream(client.chat().create_stream(request).await?).await
+}
+
+async fn print_stream(mut stream: ChatCompletionResponseStream) -> Result<(), Box<dyn Error>> {
+    let mut lock = stdout().lock();
+    while let Some(result) = stream.next().await {
+        for chat_choice in result?.choices {
+            if let Some(content) = &chat_choice.delta.content {
+                write!(lock, "{content}")?;
+            }
+        }
+        lock.flush()?;
+    }
     Ok(())
 }
 